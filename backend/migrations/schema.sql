@@ -9,7 +9,7 @@ CREATE TYPE art_style_type AS ENUM ('Wooden', 'Watercolor', 'Impressionism', 'Su
 CREATE TYPE rarity_type AS ENUM ('Common', 'Uncommon', 'Rare', 'Epic', 'Legendary', 'Mythical');
 CREATE TYPE market_status_type AS ENUM ('active', 'completed', 'cancelled');
 CREATE TYPE market_type AS ENUM ('sale', 'auction');
-CREATE TYPE event_type AS ENUM ('summoned', 'hatched', 'listed_for_sale', 'sale_cancelled', 'sold', 'transferred', 'traded', 'bid_placed', 'auction_won');
+CREATE TYPE event_type AS ENUM ('summoned', 'hatched', 'listed_for_sale', 'sale_cancelled', 'sold', 'transferred', 'traded', 'bid_placed', 'auction_won', 'crafted', 'soul_bound', 'renamed', 'entered_chaos_realm');
 CREATE TYPE item_status AS ENUM ('available', 'locked', 'trading');
 CREATE TYPE animal_type AS ENUM (
     'Dragon', 'Chimera', 'Cow', 'Unicorn', 'Lizard', 'Kraken', 'Megalodon',
@@ -23,6 +23,10 @@ CREATE TYPE animal_type AS ENUM (
     'Spider', 'Sloth', 'Toucan', 'Otter', 'Alien'
 );
 CREATE TYPE order_side_type AS ENUM ('buy', 'sell');
+CREATE TYPE trade_status_type AS ENUM ('pending', 'accepted', 'declined', 'cancelled', 'countered');
+CREATE TYPE notification_kind AS ENUM ('listing_sold', 'order_filled', 'outbid', 'membership_expired', 'patreon_linked', 'patreon_unlinked', 'chaos_realm_ready', 'energy_recharged', 'streak_expiring', 'friend_request', 'friend_accepted', 'guild_invite', 'battle_result', 'ladder_reward');
+CREATE TYPE friend_status AS ENUM ('pending', 'accepted');
+CREATE TYPE guild_role AS ENUM ('leader', 'officer', 'member');
 
 CREATE OR REPLACE FUNCTION trigger_set_timestamp()
 RETURNS TRIGGER AS $$
@@ -115,6 +119,8 @@ CREATE TABLE creatures (
     in_chaos_realm BOOLEAN NOT NULL DEFAULT false,
     chaos_realm_entry_at TIMESTAMPTZ,
     chaos_realm_reward_claimed BOOLEAN NOT NULL DEFAULT false,
+    -- Whether the run's ChaosRealmCompleted push has gone out
+    chaos_realm_completion_pushed BOOLEAN NOT NULL DEFAULT false,
     CONSTRAINT valid_item_type CHECK (item_type = 'creature')
 );
 
@@ -190,9 +196,28 @@ CREATE TABLE user_preferences (
     user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
     notification_settings JSONB NOT NULL DEFAULT '{}',
     privacy_settings JSONB NOT NULL DEFAULT '{}',
-    ui_preferences JSONB NOT NULL DEFAULT '{}'
+    ui_preferences JSONB NOT NULL DEFAULT '{}',
+    email_unsubscribe_token UUID NOT NULL UNIQUE DEFAULT uuid_generate_v4(),
+    last_digest_sent_at TIMESTAMPTZ
 );
 
+-- How a user may be shown to other players. Public listings (leaderboards, showcase, magic button)
+-- read names through this view rather than users, so privacy_settings are applied in one place.
+CREATE VIEW public_users AS
+SELECT
+    u.id,
+    CASE WHEN COALESCE((p.privacy_settings->>'appear_anonymous')::boolean, false)
+        THEN 'Anonymous'
+        ELSE u.username
+    END AS display_name,
+    COALESCE((p.privacy_settings->>'show_on_leaderboards')::boolean, true) AS on_leaderboards,
+    COALESCE((p.privacy_settings->>'show_in_showcase')::boolean, true) AS in_showcase,
+    COALESCE((p.privacy_settings->>'public_profile')::boolean, true)
+        AND NOT COALESCE((p.privacy_settings->>'appear_anonymous')::boolean, false) AS profile_visible
+FROM users u
+LEFT JOIN user_preferences p ON p.user_id = u.id
+WHERE u.deleted_at IS NULL;
+
 CREATE TABLE refresh_tokens (
     id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
     user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
@@ -201,7 +226,11 @@ CREATE TABLE refresh_tokens (
     expires_at TIMESTAMPTZ NOT NULL,
     revoked_at TIMESTAMPTZ,
     replaced_by_token TEXT,
-    ip_address INET
+    ip_address INET,
+    -- Every token rotated from the same sign-in shares a family; the family is the session
+    family_id UUID NOT NULL DEFAULT uuid_generate_v4(),
+    user_agent TEXT,
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
 );
 
 CREATE TABLE login_attempts (
@@ -235,6 +264,38 @@ CREATE TABLE magic_link_tokens (
     token_data JSONB
 );
 
+-- Authenticator-app 2FA. The row exists from enrollment; enabled_at is set once the
+-- player confirms a first code. last_used_step stops a code being used twice.
+CREATE TABLE user_two_factor (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    secret VARCHAR(64) NOT NULL,
+    enabled_at TIMESTAMPTZ,
+    last_used_step BIGINT,
+    -- Wrong step-up codes in a row, so sensitive actions can't be used to guess codes
+    step_up_failures INTEGER NOT NULL DEFAULT 0,
+    step_up_failed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE two_factor_recovery_codes (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code_hash TEXT NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- The second login step: issued when a magic link is used by a player with 2FA on
+CREATE TABLE two_factor_challenges (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token VARCHAR(255) UNIQUE NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    attempts_remaining INT NOT NULL DEFAULT 5,
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
 CREATE TABLE item_events (
     id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
     item_id UUID NOT NULL REFERENCES item_references(id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED,
@@ -259,9 +320,15 @@ CREATE TABLE market_listings (
     type market_type NOT NULL DEFAULT 'sale',
     status market_status_type NOT NULL DEFAULT 'active',
     buyer_id UUID REFERENCES users(id),
+    reserve_price INTEGER CHECK (reserve_price > 0),
+    min_increment INTEGER CHECK (min_increment > 0),
+    ends_at TIMESTAMPTZ,
+    current_bid INTEGER CHECK (current_bid > 0),
+    current_bidder_id UUID REFERENCES users(id) ON DELETE SET NULL,
     created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
     updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    CONSTRAINT valid_item_type CHECK (item_type IN ('egg', 'creature', 'scroll'))
+    CONSTRAINT valid_item_type CHECK (item_type IN ('egg', 'creature', 'scroll')),
+    CONSTRAINT auction_fields CHECK (type <> 'auction' OR (ends_at IS NOT NULL AND min_increment IS NOT NULL))
 );
 
 CREATE TABLE scroll_orderbook (
@@ -269,6 +336,8 @@ CREATE TABLE scroll_orderbook (
     user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
     side order_side_type NOT NULL,
     price INTEGER NOT NULL CHECK (price > 0 AND price <= 1000000000),
+    quantity INTEGER NOT NULL DEFAULT 1 CHECK (quantity > 0),
+    filled_quantity INTEGER NOT NULL DEFAULT 0 CHECK (filled_quantity >= 0 AND filled_quantity <= quantity),
     created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
     updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
     status market_status_type NOT NULL DEFAULT 'active'
@@ -284,9 +353,256 @@ CREATE INDEX idx_scroll_orderbook_status ON scroll_orderbook(status);
 CREATE INDEX idx_scroll_orderbook_side ON scroll_orderbook(side);
 CREATE INDEX idx_scroll_orderbook_price ON scroll_orderbook(price);
 
+CREATE TABLE scroll_trades (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    buy_order_id UUID REFERENCES scroll_orderbook(id) ON DELETE SET NULL,
+    sell_order_id UUID REFERENCES scroll_orderbook(id) ON DELETE SET NULL,
+    buyer_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    seller_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    taker_side order_side_type NOT NULL,
+    price INTEGER NOT NULL CHECK (price > 0),
+    quantity INTEGER NOT NULL DEFAULT 1 CHECK (quantity > 0),
+    executed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_scroll_trades_executed ON scroll_trades(executed_at);
+
+-- Snapshot of each completed egg/creature sale, kept even if the item is later consumed
+CREATE TABLE market_sales (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    listing_id UUID REFERENCES market_listings(id) ON DELETE SET NULL,
+    item_type VARCHAR(20) NOT NULL,
+    essence essence_type NOT NULL,
+    rarity rarity_type,
+    price INTEGER NOT NULL CHECK (price > 0),
+    sold_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT valid_item_type CHECK (item_type IN ('egg', 'creature'))
+);
+
+CREATE INDEX idx_market_sales_type_sold ON market_sales(item_type, sold_at);
+
+CREATE TABLE trade_offers (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    proposer_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    recipient_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    offered_pax INTEGER NOT NULL DEFAULT 0 CHECK (offered_pax >= 0),
+    requested_pax INTEGER NOT NULL DEFAULT 0 CHECK (requested_pax >= 0),
+    status trade_status_type NOT NULL DEFAULT 'pending',
+    countered_offer_id UUID REFERENCES trade_offers(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TIMESTAMPTZ,
+    CONSTRAINT distinct_trade_parties CHECK (proposer_id <> recipient_id)
+);
+
+-- item_id is deliberately not a foreign key: a requested item may be consumed
+-- before the offer resolves, and acceptance must then fail rather than silently
+-- dropping it from the offer
+CREATE TABLE trade_offer_items (
+    offer_id UUID NOT NULL REFERENCES trade_offers(id) ON DELETE CASCADE,
+    item_id UUID NOT NULL,
+    item_type VARCHAR(20) NOT NULL,
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    PRIMARY KEY (offer_id, item_id),
+    CONSTRAINT valid_item_type CHECK (item_type IN ('egg', 'creature'))
+);
+
+CREATE INDEX idx_trade_offers_proposer ON trade_offers(proposer_id, status);
+CREATE INDEX idx_trade_offers_recipient ON trade_offers(recipient_id, status);
+
+-- One row per pair of users: user_id sent the request, friend_id received it
+CREATE TABLE friends (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    friend_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status friend_status NOT NULL DEFAULT 'pending',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    accepted_at TIMESTAMPTZ,
+    PRIMARY KEY (user_id, friend_id),
+    CONSTRAINT distinct_friends CHECK (user_id <> friend_id)
+);
+
+CREATE UNIQUE INDEX idx_friends_pair ON friends(LEAST(user_id, friend_id), GREATEST(user_id, friend_id));
+CREATE INDEX idx_friends_friend ON friends(friend_id, status);
+
+-- Accepted friendships in both directions, so "friends of X" is a single lookup on user_id
+CREATE VIEW friendships AS
+SELECT user_id, friend_id, accepted_at FROM friends WHERE status = 'accepted'
+UNION ALL
+SELECT friend_id, user_id, accepted_at FROM friends WHERE status = 'accepted';
+
+CREATE TABLE user_blocks (
+    blocker_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    blocked_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (blocker_id, blocked_id),
+    CONSTRAINT distinct_block_parties CHECK (blocker_id <> blocked_id)
+);
+
+CREATE INDEX idx_user_blocks_blocked ON user_blocks(blocked_id);
+
+CREATE TABLE guilds (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(32) NOT NULL CHECK (length(name) >= 3),
+    description TEXT NOT NULL DEFAULT '',
+    treasury INTEGER NOT NULL DEFAULT 0 CHECK (treasury >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_guilds_name ON guilds(LOWER(name));
+
+-- user_id is the key: a player belongs to at most one guild
+CREATE TABLE guild_members (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    role guild_role NOT NULL DEFAULT 'member',
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_guild_members_guild ON guild_members(guild_id);
+CREATE UNIQUE INDEX idx_guild_members_leader ON guild_members(guild_id) WHERE role = 'leader';
+
+CREATE TABLE guild_invites (
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    invited_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE INDEX idx_guild_invites_user ON guild_invites(user_id);
+
+-- Every treasury movement: deposits are positive, spending negative
+CREATE TABLE guild_treasury_log (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    guild_id UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    recipient_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    amount INTEGER NOT NULL CHECK (amount <> 0),
+    reason TEXT NOT NULL DEFAULT '',
+    treasury_after INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_guild_treasury_log_guild ON guild_treasury_log(guild_id, created_at DESC);
+
+-- Ranked ladder. Ratings are kept per season, so opening a new season resets everyone;
+-- final_rank is filled in when the season closes and its rewards are paid.
+CREATE TABLE ladder_seasons (
+    id SERIAL PRIMARY KEY,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ends_at TIMESTAMPTZ NOT NULL,
+    closed_at TIMESTAMPTZ
+);
+
+-- At most one season is open at a time
+CREATE UNIQUE INDEX idx_ladder_seasons_open ON ladder_seasons((closed_at IS NULL)) WHERE closed_at IS NULL;
+
+INSERT INTO ladder_seasons (ends_at) VALUES (CURRENT_TIMESTAMP + INTERVAL '28 days');
+
+CREATE TABLE ladder_ratings (
+    season_id INTEGER NOT NULL REFERENCES ladder_seasons(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    rating INTEGER NOT NULL DEFAULT 1200,
+    wins INTEGER NOT NULL DEFAULT 0,
+    losses INTEGER NOT NULL DEFAULT 0,
+    final_rank INTEGER,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (season_id, user_id)
+);
+
+CREATE INDEX idx_ladder_ratings_season_rating ON ladder_ratings(season_id, rating DESC);
+
+-- Combatant snapshots and the seed are everything shared::shared_battle::simulate needs
+-- to replay a battle. Wild (AI) battles have no defender user or creature.
+CREATE TABLE battles (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    challenger_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    challenger_creature_id UUID REFERENCES creatures(id) ON DELETE SET NULL,
+    defender_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    defender_creature_id UUID REFERENCES creatures(id) ON DELETE SET NULL,
+    challenger_snapshot JSONB NOT NULL,
+    defender_snapshot JSONB NOT NULL,
+    seed BIGINT NOT NULL,
+    challenger_won BOOLEAN NOT NULL,
+    reward_pax INTEGER NOT NULL DEFAULT 0,
+    reward_experience INTEGER NOT NULL DEFAULT 0,
+    -- Set for ranked ladder battles; rating_change is the challenger's
+    season_id INTEGER REFERENCES ladder_seasons(id) ON DELETE SET NULL,
+    rating_change INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_battles_challenger ON battles(challenger_id, created_at DESC);
+CREATE INDEX idx_battles_defender ON battles(defender_id, created_at DESC);
+
+-- Double-entry pax ledger. Every balance change is a transaction of entries summing to
+-- zero: 'user' entries mirror users.currency_balance, the other accounts are the game's.
+CREATE TYPE pax_account AS ENUM ('user', 'mint', 'escrow', 'guild_treasury');
+CREATE TYPE pax_entry_kind AS ENUM (
+    'opening_balance', 'listing_fee', 'sale', 'purchase', 'daily_claim', 'game_reward',
+    'bind_cost', 'recharge_cost', 'chaos_reward', 'wheel', 'membership_purchase', 'egg_cost',
+    'rename_cost', 'crafting_cost', 'achievement_reward', 'magic_button', 'battle_reward',
+    'ladder_reward', 'guild_deposit', 'guild_payout', 'trade_escrow', 'trade_refund',
+    'trade_payment', 'auction_bid', 'auction_refund', 'order_fee', 'order_escrow', 'order_refund',
+    'guild_disbanded'
+);
+
+CREATE TABLE pax_ledger (
+    id BIGSERIAL PRIMARY KEY,
+    transaction_id UUID NOT NULL,
+    account pax_account NOT NULL,
+    -- Set on 'user' entries; kept as NULL when the user is deleted so transactions still balance
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    kind pax_entry_kind NOT NULL,
+    amount INTEGER NOT NULL,
+    -- The listing, order, creature or other row the movement belongs to, when there is one
+    reference_id UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_pax_ledger_user ON pax_ledger(user_id, created_at DESC) WHERE account = 'user';
+CREATE INDEX idx_pax_ledger_transaction ON pax_ledger(transaction_id);
+
+-- Opening entries so balances that predate the ledger reconcile
+WITH opening AS (
+    SELECT id, currency_balance, uuid_generate_v4() AS transaction_id
+    FROM users WHERE currency_balance <> 0
+)
+INSERT INTO pax_ledger (transaction_id, account, user_id, kind, amount)
+SELECT transaction_id, 'user'::pax_account, id, 'opening_balance'::pax_entry_kind, currency_balance FROM opening
+UNION ALL
+SELECT transaction_id, 'mint'::pax_account, NULL, 'opening_balance'::pax_entry_kind, -currency_balance FROM opening;
+
+CREATE TABLE notifications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind notification_kind NOT NULL,
+    message TEXT NOT NULL,
+    data JSONB NOT NULL DEFAULT '{}',
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_notifications_user_created ON notifications(user_id, created_at DESC);
+CREATE INDEX idx_notifications_user_unread ON notifications(user_id) WHERE read_at IS NULL;
+
+-- Entries waiting for the user's next email digest; deliver_after holds back timer-based ones
+CREATE TABLE email_digest_items (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind notification_kind NOT NULL,
+    message TEXT NOT NULL,
+    deliver_after TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_email_digest_items_pending ON email_digest_items(user_id, deliver_after) WHERE sent_at IS NULL;
+
 CREATE TABLE recipes (
     id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
-    result_item_id UUID NOT NULL REFERENCES scrolls(id) ON DELETE CASCADE,
+    result_name VARCHAR(100) NOT NULL UNIQUE,
+    result_description TEXT NOT NULL,
+    result_image_path VARCHAR(255) NOT NULL DEFAULT '/static/images/scroll-default.avif',
     ingredients JSONB NOT NULL,
     required_rank user_rank NOT NULL,
     created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
@@ -294,9 +610,10 @@ CREATE TABLE recipes (
 
 CREATE TABLE applied_items (
     egg_id UUID REFERENCES eggs(id) ON DELETE CASCADE,
-    scroll_id UUID REFERENCES scrolls(id) ON DELETE CASCADE,
+    scroll_name VARCHAR(100) NOT NULL,
+    target_animal animal_type,
     applied_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    PRIMARY KEY (egg_id, scroll_id)
+    PRIMARY KEY (egg_id, scroll_name)
 );
 
 CREATE TABLE achievements (
@@ -389,6 +706,7 @@ CREATE INDEX idx_users_rank ON users(rank);
 CREATE INDEX idx_refresh_tokens_user ON refresh_tokens(user_id);
 CREATE INDEX idx_refresh_tokens_token ON refresh_tokens(token);
 CREATE INDEX idx_refresh_tokens_expires ON refresh_tokens(expires_at);
+CREATE INDEX idx_refresh_tokens_family ON refresh_tokens(family_id);
 CREATE INDEX idx_login_attempts_username ON login_attempts(username);
 CREATE INDEX idx_login_attempts_time ON login_attempts(attempt_time);
 CREATE INDEX idx_login_attempts_ip ON login_attempts(ip_address);
@@ -397,6 +715,8 @@ CREATE INDEX idx_password_resets_user ON password_resets(user_id);
 CREATE INDEX idx_password_resets_expires ON password_resets(expires_at);
 CREATE INDEX idx_password_resets_email_code ON password_resets(email_code);
 CREATE INDEX idx_password_resets_user_expires ON password_resets(user_id, expires_at);
+CREATE INDEX idx_two_factor_recovery_codes_user ON two_factor_recovery_codes(user_id);
+CREATE INDEX idx_two_factor_challenges_expires ON two_factor_challenges(expires_at);
 CREATE INDEX idx_eggs_owner ON eggs(owner_id);
 CREATE INDEX idx_eggs_created ON eggs(created_at);
 CREATE INDEX idx_creatures_owner ON creatures(owner_id);
@@ -408,6 +728,7 @@ CREATE INDEX idx_market_seller ON market_listings(seller_id);
 CREATE INDEX idx_market_buyer ON market_listings(buyer_id);
 CREATE INDEX idx_market_status ON market_listings(status);
 CREATE INDEX idx_market_created ON market_listings(created_at);
+CREATE INDEX idx_market_auction_ends ON market_listings(ends_at) WHERE type = 'auction';
 CREATE INDEX idx_item_events_composite ON item_events(item_id, item_type);
 CREATE INDEX idx_item_events_timestamp ON item_events(timestamp);
 CREATE INDEX idx_item_events_type ON item_events(event_type);
@@ -527,4 +848,25 @@ CREATE TABLE magic_button_clicks (
 );
 
 CREATE INDEX idx_magic_button_clicks_time ON magic_button_clicks(clicked_at);
-CREATE INDEX idx_magic_button_last_click ON magic_button_clicks(user_id, clicked_at);
\ No newline at end of file
+CREATE INDEX idx_magic_button_last_click ON magic_button_clicks(user_id, clicked_at);
+-- Seed achievements evaluated by achievement_service
+INSERT INTO achievements (name, description, requirements, rewards) VALUES
+    ('First Hatch', 'Hatch your first creature', '{"type": "creatures_hatched", "count": 1}', '{"pax": 10, "experience": 5}'),
+    ('Hatchery Keeper', 'Hatch 10 creatures', '{"type": "creatures_hatched", "count": 10}', '{"pax": 50, "scrolls": 1, "experience": 25}'),
+    ('Brood Master', 'Hatch 50 creatures', '{"type": "creatures_hatched", "count": 50}', '{"pax": 200, "scrolls": 3, "experience": 100}'),
+    ('Soul Binder', 'Reach Uncommon rarity through a soul bind', '{"type": "rarity_reached", "rarity": "Uncommon"}', '{"pax": 20, "experience": 10}'),
+    ('Legend in the Making', 'Reach Legendary rarity through soul binds', '{"type": "rarity_reached", "rarity": "Legendary"}', '{"pax": 250, "scrolls": 2, "experience": 100}'),
+    ('Mythical Ascension', 'Reach Mythical rarity through soul binds', '{"type": "rarity_reached", "rarity": "Mythical"}', '{"pax": 500, "scrolls": 5, "experience": 200}'),
+    ('Devoted', 'Reach a 7-day daily claim streak', '{"type": "claim_streak", "days": 7}', '{"pax": 25, "experience": 15}'),
+    ('Faithful', 'Reach a 30-day daily claim streak', '{"type": "claim_streak", "days": 30}', '{"pax": 150, "scrolls": 2, "experience": 75}'),
+    ('Snake Charmer', 'Score 50 in Snake', '{"type": "game_high_score", "game_type": "snake", "score": 50}', '{"pax": 25, "experience": 10}'),
+    ('Tile Tactician', 'Score 2048 in 2048', '{"type": "game_high_score", "game_type": "2048", "score": 2048}', '{"pax": 50, "experience": 20}'),
+    ('Wordsmith', 'Guess 25 words in the word game', '{"type": "words_guessed", "count": 25}', '{"pax": 50, "scrolls": 1, "experience": 20}')
+ON CONFLICT (name) DO NOTHING;
+
+-- Seed recipes crafted through crafting_service
+INSERT INTO recipes (result_name, result_description, ingredients, required_rank) VALUES
+    ('Scroll of Haste', 'Shortens the incubation of an egg', '{"pax": 30, "scrolls": 1}', 'Novice'),
+    ('Scroll of Affinity', 'Guides an egg towards a chosen animal', '{"pax": 60, "scrolls": 1, "eggs": 1}', 'Apprentice'),
+    ('Scroll of Fortune', 'Improves the rarity an egg hatches into', '{"pax": 150, "scrolls": 2, "creatures": 1}', 'Adept')
+ON CONFLICT (result_name) DO NOTHING;
@@ -1,13 +1,14 @@
 use axum::{
     async_trait,
-    extract::FromRequestParts,
+    extract::{FromRequestParts, State},
     http::{request::Parts, StatusCode, Request},
     middleware::Next,
     response::Response,
     body::Body,
 };
 use uuid::Uuid;
-use super::validate_jwt;
+use super::{sessions, validate_jwt, validate_session_jwt};
+use crate::AppState;
 
 #[allow(dead_code)]
 #[derive(Clone, Copy)]
@@ -63,4 +64,33 @@ pub async fn require_auth(
         },
         Err(_) => Err(StatusCode::UNAUTHORIZED),
     }
-}
\ No newline at end of file
+}
+
+/// Turns away access tokens from sessions the player has signed out of, and keeps the
+/// session list's "last used" current. Runs in front of every route, since handlers check
+/// bearer tokens in several different ways; requests without one pass straight through.
+pub async fn reject_revoked_sessions(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let session_id = request
+        .headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(|token| validate_session_jwt(token.trim()).ok())
+        .and_then(|(_, session_id)| session_id);
+
+    // Both checks share one connection; without Redis the request fails open
+    if let Some(session_id) = session_id {
+        if let Ok(mut conn) = state.redis.get_async_connection().await {
+            if sessions::is_revoked(&mut conn, session_id).await {
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+            sessions::record_activity(&state.pool, &mut conn, session_id).await;
+        }
+    }
+
+    Ok(next.run(request).await)
+}
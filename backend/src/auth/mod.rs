@@ -13,6 +13,8 @@ pub mod middleware;
 pub mod models;
 pub mod routes;
 pub mod utils;
+pub mod two_factor;
+pub mod sessions;
 
 pub use services::*;
 
@@ -78,6 +80,10 @@ pub struct Claims {
     pub sub: String,
     pub exp: usize,
     pub csrf_token: String,
+    /// The session (refresh token family) the token was issued to; absent on tokens
+    /// issued before sessions were tracked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
 }
 
 fn get_token_duration(refresh: bool) -> i64 {
@@ -94,8 +100,22 @@ fn get_token_duration(refresh: bool) -> i64 {
     }
 }
 
-pub fn set_auth_cookies(user_id: Uuid, headers: &mut HeaderMap) -> Result<(String, String), AuthError> {
-    let (access_token, refresh_token, csrf_token) = create_tokens(user_id)?;
+pub(crate) fn access_token_duration() -> time::Duration {
+    time::Duration::seconds(get_token_duration(false))
+}
+
+pub(crate) fn refresh_token_duration() -> time::Duration {
+    time::Duration::seconds(get_token_duration(true))
+}
+
+/// Sets the access cookie and the opaque refresh token cookie for `session_id`.
+pub fn set_auth_cookies(
+    user_id: Uuid,
+    session_id: Uuid,
+    refresh_token: &str,
+    headers: &mut HeaderMap,
+) -> Result<(String, String), AuthError> {
+    let (access_token, csrf_token) = create_tokens(user_id, session_id)?;
     let secure = env::var("COOKIE_SECURE").unwrap_or_else(|_| "true".to_string()) == "true";
     
     // Always set persistent cookies with explicit expiration times
@@ -104,14 +124,14 @@ pub fn set_auth_cookies(user_id: Uuid, headers: &mut HeaderMap) -> Result<(Strin
     access_cookie.set_secure(secure);
     access_cookie.set_same_site(SameSite::Strict);
     access_cookie.set_path("/");
-    access_cookie.set_max_age(time::Duration::seconds(get_token_duration(false)));
+    access_cookie.set_max_age(access_token_duration());
     
-    let mut refresh_cookie = Cookie::new("refresh_token", refresh_token);
+    let mut refresh_cookie = Cookie::new("refresh_token", refresh_token.to_string());
     refresh_cookie.set_http_only(true);
     refresh_cookie.set_secure(secure);
     refresh_cookie.set_same_site(SameSite::Strict);
-    refresh_cookie.set_path("/api/refresh");
-    refresh_cookie.set_max_age(time::Duration::seconds(get_token_duration(true)));
+    refresh_cookie.set_path("/api/auth");
+    refresh_cookie.set_max_age(refresh_token_duration());
     
     headers.insert(header::SET_COOKIE, access_cookie.to_string().parse().unwrap());
     headers.append(header::SET_COOKIE, refresh_cookie.to_string().parse().unwrap());
@@ -119,7 +139,26 @@ pub fn set_auth_cookies(user_id: Uuid, headers: &mut HeaderMap) -> Result<(Strin
     Ok((access_token, csrf_token))
 }
 
+/// Copies the cookies `set_auth_cookies` appended to `headers` onto the response.
+pub fn with_auth_cookies(mut response: axum::response::Response, headers: &HeaderMap) -> axum::response::Response {
+    for cookie in headers.get_all(header::SET_COOKIE) {
+        response.headers_mut().append(header::SET_COOKIE, cookie.clone());
+    }
+    response
+}
+
 pub fn validate_jwt(token: &str) -> Result<Uuid, AuthError> {
+    validate_session_jwt(token).map(|(user_id, _)| user_id)
+}
+
+/// Like `validate_jwt`, also returning the session the token belongs to.
+pub fn validate_session_jwt(token: &str) -> Result<(Uuid, Option<Uuid>), AuthError> {
+    validate_session_jwt_with_expiry(token).map(|(user_id, session_id, _)| (user_id, session_id))
+}
+
+/// Like `validate_session_jwt`, also returning when the token expires, in seconds since
+/// the epoch, for connections that outlive a single request.
+pub fn validate_session_jwt_with_expiry(token: &str) -> Result<(Uuid, Option<Uuid>, u64), AuthError> {
     let secret = env::var("JWT_SECRET_KEY").expect("JWT_SECRET_KEY must be set");
     let mut validation = Validation::default();
     validation.validate_exp = true;
@@ -136,10 +175,12 @@ pub fn validate_jwt(token: &str) -> Result<Uuid, AuthError> {
         _ => AuthError::JWT(e)
     })?;
 
-    Uuid::parse_str(&token_data.claims.sub).map_err(|_| AuthError::InvalidToken)
+    let user_id = Uuid::parse_str(&token_data.claims.sub).map_err(|_| AuthError::InvalidToken)?;
+    let session_id = token_data.claims.sid.as_deref().and_then(|sid| Uuid::parse_str(sid).ok());
+    Ok((user_id, session_id, token_data.claims.exp as u64))
 }
 
-fn create_tokens(user_id: Uuid) -> Result<(String, String, String), AuthError> {
+fn create_tokens(user_id: Uuid, session_id: Uuid) -> Result<(String, String), AuthError> {
     let csrf_token = Uuid::new_v4().to_string();
     let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
 
@@ -149,19 +190,10 @@ fn create_tokens(user_id: Uuid) -> Result<(String, String, String), AuthError> {
             sub: user_id.to_string(),
             exp: now as usize + get_token_duration(false) as usize,
             csrf_token: csrf_token.clone(),
+            sid: Some(session_id.to_string()),
         },
         &EncodingKey::from_secret(env::var("JWT_SECRET_KEY").expect("JWT_SECRET_KEY must be set").as_bytes())
     )?;
 
-    let refresh_token = encode(
-        &Header::default(),
-        &Claims {
-            sub: user_id.to_string(),
-            exp: now as usize + get_token_duration(true) as usize,
-            csrf_token: csrf_token.clone(),
-        },
-        &EncodingKey::from_secret(env::var("JWT_REFRESH_SECRET_KEY").expect("JWT_REFRESH_SECRET_KEY must be set").as_bytes())
-    )?;
-
-    Ok((access_token, refresh_token, csrf_token))
+    Ok((access_token, csrf_token))
 }
\ No newline at end of file
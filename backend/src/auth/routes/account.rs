@@ -8,7 +8,7 @@ use axum::{
 use serde::Deserialize;
 use sqlx;
 use shared::profanity::ProfanityFilter;
-use crate::{AppState, auth::{self, AuthError}, services::user_service};
+use crate::{AppState, auth::{self, AuthError, two_factor}, services::{auction_service, guild_service, trade_service, user_service}};
 use lettre::{
     message::header::ContentType,
     transport::smtp::{
@@ -26,6 +26,7 @@ use crate::auth::utils::get_frontend_url;
 #[derive(Deserialize)]
 pub struct ChangeEmailRequest {
     email: String,
+    two_factor_code: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -36,11 +37,13 @@ pub struct ChangePasswordRequest {
 #[derive(Deserialize)]
 pub struct AccountDeleteRequest {
     // No password needed in passwordless system
+    two_factor_code: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct DeleteAccountRequest {
     email: String,
+    two_factor_code: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -53,7 +56,7 @@ struct User {
     // No password_hash field in passwordless system
 }
 
-fn validate_auth_header(headers: &HeaderMap) -> Result<uuid::Uuid, AuthError> {
+pub(crate) fn validate_auth_header(headers: &HeaderMap) -> Result<uuid::Uuid, AuthError> {
     headers
         .get("Authorization")
         .and_then(|h| h.to_str().ok())
@@ -90,6 +93,7 @@ pub async fn change_email(
     Json(request): Json<ChangeEmailRequest>,
 ) -> Result<Response<Body>, Response<Body>> {
     let user_id = validate_auth_header(&headers).map_err(|e| e.into_response())?;
+    two_factor::require_step_up(&state.pool, user_id, request.two_factor_code.as_deref()).await?;
 
     if let Err(msg) = ProfanityFilter::validate_email_local_part(&request.email) {
         return Err(Response::builder()
@@ -160,9 +164,10 @@ pub async fn change_password(
 pub async fn delete_account(
     State(state): State<AppState>,
     headers: HeaderMap,
-    _request: Json<AccountDeleteRequest>,
+    Json(request): Json<AccountDeleteRequest>,
 ) -> Result<Response<Body>, Response<Body>> {
     let user_id = validate_auth_header(&headers).map_err(|e| e.into_response())?;
+    two_factor::require_step_up(&state.pool, user_id, request.two_factor_code.as_deref()).await?;
     
     // No password verification needed in passwordless system
     
@@ -207,6 +212,21 @@ pub async fn delete_account(
     .await
     .map_err(|e| AuthError::Database(e).into_response())?;
 
+    // Refund escrowed high bids before the auctions and bidder go away
+    auction_service::refund_bids_for_deleted_user(&mut tx, user_id)
+        .await
+        .map_err(|e| AuthError::Database(e).into_response())?;
+
+    // Hand escrowed trade items and pax back before the offers cascade away
+    trade_service::cancel_offers_for_deleted_user(&mut tx, user_id)
+        .await
+        .map_err(|e| AuthError::Database(e).into_response())?;
+
+    // Pass on guild leadership, or disband a guild the user was the last member of
+    guild_service::leave_guild_for_deleted_user(&mut tx, user_id)
+        .await
+        .map_err(|e| AuthError::Database(e).into_response())?;
+
     // 2. Delete market listings (both as seller and buyer)
     sqlx::query!(
         "DELETE FROM market_listings WHERE seller_id = $1 OR buyer_id = $1",
@@ -334,6 +354,7 @@ pub async fn request_delete_account(
         Ok(id) => id,
         Err(e) => return Err(e.into_response()),
     };
+    two_factor::require_step_up(&state.pool, user_id, request.two_factor_code.as_deref()).await?;
 
     // Verify the email matches the user's email
     let user_email = match sqlx::query_scalar!("SELECT email FROM users WHERE id = $1", user_id)
@@ -544,6 +565,21 @@ pub async fn verify_delete_account(
     .await
     .map_err(|e| AuthError::Database(e).into_response())?;
 
+    // Refund escrowed high bids before the auctions and bidder go away
+    auction_service::refund_bids_for_deleted_user(&mut tx, user_id)
+        .await
+        .map_err(|e| AuthError::Database(e).into_response())?;
+
+    // Hand escrowed trade items and pax back before the offers cascade away
+    trade_service::cancel_offers_for_deleted_user(&mut tx, user_id)
+        .await
+        .map_err(|e| AuthError::Database(e).into_response())?;
+
+    // Pass on guild leadership, or disband a guild the user was the last member of
+    guild_service::leave_guild_for_deleted_user(&mut tx, user_id)
+        .await
+        .map_err(|e| AuthError::Database(e).into_response())?;
+
     // 2. Delete market listings (both as seller and buyer)
     sqlx::query!(
         "DELETE FROM market_listings WHERE seller_id = $1 OR buyer_id = $1",
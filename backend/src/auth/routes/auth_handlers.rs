@@ -27,9 +27,7 @@ use time::OffsetDateTime;
 
 use crate::{AppState, auth::{
     self, AuthError, models::{LoginCredentials, RegisterCredentials, AuthResponse},
-    services::{
-        record_login_attempt, handle_authentication
-    }
+    services::record_login_attempt
 }};
 use crate::auth::utils::get_frontend_url;
 
@@ -309,88 +307,78 @@ async fn send_magic_link_email(email: &str, magic_link: &str) -> Result<(), Auth
     Ok(())
 }
 
+/// Rotates the refresh token cookie and issues a new access token for the same session.
 pub async fn refresh_token(
     State(state): State<AppState>,
-    cookies: HeaderMap,
+    headers: HeaderMap,
 ) -> Result<Response<Body>, Response<Body>> {
-    let refresh_token = match cookies
-        .get(header::COOKIE)
-        .and_then(|v| v.to_str().ok())
-        .and_then(|cookie_str| Cookie::parse(cookie_str).ok())
-        .and_then(|cookie| (cookie.name() == "refresh_token").then(|| cookie.value().to_string()))
+    let refresh_token = match headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(Cookie::split_parse)
+        .filter_map(Result::ok)
+        .find(|cookie| cookie.name() == "refresh_token")
+        .map(|cookie| cookie.value().to_string())
     {
         Some(token) => token,
-        None => {
-            return Err(Response::builder()
-                .status(StatusCode::UNAUTHORIZED)
-                .header("Content-Type", "application/json")
-                .body(Body::from(json!({"error": "Invalid token"}).to_string()))
-                .unwrap());
-        }
+        None => return Err(create_error_response(StatusCode::UNAUTHORIZED, "Invalid token")),
     };
 
-    let user_id = match auth::validate_jwt(&refresh_token) {
-        Ok(id) => id,
-        Err(_) => {
-            return Err(Response::builder()
-                .status(StatusCode::UNAUTHORIZED)
-                .header("Content-Type", "application/json")
-                .body(Body::from(json!({"error": "Invalid token"}).to_string()))
-                .unwrap());
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        error!("Database error: {:?}", e);
+        create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+    })?;
+
+    let rotation = auth::sessions::rotate(&mut tx, &refresh_token, &headers)
+        .await
+        .map_err(|e| {
+            error!("Database error: {:?}", e);
+            create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+        })?;
+
+    let (user_id, session) = match rotation {
+        auth::sessions::Rotation::Rotated { user_id, session } => (user_id, session),
+        auth::sessions::Rotation::Reused { session_id, .. } => {
+            tx.commit().await.map_err(|e| {
+                error!("Database error: {:?}", e);
+                create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+            })?;
+            auth::sessions::deny_access_tokens(&state.redis, &[session_id]).await;
+            return Err(create_error_response(StatusCode::UNAUTHORIZED, "Session revoked. Please sign in again."));
+        }
+        auth::sessions::Rotation::Invalid => {
+            return Err(create_error_response(StatusCode::UNAUTHORIZED, "Invalid token"));
         }
     };
-    
-    let user = match sqlx::query!(
+
+    let mut response_headers = HeaderMap::new();
+    let (token, csrf_token) = auth::set_auth_cookies(
+        user_id,
+        session.session_id,
+        &session.refresh_token,
+        &mut response_headers,
+    )
+    .map_err(|e| {
+        error!("Authentication error: {:?}", e);
+        create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Authentication error")
+    })?;
+
+    let user = sqlx::query!(
         r#"SELECT currency_balance, username FROM users WHERE id = $1"#,
         user_id
     )
-    .fetch_one(&state.pool)
+    .fetch_one(&mut *tx)
     .await
-    {
-        Ok(user) => user,
-        Err(e) => {
-            error!("Database error: {:?}", e);
-            return Err(Response::builder()
-                .status(StatusCode::INTERNAL_SERVER_ERROR)
-                .header("Content-Type", "application/json")
-                .body(Body::from(json!({"error": "Database error"}).to_string()))
-                .unwrap());
-        }
-    };
+    .map_err(|e| {
+        error!("Database error: {:?}", e);
+        create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+    })?;
 
-    let mut tx = match state.pool.begin().await {
-        Ok(tx) => tx,
-        Err(e) => {
-            error!("Database error: {:?}", e);
-            return Err(Response::builder()
-                .status(StatusCode::INTERNAL_SERVER_ERROR)
-                .header("Content-Type", "application/json")
-                .body(Body::from(json!({"error": "Database error"}).to_string()))
-                .unwrap());
-        }
-    };
-    
-    let mut response_headers = HeaderMap::new();
-    let (csrf_token, token) = match handle_authentication(&mut tx, user_id, &mut response_headers).await {
-        Ok(tokens) => tokens,
-        Err(e) => {
-            error!("Authentication error: {:?}", e);
-            return Err(Response::builder()
-                .status(StatusCode::INTERNAL_SERVER_ERROR)
-                .header("Content-Type", "application/json")
-                .body(Body::from(json!({"error": "Authentication error"}).to_string()))
-                .unwrap());
-        }
-    };
-    
-    if let Err(e) = tx.commit().await {
+    tx.commit().await.map_err(|e| {
         error!("Database error: {:?}", e);
-        return Err(Response::builder()
-            .status(StatusCode::INTERNAL_SERVER_ERROR)
-            .header("Content-Type", "application/json")
-            .body(Body::from(json!({"error": "Database error"}).to_string()))
-            .unwrap());
-    }
+        create_error_response(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+    })?;
 
     let response = AuthResponse { 
         csrf_token, 
@@ -401,9 +389,11 @@ pub async fn refresh_token(
         user_id: user_id.to_string(),
         username: user.username,
     };
-    
-    Ok(Response::builder()
+
+    let response = Response::builder()
         .status(StatusCode::OK)
+        .header("Content-Type", "application/json")
         .body(Body::from(serde_json::to_string(&response).unwrap_or_default()))
-        .unwrap())
+        .unwrap();
+    Ok(auth::with_auth_cookies(response, &response_headers))
 }
\ No newline at end of file
@@ -16,7 +16,8 @@ use lettre::{
 };
 use rand::distributions::{Alphanumeric, DistString};
 use time::OffsetDateTime;
-use crate::{AppState, auth::{self, AuthError, models::{MagicLinkRequest, MagicLinkVerification, AuthResponse}}};
+use crate::{AppState, auth::{self, AuthError, two_factor, models::{MagicLinkRequest, MagicLinkVerification, AuthResponse}}};
+use super::two_factor::start_login_challenge;
 use std::env;
 use tracing;
 use shared::validation::*;
@@ -347,6 +348,41 @@ pub async fn verify_magic_link(
         }
     };
     
+    // Players with 2FA finish signing in with a code from their authenticator app
+    if !is_registration {
+        let requires_two_factor = two_factor::is_enabled(&mut tx, user_id).await.map_err(|_| {
+            Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Database error"))
+                .unwrap()
+        })?;
+
+        if requires_two_factor {
+            let two_factor_token = start_login_challenge(&mut tx, user_id).await.map_err(|_| {
+                Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from("Database error"))
+                    .unwrap()
+            })?;
+
+            tx.commit().await.map_err(|_| {
+                Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from("Database error"))
+                    .unwrap()
+            })?;
+
+            return Ok(Response::builder()
+                .status(StatusCode::ACCEPTED)
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::json!({
+                    "requires_two_factor": true,
+                    "two_factor_token": two_factor_token
+                }).to_string()))
+                .unwrap());
+        }
+    }
+
     // Authenticate the user
     let (csrf_token, token) = auth::handle_authentication(&mut tx, user_id, &mut headers).await.map_err(|e| {
         e.into_response()
@@ -388,10 +424,11 @@ pub async fn verify_magic_link(
     
     tracing::info!("👤 User '{}' logged in successfully via magic link", username);
     
-    Ok(Response::builder()
+    let response = Response::builder()
         .status(StatusCode::OK)
         .body(Body::from(serde_json::to_string(&response).unwrap_or_default()))
-        .unwrap())
+        .unwrap();
+    Ok(auth::with_auth_cookies(response, &headers))
 }
 
 async fn send_magic_link_email(email: &str, magic_link: &str) -> Result<(), AuthError> {
@@ -1,7 +1,16 @@
 pub mod account;
 pub mod auth_handlers;
 pub mod magic_link;
+pub mod password_reset;
+pub mod sessions;
+pub mod two_factor;
 
 pub use self::auth_handlers::{login, register, refresh_token};
 pub use self::account::{change_email, change_password, delete_account, get_profile, request_delete_account, verify_delete_account};
-pub use self::magic_link::{request_magic_link, verify_magic_link};
\ No newline at end of file
+pub use self::magic_link::{request_magic_link, verify_magic_link};
+pub use self::password_reset::{request_password_reset, verify_reset_code, reset_password};
+pub use self::sessions::{get_sessions, revoke_session, revoke_all_sessions};
+pub use self::two_factor::{
+    get_two_factor_status, enroll_two_factor, confirm_two_factor, regenerate_recovery_codes,
+    disable_two_factor, verify_two_factor_login,
+};
\ No newline at end of file
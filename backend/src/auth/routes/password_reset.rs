@@ -0,0 +1,40 @@
+use axum::{
+    http::{Response, StatusCode},
+    Json,
+    body::Body,
+};
+use shared::password_reset::{PasswordResetResponse, RequestResetRequest, ResetPasswordRequest, VerifyCodeRequest};
+
+// Accounts have no passwords: users sign in with magic links, so there is nothing to
+// reset. These answer the shared `/forgot-password/*` endpoints the same way
+// `change_password` does, pointing clients at the magic link instead.
+fn passwordless_response() -> Response<Body> {
+    let response = PasswordResetResponse {
+        message: "This system uses passwordless authentication. Please use magic link to login.".to_string(),
+        success: false,
+    };
+
+    Response::builder()
+        .status(StatusCode::BAD_REQUEST)
+        .header("Content-Type", "application/json")
+        .body(Body::from(serde_json::to_string(&response).unwrap_or_default()))
+        .unwrap()
+}
+
+pub async fn request_password_reset(
+    _request: Json<RequestResetRequest>,
+) -> Result<Response<Body>, Response<Body>> {
+    Err(passwordless_response())
+}
+
+pub async fn verify_reset_code(
+    _request: Json<VerifyCodeRequest>,
+) -> Result<Response<Body>, Response<Body>> {
+    Err(passwordless_response())
+}
+
+pub async fn reset_password(
+    _request: Json<ResetPasswordRequest>,
+) -> Result<Response<Body>, Response<Body>> {
+    Err(passwordless_response())
+}
@@ -0,0 +1,115 @@
+use axum::{
+    extract::{Path, State},
+    http::{Response, StatusCode, HeaderMap},
+    body::Body,
+    response::IntoResponse,
+};
+use serde_json::json;
+use uuid::Uuid;
+use crate::{AppState, auth::{self, AuthError, sessions}};
+
+fn json_response(status: StatusCode, body: serde_json::Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn database_error(e: sqlx::Error) -> Response<Body> {
+    tracing::error!("Session database error: {:?}", e);
+    json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": "Database error" }))
+}
+
+/// The signed-in user and the session their access token belongs to.
+fn current_session(headers: &HeaderMap) -> Result<(Uuid, Option<Uuid>), AuthError> {
+    headers
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .ok_or(AuthError::InvalidToken)
+        .and_then(|token| auth::validate_session_jwt(token.trim()))
+}
+
+/// `GET /api/users/me/sessions`: the user's signed-in devices, most recently used first.
+pub async fn get_sessions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response<Body>, Response<Body>> {
+    let (user_id, current_session_id) = current_session(&headers).map_err(|e| e.into_response())?;
+
+    let live = sqlx::query!(
+        r#"SELECT r.family_id, r.ip_address, r.user_agent,
+                  TO_CHAR(r.last_used_at, 'YYYY-MM-DD HH24:MI:SS') as "last_used_at!",
+                  TO_CHAR(
+                      (SELECT MIN(f.created_at) FROM refresh_tokens f WHERE f.family_id = r.family_id),
+                      'YYYY-MM-DD HH24:MI:SS'
+                  ) as "signed_in_at!"
+           FROM refresh_tokens r
+           WHERE r.user_id = $1 AND r.revoked_at IS NULL AND r.expires_at > NOW()
+           ORDER BY r.last_used_at DESC"#,
+        user_id
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let list: Vec<_> = live
+        .into_iter()
+        .map(|session| json!({
+            "id": session.family_id,
+            "device": sessions::describe_device(session.user_agent.as_deref()),
+            "user_agent": session.user_agent,
+            "ip_address": session.ip_address.map(|ip| ip.ip().to_string()),
+            "signed_in_at": session.signed_in_at,
+            "last_used_at": session.last_used_at,
+            "current": Some(session.family_id) == current_session_id,
+        }))
+        .collect();
+
+    Ok(json_response(StatusCode::OK, json!({ "sessions": list })))
+}
+
+/// `DELETE /api/users/me/sessions/:id`: signs one device out.
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(session_id): Path<Uuid>,
+) -> Result<Response<Body>, Response<Body>> {
+    let (user_id, _) = current_session(&headers).map_err(|e| e.into_response())?;
+
+    let mut conn = state.pool.acquire().await.map_err(database_error)?;
+    let revoked = sessions::revoke(&mut conn, user_id, Some(session_id))
+        .await
+        .map_err(database_error)?;
+
+    if revoked.is_empty() {
+        return Err(json_response(StatusCode::NOT_FOUND, json!({ "error": "Session not found" })));
+    }
+    sessions::deny_access_tokens(&state.redis, &revoked).await;
+    tracing::info!("🔒 User {} signed out session {}", user_id, session_id);
+
+    Ok(json_response(StatusCode::OK, json!({ "message": "Session signed out" })))
+}
+
+/// `DELETE /api/users/me/sessions`: signs every device out, this one included.
+pub async fn revoke_all_sessions(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response<Body>, Response<Body>> {
+    let (user_id, current_session_id) = current_session(&headers).map_err(|e| e.into_response())?;
+
+    let mut conn = state.pool.acquire().await.map_err(database_error)?;
+    let mut revoked = sessions::revoke(&mut conn, user_id, None)
+        .await
+        .map_err(database_error)?;
+
+    // The current token may come from a session whose refresh token already expired
+    if let Some(current) = current_session_id.filter(|id| !revoked.contains(id)) {
+        revoked.push(current);
+    }
+    sessions::deny_access_tokens(&state.redis, &revoked).await;
+    tracing::info!("🔒 User {} signed out of {} sessions", user_id, revoked.len());
+
+    Ok(json_response(StatusCode::OK, json!({ "signed_out": revoked.len() })))
+}
@@ -0,0 +1,299 @@
+use axum::{
+    extract::State,
+    http::{Response, StatusCode, HeaderMap},
+    Json,
+    body::Body,
+    response::IntoResponse,
+};
+use rand::distributions::{Alphanumeric, DistString};
+use serde::Deserialize;
+use serde_json::json;
+use time::OffsetDateTime;
+use crate::{AppState, auth::{self, two_factor, models::AuthResponse}};
+use super::account::validate_auth_header;
+
+#[derive(Deserialize)]
+pub struct TwoFactorCodeRequest {
+    code: String,
+}
+
+#[derive(Deserialize)]
+pub struct TwoFactorLoginRequest {
+    token: String,
+    code: String,
+}
+
+fn json_response(status: StatusCode, body: serde_json::Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Body> {
+    json_response(status, json!({ "error": message }))
+}
+
+fn database_error(e: sqlx::Error) -> Response<Body> {
+    tracing::error!("Two-factor database error: {:?}", e);
+    error_response(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+}
+
+/// Called from `verify_magic_link` once the link checks out for a player with 2FA on:
+/// opens the second login step instead of signing them in.
+pub async fn start_login_challenge(
+    conn: &mut sqlx::PgConnection,
+    user_id: uuid::Uuid,
+) -> Result<String, sqlx::Error> {
+    let token = Alphanumeric.sample_string(&mut rand::thread_rng(), 64);
+    let expires_at = OffsetDateTime::now_utc() + time::Duration::minutes(two_factor::CHALLENGE_MINUTES);
+
+    sqlx::query!(
+        "INSERT INTO two_factor_challenges (user_id, token, expires_at, attempts_remaining)
+         VALUES ($1, $2, $3, $4)",
+        user_id,
+        token,
+        expires_at,
+        two_factor::CHALLENGE_ATTEMPTS
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(token)
+}
+
+/// `GET /api/users/me/two-factor`
+pub async fn get_two_factor_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response<Body>, Response<Body>> {
+    let user_id = validate_auth_header(&headers).map_err(|e| e.into_response())?;
+
+    let status = sqlx::query!(
+        r#"SELECT
+               EXISTS(SELECT 1 FROM user_two_factor WHERE user_id = $1 AND enabled_at IS NOT NULL) as "enabled!",
+               (SELECT COUNT(*) FROM two_factor_recovery_codes WHERE user_id = $1 AND used_at IS NULL) as "recovery_codes_remaining!""#,
+        user_id
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(database_error)?;
+
+    Ok(json_response(StatusCode::OK, json!({
+        "enabled": status.enabled,
+        "recovery_codes_remaining": status.recovery_codes_remaining,
+    })))
+}
+
+/// `POST /api/users/me/two-factor/enroll`: starts (or restarts) enrollment with a new
+/// secret. 2FA stays off until the player confirms a code from their app.
+pub async fn enroll_two_factor(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Response<Body>, Response<Body>> {
+    let user_id = validate_auth_header(&headers).map_err(|e| e.into_response())?;
+    let mut tx = state.pool.begin().await.map_err(database_error)?;
+
+    if two_factor::is_enabled(&mut tx, user_id).await.map_err(database_error)? {
+        return Err(error_response(StatusCode::BAD_REQUEST, "Two-factor authentication is already enabled"));
+    }
+
+    let username = sqlx::query_scalar!("SELECT username FROM users WHERE id = $1", user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(database_error)?;
+
+    let secret = two_factor::generate_secret();
+    sqlx::query!(
+        "INSERT INTO user_two_factor (user_id, secret) VALUES ($1, $2)
+         ON CONFLICT (user_id) DO UPDATE SET secret = EXCLUDED.secret, last_used_step = NULL, created_at = NOW()",
+        user_id,
+        secret
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(database_error)?;
+
+    tx.commit().await.map_err(database_error)?;
+
+    let otpauth_uri = two_factor::otpauth_uri(&secret, &username);
+    Ok(json_response(StatusCode::OK, json!({
+        "secret": secret,
+        "qr_code": two_factor::qr_code_data_uri(&otpauth_uri),
+        "otpauth_uri": otpauth_uri,
+    })))
+}
+
+/// `POST /api/users/me/two-factor/confirm`: switches 2FA on once the first code from
+/// the app matches, and hands out the recovery codes.
+pub async fn confirm_two_factor(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<TwoFactorCodeRequest>,
+) -> Result<Response<Body>, Response<Body>> {
+    let user_id = validate_auth_header(&headers).map_err(|e| e.into_response())?;
+    let mut tx = state.pool.begin().await.map_err(database_error)?;
+
+    let pending = sqlx::query!(
+        "SELECT secret, enabled_at FROM user_two_factor WHERE user_id = $1 FOR UPDATE",
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(database_error)?;
+
+    let Some(pending) = pending else {
+        return Err(error_response(StatusCode::BAD_REQUEST, "Start two-factor setup first"));
+    };
+    if pending.enabled_at.is_some() {
+        return Err(error_response(StatusCode::BAD_REQUEST, "Two-factor authentication is already enabled"));
+    }
+    if !two_factor::verify_pending_code(&pending.secret, &request.code) {
+        return Err(error_response(StatusCode::BAD_REQUEST, "That code doesn't match. Check your app and try again."));
+    }
+
+    sqlx::query!("UPDATE user_two_factor SET enabled_at = NOW() WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(database_error)?;
+    let recovery_codes = two_factor::replace_recovery_codes(&mut tx, user_id)
+        .await
+        .map_err(database_error)?;
+
+    tx.commit().await.map_err(database_error)?;
+    tracing::info!("🔐 User {} enabled two-factor authentication", user_id);
+
+    Ok(json_response(StatusCode::OK, json!({ "recovery_codes": recovery_codes })))
+}
+
+/// `POST /api/users/me/two-factor/recovery-codes`: replaces the recovery codes, e.g.
+/// after the player has used most of them.
+pub async fn regenerate_recovery_codes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<TwoFactorCodeRequest>,
+) -> Result<Response<Body>, Response<Body>> {
+    let user_id = validate_auth_header(&headers).map_err(|e| e.into_response())?;
+    let mut tx = state.pool.begin().await.map_err(database_error)?;
+
+    if !two_factor::verify_code(&mut tx, user_id, &request.code).await.map_err(database_error)? {
+        return Err(error_response(StatusCode::FORBIDDEN, "Invalid two-factor code"));
+    }
+    let recovery_codes = two_factor::replace_recovery_codes(&mut tx, user_id)
+        .await
+        .map_err(database_error)?;
+
+    tx.commit().await.map_err(database_error)?;
+    Ok(json_response(StatusCode::OK, json!({ "recovery_codes": recovery_codes })))
+}
+
+/// `POST /api/users/me/two-factor/disable`
+pub async fn disable_two_factor(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<TwoFactorCodeRequest>,
+) -> Result<Response<Body>, Response<Body>> {
+    let user_id = validate_auth_header(&headers).map_err(|e| e.into_response())?;
+    let mut tx = state.pool.begin().await.map_err(database_error)?;
+
+    if !two_factor::verify_code(&mut tx, user_id, &request.code).await.map_err(database_error)? {
+        return Err(error_response(StatusCode::FORBIDDEN, "Invalid two-factor code"));
+    }
+
+    sqlx::query!("DELETE FROM two_factor_recovery_codes WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(database_error)?;
+    sqlx::query!("DELETE FROM user_two_factor WHERE user_id = $1", user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(database_error)?;
+
+    tx.commit().await.map_err(database_error)?;
+    tracing::info!("🔓 User {} disabled two-factor authentication", user_id);
+
+    Ok(json_response(StatusCode::OK, json!({ "message": "Two-factor authentication disabled" })))
+}
+
+/// `POST /api/auth/two-factor/verify`: the second login step. Takes the token
+/// `verify_magic_link` handed out plus an authenticator or recovery code, and signs the
+/// player in exactly as the magic link would have.
+pub async fn verify_two_factor_login(
+    State(state): State<AppState>,
+    mut headers: HeaderMap,
+    Json(request): Json<TwoFactorLoginRequest>,
+) -> Result<Response<Body>, Response<Body>> {
+    let mut tx = state.pool.begin().await.map_err(database_error)?;
+
+    let challenge = sqlx::query!(
+        "SELECT id, user_id, expires_at, attempts_remaining FROM two_factor_challenges
+         WHERE token = $1 AND completed_at IS NULL
+         FOR UPDATE",
+        request.token
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(database_error)?;
+
+    let Some(challenge) = challenge else {
+        return Err(error_response(StatusCode::BAD_REQUEST, "Invalid or expired sign-in. Please request a new magic link."));
+    };
+    if challenge.expires_at < OffsetDateTime::now_utc() || challenge.attempts_remaining <= 0 {
+        return Err(error_response(StatusCode::BAD_REQUEST, "Invalid or expired sign-in. Please request a new magic link."));
+    }
+
+    if !two_factor::verify_code(&mut tx, challenge.user_id, &request.code).await.map_err(database_error)? {
+        let remaining = sqlx::query_scalar!(
+            "UPDATE two_factor_challenges SET attempts_remaining = attempts_remaining - 1
+             WHERE id = $1 RETURNING attempts_remaining",
+            challenge.id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(database_error)?;
+        tx.commit().await.map_err(database_error)?;
+
+        tracing::warn!("Failed two-factor sign-in for user {}", challenge.user_id);
+        return Err(json_response(StatusCode::UNAUTHORIZED, json!({
+            "error": "Invalid two-factor code",
+            "attempts_remaining": remaining,
+        })));
+    }
+
+    sqlx::query!("UPDATE two_factor_challenges SET completed_at = NOW() WHERE id = $1", challenge.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(database_error)?;
+
+    let (csrf_token, token) = auth::handle_authentication(&mut tx, challenge.user_id, &mut headers)
+        .await
+        .map_err(|e| e.into_response())?;
+
+    let user = sqlx::query!(
+        "SELECT username, currency_balance FROM users WHERE id = $1",
+        challenge.user_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(database_error)?;
+
+    tx.commit().await.map_err(database_error)?;
+    tracing::info!("👤 User '{}' logged in successfully via magic link and two-factor code", user.username);
+
+    let response = AuthResponse {
+        csrf_token,
+        token,
+        requires_captcha: false,
+        current_attempts: None,
+        currency_balance: user.currency_balance,
+        user_id: challenge.user_id.to_string(),
+        username: user.username,
+    };
+
+    let response = Response::builder()
+        .status(StatusCode::OK)
+        .body(Body::from(serde_json::to_string(&response).unwrap_or_default()))
+        .unwrap();
+    Ok(auth::with_auth_cookies(response, &headers))
+}
@@ -4,11 +4,11 @@ use uuid::Uuid;
 use axum::http::HeaderMap;
 use ipnetwork::IpNetwork;
 use std::str::FromStr;
-use rand::distributions::{Alphanumeric, DistString};
-use time::OffsetDateTime;
 
-use super::AuthError;
+use super::{sessions, AuthError};
 
+/// Signs the user in on a new session. `headers` are the request headers: they record
+/// where the session came from, and the auth cookies are appended to them.
 pub async fn handle_authentication(
     executor: &mut Transaction<'_, Postgres>,
     user_id: Uuid,
@@ -22,32 +22,18 @@ pub async fn handle_authentication(
     .await
     .map_err(AuthError::Database)?;
 
-    // Always store refresh token
-    store_refresh_token(executor, user_id).await?;
+    let session = sessions::start(executor, user_id, headers)
+        .await
+        .map_err(AuthError::Database)?;
 
-    let (token, csrf_token) = crate::auth::set_auth_cookies(user_id, headers)?;
-
-    Ok((csrf_token, token))
-}
-
-async fn store_refresh_token(
-    executor: &mut Transaction<'_, Postgres>,
-    user_id: Uuid
-) -> Result<(), AuthError> {
-    let token = Alphanumeric.sample_string(&mut rand::thread_rng(), 64);
-    let expires_at = OffsetDateTime::now_utc() + time::Duration::days(7);
-
-    sqlx::query!(
-        "INSERT INTO refresh_tokens (user_id, token, expires_at) VALUES ($1, $2, $3)",
+    let (token, csrf_token) = crate::auth::set_auth_cookies(
         user_id,
-        token,
-        expires_at
-    )
-    .execute(&mut **executor)
-    .await
-    .map_err(AuthError::Database)?;
+        session.session_id,
+        &session.refresh_token,
+        headers,
+    )?;
 
-    Ok(())
+    Ok((csrf_token, token))
 }
 
 pub async fn record_login_attempt(
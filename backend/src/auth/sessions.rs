@@ -0,0 +1,388 @@
+use axum::http::{header, HeaderMap};
+use ipnetwork::IpNetwork;
+use rand::distributions::{Alphanumeric, DistString};
+use redis::{aio::Connection as RedisConnection, Client as RedisClient};
+use sqlx::{PgConnection, PgPool};
+use std::str::FromStr;
+use time::OffsetDateTime;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+const REFRESH_TOKEN_LENGTH: usize = 64;
+const MAX_USER_AGENT_LENGTH: usize = 512;
+/// How often a session's `last_used_at` is written while it is being used
+const ACTIVITY_INTERVAL_SECONDS: u64 = 300;
+/// How long a just-rotated token is still honoured, so two tabs refreshing at once
+/// don't look like a stolen token
+const REUSE_GRACE_SECONDS: i64 = 10;
+
+/// A session is a refresh token family: every token rotated from one sign-in shares
+/// its `family_id`, and the newest unrevoked token in the family is the live one.
+pub struct IssuedSession {
+    pub session_id: Uuid,
+    pub refresh_token: String,
+}
+
+pub enum Rotation {
+    Rotated { user_id: Uuid, session: IssuedSession },
+    /// An already-rotated token came back, so someone else holds a copy of it. The whole
+    /// family has been revoked.
+    Reused { session_id: Uuid },
+    Invalid,
+}
+
+/// What a presented refresh token is good for, going by its row.
+#[derive(Debug, PartialEq)]
+enum Presented {
+    Live,
+    /// Rotated moments ago, most likely by a concurrent request from the same client
+    RecentlyRotated,
+    Reused,
+    Invalid,
+}
+
+fn classify(
+    revoked_at: Option<OffsetDateTime>,
+    replaced: bool,
+    expires_at: OffsetDateTime,
+    now: OffsetDateTime,
+) -> Presented {
+    if replaced {
+        let grace = time::Duration::seconds(REUSE_GRACE_SECONDS);
+        return match revoked_at {
+            Some(rotated_at) if now - rotated_at <= grace => Presented::RecentlyRotated,
+            _ => Presented::Reused,
+        };
+    }
+    if revoked_at.is_some() || expires_at < now {
+        return Presented::Invalid;
+    }
+    Presented::Live
+}
+
+/// The client's address, preferring the headers our proxies set.
+pub fn client_ip(headers: &HeaderMap) -> Option<IpNetwork> {
+    ["cf-connecting-ip", "x-forwarded-for", "x-real-ip"]
+        .iter()
+        .filter_map(|name| headers.get(*name))
+        .filter_map(|value| value.to_str().ok())
+        .filter_map(|value| value.split(',').next())
+        .find_map(|ip| IpNetwork::from_str(ip.trim()).ok())
+}
+
+fn user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.chars().take(MAX_USER_AGENT_LENGTH).collect())
+}
+
+/// A short "Firefox on Windows" style label for the session list.
+pub fn describe_device(user_agent: Option<&str>) -> String {
+    let Some(ua) = user_agent else {
+        return "Unknown device".to_string();
+    };
+
+    // Order matters: Edge and Opera mention Chrome, and Chrome mentions Safari
+    let browser = [
+        ("Edg/", "Edge"),
+        ("OPR/", "Opera"),
+        ("Firefox/", "Firefox"),
+        ("Chrome/", "Chrome"),
+        ("Safari/", "Safari"),
+    ]
+    .iter()
+    .find(|(marker, _)| ua.contains(marker))
+    .map(|(_, name)| *name);
+
+    let os = [
+        ("iPhone", "iPhone"),
+        ("iPad", "iPad"),
+        ("Android", "Android"),
+        ("Windows", "Windows"),
+        ("Mac OS X", "macOS"),
+        ("Linux", "Linux"),
+    ]
+    .iter()
+    .find(|(marker, _)| ua.contains(marker))
+    .map(|(_, name)| *name);
+
+    match (browser, os) {
+        (Some(browser), Some(os)) => format!("{} on {}", browser, os),
+        (Some(name), None) | (None, Some(name)) => name.to_string(),
+        (None, None) => "Unknown device".to_string(),
+    }
+}
+
+/// Starts a new session for a fresh sign-in. `headers` are the request headers, used to
+/// record where the session came from.
+pub async fn start(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    headers: &HeaderMap,
+) -> Result<IssuedSession, sqlx::Error> {
+    let session = IssuedSession {
+        session_id: Uuid::new_v4(),
+        refresh_token: Alphanumeric.sample_string(&mut rand::thread_rng(), REFRESH_TOKEN_LENGTH),
+    };
+    let expires_at = OffsetDateTime::now_utc() + super::refresh_token_duration();
+
+    sqlx::query!(
+        "INSERT INTO refresh_tokens (user_id, token, expires_at, family_id, ip_address, user_agent)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+        user_id,
+        session.refresh_token,
+        expires_at,
+        session.session_id,
+        client_ip(headers),
+        user_agent(headers)
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(session)
+}
+
+/// Swaps a refresh token for a new one in the same family. Each token is good for one
+/// rotation; presenting it again revokes the family, since either the thief or the
+/// player is now holding a stale copy and we can't tell which. The exception is a token
+/// rotated within `REUSE_GRACE_SECONDS`, which gets its still-live successor back.
+pub async fn rotate(
+    conn: &mut PgConnection,
+    refresh_token: &str,
+    headers: &HeaderMap,
+) -> Result<Rotation, sqlx::Error> {
+    let Some(current) = sqlx::query!(
+        "SELECT id, user_id, family_id, expires_at, revoked_at, replaced_by_token
+         FROM refresh_tokens
+         WHERE token = $1
+         FOR UPDATE",
+        refresh_token
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    else {
+        return Ok(Rotation::Invalid);
+    };
+
+    let now = OffsetDateTime::now_utc();
+    match classify(current.revoked_at, current.replaced_by_token.is_some(), current.expires_at, now) {
+        Presented::Live => {}
+        Presented::RecentlyRotated => {
+            let successor = sqlx::query_scalar!(
+                "SELECT token FROM refresh_tokens
+                 WHERE token = $1 AND family_id = $2 AND revoked_at IS NULL AND expires_at > NOW()",
+                current.replaced_by_token,
+                current.family_id
+            )
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            return Ok(match successor {
+                Some(refresh_token) => Rotation::Rotated {
+                    user_id: current.user_id,
+                    session: IssuedSession { session_id: current.family_id, refresh_token },
+                },
+                None => Rotation::Invalid,
+            });
+        }
+        Presented::Reused => {
+            sqlx::query!(
+                "UPDATE refresh_tokens SET revoked_at = NOW() WHERE family_id = $1 AND revoked_at IS NULL",
+                current.family_id
+            )
+            .execute(&mut *conn)
+            .await?;
+
+            warn!(
+                "Refresh token reuse detected for user {}; revoked session {}",
+                current.user_id, current.family_id
+            );
+            return Ok(Rotation::Reused { session_id: current.family_id });
+        }
+        Presented::Invalid => return Ok(Rotation::Invalid),
+    }
+
+    let session = IssuedSession {
+        session_id: current.family_id,
+        refresh_token: Alphanumeric.sample_string(&mut rand::thread_rng(), REFRESH_TOKEN_LENGTH),
+    };
+    let expires_at = now + super::refresh_token_duration();
+
+    sqlx::query!(
+        "INSERT INTO refresh_tokens (user_id, token, expires_at, family_id, ip_address, user_agent)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+        current.user_id,
+        session.refresh_token,
+        expires_at,
+        session.session_id,
+        client_ip(headers),
+        user_agent(headers)
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE refresh_tokens SET revoked_at = NOW(), replaced_by_token = $1 WHERE id = $2",
+        session.refresh_token,
+        current.id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(Rotation::Rotated { user_id: current.user_id, session })
+}
+
+/// Revokes one of the user's sessions, or all of them when `session_id` is `None`.
+/// Returns the sessions that were still live.
+pub async fn revoke(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    session_id: Option<Uuid>,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    let mut revoked = sqlx::query_scalar!(
+        "UPDATE refresh_tokens SET revoked_at = NOW()
+         WHERE user_id = $1 AND ($2::uuid IS NULL OR family_id = $2) AND revoked_at IS NULL
+         RETURNING family_id",
+        user_id,
+        session_id
+    )
+    .fetch_all(conn)
+    .await?;
+
+    revoked.sort();
+    revoked.dedup();
+    Ok(revoked)
+}
+
+fn revoked_session_key(session_id: Uuid) -> String {
+    format!("revoked_session:{}", session_id)
+}
+
+/// Pub/sub channel announcing that a session was revoked, so long-lived connections
+/// such as the event stream can close instead of waiting for their token to expire.
+pub fn revoked_session_channel(session_id: Uuid) -> String {
+    format!("events:revoked_session:{}", session_id)
+}
+
+/// Access tokens are plain JWTs, so revoking the refresh token alone would leave them
+/// working until they expire. Remember revoked sessions for that long so
+/// `reject_revoked_sessions` can turn their access tokens away.
+pub async fn deny_access_tokens(redis: &RedisClient, session_ids: &[Uuid]) {
+    if session_ids.is_empty() {
+        return;
+    }
+
+    let mut conn = match redis.get_async_connection().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            error!("Failed to connect to Redis to revoke sessions: {:?}", e);
+            return;
+        }
+    };
+
+    let ttl = super::access_token_duration().whole_seconds().max(1);
+    for session_id in session_ids {
+        let result: Result<(), _> = redis::cmd("SETEX")
+            .arg(revoked_session_key(*session_id))
+            .arg(ttl)
+            .arg(1)
+            .query_async(&mut conn)
+            .await;
+        if let Err(e) = result {
+            error!("Failed to mark session {} as revoked: {:?}", session_id, e);
+        }
+
+        let published: Result<(), _> = redis::cmd("PUBLISH")
+            .arg(revoked_session_channel(*session_id))
+            .arg(1)
+            .query_async(&mut conn)
+            .await;
+        if let Err(e) = published {
+            error!("Failed to announce revoked session {}: {:?}", session_id, e);
+        }
+    }
+}
+
+/// Whether `session_id` has been revoked. Fails open when Redis is unavailable, like
+/// the rate limiter.
+pub async fn is_revoked(conn: &mut RedisConnection, session_id: Uuid) -> bool {
+    redis::cmd("EXISTS")
+        .arg(revoked_session_key(session_id))
+        .query_async::<_, bool>(conn)
+        .await
+        .unwrap_or(false)
+}
+
+/// Bumps the session's `last_used_at`, at most once per `ACTIVITY_INTERVAL_SECONDS` so
+/// busy sessions don't write on every request.
+pub async fn record_activity(pool: &PgPool, conn: &mut RedisConnection, session_id: Uuid) {
+    let first_in_interval: Option<String> = redis::cmd("SET")
+        .arg(format!("session_activity:{}", session_id))
+        .arg(1)
+        .arg("EX")
+        .arg(ACTIVITY_INTERVAL_SECONDS)
+        .arg("NX")
+        .query_async(conn)
+        .await
+        .unwrap_or(None);
+    if first_in_interval.is_none() {
+        return;
+    }
+
+    if let Err(e) = sqlx::query!(
+        "UPDATE refresh_tokens SET last_used_at = NOW() WHERE family_id = $1 AND revoked_at IS NULL",
+        session_id
+    )
+    .execute(pool)
+    .await
+    {
+        error!("Failed to record activity for session {}: {:?}", session_id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: i64) -> OffsetDateTime {
+        OffsetDateTime::UNIX_EPOCH + time::Duration::seconds(seconds)
+    }
+
+    const NOW: i64 = 1_000_000;
+    const FAR_FUTURE: i64 = NOW + 86_400;
+
+    #[test]
+    fn unused_token_is_live() {
+        assert_eq!(classify(None, false, at(FAR_FUTURE), at(NOW)), Presented::Live);
+    }
+
+    #[test]
+    fn expired_or_revoked_token_is_invalid() {
+        assert_eq!(classify(None, false, at(NOW - 1), at(NOW)), Presented::Invalid);
+        assert_eq!(classify(Some(at(NOW - 1)), false, at(FAR_FUTURE), at(NOW)), Presented::Invalid);
+    }
+
+    #[test]
+    fn token_rotated_within_grace_window_is_not_reuse() {
+        assert_eq!(classify(Some(at(NOW)), true, at(FAR_FUTURE), at(NOW)), Presented::RecentlyRotated);
+        assert_eq!(
+            classify(Some(at(NOW - REUSE_GRACE_SECONDS)), true, at(FAR_FUTURE), at(NOW)),
+            Presented::RecentlyRotated
+        );
+    }
+
+    #[test]
+    fn token_rotated_before_grace_window_is_reuse() {
+        assert_eq!(
+            classify(Some(at(NOW - REUSE_GRACE_SECONDS - 1)), true, at(FAR_FUTURE), at(NOW)),
+            Presented::Reused
+        );
+        assert_eq!(classify(Some(at(NOW - 3600)), true, at(NOW - 1), at(NOW)), Presented::Reused);
+    }
+
+    #[test]
+    fn replaced_token_without_revocation_time_is_reuse() {
+        assert_eq!(classify(None, true, at(FAR_FUTURE), at(NOW)), Presented::Reused);
+    }
+}
@@ -10,6 +10,7 @@ use rand::rngs::OsRng;
 use shared::shared_wheel_game::*;
 use crate::AppState;
 use crate::auth::middleware::UserId;
+use crate::services::ledger_service::{self, PaxEntryKind, SystemAccount};
 use uuid::Uuid;
 use time::OffsetDateTime;
 use serde_json;
@@ -144,13 +145,9 @@ async fn spin_wheel(
     // Apply the appropriate reward
     if is_tiny_pax_win {
         // Award 10 pax
-        let new_pax_balance = new_balance + 10;
-        sqlx::query!(
-            "UPDATE users SET currency_balance = $1 WHERE id = $2",
-            new_pax_balance,
-            user_id.0
+        let new_pax_balance = ledger_service::credit(
+            &mut tx, user_id.0, 10, PaxEntryKind::Wheel, SystemAccount::Mint, None,
         )
-        .execute(&mut *tx)
         .await
         .map_err(|e| {
             (axum::http::StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e))
@@ -160,13 +157,9 @@ async fn spin_wheel(
         new_balance = new_pax_balance;
     } else if is_small_pax_win {
         // Award 20 pax
-        let new_pax_balance = new_balance + 20;
-        sqlx::query!(
-            "UPDATE users SET currency_balance = $1 WHERE id = $2",
-            new_pax_balance,
-            user_id.0
+        let new_pax_balance = ledger_service::credit(
+            &mut tx, user_id.0, 20, PaxEntryKind::Wheel, SystemAccount::Mint, None,
         )
-        .execute(&mut *tx)
         .await
         .map_err(|e| {
             (axum::http::StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e))
@@ -215,13 +208,9 @@ async fn spin_wheel(
         }
     } else if is_big_pax_win {
         // Award 50 pax
-        let new_pax_balance = new_balance + 50;
-        sqlx::query!(
-            "UPDATE users SET currency_balance = $1 WHERE id = $2",
-            new_pax_balance,
-            user_id.0
+        let new_pax_balance = ledger_service::credit(
+            &mut tx, user_id.0, 50, PaxEntryKind::Wheel, SystemAccount::Mint, None,
         )
-        .execute(&mut *tx)
         .await
         .map_err(|e| {
             (axum::http::StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e))
@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use redis::{Client as RedisClient, AsyncCommands};
 use crate::auth::middleware::UserId;
 use crate::AppState;
+use crate::services::friend_service::leaderboard_scope;
 use tracing::{error, info};
 use uuid::Uuid;
 use sqlx;
@@ -1230,11 +1231,13 @@ async fn update_word_game_stats(
 // Add this function to get the leaderboard data
 async fn get_word_leaderboard(
     Extension(app_state): Extension<AppState>,
+    Extension(user_id): Extension<UserId>,
     Query(params): Query<HashMap<String, String>>,
 ) -> Result<Json<Vec<WordLeaderboardEntry>>, StatusCode> {
     let limit = params.get("limit")
         .and_then(|l| l.parse::<i64>().ok())
         .unwrap_or(10);
+    let friends_of = leaderboard_scope(&params, Some(user_id))?;
     
     // First, let's check if there are any entries in the table at all
     let count = sqlx::query_scalar!(
@@ -1257,7 +1260,7 @@ async fn get_word_leaderboard(
         WordLeaderboardEntry,
         r#"
         SELECT 
-            u.username,
+            u.display_name as "username!",
             wgs.current_streak,
             wgs.highest_streak,
             wgs.fastest_time,
@@ -1267,16 +1270,21 @@ async fn get_word_leaderboard(
         FROM 
             word_game_stats wgs
         JOIN 
-            users u ON wgs.user_id = u.id
+            public_users u ON wgs.user_id = u.id
         WHERE 
             wgs.total_words_guessed > 0
+            AND u.on_leaderboards
+            AND ($2::uuid IS NULL
+                 OR u.id = $2
+                 OR u.id IN (SELECT friend_id FROM friendships WHERE user_id = $2))
         ORDER BY 
             wgs.total_words_guessed DESC,
             wgs.highest_streak DESC,
             wgs.fastest_time ASC NULLS LAST
         LIMIT $1
         "#,
-        limit
+        limit,
+        friends_of
     )
     .fetch_all(&app_state.pool)
     .await
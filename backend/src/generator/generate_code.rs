@@ -18,6 +18,7 @@ use argon2::{
     Argon2
 };
 use base64::{Engine as _, engine::general_purpose::URL_SAFE};
+use crate::services::ledger_service::{self, PaxEntryKind, SystemAccount};
 use rand::Rng;
 use sqlx::Transaction;
 use crate::auth::middleware::UserId;
@@ -398,16 +399,21 @@ pub async fn purchase_temporary_membership_handler(
         dt
     };
     
-    // Deduct currency and update membership status
+    // Update membership status and deduct currency
     sqlx::query!(
-        "UPDATE users SET currency_balance = currency_balance - $1, is_member = true, member_until = $2, membership_source = 'purchase' WHERE id = $3",
-        DEFAULT_COST,
+        "UPDATE users SET is_member = true, member_until = $1, membership_source = 'purchase' WHERE id = $2",
         new_member_until,
         user_id.0
     )
     .execute(&mut *tx)
     .await
     .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let new_balance = ledger_service::debit(
+        &mut tx, user_id.0, DEFAULT_COST, PaxEntryKind::MembershipPurchase, SystemAccount::Mint, None,
+    )
+    .await
+    .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     
     // Commit the transaction
     tx.commit().await
@@ -425,7 +431,7 @@ pub async fn purchase_temporary_membership_handler(
     // Return success with new balance
     Ok(axum::Json(serde_json::json!({
         "success": true,
-        "new_balance": user.currency_balance - DEFAULT_COST,
+        "new_balance": new_balance,
         "duration_minutes": duration_minutes,
         "expires_at": formatted_time
     })))
@@ -9,12 +9,52 @@ use reqwest::Client;
 use super::prompts::{EssenceType, AnimalType, ArtStyle, Color};
 use crate::auth::middleware::UserId;
 use crate::AppState;
+use crate::services::achievement_service::{check_achievements, AchievementTrigger};
+use crate::services::creature_bind::RarityType;
+use crate::services::scroll_service::ScrollEffect;
 use time::OffsetDateTime;
 use axum::Json;
 use super::Creature;
 use redis;
 use tracing::info;
 
+/// Chance that a Scroll of Affinity turns the hatch into its target animal.
+const AFFINITY_CHANCE: f64 = 0.75;
+
+/// Rolls the hatched animal and rarity, honouring the scrolls applied to the egg.
+fn roll_hatch_outcome(applied: &[(ScrollEffect, Option<AnimalType>)]) -> (AnimalType, RarityType) {
+    let mut animal: AnimalType = rand::random();
+    let mut rarity = RarityType::Common;
+
+    for (effect, target_animal) in applied {
+        match effect {
+            ScrollEffect::Affinity => {
+                if let Some(target) = target_animal {
+                    if rand::random::<f64>() < AFFINITY_CHANCE {
+                        animal = target.clone();
+                    }
+                }
+            }
+            ScrollEffect::Fortune => {
+                let roll: f64 = rand::random();
+                rarity = if roll < 0.05 {
+                    RarityType::Epic
+                } else if roll < 0.20 {
+                    RarityType::Rare
+                } else if roll < 0.60 {
+                    RarityType::Uncommon
+                } else {
+                    RarityType::Common
+                };
+            }
+            // Haste already shortened incubation when it was applied
+            ScrollEffect::Haste => {}
+        }
+    }
+
+    (animal, rarity)
+}
+
 pub async fn generate_creature(
     State(state): State<AppState>,
     Extension(user_id): Extension<UserId>,
@@ -59,7 +99,7 @@ pub async fn generate_creature(
             incubation_ends_at,
             created_at
         FROM eggs 
-        WHERE id = $1 AND owner_id = $2
+        WHERE id = $1 AND owner_id = $2 AND status <> 'trading'::item_status
         "#,
         egg_id,
         user_id.0
@@ -77,7 +117,24 @@ pub async fn generate_creature(
         return Err(StatusCode::BAD_REQUEST);
     }
 
-    let animal: AnimalType = rand::random();
+    let applied = sqlx::query!(
+        r#"SELECT scroll_name, target_animal::text as target_animal FROM applied_items WHERE egg_id = $1"#,
+        egg_id
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch applied items: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .into_iter()
+    .filter_map(|item| {
+        let effect = ScrollEffect::from_name(&item.scroll_name)?;
+        Some((effect, item.target_animal.and_then(|a| a.parse::<AnimalType>().ok())))
+    })
+    .collect::<Vec<_>>();
+
+    let (animal, rarity) = roll_hatch_outcome(&applied);
     let essence: EssenceType = egg.essence.parse().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     let art_style: ArtStyle = egg.art_style.parse().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     let color: Color = egg.color.parse().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -169,7 +226,7 @@ pub async fn generate_creature(
         egg.color,
         egg.art_style,
         animal.to_string(),
-        "Common",
+        rarity.to_string(),
         None::<time::OffsetDateTime>,  // energy_recharge_complete_at
         0,      // Starting streak
         0,      // Starting soul
@@ -189,6 +246,20 @@ pub async fn generate_creature(
 
     let creature_id = result.id;
 
+    sqlx::query!(
+        "INSERT INTO item_events (item_id, item_type, event_type, to_user_id, performed_by_user_id, additional_data)
+         VALUES ($1, 'creature', 'hatched'::event_type, $2, $2, $3)",
+        creature_id,
+        user_id.0,
+        json!({ "egg_id": egg_id })
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to record hatch event: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
     sqlx::query!(
         "UPDATE eggs SET status = 'locked'::item_status WHERE id = $1",
         egg_id
@@ -212,6 +283,10 @@ pub async fn generate_creature(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    if let Err(e) = check_achievements(&state.pool, user_id.0, AchievementTrigger::CreatureHatched).await {
+        error!("Failed to check achievements: {}", e);
+    }
+
     let creature = sqlx::query_as!(
         Creature,
         r#"
@@ -15,6 +15,7 @@ use crate::AppState;
 use super::{Egg, EggResponse};
 use super::prompts::{ArtStyle, EssenceType, Color};
 use crate::services::scroll_service;
+use crate::services::ledger_service::{self, PaxEntryKind, SystemAccount};
 
 #[derive(Deserialize)]
 pub struct GenerateEggRequest {
@@ -223,13 +224,9 @@ pub async fn generate_egg(
     // Now consume resources and create egg in a single transaction
     let remaining_scrolls = scroll_service::consume_scroll(&mut tx, request.scroll_id).await?;
     
-    let new_balance = user.currency_balance - 55;
-    sqlx::query!(
-        "UPDATE users SET currency_balance = $1 WHERE id = $2",
-        new_balance,
-        owner_id
+    let new_balance = ledger_service::debit(
+        &mut tx, owner_id, 55, PaxEntryKind::EggCost, SystemAccount::Mint, Some(request.scroll_id),
     )
-    .execute(&mut *tx)
     .await
     .map_err(|e| {
         error!("Failed to update balance: {}", e);
@@ -1,9 +1,13 @@
 use crate::services::user_service::{self, UserLeaderboardEntry, LeaderboardEntry};
+use crate::services::friend_service::leaderboard_scope;
+use crate::services::ladder_service;
+use crate::auth::middleware::UserId;
 use axum::{
-    extract::{State, Path},
+    extract::{State, Path, Query},
     response::{Json},
     http::StatusCode,
 };
+use std::collections::HashMap;
 use tracing::{error, debug};
 use crate::AppState;
 
@@ -20,15 +24,25 @@ pub async fn user_leaderboard_handler(
     }
 }
 
-/// Handler to retrieve game leaderboards
+/// Handler to retrieve game leaderboards; `scope=friends` limits it to the caller and their friends.
+/// `ladder` serves current-season battle ratings in the same shape.
 pub async fn game_leaderboard_handler(
     State(state): State<AppState>,
     Path(game_type): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+    user_id: Option<UserId>,
 ) -> Result<Json<Vec<LeaderboardEntry>>, StatusCode> {
     debug!("Entering game_leaderboard_handler for game_type: {}", game_type);
     let limit = 10;
+    let friends_of = leaderboard_scope(&params, user_id)?;
     
-    match user_service::get_game_leaderboard(&state.pool, &game_type, limit).await {
+    let entries = if game_type == "ladder" {
+        ladder_service::get_ladder_leaderboard(&state.pool, limit, friends_of).await
+    } else {
+        user_service::get_game_leaderboard(&state.pool, &game_type, limit, friends_of).await
+    };
+
+    match entries {
         Ok(entries) => {
             debug!("Successfully fetched {} leaderboard entries for game_type: {}", entries.len(), game_type);
             match serde_json::to_string(&entries) {
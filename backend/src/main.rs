@@ -29,6 +29,8 @@ use once_cell::sync::Lazy;
 use std::time::{SystemTime, UNIX_EPOCH, Duration};
 use time;
 use crate::services::patreon_link_service;
+use crate::services::{push_service, notification_service};
+use shared::push_events::PushEvent;
 use crate::handlers::user_leaderboard_handler;
 use crate::handlers::game_leaderboard_handler;
 
@@ -36,9 +38,13 @@ use crate::auth::routes::{
     login, register, refresh_token,
     change_email, change_password, delete_account, get_profile,
     request_magic_link, verify_magic_link,
+    request_password_reset, verify_reset_code, reset_password,
+    get_two_factor_status, enroll_two_factor, confirm_two_factor, regenerate_recovery_codes,
+    disable_two_factor, verify_two_factor_login,
+    get_sessions, revoke_session, revoke_all_sessions,
     request_delete_account, verify_delete_account
 };
-use crate::auth::middleware::require_auth;
+use crate::auth::middleware::{require_auth, reject_revoked_sessions};
 use crate::generator::{generate_egg, generate_creature, membership_code_routes};
 use crate::services::{
     creature_service::*, 
@@ -47,9 +53,34 @@ use crate::services::{
     chaos_realm::*,
     energy_service::{handle_energy_recharge, check_expired_energy_recharges},
     market_service::*,
+    auction_service::{create_auction, get_active_auctions, place_bid, settle_expired_auctions},
+    price_history_service::get_price_history,
+    transfer_service::transfer_item,
+    item_history_service::get_item_history,
+    push_service::events_ws_handler,
+    notification_service::{get_notifications, mark_notification_read, mark_all_notifications_read, NotificationKind},
+    email_digest_service::{self, confirm_unsubscribe_email_digest, unsubscribe_email_digest},
+    preferences_service::{get_preferences, update_preferences},
+    public_profile_service::get_public_profile,
+    friend_service::{
+        get_friends, send_friend_request, accept_friend_request, remove_friend,
+        block_user, unblock_user, get_friend_activity,
+    },
+    guild_service::{
+        get_my_guild, create_guild, invite_to_guild, get_guild_invites, accept_guild_invite,
+        decline_guild_invite, leave_guild, set_member_role, remove_guild_member,
+        deposit_to_treasury, spend_from_treasury, get_guild_leaderboard,
+    },
+    battle_service::{start_battle, get_battles, get_battle},
+    ladder_service::{self, get_ladder_status, start_ranked_battle},
+    ledger_service,
+    wallet_service::{get_wallet_transactions, get_wallet_summary},
+    trade_service::{get_trade_offers, create_trade_offer, accept_trade_offer, counter_trade_offer, decline_trade_offer, cancel_trade_offer},
     scroll_service::*,
-    orderbook_service::{create_order, get_orders, cancel_order, fulfill_order},
+    crafting_service::{get_recipes, craft_recipe},
+    orderbook_service::{create_order, get_orders, cancel_order, fulfill_order, get_recent_trades, get_last_price},
     magic_button_service,
+    achievement_service::get_achievements,
     patreon_link_service::{link_patreon_account, unlink_patreon_account, get_patreon_status, get_oauth_url, handle_oauth_callback},
 };
 use crate::games::backend_match_game::{create_router as create_match_game_router, GameState};
@@ -110,7 +141,7 @@ pub async fn leaderboard_handler(
         .and_then(|s| s.parse::<i64>().ok())
         .unwrap_or(10);
         
-    match get_game_leaderboard(&state.pool, &game_type, limit).await {
+    match get_game_leaderboard(&state.pool, &game_type, limit, None).await {
         Ok(entries) => Ok(Json(entries)),
         Err(e) => {
             tracing::error!("Failed to get leaderboard for game {}: {}", game_type, e);
@@ -563,17 +594,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Check for creatures with expired energy recharge times on startup
     info!("Checking for creatures with expired energy recharge times on startup...");
-    if let Err(e) = check_expired_energy_recharges(&state.pool).await {
+    if let Err(e) = check_expired_energy_recharges(&state.pool, &state.redis).await {
         error!("Error checking expired energy recharges on startup: {:?}", e);
     }
 
     // Start background task to check expired memberships
     let pool_clone = state.pool.clone();
+    let redis_clone = state.redis.clone();
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(3600)); // Every hour
         loop {
             interval.tick().await;
-            if let Err(e) = check_expired_memberships(&pool_clone).await {
+            if let Err(e) = check_expired_memberships(&pool_clone, &redis_clone).await {
                 error!("Error checking expired memberships: {:?}", e);
             }
             if let Err(e) = cleanup_expired_membership_codes(&pool_clone).await {
@@ -588,9 +620,49 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 error!("Error refreshing Patreon tokens: {:?}", e);
             }
             // Check for creatures with expired energy recharge times
-            if let Err(e) = check_expired_energy_recharges(&pool_clone).await {
+            if let Err(e) = check_expired_energy_recharges(&pool_clone, &redis_clone).await {
                 error!("Error checking expired energy recharges: {:?}", e);
             }
+            // Close the ladder season once it ends and open the next one
+            if let Err(e) = ladder_service::close_expired_seasons(&pool_clone, &redis_clone).await {
+                error!("Error closing ladder seasons: {:?}", e);
+            }
+            // Check every balance against the pax ledger
+            if let Err(e) = ledger_service::reconcile(&pool_clone).await {
+                error!("Error reconciling pax ledger: {:?}", e);
+            }
+        }
+    });
+
+    // Settle expired auctions and announce finished chaos realm runs every minute, so
+    // players don't wait for the hourly sweep
+    let pool_clone = state.pool.clone();
+    let redis_clone = state.redis.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = settle_expired_auctions(&pool_clone, &redis_clone).await {
+                error!("Error settling expired auctions: {:?}", e);
+            }
+            if let Err(e) = push_completed_runs(&pool_clone, &redis_clone).await {
+                error!("Error announcing finished chaos realm runs: {:?}", e);
+            }
+        }
+    });
+
+    // Queue claim streak warnings and send any email digests that are due
+    let pool_clone = state.pool.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(600));
+        loop {
+            interval.tick().await;
+            if let Err(e) = email_digest_service::queue_streak_warnings(&pool_clone).await {
+                error!("Error queueing claim streak warnings: {:?}", e);
+            }
+            if let Err(e) = email_digest_service::send_due_digests(&pool_clone).await {
+                error!("Error sending email digests: {:?}", e);
+            }
         }
     });
 
@@ -599,19 +671,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/register", post(register))
         .route("/refresh", post(refresh_token))
         .route("/magic-link/request", post(request_magic_link))
-        .route("/magic-link/verify", post(verify_magic_link));
+        .route("/magic-link/verify", post(verify_magic_link))
+        .route("/two-factor/verify", post(verify_two_factor_login));
 
     let user_routes = Router::new()
         .route("/me", axum::routing::delete(delete_account))
         .route("/me/email", axum::routing::put(change_email))
         .route("/me/password", axum::routing::put(change_password))
         .route("/me/delete-request", axum::routing::post(request_delete_account))
-        .route("/me/verify-delete", axum::routing::post(verify_delete_account));
+        .route("/me/verify-delete", axum::routing::post(verify_delete_account))
+        .route("/me/two-factor", get(get_two_factor_status))
+        .route("/me/two-factor/enroll", post(enroll_two_factor))
+        .route("/me/two-factor/confirm", post(confirm_two_factor))
+        .route("/me/two-factor/recovery-codes", post(regenerate_recovery_codes))
+        .route("/me/two-factor/disable", post(disable_two_factor))
+        .route("/me/sessions", get(get_sessions).delete(revoke_all_sessions))
+        .route("/me/sessions/:id", axum::routing::delete(revoke_session))
+        .route("/:username", get(get_public_profile));
 
     let protected_routes = Router::new()
         .route("/api/eggs", axum::routing::get(get_user_eggs).post(generate_egg))
         .route("/api/generator/generate-egg", axum::routing::post(generate_egg))
         .route("/api/eggs/:id/generate-creature", post(generate_creature))
+        .route("/api/eggs/:id/applied-items", get(get_applied_items))
+        .route("/api/eggs/:id/apply-scroll", post(apply_scroll))
         .route("/api/creatures", axum::routing::get(get_user_creatures))
         .route("/api/creatures/:id/bind", post(bind_creature))
         .route("/api/creatures/:id/rename", post(rename_creature_handler))
@@ -619,8 +702,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/api/creatures/:id/chaos-realm/enter", post(enter_chaos_realm))
         .route("/api/creatures/:id/chaos-realm/claim", post(claim_chaos_realm_reward))
         .route("/api/creatures/:id/chaos-realm/status", axum::routing::get(get_chaos_realm_status))
+        .route("/api/creatures/:id/battle", post(start_battle))
+        .route("/api/battles", get(get_battles))
+        .route("/api/battles/:id", get(get_battle))
+        .route("/api/ladder", get(get_ladder_status))
+        .route("/api/ladder/battle", post(start_ranked_battle))
         .route("/api/scrolls", axum::routing::get(get_scrolls))
         .route("/api/scrolls/:id", axum::routing::get(get_scroll_by_id))
+        .route("/api/recipes", get(get_recipes))
+        .route("/api/recipes/:id/craft", post(craft_recipe))
         .route("/api/profile", axum::routing::get(get_profile))
         .route("/api/daily-claim", post(claim_service::claim_daily_reward))
         .route("/api/daily-claim/reset-streak", post(claim_service::reset_claim_streak))
@@ -633,13 +723,49 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/api/market/listings/:id/buy", post(purchase_item))
         .route("/api/market/listings/:id", delete(cancel_listing))
         .route("/api/market/listings/:id/item", get(get_listing_item))
+        .route("/api/market/auctions", get(get_active_auctions).post(create_auction))
+        .route("/api/market/auctions/:id/bid", post(place_bid))
+        .route("/api/market/history", get(get_price_history))
+        .route("/api/items/:id/transfer", post(transfer_item))
+        .route("/api/items/:id/history", get(get_item_history))
+        .route("/api/trades", get(get_trade_offers).post(create_trade_offer))
+        .route("/api/trades/:id/accept", post(accept_trade_offer))
+        .route("/api/trades/:id/counter", post(counter_trade_offer))
+        .route("/api/trades/:id/decline", post(decline_trade_offer))
+        .route("/api/trades/:id/cancel", post(cancel_trade_offer))
         .route("/api/scrolls/orders", get(get_orders))
         .route("/api/scrolls/orders", post(create_order))
         .route("/api/scrolls/orders/:id", delete(cancel_order))
         .route("/api/scrolls/orders/:id/fulfill", post(fulfill_order))
+        .route("/api/scrolls/trades", get(get_recent_trades))
+        .route("/api/scrolls/trades/last", get(get_last_price))
         .route("/api/webhooks/patreon", post(patreon_handler::patreon_webhook_handler))
         .route("/api/magic-button", post(magic_button_service::handle_magic_button))
         .route("/api/magic-button/status", get(magic_button_service::get_magic_button_status))
+        .route("/api/achievements", get(get_achievements))
+        .route("/api/wallet/transactions", get(get_wallet_transactions))
+        .route("/api/wallet/summary", get(get_wallet_summary))
+        .route("/api/notifications", get(get_notifications))
+        .route("/api/notifications/read-all", post(mark_all_notifications_read))
+        .route("/api/notifications/:id/read", post(mark_notification_read))
+        .route("/api/friends", get(get_friends))
+        .route("/api/friends/requests", post(send_friend_request))
+        .route("/api/friends/activity", get(get_friend_activity))
+        .route("/api/friends/blocks", post(block_user))
+        .route("/api/friends/blocks/:id", delete(unblock_user))
+        .route("/api/friends/:id", delete(remove_friend))
+        .route("/api/friends/:id/accept", post(accept_friend_request))
+        .route("/api/guilds", post(create_guild))
+        .route("/api/guilds/mine", get(get_my_guild))
+        .route("/api/guilds/leave", post(leave_guild))
+        .route("/api/guilds/invites", get(get_guild_invites).post(invite_to_guild))
+        .route("/api/guilds/invites/:id/accept", post(accept_guild_invite))
+        .route("/api/guilds/invites/:id", delete(decline_guild_invite))
+        .route("/api/guilds/members/:id/role", post(set_member_role))
+        .route("/api/guilds/members/:id", delete(remove_guild_member))
+        .route("/api/guilds/treasury/deposit", post(deposit_to_treasury))
+        .route("/api/guilds/treasury/spend", post(spend_from_treasury))
+        .route("/api/settings/preferences", get(get_preferences).put(update_preferences))
         .route("/api/settings/patreon/link", post(link_patreon_account))
         .route("/api/settings/patreon/unlink", post(unlink_patreon_account))
         .route("/api/settings/patreon/status", get(get_patreon_status))
@@ -725,6 +851,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/api/patreon/fetch", post(patreon_handler::fetch_supporters_handler))
         .route("/api/stats/global", axum::routing::get(get_global_stats))
         .route("/api/creatures/showcase", axum::routing::get(get_public_showcase_creatures))
+        .route("/api/events", get(events_ws_handler))
+        .route("/api/email/unsubscribe", get(confirm_unsubscribe_email_digest).post(unsubscribe_email_digest))
+        .route("/api/forgot-password/request", post(request_password_reset))
+        .route("/api/forgot-password/verify", post(verify_reset_code))
+        .route("/api/forgot-password/reset", post(reset_password))
         .layer(Extension(state.clone()))
         .nest("/api/auth", auth_routes)
         .nest("/api/users", user_routes)
@@ -732,6 +863,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .merge(membership_code_routes(state.pool.clone()))
         .nest_service("/static", final_static_service)
         .route("/api/leaderboard/users", get(user_leaderboard_handler))
+        .route("/api/leaderboard/guilds", get(get_guild_leaderboard))
         .route("/api/leaderboard/:game_type", get(game_leaderboard_handler))
         .layer(cors.clone())
         .layer(middleware::from_fn_with_state(state.clone(), ip_rate_limit_middleware))
@@ -759,6 +891,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .nest("/hexort", create_hexort_game_router()
             .with_state(state.clone())
             .layer(cors.clone()))
+        .layer(middleware::from_fn_with_state(state.clone(), reject_revoked_sessions))
         .route("/", axum::routing::get(serve_frontend_index))
         .fallback(serve_frontend_index)
         .with_state(state.clone());
@@ -806,19 +939,32 @@ async fn csrf_token_middleware(
 }
 
 /// Check and update expired memberships
-async fn check_expired_memberships(pool: &PgPool) -> Result<(), sqlx::Error> {
+async fn check_expired_memberships(pool: &PgPool, redis: &RedisClient) -> Result<(), sqlx::Error> {
     // Find users with expired memberships
     let now = time::OffsetDateTime::now_utc();
     
     // Update users with expired memberships
-    sqlx::query!(
+    let expired = sqlx::query_scalar!(
         "UPDATE users SET is_member = false, member_until = NULL 
-         WHERE is_member = true AND member_until < $1",
+         WHERE is_member = true AND member_until < $1
+         RETURNING id",
         now,
     )
-    .execute(pool)
+    .fetch_all(pool)
     .await?;
 
+    for user_id in expired {
+        push_service::publish(redis, user_id, &PushEvent::MembershipExpired).await;
+        notification_service::notify(
+            pool,
+            redis,
+            user_id,
+            NotificationKind::MembershipExpired,
+            "Your membership has expired".to_string(),
+            serde_json::json!({}),
+        ).await;
+    }
+
     Ok(())
 }
 
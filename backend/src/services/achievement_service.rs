@@ -0,0 +1,331 @@
+use axum::{
+    extract::{State, Extension},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::auth::middleware::UserId;
+use crate::AppState;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
+
+/// The game event that caused an achievement check. Only achievements whose
+/// requirement can change on that event are evaluated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AchievementTrigger {
+    CreatureHatched,
+    SoulBind,
+    DailyClaim,
+    GameReward,
+}
+
+/// Requirement predicate stored in `achievements.requirements`,
+/// e.g. `{"type": "creatures_hatched", "count": 10}`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Requirement {
+    CreaturesHatched { count: i64 },
+    RarityReached { rarity: String },
+    ClaimStreak { days: i32 },
+    GameHighScore { game_type: String, score: i32 },
+    WordsGuessed { count: i32 },
+}
+
+impl Requirement {
+    fn triggered_by(&self, trigger: AchievementTrigger) -> bool {
+        matches!(
+            (self, trigger),
+            (Self::CreaturesHatched { .. }, AchievementTrigger::CreatureHatched)
+                | (Self::RarityReached { .. }, AchievementTrigger::SoulBind)
+                | (Self::ClaimStreak { .. }, AchievementTrigger::DailyClaim)
+                | (Self::GameHighScore { .. }, AchievementTrigger::GameReward)
+                | (Self::WordsGuessed { .. }, AchievementTrigger::GameReward)
+        )
+    }
+}
+
+/// Rewards stored in `achievements.rewards`, e.g. `{"pax": 50, "scrolls": 1, "experience": 25}`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AchievementRewards {
+    #[serde(default)]
+    pub pax: i32,
+    #[serde(default)]
+    pub scrolls: i32,
+    #[serde(default)]
+    pub experience: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AchievementResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub rewards: AchievementRewards,
+    pub progress: i64,
+    pub target: i64,
+    pub completed: bool,
+    pub completed_at: Option<String>,
+}
+
+/// Returns (progress, target) for a requirement. The requirement is met once progress reaches target.
+async fn requirement_progress(
+    pool: &PgPool,
+    user_id: Uuid,
+    requirement: &Requirement,
+) -> Result<(i64, i64), sqlx::Error> {
+    match requirement {
+        Requirement::CreaturesHatched { count } => {
+            let hatched = sqlx::query_scalar!(
+                "SELECT COUNT(*) FROM creatures WHERE hatched_by = $1",
+                user_id
+            )
+            .fetch_one(pool)
+            .await?
+            .unwrap_or(0);
+            Ok((hatched, *count))
+        }
+        Requirement::RarityReached { rarity } => {
+            // Only rarities the user reached by soul binding count, not creatures bought or
+            // traded in at that rarity. rarity_type is declared in ascending order, so enum
+            // comparison ranks tiers.
+            let reached = sqlx::query_scalar!(
+                r#"SELECT EXISTS(
+                    SELECT 1 FROM item_events
+                    WHERE performed_by_user_id = $1
+                      AND event_type = 'soul_bound'::event_type
+                      AND (additional_data->>'to_rarity')::rarity_type >= $2::text::rarity_type
+                ) as "exists!""#,
+                user_id,
+                rarity
+            )
+            .fetch_one(pool)
+            .await?;
+            Ok((reached as i64, 1))
+        }
+        Requirement::ClaimStreak { days } => {
+            let streak = sqlx::query_scalar!(
+                "SELECT claim_streak FROM users WHERE id = $1",
+                user_id
+            )
+            .fetch_one(pool)
+            .await?;
+            Ok((streak as i64, *days as i64))
+        }
+        Requirement::GameHighScore { game_type, score } => {
+            let high_score = sqlx::query_scalar!(
+                "SELECT high_score FROM game_leaderboard WHERE user_id = $1 AND game_type = $2",
+                user_id,
+                game_type
+            )
+            .fetch_optional(pool)
+            .await?
+            .unwrap_or(0);
+            Ok((high_score as i64, *score as i64))
+        }
+        Requirement::WordsGuessed { count } => {
+            let guessed = sqlx::query_scalar!(
+                "SELECT total_words_guessed FROM word_game_stats WHERE user_id = $1",
+                user_id
+            )
+            .fetch_optional(pool)
+            .await?
+            .unwrap_or(0);
+            Ok((guessed as i64, *count as i64))
+        }
+    }
+}
+
+/// Marks the achievement as completed and pays out its rewards.
+/// Returns false if another request already granted it.
+async fn grant_achievement(
+    pool: &PgPool,
+    user_id: Uuid,
+    achievement_id: Uuid,
+    name: &str,
+    rewards: &AchievementRewards,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let inserted = sqlx::query!(
+        "INSERT INTO user_achievements (user_id, achievement_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        user_id,
+        achievement_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if inserted.rows_affected() == 0 {
+        tx.rollback().await?;
+        return Ok(false);
+    }
+
+    if rewards.pax > 0 {
+        ledger_service::credit(
+            &mut tx, user_id, rewards.pax, PaxEntryKind::AchievementReward, SystemAccount::Mint, Some(achievement_id),
+        ).await?;
+    }
+
+    if rewards.scrolls > 0 {
+        let now = OffsetDateTime::now_utc();
+        let updated_scroll = sqlx::query!(
+            r#"
+            UPDATE scrolls
+            SET quantity = quantity + $1,
+                updated_at = $2
+            WHERE owner_id = $3 AND display_name = 'Summoning Scroll'
+            RETURNING quantity
+            "#,
+            rewards.scrolls,
+            now,
+            user_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if updated_scroll.is_none() {
+            sqlx::query!(
+                r#"
+                INSERT INTO scrolls (
+                    id, owner_id, created_at, updated_at, display_name,
+                    image_path, description, quantity, item_type
+                )
+                VALUES (
+                    $1, $2, $3, $3, 'Summoning Scroll',
+                    '/static/images/scroll-default.avif',
+                    'A scroll used to summon an egg',
+                    $4, 'scroll'
+                )
+                "#,
+                Uuid::new_v4(),
+                user_id,
+                now,
+                rewards.scrolls
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    if rewards.experience > 0 {
+        super::user_service::add_experience(&mut tx, user_id, rewards.experience).await?;
+    }
+
+    tx.commit().await?;
+
+    info!("🏆 User {} unlocked achievement '{}' (+{} pax, +{} scrolls, +{} XP)",
+        user_id, name, rewards.pax, rewards.scrolls, rewards.experience);
+
+    Ok(true)
+}
+
+/// Evaluates every uncompleted achievement relevant to `trigger` and grants those now met.
+/// Returns the names of newly unlocked achievements.
+pub async fn check_achievements(
+    pool: &PgPool,
+    user_id: Uuid,
+    trigger: AchievementTrigger,
+) -> Result<Vec<String>, sqlx::Error> {
+    let pending = sqlx::query!(
+        r#"
+        SELECT a.id, a.name, a.requirements, a.rewards
+        FROM achievements a
+        WHERE NOT EXISTS (
+            SELECT 1 FROM user_achievements ua
+            WHERE ua.user_id = $1 AND ua.achievement_id = a.id
+        )
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut unlocked = Vec::new();
+
+    for achievement in pending {
+        let requirement: Requirement = match serde_json::from_value(achievement.requirements) {
+            Ok(requirement) => requirement,
+            Err(e) => {
+                warn!("Skipping achievement '{}' with invalid requirements: {}", achievement.name, e);
+                continue;
+            }
+        };
+
+        if !requirement.triggered_by(trigger) {
+            continue;
+        }
+
+        let (progress, target) = requirement_progress(pool, user_id, &requirement).await?;
+        if progress < target {
+            continue;
+        }
+
+        let rewards: AchievementRewards = serde_json::from_value(achievement.rewards).unwrap_or_default();
+        if grant_achievement(pool, user_id, achievement.id, &achievement.name, &rewards).await? {
+            unlocked.push(achievement.name);
+        }
+    }
+
+    Ok(unlocked)
+}
+
+pub async fn get_achievements(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<Vec<AchievementResponse>>, StatusCode> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT a.id, a.name, a.description, a.requirements, a.rewards,
+               ua.completed_at::text as "completed_at?"
+        FROM achievements a
+        LEFT JOIN user_achievements ua
+            ON ua.achievement_id = a.id AND ua.user_id = $1
+        ORDER BY a.name
+        "#,
+        user_id.0
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch achievements: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut achievements = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let requirement: Requirement = match serde_json::from_value(row.requirements) {
+            Ok(requirement) => requirement,
+            Err(e) => {
+                warn!("Skipping achievement '{}' with invalid requirements: {}", row.name, e);
+                continue;
+            }
+        };
+
+        let (progress, target) = requirement_progress(&state.pool, user_id.0, &requirement)
+            .await
+            .map_err(|e| {
+                error!("Failed to compute progress for achievement '{}': {}", row.name, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+        let completed = row.completed_at.is_some();
+
+        achievements.push(AchievementResponse {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            rewards: serde_json::from_value(row.rewards).unwrap_or_default(),
+            progress: if completed { target } else { progress.min(target) },
+            target,
+            completed,
+            completed_at: row.completed_at,
+        });
+    }
+
+    Ok(Json(achievements))
+}
+
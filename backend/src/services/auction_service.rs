@@ -0,0 +1,715 @@
+use axum::{
+    extract::{State, Json, Extension, Path},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use time::OffsetDateTime;
+use uuid::Uuid;
+use crate::AppState;
+use crate::auth::middleware::UserId;
+use tracing::{error, info};
+use super::market_service::ApiResponse;
+use super::price_history_service::record_sale;
+use super::push_service;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
+use super::notification_service::{notify, NotificationKind};
+use super::email_digest_service;
+use redis::Client as RedisClient;
+use shared::push_events::PushEvent;
+
+const AUCTION_LISTING_FEE: i32 = 5;
+const MIN_AUCTION_DURATION_HOURS: i64 = 1;
+const MAX_AUCTION_DURATION_HOURS: i64 = 168; // 7 days
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAuctionRequest {
+    pub item_id: Uuid,
+    pub item_type: String,
+    pub starting_price: i32,
+    pub reserve_price: Option<i32>,
+    pub min_increment: Option<i32>,
+    pub duration_hours: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlaceBidRequest {
+    pub amount: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuctionListing {
+    pub id: Uuid,
+    pub seller_id: Uuid,
+    pub seller_username: String,
+    pub item_id: Uuid,
+    pub item_type: String,
+    pub starting_price: i32,
+    pub min_increment: i32,
+    pub current_bid: Option<i32>,
+    pub current_bidder_username: Option<String>,
+    pub next_min_bid: i32,
+    pub reserve_met: bool,
+    pub ends_at: String,
+    pub created_at: String,
+}
+
+/// Smallest bid that will be accepted given the current auction state.
+fn next_min_bid(starting_price: i32, current_bid: Option<i32>, min_increment: i32) -> i32 {
+    match current_bid {
+        Some(bid) => bid.saturating_add(min_increment),
+        None => starting_price,
+    }
+}
+
+// Create a new auction listing
+pub async fn create_auction(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Json(payload): Json<CreateAuctionRequest>,
+) -> Result<Json<ApiResponse<AuctionListing>>, StatusCode> {
+    let min_increment = payload.min_increment.unwrap_or(1);
+
+    if payload.starting_price <= 0 || min_increment <= 0 {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Starting price and bid increment must be positive".to_string()),
+        }));
+    }
+
+    if let Some(reserve) = payload.reserve_price {
+        if reserve < payload.starting_price {
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some("Reserve price cannot be below the starting price".to_string()),
+            }));
+        }
+    }
+
+    if !(MIN_AUCTION_DURATION_HOURS..=MAX_AUCTION_DURATION_HOURS).contains(&payload.duration_hours) {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!(
+                "Auction duration must be between {} and {} hours",
+                MIN_AUCTION_DURATION_HOURS, MAX_AUCTION_DURATION_HOURS
+            )),
+        }));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let seller = sqlx::query!(
+        "SELECT username, currency_balance FROM users WHERE id = $1 FOR UPDATE",
+        user_id.0
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch seller: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    info!("📋 User {} is creating an auction for {} {} starting at {} PAX",
+          seller.username, payload.item_type, payload.item_id, payload.starting_price);
+
+    let owner_check = match payload.item_type.as_str() {
+        "egg" => sqlx::query!(
+            "SELECT owner_id, status::text as status FROM eggs WHERE id = $1 FOR UPDATE",
+            payload.item_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map(|row| row.map(|r| (r.owner_id, r.status))),
+        "creature" => sqlx::query!(
+            "SELECT owner_id, status::text as status FROM creatures WHERE id = $1 FOR UPDATE",
+            payload.item_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map(|row| row.map(|r| (r.owner_id, r.status))),
+        _ => {
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some("Invalid item type".to_string()),
+            }));
+        }
+    }
+    .map_err(|e| {
+        error!("Failed to check item ownership: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let (owner_id, status) = match owner_check {
+        Some(check) => check,
+        None => {
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some("Item not found".to_string()),
+            }));
+        }
+    };
+
+    if owner_id != user_id.0 {
+        info!("❌ Auction creation failed: User {} doesn't own {} {}",
+              seller.username, payload.item_type, payload.item_id);
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("You don't own this item".to_string()),
+        }));
+    }
+
+    if status.as_deref() == Some("trading") {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Item is part of a pending trade".to_string()),
+        }));
+    }
+
+    if status.as_deref() != Some("available") {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Item is not available for listing".to_string()),
+        }));
+    }
+
+    if seller.currency_balance < AUCTION_LISTING_FEE {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Insufficient funds for listing fee ({} PAX required)", AUCTION_LISTING_FEE)),
+        }));
+    }
+
+    ledger_service::debit(
+        &mut tx, user_id.0, AUCTION_LISTING_FEE, PaxEntryKind::ListingFee, SystemAccount::Mint, Some(payload.item_id),
+    )
+    .await
+    .map_err(|e| {
+        error!("Failed to deduct listing fee: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let ends_at = OffsetDateTime::now_utc() + time::Duration::hours(payload.duration_hours);
+
+    let listing = sqlx::query!(
+        "INSERT INTO market_listings (
+            seller_id, item_id, item_type, price, quantity, status, type,
+            reserve_price, min_increment, ends_at
+        )
+        VALUES ($1, $2, $3, $4, 1, 'active'::market_status_type, 'auction'::market_type, $5, $6, $7)
+        RETURNING id, created_at",
+        user_id.0,
+        payload.item_id,
+        payload.item_type,
+        payload.starting_price,
+        payload.reserve_price,
+        min_increment,
+        ends_at
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to create auction listing: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    match payload.item_type.as_str() {
+        "egg" => sqlx::query!(
+            "UPDATE eggs SET status = 'locked'::item_status WHERE id = $1",
+            payload.item_id
+        )
+        .execute(&mut *tx)
+        .await,
+        _ => sqlx::query!(
+            "UPDATE creatures SET status = 'locked'::item_status WHERE id = $1",
+            payload.item_id
+        )
+        .execute(&mut *tx)
+        .await,
+    }
+    .map_err(|e| {
+        error!("Failed to lock auctioned item: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    sqlx::query!(
+        "INSERT INTO item_events (item_id, item_type, event_type, from_user_id, performed_by_user_id, additional_data)
+        VALUES ($1, $2, 'listed_for_sale'::event_type, $3, $3, $4)",
+        payload.item_id,
+        payload.item_type,
+        user_id.0,
+        serde_json::json!({ "listing_id": listing.id, "auction": true, "starting_price": payload.starting_price })
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to create item event: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tx.commit().await.map_err(|e| {
+        error!("Failed to commit transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    info!("✅ Auction created: User {} listed {} {} starting at {} PAX, ending {} (listing ID: {})",
+          seller.username, payload.item_type, payload.item_id, payload.starting_price, ends_at, listing.id);
+
+    push_service::broadcast(&state.redis, &PushEvent::MarketChanged).await;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(AuctionListing {
+            id: listing.id,
+            seller_id: user_id.0,
+            seller_username: seller.username,
+            item_id: payload.item_id,
+            item_type: payload.item_type,
+            starting_price: payload.starting_price,
+            min_increment,
+            current_bid: None,
+            current_bidder_username: None,
+            next_min_bid: payload.starting_price,
+            reserve_met: payload.reserve_price.is_none(),
+            ends_at: ends_at.to_string(),
+            created_at: listing.created_at.to_string(),
+        }),
+        error: None,
+    }))
+}
+
+// Get all running auctions, soonest ending first
+pub async fn get_active_auctions(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<AuctionListing>>>, StatusCode> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT m.id, m.seller_id, u.display_name as "seller_username!", m.item_id, m.item_type,
+               m.price, m.reserve_price, m.min_increment as "min_increment!", m.current_bid,
+               b.display_name as "current_bidder_username?", m.ends_at as "ends_at!", m.created_at
+        FROM market_listings m
+        JOIN public_users u ON m.seller_id = u.id
+        LEFT JOIN public_users b ON m.current_bidder_id = b.id
+        WHERE m.status = 'active'::market_status_type
+        AND m.type = 'auction'::market_type
+        AND m.ends_at > NOW()
+        ORDER BY m.ends_at ASC
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch auctions: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let auctions = rows
+        .into_iter()
+        .map(|a| AuctionListing {
+            id: a.id,
+            seller_id: a.seller_id,
+            seller_username: a.seller_username,
+            item_id: a.item_id,
+            item_type: a.item_type,
+            starting_price: a.price,
+            min_increment: a.min_increment,
+            current_bid: a.current_bid,
+            current_bidder_username: a.current_bidder_username,
+            next_min_bid: next_min_bid(a.price, a.current_bid, a.min_increment),
+            reserve_met: match (a.reserve_price, a.current_bid) {
+                (None, _) => true,
+                (Some(reserve), Some(bid)) => bid >= reserve,
+                (Some(_), None) => false,
+            },
+            ends_at: a.ends_at.to_string(),
+            created_at: a.created_at.to_string(),
+        })
+        .collect();
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(auctions),
+        error: None,
+    }))
+}
+
+// Place a bid. The bid amount is escrowed from the bidder and the previous high bidder is refunded.
+pub async fn place_bid(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(listing_id): Path<Uuid>,
+    Json(payload): Json<PlaceBidRequest>,
+) -> Result<Json<ApiResponse<i32>>, StatusCode> {
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let listing = sqlx::query!(
+        r#"SELECT seller_id, item_id, item_type, price, min_increment as "min_increment!",
+                  current_bid, current_bidder_id, ends_at as "ends_at!"
+         FROM market_listings
+         WHERE id = $1 AND status = 'active'::market_status_type AND type = 'auction'::market_type
+         FOR UPDATE"#,
+        listing_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch auction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let listing = match listing {
+        Some(l) => l,
+        None => return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Auction not found or not active".to_string()),
+        })),
+    };
+
+    if listing.ends_at <= OffsetDateTime::now_utc() {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("This auction has ended".to_string()),
+        }));
+    }
+
+    if listing.seller_id == user_id.0 {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("You cannot bid on your own auction".to_string()),
+        }));
+    }
+
+    let minimum = next_min_bid(listing.price, listing.current_bid, listing.min_increment);
+    if payload.amount < minimum {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Bid must be at least {} PAX", minimum)),
+        }));
+    }
+
+    let bidder = sqlx::query!(
+        "SELECT username, currency_balance FROM users WHERE id = $1 FOR UPDATE",
+        user_id.0
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch bidder: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    // A bidder raising their own bid only needs to cover the difference
+    let already_escrowed = if listing.current_bidder_id == Some(user_id.0) {
+        listing.current_bid.unwrap_or(0)
+    } else {
+        0
+    };
+
+    if bidder.currency_balance + already_escrowed < payload.amount {
+        info!("❌ Bid failed: User {} has insufficient funds to bid {} PAX", bidder.username, payload.amount);
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Insufficient funds".to_string()),
+        }));
+    }
+
+    // Refund the previous high bidder
+    if let (Some(previous_bidder), Some(previous_bid)) = (listing.current_bidder_id, listing.current_bid) {
+        ledger_service::credit(
+            &mut tx, previous_bidder, previous_bid, PaxEntryKind::AuctionRefund, SystemAccount::Escrow, Some(listing_id),
+        )
+        .await
+        .map_err(|e| {
+            error!("Failed to refund previous bidder: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    }
+
+    let new_balance = ledger_service::debit(
+        &mut tx, user_id.0, payload.amount, PaxEntryKind::AuctionBid, SystemAccount::Escrow, Some(listing_id),
+    )
+    .await
+    .map_err(|e| {
+        error!("Failed to escrow bid: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    sqlx::query!(
+        "UPDATE market_listings SET current_bid = $1, current_bidder_id = $2 WHERE id = $3",
+        payload.amount,
+        user_id.0,
+        listing_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to update auction bid: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    sqlx::query!(
+        "INSERT INTO item_events (item_id, item_type, event_type, from_user_id, performed_by_user_id, additional_data)
+         VALUES ($1, $2, 'bid_placed'::event_type, $3, $4, $5)",
+        listing.item_id,
+        listing.item_type,
+        listing.seller_id,
+        user_id.0,
+        serde_json::json!({ "listing_id": listing_id, "amount": payload.amount })
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to record bid event: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tx.commit().await.map_err(|e| {
+        error!("Failed to commit transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    info!("🔨 User {} bid {} PAX on {} {} (listing ID: {})",
+          bidder.username, payload.amount, listing.item_type, listing.item_id, listing_id);
+
+    if let Some(previous_bidder) = listing.current_bidder_id {
+        push_service::publish_balance(&state.pool, &state.redis, previous_bidder).await;
+        notify(
+            &state.pool,
+            &state.redis,
+            previous_bidder,
+            NotificationKind::Outbid,
+            format!("You were outbid on a {} auction, the new high bid is {} pax", listing.item_type, payload.amount),
+            serde_json::json!({ "listing_id": listing_id, "item_id": listing.item_id, "amount": payload.amount }),
+        ).await;
+    }
+    push_service::broadcast(&state.redis, &PushEvent::MarketChanged).await;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(new_balance),
+        error: None,
+    }))
+}
+
+/// Settles a single expired auction: pays the seller and hands over the item if the
+/// reserve was met, otherwise refunds the high bidder and returns the item to the seller.
+async fn settle_auction(pool: &PgPool, redis: &RedisClient, listing_id: Uuid) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let listing = sqlx::query!(
+        r#"SELECT seller_id, item_id, item_type, reserve_price, current_bid, current_bidder_id
+         FROM market_listings
+         WHERE id = $1 AND status = 'active'::market_status_type AND type = 'auction'::market_type
+         AND ends_at <= NOW()
+         FOR UPDATE"#,
+        listing_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let listing = match listing {
+        Some(l) => l,
+        None => return Ok(()),
+    };
+
+    let winner = match (listing.current_bidder_id, listing.current_bid) {
+        (Some(bidder), Some(bid)) if listing.reserve_price.is_none_or(|reserve| bid >= reserve) => Some((bidder, bid)),
+        _ => None,
+    };
+
+    if let Some((winner_id, winning_bid)) = winner {
+        ledger_service::credit(
+            &mut tx, listing.seller_id, winning_bid, PaxEntryKind::Sale, SystemAccount::Escrow, Some(listing_id),
+        ).await?;
+
+        match listing.item_type.as_str() {
+            "egg" => sqlx::query!(
+                "UPDATE eggs SET owner_id = $1, status = 'available'::item_status WHERE id = $2",
+                winner_id,
+                listing.item_id
+            )
+            .execute(&mut *tx)
+            .await?,
+            _ => sqlx::query!(
+                "UPDATE creatures SET owner_id = $1, status = 'available'::item_status WHERE id = $2",
+                winner_id,
+                listing.item_id
+            )
+            .execute(&mut *tx)
+            .await?,
+        };
+
+        // Record the hammer price so completed auctions read like completed sales
+        sqlx::query!(
+            "UPDATE market_listings
+             SET status = 'completed'::market_status_type, buyer_id = $1, price = $2
+             WHERE id = $3",
+            winner_id,
+            winning_bid,
+            listing_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        record_sale(&mut tx, listing_id, listing.item_id, winning_bid).await?;
+
+        sqlx::query!(
+            "INSERT INTO item_events (item_id, item_type, event_type, from_user_id, to_user_id, performed_by_user_id, additional_data)
+             VALUES ($1, $2, 'auction_won'::event_type, $3, $4, $4, $5)",
+            listing.item_id,
+            listing.item_type,
+            listing.seller_id,
+            winner_id,
+            serde_json::json!({ "listing_id": listing_id, "price": winning_bid })
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        info!("🔨 Auction {} settled: {} {} sold for {} PAX", listing_id, listing.item_type, listing.item_id, winning_bid);
+    } else {
+        if let (Some(bidder), Some(bid)) = (listing.current_bidder_id, listing.current_bid) {
+            ledger_service::credit(
+                &mut tx, bidder, bid, PaxEntryKind::AuctionRefund, SystemAccount::Escrow, Some(listing_id),
+            ).await?;
+        }
+
+        match listing.item_type.as_str() {
+            "egg" => sqlx::query!(
+                "UPDATE eggs SET status = 'available'::item_status WHERE id = $1",
+                listing.item_id
+            )
+            .execute(&mut *tx)
+            .await?,
+            _ => sqlx::query!(
+                "UPDATE creatures SET status = 'available'::item_status WHERE id = $1",
+                listing.item_id
+            )
+            .execute(&mut *tx)
+            .await?,
+        };
+
+        sqlx::query!(
+            "UPDATE market_listings SET status = 'cancelled'::market_status_type WHERE id = $1",
+            listing_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO item_events (item_id, item_type, event_type, from_user_id, performed_by_user_id, additional_data)
+             VALUES ($1, $2, 'sale_cancelled'::event_type, $3, $3, $4)",
+            listing.item_id,
+            listing.item_type,
+            listing.seller_id,
+            serde_json::json!({ "listing_id": listing_id, "reason": "auction_ended_without_sale" })
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        info!("🔨 Auction {} ended without a sale, {} {} returned to seller", listing_id, listing.item_type, listing.item_id);
+    }
+
+    tx.commit().await?;
+
+    if let Some((winner_id, winning_bid)) = winner {
+        push_service::publish(redis, listing.seller_id, &PushEvent::ListingSold {
+            listing_id: listing_id.to_string(),
+            price: winning_bid,
+        }).await;
+        push_service::publish_balance(pool, redis, listing.seller_id).await;
+        notify(
+            pool,
+            redis,
+            listing.seller_id,
+            NotificationKind::ListingSold,
+            format!("Your {} auction sold for {} pax", listing.item_type, winning_bid),
+            serde_json::json!({ "listing_id": listing_id, "item_id": listing.item_id, "price": winning_bid }),
+        ).await;
+        email_digest_service::queue(
+            pool,
+            listing.seller_id,
+            NotificationKind::ListingSold,
+            format!("Your {} auction sold for {} pax", listing.item_type, winning_bid),
+            None,
+        ).await;
+        push_service::publish(redis, winner_id, &PushEvent::ListingBought {
+            listing_id: listing_id.to_string(),
+            price: winning_bid,
+        }).await;
+    } else if let Some(bidder) = listing.current_bidder_id {
+        push_service::publish_balance(pool, redis, bidder).await;
+    }
+    push_service::broadcast(redis, &PushEvent::MarketChanged).await;
+
+    Ok(())
+}
+
+/// Settles every auction whose end time has passed. Each auction is settled in its own
+/// transaction so one failure doesn't hold up the rest.
+pub async fn settle_expired_auctions(pool: &PgPool, redis: &RedisClient) -> Result<(), sqlx::Error> {
+    let expired = sqlx::query_scalar!(
+        "SELECT id FROM market_listings
+         WHERE type = 'auction'::market_type AND status = 'active'::market_status_type AND ends_at <= NOW()"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for listing_id in expired {
+        if let Err(e) = settle_auction(pool, redis, listing_id).await {
+            error!("Failed to settle auction {}: {:?}", listing_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Hands back the escrowed high bids an account deletion would otherwise strand: the
+/// user's own leading bids, and the leading bids on the user's auctions, which are
+/// deleted along with them.
+pub async fn refund_bids_for_deleted_user(conn: &mut PgConnection, user_id: Uuid) -> Result<(), sqlx::Error> {
+    let bids = sqlx::query!(
+        r#"SELECT id, current_bidder_id as "current_bidder_id!", current_bid as "current_bid!"
+         FROM market_listings
+         WHERE type = 'auction'::market_type AND status = 'active'::market_status_type
+         AND current_bidder_id IS NOT NULL AND current_bid IS NOT NULL
+         AND (current_bidder_id = $1 OR seller_id = $1)
+         FOR UPDATE"#,
+        user_id
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for bid in bids {
+        ledger_service::credit(
+            &mut *conn, bid.current_bidder_id, bid.current_bid, PaxEntryKind::AuctionRefund, SystemAccount::Escrow, Some(bid.id),
+        ).await?;
+
+        sqlx::query!(
+            "UPDATE market_listings SET current_bid = NULL, current_bidder_id = NULL WHERE id = $1",
+            bid.id
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
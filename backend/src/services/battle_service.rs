@@ -0,0 +1,443 @@
+use axum::{
+    extract::{State, Extension, Path},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use shared::shared_battle::{self, Combatant, CreatureStats, Essence, Side};
+use sqlx::{Postgres, Transaction};
+use tracing::{error, info};
+use uuid::Uuid;
+use crate::auth::middleware::UserId;
+use crate::AppState;
+use super::market_service::ApiResponse;
+use super::friend_service::is_blocked;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
+use super::notification_service::{notify, NotificationKind};
+use super::push_service;
+use super::user_service::update_experience_and_rank;
+
+const PVP_REWARD_PAX: i32 = 10;
+const WILD_REWARD_PAX: i32 = 5;
+const REWARD_EXPERIENCE: i32 = 5;
+const REWARD_SOUL: i32 = 1;
+const HISTORY_LIMIT: i64 = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct BattleRequest {
+    /// Another player's creature to fight; a wild opponent is generated when absent.
+    pub opponent_creature_id: Option<Uuid>,
+}
+
+/// Everything the frontend needs to replay a battle with `shared_battle::simulate`.
+#[derive(Debug, Serialize)]
+pub struct BattleRecord {
+    pub id: Uuid,
+    pub challenger_username: String,
+    pub defender_username: Option<String>,
+    pub challenger: Combatant,
+    pub defender: Combatant,
+    pub seed: u64,
+    pub challenger_won: bool,
+    pub reward_pax: i32,
+    pub reward_experience: i32,
+    /// The challenger's rating change, for ranked ladder battles
+    pub rating_change: Option<i32>,
+    pub created_at: String,
+}
+
+/// Failure of a battle: either a rule the caller broke, or a database error.
+pub(crate) enum BattleError {
+    Rejected(String),
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for BattleError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+pub(crate) fn rejected<T>(message: &str) -> Result<T, BattleError> {
+    Err(BattleError::Rejected(message.to_string()))
+}
+
+/// Turns the outcome of a battle operation into the API response, logging database failures.
+pub(crate) fn respond<T>(action: &str, result: Result<T, BattleError>) -> Result<Json<ApiResponse<T>>, StatusCode> {
+    match result {
+        Ok(data) => Ok(Json(ApiResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })),
+        Err(BattleError::Rejected(message)) => {
+            info!("❌ Battle {} failed: {}", action, message);
+            Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(message),
+            }))
+        }
+        Err(BattleError::Database(e)) => {
+            error!("Failed to {} battle: {}", action, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Builds a combatant from a creature row. Unknown essences fall back to Ancient and
+/// malformed stats to the column default, so an odd row can still fight.
+fn combatant(name: String, essence: &str, stats: serde_json::Value) -> Combatant {
+    Combatant {
+        name,
+        essence: Essence::from_name(essence).unwrap_or(Essence::Ancient),
+        stats: serde_json::from_value::<CreatureStats>(stats).unwrap_or_default(),
+    }
+}
+
+pub(crate) async fn begin(state: &AppState) -> Result<Transaction<'static, Postgres>, StatusCode> {
+    state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+pub(crate) async fn commit(tx: Transaction<'_, Postgres>) -> Result<(), StatusCode> {
+    tx.commit().await.map_err(|e| {
+        error!("Failed to commit transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// Who collects the rewards of a settled battle.
+struct Winner {
+    user_id: Uuid,
+    creature_id: Uuid,
+    pax: i32,
+}
+
+/// A battle written inside the caller's transaction. Pass it to `finish_battle` once
+/// that transaction has committed.
+pub(crate) struct SettledBattle {
+    pub record: BattleRecord,
+    pub creature_id: Uuid,
+    pub defender_id: Option<Uuid>,
+    winner: Option<Winner>,
+}
+
+/// Fights `creature_id` against another player's creature, or a wild one when no opponent
+/// is given, and records the result. The challenger's energy is spent either way; the
+/// winner's owner gets pax and the winning creature gains soul. Losing to a wild creature
+/// costs nothing beyond the energy.
+pub(crate) async fn fight(
+    tx: &mut Transaction<'static, Postgres>,
+    user_id: Uuid,
+    creature_id: Uuid,
+    opponent_creature_id: Option<Uuid>,
+) -> Result<SettledBattle, BattleError> {
+    let Some(creature) = sqlx::query!(
+        r#"SELECT c.display_name, c.essence::text as "essence!", c.stats, c.energy_full,
+                  c.status::text as "status!", c.in_chaos_realm, u.display_name as "username!"
+           FROM creatures c
+           JOIN public_users u ON u.id = c.owner_id
+           WHERE c.id = $1 AND c.owner_id = $2
+           FOR UPDATE OF c"#,
+        creature_id,
+        user_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    else {
+        return rejected("Creature not found");
+    };
+
+    if creature.status != "available" || creature.in_chaos_realm {
+        return rejected("Creature is busy and cannot battle right now");
+    }
+    if !creature.energy_full {
+        return rejected("Creature needs full energy to battle");
+    }
+
+    let challenger = combatant(creature.display_name, &creature.essence, creature.stats);
+    let seed = rand::random::<u64>();
+
+    // (owner, creature, owner's username, combatant) of the defending player, if any
+    let opponent = match opponent_creature_id {
+        Some(opponent_id) => {
+            let Some(row) = sqlx::query!(
+                r#"SELECT c.owner_id, c.display_name, c.essence::text as "essence!", c.stats,
+                          c.in_chaos_realm, pu.display_name as "username!"
+                   FROM creatures c
+                   JOIN users u ON u.id = c.owner_id
+                   JOIN public_users pu ON pu.id = c.owner_id
+                   WHERE c.id = $1 AND u.deleted_at IS NULL"#,
+                opponent_id
+            )
+            .fetch_optional(&mut **tx)
+            .await?
+            else {
+                return rejected("Opponent creature not found");
+            };
+
+            if row.owner_id == user_id {
+                return rejected("You cannot battle your own creatures");
+            }
+            if is_blocked(&mut **tx, user_id, row.owner_id).await? {
+                return rejected("You cannot battle this player");
+            }
+            if row.in_chaos_realm {
+                return rejected("That creature is away in the Chaos Realm");
+            }
+
+            Some((row.owner_id, opponent_id, row.username, combatant(row.display_name, &row.essence, row.stats)))
+        }
+        None => None,
+    };
+
+    let defender = match &opponent {
+        Some((_, _, _, defender)) => defender.clone(),
+        None => shared_battle::wild_opponent(&challenger, seed),
+    };
+    let outcome = shared_battle::simulate(&challenger, &defender, seed);
+    let challenger_won = outcome.winner == Side::Challenger;
+
+    sqlx::query!(
+        "UPDATE creatures SET energy_full = false, energy_recharge_complete_at = NULL WHERE id = $1",
+        creature_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    let winner = match (&opponent, challenger_won) {
+        (None, true) => Some(Winner { user_id, creature_id, pax: WILD_REWARD_PAX }),
+        (Some(_), true) => Some(Winner { user_id, creature_id, pax: PVP_REWARD_PAX }),
+        (Some((owner_id, opponent_id, _, _)), false) => {
+            Some(Winner { user_id: *owner_id, creature_id: *opponent_id, pax: PVP_REWARD_PAX })
+        }
+        (None, false) => None,
+    };
+
+    if let Some(winner) = &winner {
+        ledger_service::credit(
+            tx, winner.user_id, winner.pax,
+            PaxEntryKind::BattleReward, SystemAccount::Mint, Some(winner.creature_id),
+        )
+        .await?;
+
+        sqlx::query!(
+            "UPDATE creatures SET soul = soul + $1 WHERE id = $2",
+            REWARD_SOUL,
+            winner.creature_id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    let reward_pax = winner.as_ref().map_or(0, |w| w.pax);
+    let reward_experience = if winner.is_some() { REWARD_EXPERIENCE } else { 0 };
+    let to_json = |c: &Combatant| serde_json::to_value(c).unwrap_or_default();
+
+    let saved = sqlx::query!(
+        r#"INSERT INTO battles (challenger_id, challenger_creature_id, defender_id, defender_creature_id,
+                                challenger_snapshot, defender_snapshot, seed, challenger_won,
+                                reward_pax, reward_experience)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+           RETURNING id, TO_CHAR(created_at, 'YYYY-MM-DD HH24:MI:SS') as "created_at!""#,
+        user_id,
+        creature_id,
+        opponent.as_ref().map(|(owner_id, _, _, _)| *owner_id),
+        opponent.as_ref().map(|(_, opponent_id, _, _)| *opponent_id),
+        to_json(&challenger),
+        to_json(&defender),
+        // Stored bit-for-bit; read back with `as u64`
+        seed as i64,
+        challenger_won,
+        reward_pax,
+        reward_experience
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    let defender_id = opponent.as_ref().map(|(owner_id, _, _, _)| *owner_id);
+    let record = BattleRecord {
+        id: saved.id,
+        challenger_username: creature.username,
+        defender_username: opponent.map(|(_, _, username, _)| username),
+        challenger,
+        defender,
+        seed,
+        challenger_won,
+        reward_pax,
+        reward_experience,
+        rating_change: None,
+        created_at: saved.created_at,
+    };
+
+    Ok(SettledBattle { record, creature_id, defender_id, winner })
+}
+
+/// Post-commit side effects of a battle: the winner's experience and balance push, and a
+/// notification for the defending player.
+pub(crate) async fn finish_battle(state: &AppState, settled: &SettledBattle) {
+    let record = &settled.record;
+    info!(
+        "⚔️ {} battled {} with creature {} and {}",
+        record.challenger_username,
+        record.defender_username.as_deref().unwrap_or("a wild creature"),
+        settled.creature_id,
+        if record.challenger_won { "won" } else { "lost" }
+    );
+
+    if let Some(winner) = &settled.winner {
+        if let Err(e) = update_experience_and_rank(&state.pool, winner.user_id, REWARD_EXPERIENCE).await {
+            error!("Failed to award battle experience to {}: {}", winner.user_id, e);
+        }
+        push_service::publish_balance(&state.pool, &state.redis, winner.user_id).await;
+    }
+
+    if let Some(defender_id) = settled.defender_id {
+        let message = if record.challenger_won {
+            format!("{} defeated your {} in battle", record.challenger_username, record.defender.name)
+        } else {
+            format!("Your {} fought off {}'s challenge and won {} pax", record.defender.name, record.challenger_username, record.reward_pax)
+        };
+        notify(
+            &state.pool,
+            &state.redis,
+            defender_id,
+            NotificationKind::BattleResult,
+            message,
+            serde_json::json!({ "battle_id": record.id }),
+        ).await;
+    }
+}
+
+/// `POST /api/creatures/:id/battle`. An unranked battle against another player's creature
+/// or a wild one.
+pub async fn start_battle(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(creature_id): Path<Uuid>,
+    Json(payload): Json<BattleRequest>,
+) -> Result<Json<ApiResponse<BattleRecord>>, StatusCode> {
+    let mut tx = begin(&state).await?;
+    let result = fight(&mut tx, user_id.0, creature_id, payload.opponent_creature_id).await;
+
+    let result = match result {
+        Ok(settled) => {
+            commit(tx).await?;
+            finish_battle(&state, &settled).await;
+            Ok(settled.record)
+        }
+        Err(e) => Err(e),
+    };
+
+    respond("start", result)
+}
+
+struct BattleRow {
+    id: Uuid,
+    challenger_username: String,
+    defender_username: Option<String>,
+    challenger_snapshot: serde_json::Value,
+    defender_snapshot: serde_json::Value,
+    seed: i64,
+    challenger_won: bool,
+    reward_pax: i32,
+    reward_experience: i32,
+    rating_change: Option<i32>,
+    created_at: String,
+}
+
+impl BattleRow {
+    fn into_record(self) -> Option<BattleRecord> {
+        let challenger = serde_json::from_value(self.challenger_snapshot).ok()?;
+        let defender = serde_json::from_value(self.defender_snapshot).ok()?;
+        Some(BattleRecord {
+            id: self.id,
+            challenger_username: self.challenger_username,
+            defender_username: self.defender_username,
+            challenger,
+            defender,
+            seed: self.seed as u64,
+            challenger_won: self.challenger_won,
+            reward_pax: self.reward_pax,
+            reward_experience: self.reward_experience,
+            rating_change: self.rating_change,
+            created_at: self.created_at,
+        })
+    }
+}
+
+/// `GET /api/battles`. The caller's most recent battles, as challenger or defender.
+pub async fn get_battles(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<ApiResponse<Vec<BattleRecord>>>, StatusCode> {
+    let result: Result<_, BattleError> = async {
+        let rows = sqlx::query_as!(
+            BattleRow,
+            r#"SELECT b.id, cu.display_name as "challenger_username!", du.display_name as "defender_username?",
+                      b.challenger_snapshot, b.defender_snapshot, b.seed, b.challenger_won,
+                      b.reward_pax, b.reward_experience, b.rating_change,
+                      TO_CHAR(b.created_at, 'YYYY-MM-DD HH24:MI:SS') as "created_at!"
+               FROM battles b
+               JOIN public_users cu ON cu.id = b.challenger_id
+               LEFT JOIN public_users du ON du.id = b.defender_id
+               WHERE b.challenger_id = $1 OR b.defender_id = $1
+               ORDER BY b.created_at DESC
+               LIMIT $2"#,
+            user_id.0,
+            HISTORY_LIMIT
+        )
+        .fetch_all(&state.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let id = row.id;
+                let record = row.into_record();
+                if record.is_none() {
+                    error!("Skipping battle {} with unreadable snapshots", id);
+                }
+                record
+            })
+            .collect())
+    }
+    .await;
+
+    respond("list", result)
+}
+
+/// `GET /api/battles/:id`. A single battle, for replaying it.
+pub async fn get_battle(
+    State(state): State<AppState>,
+    Extension(_user_id): Extension<UserId>,
+    Path(battle_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<BattleRecord>>, StatusCode> {
+    let result: Result<_, BattleError> = async {
+        let row = sqlx::query_as!(
+            BattleRow,
+            r#"SELECT b.id, cu.display_name as "challenger_username!", du.display_name as "defender_username?",
+                      b.challenger_snapshot, b.defender_snapshot, b.seed, b.challenger_won,
+                      b.reward_pax, b.reward_experience, b.rating_change,
+                      TO_CHAR(b.created_at, 'YYYY-MM-DD HH24:MI:SS') as "created_at!"
+               FROM battles b
+               JOIN public_users cu ON cu.id = b.challenger_id
+               LEFT JOIN public_users du ON du.id = b.defender_id
+               WHERE b.id = $1"#,
+            battle_id
+        )
+        .fetch_optional(&state.pool)
+        .await?;
+
+        match row.and_then(BattleRow::into_record) {
+            Some(record) => Ok(record),
+            None => rejected("Battle not found"),
+        }
+    }
+    .await;
+
+    respond("load", result)
+}
@@ -7,9 +7,16 @@ use serde::Serialize;
 use time::OffsetDateTime;
 use tracing::{error, info};
 use uuid::Uuid;
+use redis::Client as RedisClient;
+use sqlx::PgPool;
 
 use crate::auth::middleware::UserId;
 use crate::AppState;
+use shared::push_events::PushEvent;
+use super::push_service;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
+use super::email_digest_service;
+use super::notification_service::NotificationKind;
 
 const CHAOS_REALM_DURATION_SECS: i64 = 82800;  // 23 hours (was 10 seconds)
 
@@ -89,6 +96,17 @@ pub async fn enter_chaos_realm(
         }));
     }
 
+    // Check if creature is escrowed in a trade offer
+    if creature.status == "trading" {
+        info!("🚫 Chaos Realm entry failed for {} - Creature is part of a pending trade", creature.username);
+        return Ok(Json(ChaosRealmResponse {
+            success: false,
+            error: Some("Cannot enter Chaos Realm while creature is part of a pending trade".to_string()),
+            new_balance: creature.currency_balance,
+            reward_amount: 0,
+        }));
+    }
+
     // Check energy requirement
     if !creature.energy_full {
         info!("🚫 Chaos Realm entry failed for {} - Insufficient energy", creature.username);
@@ -118,6 +136,7 @@ pub async fn enter_chaos_realm(
             in_chaos_realm = true,
             chaos_realm_entry_at = CURRENT_TIMESTAMP,
             chaos_realm_reward_claimed = false,
+            chaos_realm_completion_pushed = false,
             energy_full = false,
             energy_recharge_complete_at = NULL,
             soul = soul + 1
@@ -132,11 +151,33 @@ pub async fn enter_chaos_realm(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    sqlx::query!(
+        "INSERT INTO item_events (item_id, item_type, event_type, performed_by_user_id, additional_data)
+         VALUES ($1, 'creature', 'entered_chaos_realm'::event_type, $2, $3)",
+        creature_id,
+        user_id.0,
+        serde_json::json!({ "rarity": creature.rarity })
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to record item event: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
     tx.commit().await.map_err(|e| {
         error!("Failed to commit transaction: {}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    email_digest_service::queue(
+        &state.pool,
+        user_id.0,
+        NotificationKind::ChaosRealmReady,
+        format!("Your {} creature is back from the chaos realm and its reward is ready to claim", creature.rarity),
+        Some(OffsetDateTime::now_utc() + time::Duration::seconds(CHAOS_REALM_DURATION_SECS)),
+    ).await;
+
     Ok(Json(ChaosRealmResponse {
         success: true,
         error: None,
@@ -145,6 +186,28 @@ pub async fn enter_chaos_realm(
     }))
 }
 
+/// Pushes `ChaosRealmCompleted` for runs that have finished. Runs are marked in the
+/// database as they're announced, so a restart neither drops nor repeats the push.
+pub async fn push_completed_runs(pool: &PgPool, redis: &RedisClient) -> Result<(), sqlx::Error> {
+    let completed = sqlx::query!(
+        "UPDATE creatures SET chaos_realm_completion_pushed = true
+         WHERE in_chaos_realm AND NOT chaos_realm_completion_pushed
+         AND chaos_realm_entry_at <= NOW() - make_interval(secs => $1)
+         RETURNING id, owner_id",
+        CHAOS_REALM_DURATION_SECS as f64
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for run in completed {
+        push_service::publish(redis, run.owner_id, &PushEvent::ChaosRealmCompleted {
+            creature_id: run.id.to_string(),
+        }).await;
+    }
+
+    Ok(())
+}
+
 pub async fn claim_chaos_realm_reward(
     State(state): State<AppState>,
     Extension(user_id): Extension<UserId>,
@@ -234,7 +297,6 @@ pub async fn claim_chaos_realm_reward(
     }
 
     let (_, reward) = get_chaos_realm_amounts(&creature.rarity);
-    let new_balance = creature.currency_balance + reward;
 
     // Update creature and user state
     sqlx::query!(
@@ -255,12 +317,9 @@ pub async fn claim_chaos_realm_reward(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    sqlx::query!(
-        "UPDATE users SET currency_balance = $1 WHERE id = $2",
-        new_balance,
-        user_id.0
+    let new_balance = ledger_service::credit(
+        &mut tx, user_id.0, reward, PaxEntryKind::ChaosReward, SystemAccount::Mint, Some(creature_id),
     )
-    .execute(&mut *tx)
     .await
     .map_err(|e| {
         error!("Failed to update balance: {}", e);
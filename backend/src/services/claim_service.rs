@@ -17,13 +17,15 @@ use sha2::Sha256;
 use hex;
 use axum::http::header;
 use tracing::{info, error};
+use super::achievement_service::{check_achievements, AchievementTrigger};
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
 
-const DAILY_CLAIM_COOLDOWN: i32 = 82800; // 23 hours
+pub(crate) const DAILY_CLAIM_COOLDOWN: i32 = 82800; // 23 hours
 const DAILY_CLAIM_AMOUNT: i32 = 10;
 const MAX_REWARDS_PER_MINUTE: u32 = 100;
 const REWARD_WINDOW_DURATION: u64 = 300; // 5 minutes in seconds
 const MAX_REWARDS_PER_WINDOW: u32 = 200;
-const STREAK_RESET_WINDOW: i32 = 169600; // Changed from 86400 (24 hours) to 169600 (47 hours)
+pub(crate) const STREAK_RESET_WINDOW: i32 = 169600; // Changed from 86400 (24 hours) to 169600 (47 hours)
 const SCROLL_REWARD_DAY: i32 = 7; // Award scroll every 7th day
 
 #[derive(Debug, Deserialize, Clone)]
@@ -314,18 +316,28 @@ end
         }));
     }
 
-    match sqlx::query!(
-        "UPDATE users SET currency_balance = currency_balance + $1 WHERE id = $2 RETURNING currency_balance",
-        payload.score,
-        user_id.0
-    )
-    .fetch_one(&state.pool)
-    .await {
-        Ok(record) => Ok(Json(GameRewardResponse {
-            success: true,
-            new_balance: record.currency_balance,
-            error: None,
-        })),
+    let credited = async {
+        let mut tx = state.pool.begin().await?;
+        let balance = ledger_service::credit(
+            &mut tx, user_id.0, payload.score, PaxEntryKind::GameReward, SystemAccount::Mint, None,
+        ).await?;
+        tx.commit().await?;
+        Ok::<_, sqlx::Error>(balance)
+    }
+    .await;
+
+    match credited {
+        Ok(new_balance) => {
+            if let Err(e) = check_achievements(&state.pool, user_id.0, AchievementTrigger::GameReward).await {
+                error!("Failed to check achievements: {}", e);
+            }
+
+            Ok(Json(GameRewardResponse {
+                success: true,
+                new_balance,
+                error: None,
+            }))
+        },
         Err(e) => {
             eprintln!("Database update error: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -448,12 +460,10 @@ pub async fn claim_daily_reward(
     // Calculate reward based on week number instead of daily streak
     let reward = DAILY_CLAIM_AMOUNT + (week_number - 1);
     
-    let new_balance = user.currency_balance + reward;
     let now = OffsetDateTime::now_utc();
     
     sqlx::query!(
-        "UPDATE users SET currency_balance = $1, last_daily_reward = $2, claim_streak = $3 WHERE id = $4",
-        new_balance,
+        "UPDATE users SET last_daily_reward = $1, claim_streak = $2 WHERE id = $3",
         now,
         new_claim_streak,
         user_id.0
@@ -468,6 +478,18 @@ pub async fn claim_daily_reward(
             .unwrap()
     })?;
 
+    let new_balance = ledger_service::credit(
+        &mut tx, user_id.0, reward, PaxEntryKind::DailyClaim, SystemAccount::Mint, None,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Update reward error: {}", e);
+        Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Failed to update reward"))
+            .unwrap()
+    })?;
+
     // Check if the user should receive a scroll (only on 7th day)
     let scroll_awarded = new_claim_streak % SCROLL_REWARD_DAY == 0;
     
@@ -535,6 +557,10 @@ pub async fn claim_daily_reward(
             .unwrap()
     })?;
 
+    if let Err(e) = check_achievements(&state.pool, user_id.0, AchievementTrigger::DailyClaim).await {
+        error!("Failed to check achievements: {}", e);
+    }
+
     // Set the cooldown in Redis with a small buffer (5 seconds) to ensure consistency
     let cooldown_key = format!("user:{}:claim_cooldown", user_id.0);
     let _: () = redis::cmd("SETEX")
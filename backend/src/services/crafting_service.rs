@@ -0,0 +1,367 @@
+use axum::{
+    extract::{State, Json, Extension, Path},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+use crate::AppState;
+use crate::auth::middleware::UserId;
+use tracing::{error, info};
+use super::market_service::ApiResponse;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
+use super::orderbook_service::debit_scrolls;
+use super::scroll_service::{Scroll, ScrollResponse};
+
+/// Ingredients stored in `recipes.ingredients`, e.g. `{"pax": 60, "scrolls": 1, "eggs": 1}`.
+/// `scrolls` counts Summoning Scrolls; eggs and creatures are sacrificed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Ingredients {
+    #[serde(default)]
+    pub pax: i32,
+    #[serde(default)]
+    pub scrolls: i32,
+    #[serde(default)]
+    pub eggs: i32,
+    #[serde(default)]
+    pub creatures: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecipeResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub image_path: String,
+    pub ingredients: Ingredients,
+    pub required_rank: String,
+    pub rank_met: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CraftRequest {
+    #[serde(default)]
+    pub egg_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub creature_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CraftResponse {
+    pub scroll: ScrollResponse,
+    pub new_balance: i32,
+}
+
+fn has_duplicates(ids: &[Uuid]) -> bool {
+    let mut sorted = ids.to_vec();
+    sorted.sort();
+    sorted.dedup();
+    sorted.len() != ids.len()
+}
+
+// List all recipes along with whether the user's rank allows crafting them
+pub async fn get_recipes(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<Vec<RecipeResponse>>, StatusCode> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT r.id, r.result_name, r.result_description, r.result_image_path,
+               r.ingredients, r.required_rank::text as "required_rank!",
+               u.rank >= r.required_rank as "rank_met!"
+        FROM recipes r
+        CROSS JOIN users u
+        WHERE u.id = $1
+        ORDER BY r.required_rank, r.result_name
+        "#,
+        user_id.0
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch recipes: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(rows.into_iter().map(|row| RecipeResponse {
+        id: row.id,
+        name: row.result_name,
+        description: row.result_description,
+        image_path: row.result_image_path,
+        ingredients: serde_json::from_value(row.ingredients).unwrap_or_default(),
+        required_rank: row.required_rank,
+        rank_met: row.rank_met,
+    }).collect()))
+}
+
+// Craft a recipe, consuming its ingredients and producing one scroll
+pub async fn craft_recipe(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(recipe_id): Path<Uuid>,
+    Json(payload): Json<CraftRequest>,
+) -> Result<Json<ApiResponse<CraftResponse>>, StatusCode> {
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let recipe = match sqlx::query!(
+        r#"
+        SELECT id, result_name, result_description, result_image_path,
+               ingredients, required_rank::text as "required_rank!"
+        FROM recipes
+        WHERE id = $1
+        "#,
+        recipe_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch recipe: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })? {
+        Some(recipe) => recipe,
+        None => {
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some("Recipe not found".to_string()),
+            }));
+        }
+    };
+
+    let ingredients: Ingredients = serde_json::from_value(recipe.ingredients).map_err(|e| {
+        error!("Recipe '{}' has invalid ingredients: {}", recipe.result_name, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let user = sqlx::query!(
+        r#"
+        SELECT username, currency_balance, rank >= $2::text::user_rank as "rank_met!"
+        FROM users
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        user_id.0,
+        recipe.required_rank
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch crafter: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    info!("📋 User {} is crafting '{}'", user.username, recipe.result_name);
+
+    if !user.rank_met {
+        info!("❌ Crafting failed: User {} is below rank {} for '{}'",
+              user.username, recipe.required_rank, recipe.result_name);
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Requires {} rank", recipe.required_rank)),
+        }));
+    }
+
+    if user.currency_balance < ingredients.pax {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Insufficient funds ({} PAX required)", ingredients.pax)),
+        }));
+    }
+
+    if payload.egg_ids.len() != ingredients.eggs as usize
+        || payload.creature_ids.len() != ingredients.creatures as usize
+        || has_duplicates(&payload.egg_ids)
+        || has_duplicates(&payload.creature_ids)
+    {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!(
+                "This recipe requires {} egg(s) and {} creature(s) to sacrifice",
+                ingredients.eggs, ingredients.creatures
+            )),
+        }));
+    }
+
+    // Consume Summoning Scrolls, oldest stacks first
+    let has_scrolls = debit_scrolls(&mut tx, user_id.0, ingredients.scrolls).await.map_err(|e| {
+        error!("Failed to consume scrolls: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if !has_scrolls {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Not enough Summoning Scrolls ({} required)", ingredients.scrolls)),
+        }));
+    }
+
+    if !payload.egg_ids.is_empty() {
+        let eggs = sqlx::query_scalar!(
+            "SELECT id FROM eggs WHERE id = ANY($1) AND owner_id = $2 AND status = 'available' FOR UPDATE",
+            &payload.egg_ids,
+            user_id.0
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch sacrificed eggs: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+        if eggs.len() != payload.egg_ids.len() {
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some("Sacrificed eggs must be yours and not listed or traded".to_string()),
+            }));
+        }
+
+        sqlx::query!("DELETE FROM eggs WHERE id = ANY($1)", &payload.egg_ids)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to delete sacrificed eggs: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+    }
+
+    if !payload.creature_ids.is_empty() {
+        let creatures = sqlx::query_scalar!(
+            "SELECT id FROM creatures WHERE id = ANY($1) AND owner_id = $2 AND status = 'available' AND NOT in_chaos_realm FOR UPDATE",
+            &payload.creature_ids,
+            user_id.0
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch sacrificed creatures: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+        if creatures.len() != payload.creature_ids.len() {
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some("Sacrificed creatures must be yours, available and outside the Chaos Realm".to_string()),
+            }));
+        }
+
+        sqlx::query!("DELETE FROM creatures WHERE id = ANY($1)", &payload.creature_ids)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to delete sacrificed creatures: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+    }
+
+    let new_balance = ledger_service::debit(
+        &mut tx, user_id.0, ingredients.pax, PaxEntryKind::CraftingCost, SystemAccount::Mint, Some(recipe_id),
+    )
+    .await
+    .map_err(|e| {
+        error!("Failed to deduct crafting cost: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    // Crafted scrolls stack by name, like Summoning Scrolls
+    let now = OffsetDateTime::now_utc();
+    let existing_id = sqlx::query_scalar!(
+        "UPDATE scrolls SET quantity = quantity + 1, updated_at = $1 WHERE owner_id = $2 AND display_name = $3 RETURNING id",
+        now,
+        user_id.0,
+        recipe.result_name
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to update crafted scroll: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let scroll_id = match existing_id {
+        Some(id) => id,
+        None => sqlx::query_scalar!(
+            r#"
+            INSERT INTO scrolls (
+                id, owner_id, created_at, updated_at, display_name,
+                image_path, description, quantity, item_type
+            )
+            VALUES ($1, $2, $3, $3, $4, $5, $6, 1, 'scroll')
+            RETURNING id
+            "#,
+            Uuid::new_v4(),
+            user_id.0,
+            now,
+            recipe.result_name,
+            recipe.result_image_path,
+            recipe.result_description
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to create crafted scroll: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?,
+    };
+
+    sqlx::query!(
+        "INSERT INTO item_events (item_id, item_type, event_type, to_user_id, performed_by_user_id, additional_data)
+        VALUES ($1, 'scroll', 'crafted'::event_type, $2, $2, $3)",
+        scroll_id,
+        user_id.0,
+        serde_json::json!({
+            "recipe_id": recipe.id,
+            "pax": ingredients.pax,
+            "scrolls": ingredients.scrolls,
+            "sacrificed_eggs": payload.egg_ids,
+            "sacrificed_creatures": payload.creature_ids,
+        })
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to create item event: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let scroll = sqlx::query_as!(
+        Scroll,
+        r#"
+        SELECT id, owner_id, created_at, display_name, image_path, description, quantity
+        FROM scrolls
+        WHERE id = $1
+        "#,
+        scroll_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch crafted scroll: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tx.commit().await.map_err(|e| {
+        error!("Failed to commit transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    info!("✅ User {} crafted '{}' for {} PAX, {} scroll(s), {} egg(s) and {} creature(s)",
+          user.username, recipe.result_name, ingredients.pax, ingredients.scrolls,
+          ingredients.eggs, ingredients.creatures);
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(CraftResponse {
+            scroll: scroll.into(),
+            new_balance,
+        }),
+        error: None,
+    }))
+}
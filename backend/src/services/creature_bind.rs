@@ -1,4 +1,5 @@
 use super::*;
+use super::ledger_service::{PaxEntryKind, SystemAccount};
 use axum::{
     extract::{Path, State},
     Json,
@@ -212,6 +213,16 @@ async fn bind_creature_inner(
         }))))
     }
 
+    // Check if either creature is escrowed in a trade offer
+    if target.status == "trading" || sacrifice.status == "trading" {
+        return Ok((StatusCode::OK, Json(json!({
+            "success": false,
+            "error": "Cannot soul bind while creatures are part of a pending trade",
+            "creature": null,
+            "new_balance": user.currency_balance
+        }))))
+    }
+
     // Check if target has full energy
     if !target.energy_full {
         return Ok((StatusCode::OK, Json(json!({
@@ -338,6 +349,29 @@ async fn bind_creature_inner(
         })))
     })?;
 
+    sqlx::query!(
+        "INSERT INTO item_events (item_id, item_type, event_type, performed_by_user_id, additional_data)
+         VALUES ($1, 'creature', 'soul_bound'::event_type, $2, $3)",
+        target.id,
+        user_id.0,
+        json!({
+            "sacrifice_id": sacrifice.id,
+            "from_rarity": target.rarity,
+            "to_rarity": new_rarity.to_string()
+        })
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to record item event: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
+            "success": false,
+            "error": "Database error",
+            "creature": null,
+            "new_balance": null
+        })))
+    })?;
+
     // Get the updated creature
     let updated = sqlx::query!(
         r#"
@@ -360,13 +394,9 @@ async fn bind_creature_inner(
     })?;
 
     // Only deduct currency after all operations have succeeded
-    let new_balance = user.currency_balance - 55;
-    sqlx::query!(
-        "UPDATE users SET currency_balance = $1 WHERE id = $2",
-        new_balance,
-        user_id.0
+    let new_balance = ledger_service::debit(
+        &mut tx, user_id.0, 55, PaxEntryKind::BindCost, SystemAccount::Mint, Some(target.id),
     )
-    .execute(&mut *tx)
     .await
     .map_err(|e| {
         error!("Failed to update balance: {}", e);
@@ -451,6 +481,12 @@ async fn bind_creature_inner(
         })))
     })?;
 
+    if let Err(e) = super::achievement_service::check_achievements(
+        &state.pool, user_id.0, super::achievement_service::AchievementTrigger::SoulBind
+    ).await {
+        error!("Failed to check achievements: {}", e);
+    }
+
     // Get usernames for logging
     let username = sqlx::query!(
         "SELECT username FROM users WHERE id = $1",
@@ -13,6 +13,7 @@ use crate::auth::middleware::UserId;
 use crate::generator::generate_code::is_member;
 use shared::profanity::ProfanityFilter;
 use crate::models::ShowcaseCreature;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
 
 #[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct Creature {
@@ -215,7 +216,7 @@ pub async fn rename_creature_handler(
     
     // Verify creature ownership
     let creature = sqlx::query!(
-        "SELECT owner_id FROM creatures WHERE id = $1",
+        "SELECT owner_id, display_name FROM creatures WHERE id = $1",
         creature_id
     )
     .fetch_optional(&state.pool)
@@ -262,15 +263,11 @@ pub async fn rename_creature_handler(
     }
     
     // Deduct currency
-    let new_balance = sqlx::query!(
-        "UPDATE users SET currency_balance = currency_balance - $1 WHERE id = $2 RETURNING currency_balance",
-        RENAME_COST,
-        user_id.0
+    let new_balance = ledger_service::debit(
+        &mut tx, user_id.0, RENAME_COST, PaxEntryKind::RenameCost, SystemAccount::Mint, Some(creature_id),
     )
-    .fetch_one(&mut *tx)
     .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-    .currency_balance;
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     
     // Update creature name
     sqlx::query!(
@@ -281,6 +278,18 @@ pub async fn rename_creature_handler(
     .execute(&mut *tx)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    // Record the rename in the creature's history
+    sqlx::query!(
+        "INSERT INTO item_events (item_id, item_type, event_type, performed_by_user_id, additional_data)
+         VALUES ($1, 'creature', 'renamed'::event_type, $2, $3)",
+        creature_id,
+        user_id.0,
+        serde_json::json!({ "old_name": creature.display_name, "new_name": new_name })
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     
     // Commit transaction
     tx.commit().await
@@ -323,12 +332,13 @@ pub async fn get_public_showcase_creatures(
             c.display_name,
             c.image_path,
             c.rarity::text as "rarity!",
-            u.username as "owner_username!",
+            u.display_name as "owner_username!",
             c.hatched_at::text as "hatched_at!"
         FROM creatures c
-        JOIN users u ON c.owner_id = u.id
+        JOIN public_users u ON c.owner_id = u.id
         WHERE c.rarity IN ('Uncommon', 'Rare', 'Epic', 'Legendary', 'Mythical')
         AND c.status = 'available'
+        AND u.in_showcase
         ORDER BY 
             CASE c.rarity
                 WHEN 'Mythical' THEN 5
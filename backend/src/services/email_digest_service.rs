@@ -0,0 +1,443 @@
+use axum::{
+    extract::{State, Query},
+    http::StatusCode,
+    response::Html,
+};
+use lettre::{
+    message::header::{ContentType, Header, HeaderName, HeaderValue},
+    transport::smtp::{
+        authentication::Credentials,
+        client::{Tls, TlsParameters},
+    },
+    Message,
+    SmtpTransport,
+    Transport,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tracing::{error, info};
+use uuid::Uuid;
+use crate::auth::utils::get_frontend_url;
+use crate::AppState;
+use super::claim_service::{DAILY_CLAIM_COOLDOWN, STREAK_RESET_WINDOW};
+use super::notification_service::NotificationKind;
+use super::preferences_service::load_preferences;
+
+/// How long before a claim streak resets we warn about it.
+const STREAK_WARNING_LEAD_SECS: f64 = 6.0 * 3600.0;
+
+/// A digest entry as it appears in the email.
+pub struct DigestEntry {
+    pub kind: String,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnsubscribeQuery {
+    pub token: Uuid,
+}
+
+/// Email digests are opt-in: `email_digest` in the notification settings is off by default,
+/// and individual kinds can be left out through the `email` map.
+async fn wants_email(pool: &PgPool, user_id: Uuid, kind: NotificationKind) -> Result<bool, sqlx::Error> {
+    let preferences = load_preferences(pool, user_id).await?;
+    Ok(preferences.notification_settings.email_enabled(kind.as_str()))
+}
+
+/// Adds an entry to the user's next digest if they've opted in. `deliver_after` holds
+/// back entries for things that haven't happened yet, like a chaos realm run finishing.
+/// Best effort, like `notify`: call it after the triggering transaction commits.
+pub async fn queue(
+    pool: &PgPool,
+    user_id: Uuid,
+    kind: NotificationKind,
+    message: String,
+    deliver_after: Option<OffsetDateTime>,
+) {
+    match wants_email(pool, user_id, kind).await {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(e) => {
+            error!("Failed to read email digest settings: {}", e);
+            return;
+        }
+    }
+
+    if let Err(e) = sqlx::query!(
+        "INSERT INTO email_digest_items (user_id, kind, message, deliver_after)
+         VALUES ($1, $2::text::notification_kind, $3, COALESCE($4, NOW()))",
+        user_id,
+        kind.as_str(),
+        message,
+        deliver_after
+    )
+    .execute(pool)
+    .await
+    {
+        error!("Failed to queue {} digest entry: {}", kind.as_str(), e);
+    }
+}
+
+/// Queues a warning for subscribed users whose claim streak resets within the next few hours.
+/// Each streak is only warned about once: a new claim moves `last_daily_reward` past the old warning.
+pub async fn queue_streak_warnings(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let deadline_secs = (DAILY_CLAIM_COOLDOWN + STREAK_RESET_WINDOW) as f64;
+
+    let queued = sqlx::query!(
+        r#"
+        INSERT INTO email_digest_items (user_id, kind, message)
+        SELECT u.id, $3::text::notification_kind,
+               'Your ' || u.claim_streak || '-day claim streak resets soon. Claim your daily reward to keep it going.'
+        FROM users u
+        JOIN user_preferences p ON p.user_id = u.id
+        WHERE p.notification_settings->>'email_digest' IN ('hourly', 'daily')
+          AND COALESCE((p.notification_settings->'email'->>'streak_expiring')::boolean, true)
+          AND u.deleted_at IS NULL
+          AND u.claim_streak > 1
+          AND u.last_daily_reward + make_interval(secs => $1) BETWEEN NOW() AND NOW() + make_interval(secs => $2)
+          AND NOT EXISTS (
+              SELECT 1 FROM email_digest_items i
+              WHERE i.user_id = u.id AND i.kind = $3::text::notification_kind AND i.created_at > u.last_daily_reward
+          )
+        "#,
+        deadline_secs,
+        STREAK_WARNING_LEAD_SECS,
+        NotificationKind::StreakExpiring.as_str()
+    )
+    .execute(pool)
+    .await?;
+
+    if queued.rows_affected() > 0 {
+        info!("Queued {} claim streak warnings", queued.rows_affected());
+    }
+
+    Ok(())
+}
+
+/// Renders the digest email body. Kept free of any I/O so the template can be unit
+/// tested; to see a real email, point `SMTP_HOST` at a local stand-in (see `build_mailer`).
+pub fn render_digest(username: &str, entries: &[DigestEntry], unsubscribe_link: &str) -> String {
+    let items: String = entries
+        .iter()
+        .map(|entry| format!(
+            r#"<li style="margin-bottom: 10px;"><strong>{}</strong><br>{}</li>"#,
+            digest_heading(&entry.kind),
+            html_escape(&entry.message)
+        ))
+        .collect();
+
+    format!(
+        r#"
+        <html>
+        <body style="font-family: Arial, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
+            <div style="text-align: center; margin-bottom: 20px;">
+                <h1 style="color: #4a5568; margin-bottom: 10px;">What happened on FRTL</h1>
+            </div>
+            <p>Hi {},</p>
+            <p>Here's what happened since your last update:</p>
+            <ul style="padding-left: 20px;">{}</ul>
+            <div style="text-align: center; margin: 30px 0;">
+                <a href="{}" style="background-color: #4299e1; color: white; padding: 16px 32px; text-decoration: none; border-radius: 6px; font-weight: bold; display: inline-block; font-size: 18px; box-shadow: 0 4px 6px rgba(0, 0, 0, 0.1);">Open FRTL</a>
+            </div>
+            <hr style="border: none; border-top: 1px solid #e2e8f0; margin: 20px 0;">
+            <p style="color: #718096; font-size: 12px; text-align: center;">You're receiving this because you turned on email digests. <a href="{}" style="color: #718096;">Unsubscribe</a></p>
+        </body>
+        </html>
+        "#,
+        html_escape(username),
+        items,
+        get_frontend_url(),
+        unsubscribe_link
+    )
+}
+
+fn digest_heading(kind: &str) -> &'static str {
+    match kind {
+        "listing_sold" => "Item sold",
+        "chaos_realm_ready" => "Chaos realm reward ready",
+        "energy_recharged" => "Energy recharged",
+        "streak_expiring" => "Claim streak about to reset",
+        _ => "Update",
+    }
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Same SMTP settings as the magic link emails: port 587 with STARTTLS required. For
+/// local testing set `SMTP_TLS=false` and `SMTP_PORT` to a stand-in such as MailHog
+/// (1025); credentials are then optional.
+fn build_mailer() -> Result<SmtpTransport, String> {
+    let smtp_host = std::env::var("SMTP_HOST").map_err(|_| "SMTP_HOST not set".to_string())?;
+    let use_tls = std::env::var("SMTP_TLS").map(|v| v != "false").unwrap_or(true);
+    let port = match std::env::var("SMTP_PORT") {
+        Ok(port) => port.parse::<u16>().map_err(|e| format!("Invalid SMTP_PORT: {}", e))?,
+        Err(_) if use_tls => 587,
+        Err(_) => 1025,
+    };
+
+    let builder = if use_tls {
+        let tls = TlsParameters::new(smtp_host.clone())
+            .map_err(|e| format!("Failed to create TLS parameters: {}", e))?;
+        SmtpTransport::relay(&smtp_host)
+            .map_err(|e| format!("Failed to create SMTP transport: {}", e))?
+            .tls(Tls::Required(tls))
+    } else {
+        SmtpTransport::builder_dangerous(&smtp_host)
+    };
+
+    let builder = match (std::env::var("SMTP_USERNAME"), std::env::var("SMTP_PASSWORD")) {
+        (Ok(username), Ok(password)) => builder.credentials(Credentials::new(username, password)),
+        _ if use_tls => return Err("SMTP_USERNAME and SMTP_PASSWORD must be set".to_string()),
+        _ => builder,
+    };
+
+    Ok(builder.port(port).build())
+}
+
+/// `List-Unsubscribe`, so mail clients can show their own unsubscribe button.
+#[derive(Clone)]
+struct ListUnsubscribe(String);
+
+impl Header for ListUnsubscribe {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("List-Unsubscribe")
+    }
+
+    fn parse(s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self(s.trim().trim_start_matches('<').trim_end_matches('>').to_string()))
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), format!("<{}>", self.0))
+    }
+}
+
+/// `List-Unsubscribe-Post` (RFC 8058): the client's button POSTs to the
+/// `List-Unsubscribe` link instead of opening it.
+#[derive(Clone)]
+struct ListUnsubscribePost;
+
+impl Header for ListUnsubscribePost {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("List-Unsubscribe-Post")
+    }
+
+    fn parse(_s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self)
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), "List-Unsubscribe=One-Click".to_string())
+    }
+}
+
+async fn send_digest_email(email: &str, body: String, unsubscribe_link: &str) -> Result<(), String> {
+    let message = Message::builder()
+        .from("frtl@jaykrown.com".parse().map_err(|e| format!("Failed to parse from address: {}", e))?)
+        .to(format!("<{}>", email).parse().map_err(|e| format!("Failed to parse to address: {}", e))?)
+        .subject("Your FRTL digest")
+        .header(ContentType::TEXT_HTML)
+        .header(ListUnsubscribe(unsubscribe_link.to_string()))
+        .header(ListUnsubscribePost)
+        .body(body)
+        .map_err(|e| format!("Failed to build email: {}", e))?;
+
+    let mailer = build_mailer()?;
+
+    // The SMTP transport is blocking, so keep it off the async workers
+    tokio::task::spawn_blocking(move || mailer.send(&message))
+        .await
+        .map_err(|e| format!("Email task failed: {}", e))?
+        .map(|_| ())
+        .map_err(|e| format!("Failed to send email: {}", e))
+}
+
+/// Sends a digest to every subscribed user whose interval has passed and who has entries
+/// waiting. Streak warnings can't wait for a daily digest, so they go out on the next run.
+pub async fn send_due_digests(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let recipients = sqlx::query!(
+        r#"
+        SELECT p.user_id, u.username, u.email, p.email_unsubscribe_token
+        FROM user_preferences p
+        JOIN users u ON u.id = p.user_id
+        WHERE u.deleted_at IS NULL
+          AND p.notification_settings->>'email_digest' IN ('hourly', 'daily')
+          AND EXISTS (
+              SELECT 1 FROM email_digest_items i
+              WHERE i.user_id = p.user_id AND i.sent_at IS NULL AND i.deliver_after <= NOW()
+          )
+          AND (
+              p.last_digest_sent_at IS NULL
+              OR p.last_digest_sent_at <= NOW() - CASE p.notification_settings->>'email_digest'
+                  WHEN 'hourly' THEN INTERVAL '1 hour'
+                  ELSE INTERVAL '1 day'
+              END
+              OR EXISTS (
+                  SELECT 1 FROM email_digest_items i
+                  WHERE i.user_id = p.user_id AND i.sent_at IS NULL AND i.kind = 'streak_expiring'
+              )
+          )
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for recipient in recipients {
+        let items = sqlx::query!(
+            r#"
+            SELECT id, kind::text as "kind!", message
+            FROM email_digest_items
+            WHERE user_id = $1 AND sent_at IS NULL AND deliver_after <= NOW()
+            ORDER BY deliver_after
+            "#,
+            recipient.user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let item_ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+        let entries: Vec<DigestEntry> = items
+            .into_iter()
+            .map(|item| DigestEntry { kind: item.kind, message: item.message })
+            .collect();
+
+        let unsubscribe_link = format!(
+            "{}/api/email/unsubscribe?token={}",
+            get_frontend_url(),
+            recipient.email_unsubscribe_token
+        );
+        let body = render_digest(&recipient.username, &entries, &unsubscribe_link);
+
+        if let Err(e) = send_digest_email(&recipient.email, body, &unsubscribe_link).await {
+            // Leave the entries pending so the next run retries them
+            error!("Failed to send email digest to user {}: {}", recipient.user_id, e);
+            continue;
+        }
+
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "UPDATE email_digest_items SET sent_at = NOW() WHERE id = ANY($1)",
+            &item_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE user_preferences SET last_digest_sent_at = NOW() WHERE user_id = $1",
+            recipient.user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        info!("Sent email digest with {} entries to user {}", entries.len(), recipient.user_id);
+    }
+
+    Ok(())
+}
+
+/// Public link from the digest footer. Mail scanners and link prefetchers follow links
+/// on their own, so the GET only asks for confirmation and the form POSTs back here.
+pub async fn confirm_unsubscribe_email_digest(Query(_query): Query<UnsubscribeQuery>) -> Html<&'static str> {
+    Html(
+        r#"<html><body style="font-family: Arial, sans-serif; text-align: center; padding: 40px;">
+        <h1>Unsubscribe from FRTL digests?</h1>
+        <p>You won't receive FRTL email digests anymore. You can turn them back on in your settings.</p>
+        <form method="post">
+            <button type="submit" style="background-color: #4299e1; color: white; padding: 12px 24px; border: none; border-radius: 6px; font-weight: bold; cursor: pointer;">Unsubscribe</button>
+        </form>
+        </body></html>"#,
+    )
+}
+
+/// Turns digests off, from the confirmation form or a mail client's one-click
+/// `List-Unsubscribe-Post`. It only ever turns digests off, so the token doesn't need
+/// to expire.
+pub async fn unsubscribe_email_digest(
+    State(state): State<AppState>,
+    Query(query): Query<UnsubscribeQuery>,
+) -> Result<Html<&'static str>, StatusCode> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE user_preferences
+        SET notification_settings = jsonb_set(notification_settings, '{email_digest}', '"off"')
+        WHERE email_unsubscribe_token = $1
+        "#,
+        query.token
+    )
+    .execute(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to unsubscribe from email digest: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if updated.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Html(
+        r#"<html><body style="font-family: Arial, sans-serif; text-align: center; padding: 40px;">
+        <h1>You've been unsubscribed</h1>
+        <p>You won't receive FRTL email digests anymore. You can turn them back on in your settings.</p>
+        </body></html>"#,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: &str, message: &str) -> DigestEntry {
+        DigestEntry { kind: kind.to_string(), message: message.to_string() }
+    }
+
+    #[test]
+    fn entries_are_html_escaped() {
+        let body = render_digest(
+            "<b>Jay</b>",
+            &[entry("listing_sold", r#"Your <script>"egg"</script> sold & shipped"#)],
+            "https://frtl.dev/api/email/unsubscribe?token=abc",
+        );
+        assert!(body.contains("Hi &lt;b&gt;Jay&lt;/b&gt;,"));
+        assert!(body.contains("Your &lt;script&gt;&quot;egg&quot;&lt;/script&gt; sold &amp; shipped"));
+        assert!(!body.contains("<script>"));
+    }
+
+    #[test]
+    fn headings_follow_entry_kind() {
+        let body = render_digest(
+            "jay",
+            &[
+                entry("listing_sold", "a"),
+                entry("chaos_realm_ready", "b"),
+                entry("energy_recharged", "c"),
+                entry("streak_expiring", "d"),
+                entry("friend_request", "e"),
+            ],
+            "https://frtl.dev/api/email/unsubscribe?token=abc",
+        );
+        for heading in [
+            "<strong>Item sold</strong>",
+            "<strong>Chaos realm reward ready</strong>",
+            "<strong>Energy recharged</strong>",
+            "<strong>Claim streak about to reset</strong>",
+            "<strong>Update</strong>",
+        ] {
+            assert!(body.contains(heading), "missing {}", heading);
+        }
+    }
+
+    #[test]
+    fn unsubscribe_link_is_included() {
+        let link = "https://frtl.dev/api/email/unsubscribe?token=abc";
+        let body = render_digest("jay", &[entry("listing_sold", "a")], link);
+        assert!(body.contains(&format!(r#"<a href="{}""#, link)));
+        assert!(body.contains(">Unsubscribe</a>"));
+    }
+}
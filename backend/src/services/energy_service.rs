@@ -8,6 +8,11 @@ use time::OffsetDateTime;
 use sqlx::Acquire;
 use uuid::Uuid;
 use crate::auth::middleware::UserId;
+use shared::push_events::PushEvent;
+use super::push_service;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
+use super::email_digest_service;
+use super::notification_service::NotificationKind;
 
 #[derive(Debug, Serialize)]
 pub struct EnergyResponse {
@@ -166,17 +171,9 @@ pub async fn handle_energy_recharge(
         return Err(StatusCode::BAD_REQUEST);
     }
 
-    let updated_user = sqlx::query!(
-        r#"
-        UPDATE users
-        SET currency_balance = currency_balance - $1
-        WHERE id = $2
-        RETURNING currency_balance as "pax!: i32"
-        "#,
-        energy_cost,
-        user_id
+    let new_balance = ledger_service::debit(
+        &mut tx, user_id, energy_cost, PaxEntryKind::RechargeCost, SystemAccount::Mint, Some(creature_id),
     )
-    .fetch_one(&mut *tx)
     .await
     .map_err(|e| {
         log::error!("Failed to update user balance: {:?}", e);
@@ -186,8 +183,17 @@ pub async fn handle_energy_recharge(
     // Commit the transaction
     tx.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    email_digest_service::queue(
+        &pool,
+        user_id,
+        NotificationKind::EnergyRecharged,
+        format!("Your {} creature's energy is fully recharged", creature.rarity),
+        Some(recharge_complete_at_parsed),
+    ).await;
+
     // Clone the pool for the background task
     let pool_clone = pool.clone();
+    let redis_clone = redis_pool.clone();
     let creature_id_clone = creature_id;
     
     // Spawn a background task to update creature energy status to full after recharge time expires
@@ -213,6 +219,9 @@ pub async fn handle_energy_recharge(
                     log::error!("Failed to update creature energy to full: {:?}", e);
                 } else {
                     log::info!("Creature {} energy updated to full.", creature_id_clone);
+                    push_service::publish(&redis_clone, user_id, &PushEvent::EnergyRecharged {
+                        creature_id: creature_id_clone.to_string(),
+                    }).await;
                 }
             },
             Err(e) => {
@@ -226,7 +235,7 @@ pub async fn handle_energy_recharge(
         error: None,
         creature_id,
         energy_full: false,
-        pax_balance: updated_user.pax as i64,
+        pax_balance: new_balance as i64,
         energy_recharge_complete_at: Some(recharge_complete_at),
     }))
 }
@@ -234,7 +243,7 @@ pub async fn handle_energy_recharge(
 /// Checks for creatures with expired energy recharge times and updates them to have full energy.
 /// This function is meant to be called periodically to recover from situations where the
 /// background task that updates energy status after recharge time expires fails (e.g., due to server restart).
-pub async fn check_expired_energy_recharges(pool: &PgPool) -> Result<(), sqlx::Error> {
+pub async fn check_expired_energy_recharges(pool: &PgPool, redis: &RedisClient) -> Result<(), sqlx::Error> {
     log::info!("Checking for creatures with expired energy recharge times...");
     
     let now = OffsetDateTime::now_utc();
@@ -259,7 +268,7 @@ pub async fn check_expired_energy_recharges(pool: &PgPool) -> Result<(), sqlx::E
         
         // Update all expired creatures to have full energy
         let mut conn = pool.acquire().await?;
-        let result = sqlx::query!(
+        let recharged = sqlx::query!(
             r#"
             UPDATE creatures
             SET energy_full = true,
@@ -267,13 +276,20 @@ pub async fn check_expired_energy_recharges(pool: &PgPool) -> Result<(), sqlx::E
             WHERE energy_full = false 
             AND energy_recharge_complete_at IS NOT NULL 
             AND energy_recharge_complete_at < $1
+            RETURNING id, owner_id
             "#,
             now
         )
-        .execute(&mut *conn)
+        .fetch_all(&mut *conn)
         .await?;
         
-        log::info!("Updated {} creatures to have full energy", result.rows_affected());
+        log::info!("Updated {} creatures to have full energy", recharged.len());
+
+        for creature in recharged {
+            push_service::publish(redis, creature.owner_id, &PushEvent::EnergyRecharged {
+                creature_id: creature.id.to_string(),
+            }).await;
+        }
     } else {
         log::info!("No creatures with expired energy recharge times found");
     }
@@ -0,0 +1,433 @@
+use axum::{
+    extract::{State, Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgExecutor;
+use std::collections::HashMap;
+use tracing::{error, info};
+use uuid::Uuid;
+use crate::auth::middleware::UserId;
+use crate::AppState;
+use super::market_service::ApiResponse;
+use super::notification_service::{notify, NotificationKind};
+
+const ACTIVITY_DEFAULT_LIMIT: i64 = 30;
+const ACTIVITY_MAX_LIMIT: i64 = 100;
+
+#[derive(Debug, Serialize)]
+pub struct FriendEntry {
+    pub user_id: Uuid,
+    pub username: String,
+    pub since: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FriendsOverview {
+    pub friends: Vec<FriendEntry>,
+    pub incoming: Vec<FriendEntry>,
+    pub outgoing: Vec<FriendEntry>,
+    pub blocked: Vec<FriendEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FriendActivity {
+    pub id: Uuid,
+    pub username: String,
+    pub event_type: String,
+    pub item_type: String,
+    pub item_name: String,
+    pub image_path: Option<String>,
+    pub price: Option<i32>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UsernameRequest {
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    pub limit: Option<i64>,
+}
+
+fn db_error(action: &str) -> impl Fn(sqlx::Error) -> StatusCode + '_ {
+    move |e| {
+        error!("Failed to {}: {}", action, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+fn failure<T>(message: &str) -> Json<ApiResponse<T>> {
+    Json(ApiResponse {
+        success: false,
+        data: None,
+        error: Some(message.to_string()),
+    })
+}
+
+fn done() -> Json<ApiResponse<()>> {
+    Json(ApiResponse {
+        success: true,
+        data: Some(()),
+        error: None,
+    })
+}
+
+/// True if either user has blocked the other. Trades and gifts check this so a block
+/// cuts off every direct interaction, whichever side set it.
+pub async fn is_blocked<'e>(
+    executor: impl PgExecutor<'e>,
+    user_a: Uuid,
+    user_b: Uuid,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM user_blocks
+            WHERE (blocker_id = $1 AND blocked_id = $2) OR (blocker_id = $2 AND blocked_id = $1)
+        ) as "blocked!"
+        "#,
+        user_a,
+        user_b
+    )
+    .fetch_one(executor)
+    .await
+}
+
+/// Reads `scope=friends` from a leaderboard query. Returns the user whose friends the
+/// board should be limited to, or `None` for the global board. Leaderboards are public,
+/// so the friends scope is the only part that needs a signed-in caller.
+pub fn leaderboard_scope(
+    params: &HashMap<String, String>,
+    user_id: Option<UserId>,
+) -> Result<Option<Uuid>, StatusCode> {
+    match params.get("scope").map(String::as_str) {
+        Some("friends") => user_id.map(|id| Some(id.0)).ok_or(StatusCode::UNAUTHORIZED),
+        Some("global") | None => Ok(None),
+        Some(_) => Err(StatusCode::BAD_REQUEST),
+    }
+}
+
+async fn find_user(state: &AppState, username: &str) -> Result<Option<Uuid>, StatusCode> {
+    sqlx::query_scalar!(
+        "SELECT id FROM users WHERE LOWER(username) = LOWER($1) AND deleted_at IS NULL",
+        username
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(db_error("look up user"))
+}
+
+/// `GET /api/friends`: accepted friends, pending requests both ways, and blocked users.
+pub async fn get_friends(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<ApiResponse<FriendsOverview>>, StatusCode> {
+    let friends = sqlx::query_as!(
+        FriendEntry,
+        r#"
+        SELECT u.id as "user_id!", u.username as "username!",
+               TO_CHAR(f.accepted_at, 'YYYY-MM-DD') as "since!"
+        FROM friendships f
+        JOIN users u ON u.id = f.friend_id
+        WHERE f.user_id = $1
+        ORDER BY LOWER(u.username)
+        "#,
+        user_id.0
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(db_error("fetch friends"))?;
+
+    let incoming = sqlx::query_as!(
+        FriendEntry,
+        r#"
+        SELECT u.id as "user_id!", u.username as "username!",
+               TO_CHAR(f.created_at, 'YYYY-MM-DD') as "since!"
+        FROM friends f
+        JOIN users u ON u.id = f.user_id
+        WHERE f.friend_id = $1 AND f.status = 'pending'
+        ORDER BY f.created_at DESC
+        "#,
+        user_id.0
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(db_error("fetch incoming friend requests"))?;
+
+    let outgoing = sqlx::query_as!(
+        FriendEntry,
+        r#"
+        SELECT u.id as "user_id!", u.username as "username!",
+               TO_CHAR(f.created_at, 'YYYY-MM-DD') as "since!"
+        FROM friends f
+        JOIN users u ON u.id = f.friend_id
+        WHERE f.user_id = $1 AND f.status = 'pending'
+        ORDER BY f.created_at DESC
+        "#,
+        user_id.0
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(db_error("fetch outgoing friend requests"))?;
+
+    let blocked = sqlx::query_as!(
+        FriendEntry,
+        r#"
+        SELECT u.id as "user_id!", u.username as "username!",
+               TO_CHAR(b.created_at, 'YYYY-MM-DD') as "since!"
+        FROM user_blocks b
+        JOIN users u ON u.id = b.blocked_id
+        WHERE b.blocker_id = $1
+        ORDER BY LOWER(u.username)
+        "#,
+        user_id.0
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(db_error("fetch blocked users"))?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(FriendsOverview { friends, incoming, outgoing, blocked }),
+        error: None,
+    }))
+}
+
+/// `POST /api/friends/requests`. If the other player already asked us, this accepts
+/// their request instead of opening a second one.
+pub async fn send_friend_request(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Json(payload): Json<UsernameRequest>,
+) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    let Some(target_id) = find_user(&state, &payload.username).await? else {
+        return Ok(failure("Player not found"));
+    };
+    if target_id == user_id.0 {
+        return Ok(failure("You cannot add yourself as a friend"));
+    }
+    if is_blocked(&state.pool, user_id.0, target_id).await.map_err(db_error("check blocks"))? {
+        return Ok(failure("You cannot add this player as a friend"));
+    }
+
+    let existing = sqlx::query!(
+        r#"
+        SELECT user_id, status::text as "status!"
+        FROM friends
+        WHERE (user_id = $1 AND friend_id = $2) OR (user_id = $2 AND friend_id = $1)
+        "#,
+        user_id.0,
+        target_id
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(db_error("check existing friendship"))?;
+
+    match existing {
+        Some(row) if row.status == "accepted" => Ok(failure("You are already friends")),
+        Some(row) if row.user_id == user_id.0 => Ok(failure("Friend request already sent")),
+        Some(_) => accept_request(&state, user_id.0, target_id).await,
+        None => {
+            sqlx::query!(
+                "INSERT INTO friends (user_id, friend_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+                user_id.0,
+                target_id
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(db_error("create friend request"))?;
+
+            let sender = sqlx::query_scalar!("SELECT username FROM users WHERE id = $1", user_id.0)
+                .fetch_one(&state.pool)
+                .await
+                .map_err(db_error("fetch username"))?;
+
+            info!("👋 {} sent a friend request to {}", sender, payload.username);
+            notify(
+                &state.pool,
+                &state.redis,
+                target_id,
+                NotificationKind::FriendRequest,
+                format!("{} sent you a friend request", sender),
+                serde_json::json!({ "user_id": user_id.0 }),
+            ).await;
+
+            Ok(done())
+        }
+    }
+}
+
+async fn accept_request(
+    state: &AppState,
+    user_id: Uuid,
+    requester_id: Uuid,
+) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    let accepted = sqlx::query!(
+        "UPDATE friends SET status = 'accepted', accepted_at = CURRENT_TIMESTAMP
+         WHERE user_id = $1 AND friend_id = $2 AND status = 'pending'",
+        requester_id,
+        user_id
+    )
+    .execute(&state.pool)
+    .await
+    .map_err(db_error("accept friend request"))?;
+
+    if accepted.rows_affected() == 0 {
+        return Ok(failure("Friend request not found"));
+    }
+
+    let username = sqlx::query_scalar!("SELECT username FROM users WHERE id = $1", user_id)
+        .fetch_one(&state.pool)
+        .await
+        .map_err(db_error("fetch username"))?;
+
+    info!("🤝 {} accepted a friend request", username);
+    notify(
+        &state.pool,
+        &state.redis,
+        requester_id,
+        NotificationKind::FriendAccepted,
+        format!("{} accepted your friend request", username),
+        serde_json::json!({ "user_id": user_id }),
+    ).await;
+
+    Ok(done())
+}
+
+/// `POST /api/friends/:user_id/accept`
+pub async fn accept_friend_request(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(requester_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    accept_request(&state, user_id.0, requester_id).await
+}
+
+/// `DELETE /api/friends/:user_id`: unfriends, declines an incoming request or cancels
+/// an outgoing one. Whichever it was, the pair is left with no row at all.
+pub async fn remove_friend(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(other_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    let removed = sqlx::query!(
+        "DELETE FROM friends
+         WHERE (user_id = $1 AND friend_id = $2) OR (user_id = $2 AND friend_id = $1)",
+        user_id.0,
+        other_id
+    )
+    .execute(&state.pool)
+    .await
+    .map_err(db_error("remove friend"))?;
+
+    if removed.rows_affected() == 0 {
+        return Ok(failure("Friend not found"));
+    }
+    Ok(done())
+}
+
+/// `POST /api/friends/blocks`. Blocking also drops any friendship or pending request.
+pub async fn block_user(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Json(payload): Json<UsernameRequest>,
+) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    let Some(target_id) = find_user(&state, &payload.username).await? else {
+        return Ok(failure("Player not found"));
+    };
+    if target_id == user_id.0 {
+        return Ok(failure("You cannot block yourself"));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(db_error("begin transaction"))?;
+
+    sqlx::query!(
+        "INSERT INTO user_blocks (blocker_id, blocked_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        user_id.0,
+        target_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error("block user"))?;
+
+    sqlx::query!(
+        "DELETE FROM friends
+         WHERE (user_id = $1 AND friend_id = $2) OR (user_id = $2 AND friend_id = $1)",
+        user_id.0,
+        target_id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error("remove friendship of blocked user"))?;
+
+    tx.commit().await.map_err(db_error("commit transaction"))?;
+
+    info!("🚫 User {} blocked {}", user_id.0, payload.username);
+    Ok(done())
+}
+
+/// `DELETE /api/friends/blocks/:user_id`
+pub async fn unblock_user(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(blocked_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    let removed = sqlx::query!(
+        "DELETE FROM user_blocks WHERE blocker_id = $1 AND blocked_id = $2",
+        user_id.0,
+        blocked_id
+    )
+    .execute(&state.pool)
+    .await
+    .map_err(db_error("unblock user"))?;
+
+    if removed.rows_affected() == 0 {
+        return Ok(failure("Player is not blocked"));
+    }
+    Ok(done())
+}
+
+/// `GET /api/friends/activity`: friends' recent hatches and sales, newest first.
+/// Hatches belong to the new owner, sales and won auctions to the seller.
+pub async fn get_friend_activity(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Query(params): Query<ActivityQuery>,
+) -> Result<Json<ApiResponse<Vec<FriendActivity>>>, StatusCode> {
+    let limit = params.limit.unwrap_or(ACTIVITY_DEFAULT_LIMIT).clamp(1, ACTIVITY_MAX_LIMIT);
+
+    let activity = sqlx::query_as!(
+        FriendActivity,
+        r#"
+        SELECT e.id, u.username as "username!", e.event_type::text as "event_type!", e.item_type,
+               COALESCE(c.display_name, INITCAP(e.item_type)) as "item_name!",
+               c.image_path as "image_path?",
+               (e.additional_data->>'price')::int as "price?",
+               TO_CHAR(e.timestamp, 'YYYY-MM-DD HH24:MI:SS') as "created_at!"
+        FROM friendships f
+        JOIN item_events e
+          ON (e.event_type = 'hatched' AND e.to_user_id = f.friend_id)
+          OR (e.event_type IN ('sold', 'auction_won') AND e.from_user_id = f.friend_id)
+        JOIN users u ON u.id = f.friend_id
+        LEFT JOIN creatures c ON c.id = e.item_id
+        WHERE f.user_id = $1
+        ORDER BY e.timestamp DESC
+        LIMIT $2
+        "#,
+        user_id.0,
+        limit
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(db_error("fetch friend activity"))?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(activity),
+        error: None,
+    }))
+}
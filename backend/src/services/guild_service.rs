@@ -0,0 +1,933 @@
+use axum::{
+    extract::{State, Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgExecutor, Postgres, Transaction};
+use tracing::{error, info};
+use uuid::Uuid;
+use crate::auth::middleware::UserId;
+use crate::AppState;
+use super::market_service::ApiResponse;
+use super::friend_service::is_blocked;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
+use super::notification_service::{notify, NotificationKind};
+use super::push_service;
+
+const MAX_GUILD_MEMBERS: i64 = 30;
+const GUILD_NAME_MIN: usize = 3;
+const GUILD_NAME_MAX: usize = 32;
+const DESCRIPTION_MAX: usize = 280;
+const REASON_MAX: usize = 140;
+const TREASURY_LOG_LIMIT: i64 = 20;
+const LEADERBOARD_DEFAULT_LIMIT: i64 = 20;
+const LEADERBOARD_MAX_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateGuildRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GuildInviteRequest {
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetRoleRequest {
+    pub role: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DepositRequest {
+    pub amount: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpendRequest {
+    pub recipient_username: String,
+    pub amount: i32,
+    #[serde(default)]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GuildLeaderboardQuery {
+    pub sort: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GuildMember {
+    pub user_id: Uuid,
+    pub username: String,
+    pub role: String,
+    pub experience: i32,
+    pub joined_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TreasuryEntry {
+    pub username: Option<String>,
+    pub recipient_username: Option<String>,
+    pub amount: i32,
+    pub reason: String,
+    pub treasury_after: i32,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GuildDetails {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub treasury: i32,
+    pub my_role: String,
+    pub members: Vec<GuildMember>,
+    pub pending_invites: Vec<String>,
+    pub treasury_log: Vec<TreasuryEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingGuildInvite {
+    pub guild_id: Uuid,
+    pub guild_name: String,
+    pub invited_by: String,
+    pub member_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GuildLeaderboardEntry {
+    pub name: String,
+    pub member_count: i64,
+    pub total_experience: i64,
+    pub total_score: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GuildActionResponse {
+    pub guild_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TreasuryResponse {
+    pub treasury: i32,
+    pub new_balance: i32,
+}
+
+/// Failure of a guild operation: either a rule the caller broke, or a database error.
+enum GuildError {
+    Rejected(String),
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for GuildError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+fn rejected<T>(message: &str) -> Result<T, GuildError> {
+    Err(GuildError::Rejected(message.to_string()))
+}
+
+/// Turns the outcome of a guild operation into the API response, logging database failures.
+fn respond<T>(action: &str, result: Result<T, GuildError>) -> Result<Json<ApiResponse<T>>, StatusCode> {
+    match result {
+        Ok(data) => Ok(Json(ApiResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })),
+        Err(GuildError::Rejected(message)) => {
+            info!("❌ Guild {} failed: {}", action, message);
+            Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(message),
+            }))
+        }
+        Err(GuildError::Database(e)) => {
+            error!("Failed to {} guild: {}", action, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+struct Membership {
+    guild_id: Uuid,
+    role: String,
+}
+
+impl Membership {
+    fn is_leader(&self) -> bool {
+        self.role == "leader"
+    }
+
+    fn is_officer(&self) -> bool {
+        self.role == "leader" || self.role == "officer"
+    }
+}
+
+async fn membership<'e>(
+    executor: impl PgExecutor<'e>,
+    user_id: Uuid,
+) -> Result<Option<Membership>, sqlx::Error> {
+    sqlx::query_as!(
+        Membership,
+        r#"SELECT guild_id, role::text as "role!" FROM guild_members WHERE user_id = $1"#,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Looks up a live account by username, ignoring case.
+pub(crate) async fn find_user<'e>(executor: impl PgExecutor<'e>, username: &str) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT id FROM users WHERE LOWER(username) = LOWER($1) AND deleted_at IS NULL",
+        username
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+async fn begin(state: &AppState) -> Result<Transaction<'static, Postgres>, StatusCode> {
+    state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+async fn commit(tx: Transaction<'_, Postgres>) -> Result<(), StatusCode> {
+    tx.commit().await.map_err(|e| {
+        error!("Failed to commit transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+fn validate_name(name: &str) -> Result<(), GuildError> {
+    let length = name.chars().count();
+    if !(GUILD_NAME_MIN..=GUILD_NAME_MAX).contains(&length) {
+        return rejected(&format!("Guild names must be {} to {} characters", GUILD_NAME_MIN, GUILD_NAME_MAX));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '-' || c == '_') {
+        return rejected("Guild names may only contain letters, numbers, spaces, dashes and underscores");
+    }
+    Ok(())
+}
+
+/// `GET /api/guilds/mine`. `data` is null when the player isn't in a guild.
+pub async fn get_my_guild(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<ApiResponse<Option<GuildDetails>>>, StatusCode> {
+    let result: Result<_, GuildError> = async {
+        let Some(member) = membership(&state.pool, user_id.0).await? else {
+            return Ok(None);
+        };
+
+        let guild = sqlx::query!(
+            "SELECT id, name, description, treasury FROM guilds WHERE id = $1",
+            member.guild_id
+        )
+        .fetch_one(&state.pool)
+        .await?;
+
+        let members = sqlx::query_as!(
+            GuildMember,
+            r#"
+            SELECT u.id as user_id, u.username, m.role::text as "role!", u.experience,
+                   TO_CHAR(m.joined_at, 'YYYY-MM-DD') as "joined_at!"
+            FROM guild_members m
+            JOIN users u ON u.id = m.user_id
+            WHERE m.guild_id = $1 AND u.deleted_at IS NULL
+            ORDER BY CASE m.role WHEN 'leader' THEN 0 WHEN 'officer' THEN 1 ELSE 2 END, LOWER(u.username)
+            "#,
+            member.guild_id
+        )
+        .fetch_all(&state.pool)
+        .await?;
+
+        let pending_invites = if member.is_officer() {
+            sqlx::query_scalar!(
+                "SELECT u.username FROM guild_invites i JOIN users u ON u.id = i.user_id
+                 WHERE i.guild_id = $1 ORDER BY i.created_at DESC",
+                member.guild_id
+            )
+            .fetch_all(&state.pool)
+            .await?
+        } else {
+            Vec::new()
+        };
+
+        let treasury_log = sqlx::query_as!(
+            TreasuryEntry,
+            r#"
+            SELECT u.username as "username?", r.username as "recipient_username?", l.amount, l.reason,
+                   l.treasury_after, TO_CHAR(l.created_at, 'YYYY-MM-DD HH24:MI:SS') as "created_at!"
+            FROM guild_treasury_log l
+            LEFT JOIN users u ON u.id = l.user_id
+            LEFT JOIN users r ON r.id = l.recipient_id
+            WHERE l.guild_id = $1
+            ORDER BY l.created_at DESC
+            LIMIT $2
+            "#,
+            member.guild_id,
+            TREASURY_LOG_LIMIT
+        )
+        .fetch_all(&state.pool)
+        .await?;
+
+        Ok(Some(GuildDetails {
+            id: guild.id,
+            name: guild.name,
+            description: guild.description,
+            treasury: guild.treasury,
+            my_role: member.role,
+            members,
+            pending_invites,
+            treasury_log,
+        }))
+    }
+    .await;
+
+    respond("load", result)
+}
+
+/// `POST /api/guilds`: founds a guild with the caller as its leader.
+pub async fn create_guild(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Json(payload): Json<CreateGuildRequest>,
+) -> Result<Json<ApiResponse<GuildActionResponse>>, StatusCode> {
+    let name = payload.name.trim().to_string();
+    let description = payload.description.trim().to_string();
+    let mut tx = begin(&state).await?;
+
+    let result = async {
+        validate_name(&name)?;
+        if description.chars().count() > DESCRIPTION_MAX {
+            return rejected(&format!("Descriptions can be at most {} characters", DESCRIPTION_MAX));
+        }
+        if membership(&mut *tx, user_id.0).await?.is_some() {
+            return rejected("Leave your current guild before founding a new one");
+        }
+
+        let taken = sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM guilds WHERE LOWER(name) = LOWER($1)) as "taken!""#,
+            name
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if taken {
+            return rejected("That guild name is taken");
+        }
+
+        let guild_id = sqlx::query_scalar!(
+            "INSERT INTO guilds (name, description) VALUES ($1, $2) RETURNING id",
+            name,
+            description
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO guild_members (user_id, guild_id, role) VALUES ($1, $2, 'leader')",
+            user_id.0,
+            guild_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!("DELETE FROM guild_invites WHERE user_id = $1", user_id.0)
+            .execute(&mut *tx)
+            .await?;
+
+        Ok(GuildActionResponse { guild_id })
+    }
+    .await;
+
+    if result.is_ok() {
+        commit(tx).await?;
+        info!("🏰 Guild '{}' founded by {}", name, user_id.0);
+    }
+
+    respond("create", result)
+}
+
+/// `POST /api/guilds/invites`. Officers and the leader can invite players who aren't in a guild.
+pub async fn invite_to_guild(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Json(payload): Json<GuildInviteRequest>,
+) -> Result<Json<ApiResponse<GuildActionResponse>>, StatusCode> {
+    let result = async {
+        let Some(member) = membership(&state.pool, user_id.0).await? else {
+            return rejected("You are not in a guild");
+        };
+        if !member.is_officer() {
+            return rejected("Only officers can invite players");
+        }
+
+        let Some(target_id) = find_user(&state.pool, &payload.username).await? else {
+            return rejected("Player not found");
+        };
+        if target_id == user_id.0 {
+            return rejected("You are already in this guild");
+        }
+        if membership(&state.pool, target_id).await?.is_some() {
+            return rejected("That player is already in a guild");
+        }
+        if is_blocked(&state.pool, user_id.0, target_id).await? {
+            return rejected("You cannot invite this player");
+        }
+
+        let created = sqlx::query!(
+            "INSERT INTO guild_invites (guild_id, user_id, invited_by) VALUES ($1, $2, $3)
+             ON CONFLICT DO NOTHING",
+            member.guild_id,
+            target_id,
+            user_id.0
+        )
+        .execute(&state.pool)
+        .await?;
+        if created.rows_affected() == 0 {
+            return rejected("That player has already been invited");
+        }
+
+        Ok((member.guild_id, target_id))
+    }
+    .await;
+
+    if let Ok((guild_id, target_id)) = &result {
+        let names = sqlx::query!(
+            r#"SELECT g.name, u.username FROM guilds g, users u WHERE g.id = $1 AND u.id = $2"#,
+            guild_id,
+            user_id.0
+        )
+        .fetch_one(&state.pool)
+        .await;
+
+        match names {
+            Ok(names) => {
+                info!("✉️ {} invited {} to {}", names.username, payload.username, names.name);
+                notify(
+                    &state.pool,
+                    &state.redis,
+                    *target_id,
+                    NotificationKind::GuildInvite,
+                    format!("{} invited you to join {}", names.username, names.name),
+                    serde_json::json!({ "guild_id": guild_id }),
+                ).await;
+            }
+            Err(e) => error!("Failed to fetch guild invite details: {}", e),
+        }
+    }
+
+    respond("invite", result.map(|(guild_id, _)| GuildActionResponse { guild_id }))
+}
+
+/// `GET /api/guilds/invites`: invitations waiting for the caller.
+pub async fn get_guild_invites(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<ApiResponse<Vec<PendingGuildInvite>>>, StatusCode> {
+    let result = sqlx::query_as!(
+        PendingGuildInvite,
+        r#"
+        SELECT g.id as guild_id, g.name as guild_name, u.username as invited_by,
+               (SELECT COUNT(*) FROM guild_members m WHERE m.guild_id = g.id) as "member_count!"
+        FROM guild_invites i
+        JOIN guilds g ON g.id = i.guild_id
+        JOIN users u ON u.id = i.invited_by
+        WHERE i.user_id = $1
+        ORDER BY i.created_at DESC
+        "#,
+        user_id.0
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(GuildError::from);
+
+    respond("list invites for", result)
+}
+
+/// `POST /api/guilds/invites/:guild_id/accept`. Joining clears the player's other invites.
+pub async fn accept_guild_invite(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<GuildActionResponse>>, StatusCode> {
+    let mut tx = begin(&state).await?;
+
+    let result = async {
+        let invited = sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM guild_invites WHERE guild_id = $1 AND user_id = $2) as "invited!""#,
+            guild_id,
+            user_id.0
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if !invited {
+            return rejected("Invite not found");
+        }
+        if membership(&mut *tx, user_id.0).await?.is_some() {
+            return rejected("Leave your current guild before joining another");
+        }
+
+        // Locking the guild row serialises joins so the member cap holds
+        sqlx::query!("SELECT id FROM guilds WHERE id = $1 FOR UPDATE", guild_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        let member_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM guild_members WHERE guild_id = $1"#,
+            guild_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if member_count >= MAX_GUILD_MEMBERS {
+            return rejected(&format!("Guilds are limited to {} members", MAX_GUILD_MEMBERS));
+        }
+
+        sqlx::query!(
+            "INSERT INTO guild_members (user_id, guild_id) VALUES ($1, $2)",
+            user_id.0,
+            guild_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!("DELETE FROM guild_invites WHERE user_id = $1", user_id.0)
+            .execute(&mut *tx)
+            .await?;
+
+        Ok(GuildActionResponse { guild_id })
+    }
+    .await;
+
+    if result.is_ok() {
+        commit(tx).await?;
+        info!("🏰 User {} joined guild {}", user_id.0, guild_id);
+    }
+
+    respond("join", result)
+}
+
+/// `DELETE /api/guilds/invites/:guild_id`
+pub async fn decline_guild_invite(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(guild_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<GuildActionResponse>>, StatusCode> {
+    let result = async {
+        let removed = sqlx::query!(
+            "DELETE FROM guild_invites WHERE guild_id = $1 AND user_id = $2",
+            guild_id,
+            user_id.0
+        )
+        .execute(&state.pool)
+        .await?;
+        if removed.rows_affected() == 0 {
+            return rejected("Invite not found");
+        }
+        Ok(GuildActionResponse { guild_id })
+    }
+    .await;
+
+    respond("decline invite for", result)
+}
+
+/// `POST /api/guilds/leave`. The leader must hand over leadership first; a leader who is
+/// the last member disbands the guild, which requires an empty treasury.
+pub async fn leave_guild(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<ApiResponse<GuildActionResponse>>, StatusCode> {
+    let mut tx = begin(&state).await?;
+
+    let result = async {
+        let Some(member) = membership(&mut *tx, user_id.0).await? else {
+            return rejected("You are not in a guild");
+        };
+
+        if member.is_leader() {
+            let treasury = sqlx::query_scalar!(
+                "SELECT treasury FROM guilds WHERE id = $1 FOR UPDATE",
+                member.guild_id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            let member_count = sqlx::query_scalar!(
+                r#"SELECT COUNT(*) as "count!" FROM guild_members WHERE guild_id = $1"#,
+                member.guild_id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            if member_count > 1 {
+                return rejected("Make another member leader before leaving");
+            }
+            if treasury > 0 {
+                return rejected("Spend the treasury before disbanding the guild");
+            }
+
+            sqlx::query!("DELETE FROM guilds WHERE id = $1", member.guild_id)
+                .execute(&mut *tx)
+                .await?;
+        } else {
+            sqlx::query!("DELETE FROM guild_members WHERE user_id = $1", user_id.0)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        Ok(GuildActionResponse { guild_id: member.guild_id })
+    }
+    .await;
+
+    if let Ok(response) = &result {
+        commit(tx).await?;
+        info!("🚪 User {} left guild {}", user_id.0, response.guild_id);
+    }
+
+    respond("leave", result)
+}
+
+/// Account deletion cascades the user's membership away, skipping `leave_guild`'s
+/// checks. A departing leader hands over to the longest-serving officer, or failing that
+/// member; a guild left empty is disbanded and its treasury returned to the mint.
+pub async fn leave_guild_for_deleted_user(conn: &mut PgConnection, user_id: Uuid) -> Result<(), sqlx::Error> {
+    let Some(member) = membership(&mut *conn, user_id).await? else {
+        return Ok(());
+    };
+    if !member.is_leader() {
+        return Ok(());
+    }
+
+    let treasury = sqlx::query_scalar!(
+        "SELECT treasury FROM guilds WHERE id = $1 FOR UPDATE",
+        member.guild_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let successor = sqlx::query_scalar!(
+        "SELECT user_id FROM guild_members
+         WHERE guild_id = $1 AND user_id <> $2
+         ORDER BY role = 'officer' DESC, joined_at
+         LIMIT 1",
+        member.guild_id,
+        user_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let Some(successor) = successor else {
+        ledger_service::move_between_accounts(
+            &mut *conn, SystemAccount::GuildTreasury, SystemAccount::Mint, treasury,
+            PaxEntryKind::GuildDisbanded, Some(member.guild_id),
+        ).await?;
+        sqlx::query!("DELETE FROM guilds WHERE id = $1", member.guild_id)
+            .execute(&mut *conn)
+            .await?;
+        info!("🏚️ Guild {} disbanded after its last member deleted their account", member.guild_id);
+        return Ok(());
+    };
+
+    // Only one leader may exist at a time, so drop the old one before promoting
+    sqlx::query!("DELETE FROM guild_members WHERE user_id = $1", user_id)
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query!("UPDATE guild_members SET role = 'leader' WHERE user_id = $1", successor)
+        .execute(&mut *conn)
+        .await?;
+    info!("👑 User {} now leads guild {} after the leader deleted their account", successor, member.guild_id);
+    Ok(())
+}
+
+/// `POST /api/guilds/members/:user_id/role`. Leader only; promoting someone to leader
+/// hands over leadership and makes the old leader an officer.
+pub async fn set_member_role(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(target_id): Path<Uuid>,
+    Json(payload): Json<SetRoleRequest>,
+) -> Result<Json<ApiResponse<GuildActionResponse>>, StatusCode> {
+    let mut tx = begin(&state).await?;
+
+    let result = async {
+        if !matches!(payload.role.as_str(), "leader" | "officer" | "member") {
+            return rejected("Unknown guild role");
+        }
+        let Some(member) = membership(&mut *tx, user_id.0).await? else {
+            return rejected("You are not in a guild");
+        };
+        if !member.is_leader() {
+            return rejected("Only the leader can change roles");
+        }
+        if target_id == user_id.0 {
+            return rejected("Promote another member to leader to step down");
+        }
+        match membership(&mut *tx, target_id).await? {
+            Some(target) if target.guild_id == member.guild_id => {}
+            _ => return rejected("That player is not in your guild"),
+        }
+
+        // Only one leader may exist at a time, so step down before promoting
+        if payload.role == "leader" {
+            sqlx::query!("UPDATE guild_members SET role = 'officer' WHERE user_id = $1", user_id.0)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        sqlx::query!(
+            "UPDATE guild_members SET role = $1::text::guild_role WHERE user_id = $2",
+            payload.role,
+            target_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(GuildActionResponse { guild_id: member.guild_id })
+    }
+    .await;
+
+    if result.is_ok() {
+        commit(tx).await?;
+        info!("🎖️ User {} set {} to {}", user_id.0, target_id, payload.role);
+    }
+
+    respond("change role in", result)
+}
+
+/// `DELETE /api/guilds/members/:user_id`. Officers can remove members; the leader can
+/// also remove officers.
+pub async fn remove_guild_member(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(target_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<GuildActionResponse>>, StatusCode> {
+    let result = async {
+        let Some(member) = membership(&state.pool, user_id.0).await? else {
+            return rejected("You are not in a guild");
+        };
+        if target_id == user_id.0 {
+            return rejected("Use leave to remove yourself");
+        }
+        let target = match membership(&state.pool, target_id).await? {
+            Some(target) if target.guild_id == member.guild_id => target,
+            _ => return rejected("That player is not in your guild"),
+        };
+        let allowed = match target.role.as_str() {
+            "member" => member.is_officer(),
+            "officer" => member.is_leader(),
+            _ => false,
+        };
+        if !allowed {
+            return rejected("You cannot remove this member");
+        }
+
+        sqlx::query!("DELETE FROM guild_members WHERE user_id = $1", target_id)
+            .execute(&state.pool)
+            .await?;
+
+        Ok(GuildActionResponse { guild_id: member.guild_id })
+    }
+    .await;
+
+    respond("remove member from", result)
+}
+
+/// `POST /api/guilds/treasury/deposit`. Moves pax from the member's balance into the
+/// treasury, locking both rows the way market purchases lock buyer and seller.
+pub async fn deposit_to_treasury(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Json(payload): Json<DepositRequest>,
+) -> Result<Json<ApiResponse<TreasuryResponse>>, StatusCode> {
+    let mut tx = begin(&state).await?;
+
+    let result = async {
+        if payload.amount <= 0 {
+            return rejected("Deposits must be at least 1 pax");
+        }
+        let Some(member) = membership(&mut *tx, user_id.0).await? else {
+            return rejected("You are not in a guild");
+        };
+
+        let balance = sqlx::query_scalar!(
+            "SELECT currency_balance FROM users WHERE id = $1 FOR UPDATE",
+            user_id.0
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if balance < payload.amount {
+            return rejected("Insufficient funds");
+        }
+
+        let new_balance = ledger_service::debit(
+            &mut tx, user_id.0, payload.amount,
+            PaxEntryKind::GuildDeposit, SystemAccount::GuildTreasury, Some(member.guild_id),
+        )
+        .await?;
+
+        let treasury = sqlx::query_scalar!(
+            "UPDATE guilds SET treasury = treasury + $1 WHERE id = $2 RETURNING treasury",
+            payload.amount,
+            member.guild_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO guild_treasury_log (guild_id, user_id, amount, reason, treasury_after)
+             VALUES ($1, $2, $3, 'Deposit', $4)",
+            member.guild_id,
+            user_id.0,
+            payload.amount,
+            treasury
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(TreasuryResponse { treasury, new_balance })
+    }
+    .await;
+
+    if result.is_ok() {
+        commit(tx).await?;
+        info!("💰 User {} deposited {} pax into their guild treasury", user_id.0, payload.amount);
+        push_service::publish_balance(&state.pool, &state.redis, user_id.0).await;
+    }
+
+    respond("deposit to", result)
+}
+
+/// `POST /api/guilds/treasury/spend`. Officers pay pax out of the treasury to a member.
+pub async fn spend_from_treasury(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Json(payload): Json<SpendRequest>,
+) -> Result<Json<ApiResponse<TreasuryResponse>>, StatusCode> {
+    let reason = payload.reason.trim().to_string();
+    let mut tx = begin(&state).await?;
+
+    let result = async {
+        if payload.amount <= 0 {
+            return rejected("Payments must be at least 1 pax");
+        }
+        if reason.chars().count() > REASON_MAX {
+            return rejected(&format!("Reasons can be at most {} characters", REASON_MAX));
+        }
+        let Some(member) = membership(&mut *tx, user_id.0).await? else {
+            return rejected("You are not in a guild");
+        };
+        if !member.is_officer() {
+            return rejected("Only officers can spend the treasury");
+        }
+
+        let Some(recipient_id) = find_user(&mut *tx, &payload.recipient_username).await? else {
+            return rejected("Player not found");
+        };
+        match membership(&mut *tx, recipient_id).await? {
+            Some(recipient) if recipient.guild_id == member.guild_id => {}
+            _ => return rejected("The treasury can only pay guild members"),
+        }
+
+        let treasury = sqlx::query_scalar!(
+            "SELECT treasury FROM guilds WHERE id = $1 FOR UPDATE",
+            member.guild_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if treasury < payload.amount {
+            return rejected("The treasury doesn't hold enough pax");
+        }
+
+        let treasury = sqlx::query_scalar!(
+            "UPDATE guilds SET treasury = treasury - $1 WHERE id = $2 RETURNING treasury",
+            payload.amount,
+            member.guild_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let new_balance = ledger_service::credit(
+            &mut tx, recipient_id, payload.amount,
+            PaxEntryKind::GuildPayout, SystemAccount::GuildTreasury, Some(member.guild_id),
+        )
+        .await?;
+
+        sqlx::query!(
+            "INSERT INTO guild_treasury_log (guild_id, user_id, recipient_id, amount, reason, treasury_after)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            member.guild_id,
+            user_id.0,
+            recipient_id,
+            -payload.amount,
+            reason,
+            treasury
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok((recipient_id, TreasuryResponse { treasury, new_balance }))
+    }
+    .await;
+
+    if let Ok((recipient_id, _)) = &result {
+        commit(tx).await?;
+        info!("💸 User {} paid {} pax from the guild treasury to {}", user_id.0, payload.amount, payload.recipient_username);
+        push_service::publish_balance(&state.pool, &state.redis, *recipient_id).await;
+    }
+
+    respond("spend from", result.map(|(_, response)| response))
+}
+
+/// `GET /api/guilds/leaderboard`: guilds ranked by their members' combined XP, or with
+/// `sort=score` by the sum of their members' game high scores.
+pub async fn get_guild_leaderboard(
+    State(state): State<AppState>,
+    Query(params): Query<GuildLeaderboardQuery>,
+) -> Result<Json<ApiResponse<Vec<GuildLeaderboardEntry>>>, StatusCode> {
+    let sort = params.sort.unwrap_or_else(|| "experience".to_string());
+    if sort != "experience" && sort != "score" {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let limit = params.limit.unwrap_or(LEADERBOARD_DEFAULT_LIMIT).clamp(1, LEADERBOARD_MAX_LIMIT);
+
+    let result = sqlx::query_as!(
+        GuildLeaderboardEntry,
+        r#"
+        SELECT g.name,
+               COUNT(m.user_id) as "member_count!",
+               COALESCE(SUM(u.experience), 0)::bigint as "total_experience!",
+               COALESCE(SUM(s.score), 0)::bigint as "total_score!"
+        FROM guilds g
+        JOIN guild_members m ON m.guild_id = g.id
+        JOIN users u ON u.id = m.user_id AND u.deleted_at IS NULL
+        LEFT JOIN (
+            SELECT user_id, SUM(high_score)::bigint as score
+            FROM game_leaderboard
+            GROUP BY user_id
+        ) s ON s.user_id = m.user_id
+        GROUP BY g.id, g.name
+        ORDER BY CASE WHEN $1 = 'score'
+                      THEN COALESCE(SUM(s.score), 0)::bigint
+                      ELSE COALESCE(SUM(u.experience), 0)::bigint
+                 END DESC,
+                 g.name
+        LIMIT $2
+        "#,
+        sort,
+        limit
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(GuildError::from);
+
+    respond("rank", result)
+}
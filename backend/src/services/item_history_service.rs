@@ -0,0 +1,92 @@
+use axum::{
+    extract::{State, Json, Path},
+    http::StatusCode,
+};
+use serde::Serialize;
+use uuid::Uuid;
+use crate::AppState;
+use tracing::error;
+use super::market_service::ApiResponse;
+
+#[derive(Debug, Serialize)]
+pub struct ItemHistoryEvent {
+    pub event_type: String,
+    /// Creatures also carry the events of the egg they hatched from.
+    pub item_type: String,
+    pub timestamp: String,
+    pub performed_by: Option<String>,
+    pub from_user: Option<String>,
+    pub to_user: Option<String>,
+    pub additional_data: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ItemHistoryResponse {
+    pub item_id: Uuid,
+    pub item_type: String,
+    pub events: Vec<ItemHistoryEvent>,
+}
+
+pub async fn get_item_history(
+    State(state): State<AppState>,
+    Path(item_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ItemHistoryResponse>>, StatusCode> {
+    let item_type = sqlx::query_scalar!(
+        "SELECT item_type FROM item_references WHERE id = $1",
+        item_id
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch item reference: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let Some(item_type) = item_type else {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Item not found".to_string()),
+        }));
+    };
+
+    // A creature's chain starts with its egg being summoned, so pull in the
+    // original egg's events alongside the creature's own
+    let events = sqlx::query_as!(
+        ItemHistoryEvent,
+        r#"
+        SELECT
+            e.event_type::text as "event_type!",
+            e.item_type,
+            e.timestamp::text as "timestamp!",
+            pb.display_name as "performed_by?",
+            fu.display_name as "from_user?",
+            tu.display_name as "to_user?",
+            e.additional_data
+        FROM item_events e
+        LEFT JOIN public_users pb ON e.performed_by_user_id = pb.id
+        LEFT JOIN public_users fu ON e.from_user_id = fu.id
+        LEFT JOIN public_users tu ON e.to_user_id = tu.id
+        WHERE e.item_id = $1
+           OR e.item_id = (SELECT original_egg_id FROM creatures WHERE id = $1)
+        ORDER BY e.timestamp ASC
+        "#,
+        item_id
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch item history: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(ItemHistoryResponse {
+            item_id,
+            item_type,
+            events,
+        }),
+        error: None,
+    }))
+}
@@ -0,0 +1,415 @@
+use axum::{
+    extract::{State, Extension},
+    http::StatusCode,
+    Json,
+};
+use redis::Client as RedisClient;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use tracing::info;
+use uuid::Uuid;
+use crate::auth::middleware::UserId;
+use crate::AppState;
+use super::market_service::ApiResponse;
+use super::battle_service::{self, BattleError, BattleRecord, SettledBattle, rejected};
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
+use super::notification_service::{notify, NotificationKind};
+use super::push_service;
+use super::user_service::LeaderboardEntry;
+
+const STARTING_RATING: i32 = 1200;
+const K_FACTOR: f64 = 32.0;
+/// Matchmaking tries each band in turn, so players meet the closest rating available.
+const RATING_BANDS: [i32; 3] = [100, 200, 400];
+const SEASON_LENGTH_DAYS: i32 = 28;
+/// Players need this many ranked battles in a season to be ranked when it closes.
+const MIN_RANKED_BATTLES: i32 = 5;
+/// (lowest final rank in the tier, pax, scrolls), best tier first.
+const SEASON_REWARDS: [(i32, i32, i32); 3] = [(1, 500, 5), (3, 250, 3), (10, 100, 1)];
+
+#[derive(Debug, Deserialize)]
+pub struct LadderBattleRequest {
+    pub creature_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LadderStatus {
+    pub season_id: i32,
+    pub ends_at: String,
+    pub rating: i32,
+    pub wins: i32,
+    pub losses: i32,
+    /// Position in the current season, once the player has a ranked battle
+    pub rank: Option<i64>,
+    pub last_season_rank: Option<i32>,
+}
+
+/// Elo rating change for a player rated `rating` after a battle against `opponent`.
+fn rating_change(rating: i32, opponent: i32, won: bool) -> i32 {
+    let expected = 1.0 / (1.0 + 10f64.powf((opponent - rating) as f64 / 400.0));
+    let score = if won { 1.0 } else { 0.0 };
+    (K_FACTOR * (score - expected)).round() as i32
+}
+
+fn season_reward(rank: i32) -> Option<(i32, i32)> {
+    SEASON_REWARDS
+        .iter()
+        .find(|(lowest_rank, _, _)| rank <= *lowest_rank)
+        .map(|(_, pax, scrolls)| (*pax, *scrolls))
+}
+
+async fn rating_of<'e>(executor: impl PgExecutor<'e>, season_id: i32, user_id: Uuid) -> Result<i32, sqlx::Error> {
+    let rating = sqlx::query_scalar!(
+        "SELECT rating FROM ladder_ratings WHERE season_id = $1 AND user_id = $2",
+        season_id,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?;
+    Ok(rating.unwrap_or(STARTING_RATING))
+}
+
+async fn record_result(
+    tx: &mut Transaction<'static, Postgres>,
+    season_id: i32,
+    user_id: Uuid,
+    change: i32,
+    won: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO ladder_ratings (season_id, user_id, rating, wins, losses)
+         VALUES ($1, $2, $3, $5, $6)
+         ON CONFLICT (season_id, user_id) DO UPDATE
+         SET rating = ladder_ratings.rating + $4,
+             wins = ladder_ratings.wins + EXCLUDED.wins,
+             losses = ladder_ratings.losses + EXCLUDED.losses,
+             updated_at = CURRENT_TIMESTAMP",
+        season_id,
+        user_id,
+        STARTING_RATING + change,
+        change,
+        won as i32,
+        !won as i32
+    )
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+struct Match {
+    user_id: Uuid,
+    creature_id: Uuid,
+    rating: i32,
+}
+
+/// Finds an opponent within the narrowest rating band that has one. Players without a
+/// ranked battle this season count as the starting rating; each defends with their
+/// highest-soul creature that is free to fight.
+async fn find_opponent(
+    tx: &mut Transaction<'static, Postgres>,
+    season_id: i32,
+    user_id: Uuid,
+    rating: i32,
+) -> Result<Option<Match>, sqlx::Error> {
+    for band in RATING_BANDS {
+        let found = sqlx::query_as!(
+            Match,
+            r#"SELECT u.id as user_id, c.id as creature_id, COALESCE(r.rating, $4) as "rating!"
+               FROM users u
+               LEFT JOIN ladder_ratings r ON r.user_id = u.id AND r.season_id = $1
+               JOIN LATERAL (
+                   SELECT id FROM creatures
+                   WHERE owner_id = u.id AND status = 'available' AND NOT in_chaos_realm
+                   ORDER BY soul DESC
+                   LIMIT 1
+               ) c ON true
+               WHERE u.id <> $2
+                 AND u.deleted_at IS NULL
+                 AND ABS(COALESCE(r.rating, $4) - $3) <= $5
+                 AND NOT EXISTS (
+                     SELECT 1 FROM user_blocks b
+                     WHERE (b.blocker_id = $2 AND b.blocked_id = u.id)
+                        OR (b.blocker_id = u.id AND b.blocked_id = $2)
+                 )
+               ORDER BY ABS(COALESCE(r.rating, $4) - $3), random()
+               LIMIT 1"#,
+            season_id,
+            user_id,
+            rating,
+            STARTING_RATING,
+            band
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        if found.is_some() {
+            return Ok(found);
+        }
+    }
+    Ok(None)
+}
+
+/// `GET /api/ladder`. The caller's standing in the current season.
+pub async fn get_ladder_status(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<ApiResponse<LadderStatus>>, StatusCode> {
+    let result: Result<_, BattleError> = async {
+        let Some(season) = sqlx::query!(
+            r#"SELECT id, TO_CHAR(ends_at, 'YYYY-MM-DD HH24:MI:SS') as "ends_at!"
+               FROM ladder_seasons WHERE closed_at IS NULL"#
+        )
+        .fetch_optional(&state.pool)
+        .await?
+        else {
+            return rejected("No ladder season is running");
+        };
+
+        let standing = sqlx::query!(
+            "SELECT rating, wins, losses FROM ladder_ratings WHERE season_id = $1 AND user_id = $2",
+            season.id,
+            user_id.0
+        )
+        .fetch_optional(&state.pool)
+        .await?;
+
+        let rank = match &standing {
+            Some(standing) => Some(
+                sqlx::query_scalar!(
+                    r#"SELECT COUNT(*) + 1 as "rank!" FROM ladder_ratings WHERE season_id = $1 AND rating > $2"#,
+                    season.id,
+                    standing.rating
+                )
+                .fetch_one(&state.pool)
+                .await?,
+            ),
+            None => None,
+        };
+
+        let last_season_rank = sqlx::query_scalar!(
+            "SELECT r.final_rank
+             FROM ladder_ratings r
+             JOIN ladder_seasons s ON s.id = r.season_id
+             WHERE r.user_id = $1 AND s.closed_at IS NOT NULL
+             ORDER BY s.closed_at DESC
+             LIMIT 1",
+            user_id.0
+        )
+        .fetch_optional(&state.pool)
+        .await?
+        .flatten();
+
+        Ok(LadderStatus {
+            season_id: season.id,
+            ends_at: season.ends_at,
+            rating: standing.as_ref().map_or(STARTING_RATING, |s| s.rating),
+            wins: standing.as_ref().map_or(0, |s| s.wins),
+            losses: standing.as_ref().map_or(0, |s| s.losses),
+            rank,
+            last_season_rank,
+        })
+    }
+    .await;
+
+    battle_service::respond("load ladder for", result)
+}
+
+/// `POST /api/ladder/battle`. Matches the caller against a player of similar rating and
+/// fights with the usual battle rewards, then moves both players' ratings.
+pub async fn start_ranked_battle(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Json(payload): Json<LadderBattleRequest>,
+) -> Result<Json<ApiResponse<BattleRecord>>, StatusCode> {
+    let mut tx = battle_service::begin(&state).await?;
+
+    let result: Result<SettledBattle, BattleError> = async {
+        // Shares the season row with other ranked battles; closing the season waits for them
+        let Some(season_id) = sqlx::query_scalar!(
+            "SELECT id FROM ladder_seasons WHERE closed_at IS NULL AND ends_at > NOW() FOR SHARE"
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return rejected("The ladder season is ending; try again shortly");
+        };
+
+        let rating = rating_of(&mut *tx, season_id, user_id.0).await?;
+        let Some(opponent) = find_opponent(&mut tx, season_id, user_id.0, rating).await? else {
+            return rejected("No opponents in your rating band right now");
+        };
+
+        let mut settled = battle_service::fight(&mut tx, user_id.0, payload.creature_id, Some(opponent.creature_id)).await?;
+        let won = settled.record.challenger_won;
+        let change = rating_change(rating, opponent.rating, won);
+
+        record_result(&mut tx, season_id, user_id.0, change, won).await?;
+        record_result(&mut tx, season_id, opponent.user_id, rating_change(opponent.rating, rating, !won), !won).await?;
+
+        sqlx::query!(
+            "UPDATE battles SET season_id = $1, rating_change = $2 WHERE id = $3",
+            season_id,
+            change,
+            settled.record.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        settled.record.rating_change = Some(change);
+        Ok(settled)
+    }
+    .await;
+
+    let result = match result {
+        Ok(settled) => {
+            battle_service::commit(tx).await?;
+            battle_service::finish_battle(&state, &settled).await;
+            Ok(settled.record)
+        }
+        Err(e) => Err(e),
+    };
+
+    battle_service::respond("start ranked", result)
+}
+
+/// Current-season ratings in the shape of the game leaderboards, so the ladder can be served
+/// from `/api/leaderboard/ladder`.
+pub async fn get_ladder_leaderboard(
+    pool: &PgPool,
+    limit: i64,
+    friends_of: Option<Uuid>,
+) -> Result<Vec<LeaderboardEntry>, sqlx::Error> {
+    sqlx::query_as::<_, LeaderboardEntry>(
+        r#"
+        SELECT
+            u.display_name as username,
+            r.rating as high_score,
+            TO_CHAR(r.updated_at, 'YYYY-MM-DD HH24:MI:SS') as updated_at
+        FROM ladder_ratings r
+        JOIN ladder_seasons s ON s.id = r.season_id AND s.closed_at IS NULL
+        JOIN public_users u ON r.user_id = u.id
+        WHERE u.on_leaderboards
+        AND ($2::uuid IS NULL
+             OR u.id = $2
+             OR u.id IN (SELECT friend_id FROM friendships WHERE user_id = $2))
+        ORDER BY r.rating DESC, r.wins DESC
+        LIMIT $1
+        "#,
+    )
+    .bind(limit)
+    .bind(friends_of)
+    .fetch_all(pool)
+    .await
+}
+
+/// Closes the season once it has ended: fixes everyone's final rank, pays the top players
+/// in pax and scrolls, and opens the next season so ratings start over. Also opens a season
+/// if none is running.
+pub async fn close_expired_seasons(pool: &PgPool, redis: &RedisClient) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut rewarded = Vec::new();
+
+    let expired = sqlx::query_scalar!(
+        "SELECT id FROM ladder_seasons WHERE closed_at IS NULL AND ends_at <= NOW() FOR UPDATE"
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some(season_id) = expired {
+        let ranked = sqlx::query!(
+            r#"UPDATE ladder_ratings r
+               SET final_rank = ranked.rank
+               FROM (
+                   SELECT lr.user_id, ROW_NUMBER() OVER (ORDER BY lr.rating DESC, lr.wins DESC, lr.updated_at) as rank
+                   FROM ladder_ratings lr
+                   JOIN users u ON u.id = lr.user_id AND u.deleted_at IS NULL
+                   WHERE lr.season_id = $1 AND lr.wins + lr.losses >= $2
+               ) ranked
+               WHERE r.season_id = $1 AND r.user_id = ranked.user_id
+               RETURNING r.user_id, r.final_rank as "final_rank!""#,
+            season_id,
+            MIN_RANKED_BATTLES
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for player in ranked {
+            let Some((pax, scrolls)) = season_reward(player.final_rank) else {
+                continue;
+            };
+
+            ledger_service::credit(
+                &mut tx, player.user_id, pax, PaxEntryKind::LadderReward, SystemAccount::Mint, None,
+            )
+            .await?;
+
+            let updated_scroll = sqlx::query!(
+                r#"
+                UPDATE scrolls
+                SET quantity = quantity + $1,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE owner_id = $2 AND display_name = 'Summoning Scroll'
+                RETURNING quantity
+                "#,
+                scrolls,
+                player.user_id
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            if updated_scroll.is_none() {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO scrolls (
+                        id, owner_id, display_name, image_path, description, quantity, item_type
+                    )
+                    VALUES (
+                        $1, $2, 'Summoning Scroll',
+                        '/static/images/scroll-default.avif',
+                        'A scroll used to summon an egg',
+                        $3, 'scroll'
+                    )
+                    "#,
+                    Uuid::new_v4(),
+                    player.user_id,
+                    scrolls
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            rewarded.push((player.user_id, player.final_rank, pax, scrolls));
+        }
+
+        sqlx::query!("UPDATE ladder_seasons SET closed_at = NOW() WHERE id = $1", season_id)
+            .execute(&mut *tx)
+            .await?;
+
+        info!("🏁 Closed ladder season {} and rewarded {} players", season_id, rewarded.len());
+    }
+
+    sqlx::query!(
+        "INSERT INTO ladder_seasons (ends_at)
+         SELECT NOW() + make_interval(days => $1)
+         WHERE NOT EXISTS (SELECT 1 FROM ladder_seasons WHERE closed_at IS NULL)",
+        SEASON_LENGTH_DAYS
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    for (user_id, rank, pax, scrolls) in rewarded {
+        push_service::publish_balance(pool, redis, user_id).await;
+        notify(
+            pool,
+            redis,
+            user_id,
+            NotificationKind::LadderReward,
+            format!("You finished the ladder season ranked #{} and earned {} pax and {} scrolls", rank, pax, scrolls),
+            serde_json::json!({ "rank": rank, "pax": pax, "scrolls": scrolls }),
+        ).await;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+use sqlx::{PgConnection, PgPool};
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Why a user's pax balance changed. Mirrors the `pax_entry_kind` enum in the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaxEntryKind {
+    ListingFee,
+    Sale,
+    Purchase,
+    DailyClaim,
+    GameReward,
+    BindCost,
+    RechargeCost,
+    ChaosReward,
+    Wheel,
+    MembershipPurchase,
+    EggCost,
+    RenameCost,
+    CraftingCost,
+    AchievementReward,
+    MagicButton,
+    BattleReward,
+    LadderReward,
+    GuildDeposit,
+    GuildPayout,
+    TradeEscrow,
+    TradeRefund,
+    TradePayment,
+    AuctionBid,
+    AuctionRefund,
+    OrderFee,
+    OrderEscrow,
+    OrderRefund,
+    GuildDisbanded,
+}
+
+impl PaxEntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ListingFee => "listing_fee",
+            Self::Sale => "sale",
+            Self::Purchase => "purchase",
+            Self::DailyClaim => "daily_claim",
+            Self::GameReward => "game_reward",
+            Self::BindCost => "bind_cost",
+            Self::RechargeCost => "recharge_cost",
+            Self::ChaosReward => "chaos_reward",
+            Self::Wheel => "wheel",
+            Self::MembershipPurchase => "membership_purchase",
+            Self::EggCost => "egg_cost",
+            Self::RenameCost => "rename_cost",
+            Self::CraftingCost => "crafting_cost",
+            Self::AchievementReward => "achievement_reward",
+            Self::MagicButton => "magic_button",
+            Self::BattleReward => "battle_reward",
+            Self::LadderReward => "ladder_reward",
+            Self::GuildDeposit => "guild_deposit",
+            Self::GuildPayout => "guild_payout",
+            Self::TradeEscrow => "trade_escrow",
+            Self::TradeRefund => "trade_refund",
+            Self::TradePayment => "trade_payment",
+            Self::AuctionBid => "auction_bid",
+            Self::AuctionRefund => "auction_refund",
+            Self::OrderFee => "order_fee",
+            Self::OrderEscrow => "order_escrow",
+            Self::OrderRefund => "order_refund",
+            Self::GuildDisbanded => "guild_disbanded",
+        }
+    }
+}
+
+/// The game-side accounts that balance user entries. Mirrors the `pax_account` enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemAccount {
+    /// Source of rewards and sink of fees and costs: pax entering or leaving circulation
+    Mint,
+    /// Pax held for open bids, orders and trade offers
+    Escrow,
+    /// Pax held in guild treasuries
+    GuildTreasury,
+}
+
+impl SystemAccount {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mint => "mint",
+            Self::Escrow => "escrow",
+            Self::GuildTreasury => "guild_treasury",
+        }
+    }
+}
+
+/// Writes one side of a ledger transaction.
+async fn write_entry(
+    conn: &mut PgConnection,
+    transaction_id: Uuid,
+    account: &str,
+    user_id: Option<Uuid>,
+    kind: PaxEntryKind,
+    amount: i32,
+    reference_id: Option<Uuid>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO pax_ledger (transaction_id, account, user_id, kind, amount, reference_id)
+         VALUES ($1, $2::text::pax_account, $3, $4::text::pax_entry_kind, $5, $6)",
+        transaction_id,
+        account,
+        user_id,
+        kind.as_str(),
+        amount,
+        reference_id
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+async fn apply(conn: &mut PgConnection, user_id: Uuid, amount: i32) -> Result<i32, sqlx::Error> {
+    sqlx::query_scalar!(
+        "UPDATE users SET currency_balance = currency_balance + $1 WHERE id = $2 RETURNING currency_balance",
+        amount,
+        user_id
+    )
+    .fetch_one(conn)
+    .await
+}
+
+/// Moves `amount` pax from a system account into the user's balance, in the caller's
+/// transaction. Negative amounts move pax the other way. Returns the new balance.
+async fn post(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    amount: i32,
+    kind: PaxEntryKind,
+    account: SystemAccount,
+    reference_id: Option<Uuid>,
+) -> Result<i32, sqlx::Error> {
+    let balance = apply(&mut *conn, user_id, amount).await?;
+    if amount == 0 {
+        return Ok(balance);
+    }
+
+    let transaction_id = Uuid::new_v4();
+    write_entry(&mut *conn, transaction_id, "user", Some(user_id), kind, amount, reference_id).await?;
+    write_entry(&mut *conn, transaction_id, account.as_str(), None, kind, -amount, reference_id).await?;
+    Ok(balance)
+}
+
+/// Credits the user with pax from `account`. Returns the new balance.
+pub async fn credit(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    amount: i32,
+    kind: PaxEntryKind,
+    account: SystemAccount,
+    reference_id: Option<Uuid>,
+) -> Result<i32, sqlx::Error> {
+    post(conn, user_id, amount, kind, account, reference_id).await
+}
+
+/// Takes pax from the user into `account`. The `currency_balance >= 0` check rejects
+/// overdrafts, so callers still check funds first to give a friendly error. Returns the
+/// new balance.
+pub async fn debit(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    amount: i32,
+    kind: PaxEntryKind,
+    account: SystemAccount,
+    reference_id: Option<Uuid>,
+) -> Result<i32, sqlx::Error> {
+    post(conn, user_id, -amount, kind, account, reference_id).await
+}
+
+/// Pays `amount` pax from one user straight to another, e.g. a market purchase, with each
+/// side recorded under its own kind. Returns both new balances, payer first.
+pub async fn transfer(
+    conn: &mut PgConnection,
+    from_user: Uuid,
+    to_user: Uuid,
+    amount: i32,
+    from_kind: PaxEntryKind,
+    to_kind: PaxEntryKind,
+    reference_id: Option<Uuid>,
+) -> Result<(i32, i32), sqlx::Error> {
+    let from_balance = apply(&mut *conn, from_user, -amount).await?;
+    let to_balance = apply(&mut *conn, to_user, amount).await?;
+    if amount == 0 {
+        return Ok((from_balance, to_balance));
+    }
+
+    let transaction_id = Uuid::new_v4();
+    write_entry(&mut *conn, transaction_id, "user", Some(from_user), from_kind, -amount, reference_id).await?;
+    write_entry(&mut *conn, transaction_id, "user", Some(to_user), to_kind, amount, reference_id).await?;
+    Ok((from_balance, to_balance))
+}
+
+/// Moves `amount` pax between two system accounts, e.g. a disbanded guild's treasury
+/// back to the mint. No user balance changes.
+pub async fn move_between_accounts(
+    conn: &mut PgConnection,
+    from: SystemAccount,
+    to: SystemAccount,
+    amount: i32,
+    kind: PaxEntryKind,
+    reference_id: Option<Uuid>,
+) -> Result<(), sqlx::Error> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let transaction_id = Uuid::new_v4();
+    write_entry(&mut *conn, transaction_id, from.as_str(), None, kind, -amount, reference_id).await?;
+    write_entry(conn, transaction_id, to.as_str(), None, kind, amount, reference_id).await
+}
+
+/// Compares every user's balance with the sum of their ledger entries and checks that each
+/// ledger transaction sums to zero. Mismatches are logged; returns how many were found.
+pub async fn reconcile(pool: &PgPool) -> Result<usize, sqlx::Error> {
+    let mismatched_users = sqlx::query!(
+        r#"SELECT u.id, u.currency_balance, COALESCE(SUM(l.amount), 0) as "ledger_balance!"
+           FROM users u
+           LEFT JOIN pax_ledger l ON l.user_id = u.id AND l.account = 'user'
+           GROUP BY u.id
+           HAVING u.currency_balance <> COALESCE(SUM(l.amount), 0)"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for user in &mismatched_users {
+        error!(
+            "Pax ledger mismatch for user {}: balance {} but ledger sums to {}",
+            user.id, user.currency_balance, user.ledger_balance
+        );
+    }
+
+    let unbalanced = sqlx::query!(
+        r#"SELECT transaction_id, SUM(amount) as "total!"
+           FROM pax_ledger
+           GROUP BY transaction_id
+           HAVING SUM(amount) <> 0"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for transaction in &unbalanced {
+        error!(
+            "Pax ledger transaction {} does not balance (off by {})",
+            transaction.transaction_id, transaction.total
+        );
+    }
+
+    let problems = mismatched_users.len() + unbalanced.len();
+    if problems == 0 {
+        info!("📒 Pax ledger reconciled cleanly");
+    }
+    Ok(problems)
+}
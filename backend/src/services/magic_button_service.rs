@@ -6,6 +6,9 @@ use axum::{
 use serde::Serialize;
 use crate::auth::middleware::UserId;
 use crate::AppState;
+use shared::push_events::PushEvent;
+use super::push_service;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
 use tracing::info;
 use time;
 
@@ -47,11 +50,11 @@ pub async fn handle_magic_button(
             let last_clicks = sqlx::query!(
                 r#"
                 SELECT 
-                    u.username,
+                    u.display_name as "username!",
                     mb.clicked_at,
                     mb.reward_amount
                 FROM magic_button_clicks mb
-                JOIN users u ON mb.user_id = u.id
+                JOIN public_users u ON mb.user_id = u.id
                 ORDER BY mb.clicked_at DESC
                 LIMIT 3
                 "#
@@ -93,24 +96,20 @@ pub async fn handle_magic_button(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     // Record click and update user balance
-    let new_balance = sqlx::query!(
-        r#"
-        WITH click_insert AS (
-            INSERT INTO magic_button_clicks (user_id, reward_amount)
-            VALUES ($1, $2)
-        )
-        UPDATE users 
-        SET currency_balance = currency_balance + $2
-        WHERE id = $1
-        RETURNING currency_balance
-        "#,
+    sqlx::query!(
+        "INSERT INTO magic_button_clicks (user_id, reward_amount) VALUES ($1, $2)",
         user_id.0,
         reward
     )
-    .fetch_one(&mut *tx)
+    .execute(&mut *tx)
     .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-    .currency_balance;
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let new_balance = ledger_service::credit(
+        &mut tx, user_id.0, reward, PaxEntryKind::MagicButton, SystemAccount::Mint, None,
+    )
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     // Set cooldown (2 minutes)
     redis::cmd("SETEX")
@@ -137,15 +136,17 @@ pub async fn handle_magic_button(
     // Log the click
     info!("🎯 {} clicked the magic button and received {} pax!", username, reward);
 
+    push_service::broadcast(&state.redis, &PushEvent::MagicButtonPressed).await;
+
     // Fetch the last 3 clicks after successful click
     let last_clicks = sqlx::query!(
         r#"
         SELECT 
-            u.username,
+            u.display_name as "username!",
             mb.clicked_at,
             mb.reward_amount
         FROM magic_button_clicks mb
-        JOIN users u ON mb.user_id = u.id
+        JOIN public_users u ON mb.user_id = u.id
         ORDER BY mb.clicked_at DESC
         LIMIT 3
         "#
@@ -197,11 +198,11 @@ pub async fn get_magic_button_status(
     let last_clicks = sqlx::query!(
         r#"
         SELECT 
-            u.username,
+            u.display_name as "username!",
             mb.clicked_at,
             mb.reward_amount
         FROM magic_button_clicks mb
-        JOIN users u ON mb.user_id = u.id
+        JOIN public_users u ON mb.user_id = u.id
         ORDER BY mb.clicked_at DESC
         LIMIT 3
         "#
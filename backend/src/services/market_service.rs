@@ -8,6 +8,12 @@ use crate::AppState;
 use crate::auth::middleware::UserId;
 use tracing::{error, info};
 use crate::models::{DisplayItem, Egg, Creature as ModelCreature};
+use super::price_history_service::record_sale;
+use super::push_service;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
+use super::notification_service::{notify, NotificationKind};
+use super::email_digest_service;
+use shared::push_events::PushEvent;
 
 #[derive(Debug, sqlx::FromRow)]
 struct OwnerCheck {
@@ -156,6 +162,15 @@ pub async fn create_listing(
         }));
     }
 
+    if owner_check.status.as_deref() == Some("trading") {
+        info!("❌ Listing creation failed: {} {} is part of a pending trade", payload.item_type, payload.item_id);
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Item is part of a pending trade".to_string()),
+        }));
+    }
+
     if owner_check.status.as_deref() != Some("available") {
         info!("❌ Listing creation failed: {} {} is not available for listing (status: {})", 
               payload.item_type, payload.item_id, owner_check.status.unwrap_or_default());
@@ -190,12 +205,9 @@ pub async fn create_listing(
     }
 
     // Deduct listing fee
-    sqlx::query!(
-        "UPDATE users SET currency_balance = currency_balance - $1 WHERE id = $2",
-        listing_fee,
-        user_id.0
+    ledger_service::debit(
+        &mut tx, user_id.0, listing_fee, PaxEntryKind::ListingFee, SystemAccount::Mint, Some(payload.item_id),
     )
-    .execute(&mut *tx)
     .await
     .map_err(|e| {
         error!("Failed to deduct listing fee: {}", e);
@@ -269,6 +281,8 @@ pub async fn create_listing(
     info!("✅ Listing created successfully: User {} listed {} {} for {} PAX (listing ID: {})", 
           seller.username, payload.item_type, payload.item_id, payload.price, listing.id);
 
+    push_service::broadcast(&state.redis, &PushEvent::MarketChanged).await;
+
     Ok(Json(ApiResponse {
         success: true,
         data: Some(MarketListing {
@@ -291,9 +305,9 @@ pub async fn get_active_listings(
 ) -> Result<Json<ApiResponse<Vec<MarketListing>>>, StatusCode> {
     let listings = sqlx::query!(
         r#"
-        SELECT DISTINCT m.id, m.seller_id, u.username as seller_username, m.item_id, m.item_type, m.price, m.created_at
+        SELECT DISTINCT m.id, m.seller_id, u.display_name as "seller_username!", m.item_id, m.item_type, m.price, m.created_at
         FROM market_listings m
-        JOIN users u ON m.seller_id = u.id
+        JOIN public_users u ON m.seller_id = u.id
         LEFT JOIN eggs e ON m.item_id = e.id AND m.item_type = 'egg'
         LEFT JOIN creatures c ON m.item_id = c.id AND m.item_type = 'creature'
         WHERE m.status = 'active'::market_status_type 
@@ -349,7 +363,7 @@ pub async fn purchase_item(
     let listing = sqlx::query!(
         "SELECT seller_id, item_id, item_type, price
          FROM market_listings
-         WHERE id = $1 AND status = 'active'::market_status_type
+         WHERE id = $1 AND status = 'active'::market_status_type AND type = 'sale'::market_type
          FOR UPDATE",
         listing_id
     )
@@ -496,35 +510,14 @@ pub async fn purchase_item(
         }));
     }
 
-    // 4. Update buyer's balance
-    let new_balance = sqlx::query!(
-        "UPDATE users 
-         SET currency_balance = currency_balance - $1 
-         WHERE id = $2
-         RETURNING currency_balance",
-        listing.price,
-        user_id.0
-    )
-    .fetch_one(&mut *tx)
-    .await
-    .map_err(|e| {
-        error!("Failed to update buyer balance: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?
-    .currency_balance;
-
-    // 5. Update seller's balance
-    sqlx::query!(
-        "UPDATE users 
-         SET currency_balance = currency_balance + $1 
-         WHERE id = $2",
-        listing.price,
-        listing.seller_id
+    // 4-5. Pay the seller from the buyer's balance
+    let (new_balance, seller_balance) = ledger_service::transfer(
+        &mut tx, user_id.0, listing.seller_id, listing.price,
+        PaxEntryKind::Purchase, PaxEntryKind::Sale, Some(listing_id),
     )
-    .execute(&mut *tx)
     .await
     .map_err(|e| {
-        error!("Failed to update seller balance: {}", e);
+        error!("Failed to transfer purchase payment: {}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
@@ -578,14 +571,23 @@ pub async fn purchase_item(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    // 8. Record the event
+    // 8. Snapshot the sale for price history
+    record_sale(&mut tx, listing_id, listing.item_id, listing.price)
+        .await
+        .map_err(|e| {
+            error!("Failed to record sale: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    // 9. Record the event
     sqlx::query!(
-        "INSERT INTO item_events (item_id, item_type, event_type, from_user_id, to_user_id, performed_by_user_id)
-         VALUES ($1, $2, 'sold'::event_type, $3, $4, $4)",
+        "INSERT INTO item_events (item_id, item_type, event_type, from_user_id, to_user_id, performed_by_user_id, additional_data)
+         VALUES ($1, $2, 'sold'::event_type, $3, $4, $4, $5)",
         listing.item_id,
         listing.item_type,
         listing.seller_id,
         user_id.0,
+        serde_json::json!({ "listing_id": listing_id, "price": listing.price })
     )
     .execute(&mut *tx)
     .await
@@ -602,6 +604,34 @@ pub async fn purchase_item(
     info!("✅ Purchase successful: User {} bought {} {} from {} for {} PAX", 
           buyer_username, listing.item_type, listing.item_id, seller_username, listing.price);
 
+    // 10. Let both sides and anyone watching the market know
+    push_service::publish(&state.redis, listing.seller_id, &PushEvent::ListingSold {
+        listing_id: listing_id.to_string(),
+        price: listing.price,
+    }).await;
+    push_service::publish(&state.redis, listing.seller_id, &PushEvent::BalanceChanged { balance: seller_balance }).await;
+    push_service::publish(&state.redis, user_id.0, &PushEvent::ListingBought {
+        listing_id: listing_id.to_string(),
+        price: listing.price,
+    }).await;
+    push_service::publish(&state.redis, user_id.0, &PushEvent::BalanceChanged { balance: new_balance }).await;
+    push_service::broadcast(&state.redis, &PushEvent::MarketChanged).await;
+    notify(
+        &state.pool,
+        &state.redis,
+        listing.seller_id,
+        NotificationKind::ListingSold,
+        format!("{} bought your {} for {} pax", buyer_username, listing.item_type, listing.price),
+        serde_json::json!({ "listing_id": listing_id, "item_id": listing.item_id, "price": listing.price }),
+    ).await;
+    email_digest_service::queue(
+        &state.pool,
+        listing.seller_id,
+        NotificationKind::ListingSold,
+        format!("{} bought your {} for {} pax", buyer_username, listing.item_type, listing.price),
+        None,
+    ).await;
+
     Ok(Json(ApiResponse {
         success: true,
         data: Some(new_balance),
@@ -636,9 +666,10 @@ pub async fn cancel_listing(
     info!("🔄 User {} is attempting to cancel listing {}", username, listing_id);
 
     let listing = sqlx::query!(
-        "SELECT seller_id, item_id, item_type
+        "SELECT seller_id, item_id, item_type, type::text as \"listing_type!\", current_bidder_id
          FROM market_listings
-         WHERE id = $1 AND status = 'active'::market_status_type",
+         WHERE id = $1 AND status = 'active'::market_status_type
+         FOR UPDATE",
         listing_id
     )
     .fetch_optional(&mut *tx)
@@ -670,6 +701,15 @@ pub async fn cancel_listing(
         }));
     }
 
+    if listing.listing_type == "auction" && listing.current_bidder_id.is_some() {
+        info!("❌ Cancellation failed: Auction {} already has bids", listing_id);
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Auctions cannot be cancelled once a bid has been placed".to_string()),
+        }));
+    }
+
     match listing.item_type.as_str() {
         "egg" => {
             sqlx::query!(
@@ -738,6 +778,8 @@ pub async fn cancel_listing(
     info!("✅ Cancellation successful: User {} cancelled listing {} for {} {}", 
           username, listing_id, listing.item_type, listing.item_id);
 
+    push_service::broadcast(&state.redis, &PushEvent::MarketChanged).await;
+
     Ok(Json(ApiResponse {
         success: true,
         data: None,
@@ -756,7 +798,6 @@ pub async fn get_listing_item(
         FROM market_listings m
         WHERE m.id = $1 
         AND m.status = 'active'::market_status_type 
-        AND m.type = 'sale'::market_type
         "#,
         listing_id
     )
@@ -784,8 +825,8 @@ pub async fn get_listing_item(
                     e.id,
                     e.owner_id,
                     e.summoned_by,
-                    u_summoner.username as "summoned_by_username",
-                    u_owner.username as "owner_username",
+                    u_summoner.display_name as "summoned_by_username",
+                    u_owner.display_name as "owner_username",
                     e.essence::text as "essence!",
                     e.color::text as "color!",
                     e.art_style::text as "art_style!",
@@ -795,8 +836,8 @@ pub async fn get_listing_item(
                     TO_CHAR(e.created_at, 'YYYY-MM-DD"T"HH24:MI:SS.MS"Z"') as "created_at!",
                     TO_CHAR(e.incubation_ends_at, 'YYYY-MM-DD"T"HH24:MI:SS.MS"Z"') as "incubation_ends_at!"
                 FROM eggs e
-                LEFT JOIN users u_summoner ON e.summoned_by = u_summoner.id
-                LEFT JOIN users u_owner ON e.owner_id = u_owner.id
+                LEFT JOIN public_users u_summoner ON e.summoned_by = u_summoner.id
+                LEFT JOIN public_users u_owner ON e.owner_id = u_owner.id
                 WHERE e.id = $1 AND e.status = 'locked'::item_status"#,
                 listing.item_id
             )
@@ -818,9 +859,9 @@ pub async fn get_listing_item(
                     c.original_egg_id,
                     c.original_egg_summoned_by,
                     c.hatched_by,
-                    u1.username as "egg_summoned_by_username",
-                    u2.username as "hatched_by_username",
-                    u3.username as "owner_username",
+                    u1.display_name as "egg_summoned_by_username",
+                    u2.display_name as "hatched_by_username",
+                    u3.display_name as "owner_username",
                     c.essence::text as "essence!",
                     c.color::text as "color!",
                     c.art_style::text as "art_style!",
@@ -841,9 +882,9 @@ pub async fn get_listing_item(
                     c.chaos_realm_entry_at::text as "chaos_realm_entry_at",
                     c.chaos_realm_reward_claimed
                 FROM creatures c
-                JOIN users u1 ON c.original_egg_summoned_by = u1.id
-                JOIN users u2 ON c.hatched_by = u2.id
-                JOIN users u3 ON c.owner_id = u3.id
+                JOIN public_users u1 ON c.original_egg_summoned_by = u1.id
+                JOIN public_users u2 ON c.hatched_by = u2.id
+                JOIN public_users u3 ON c.owner_id = u3.id
                 WHERE c.id = $1 AND c.status = 'locked'::item_status"#,
                 listing.item_id
             )
@@ -32,7 +32,25 @@ pub mod creature_bind;
 pub mod energy_service;
 pub mod chaos_realm;
 pub mod market_service;
+pub mod auction_service;
+pub mod price_history_service;
+pub mod trade_service;
+pub mod transfer_service;
+pub mod item_history_service;
+pub mod push_service;
+pub mod notification_service;
+pub mod email_digest_service;
+pub mod preferences_service;
+pub mod public_profile_service;
+pub mod friend_service;
+pub mod guild_service;
+pub mod battle_service;
+pub mod ladder_service;
+pub mod ledger_service;
+pub mod wallet_service;
 pub mod scroll_service;
+pub mod crafting_service;
 pub mod orderbook_service;
 pub mod magic_button_service;
-pub mod patreon_link_service;
\ No newline at end of file
+pub mod patreon_link_service;
+pub mod achievement_service;
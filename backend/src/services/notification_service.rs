@@ -0,0 +1,228 @@
+use axum::{
+    extract::{State, Extension, Path, Query},
+    http::StatusCode,
+    Json,
+};
+use redis::Client as RedisClient;
+use serde::{Deserialize, Serialize};
+use shared::push_events::PushEvent;
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+use crate::auth::middleware::UserId;
+use crate::AppState;
+use super::market_service::ApiResponse;
+use super::push_service;
+use super::preferences_service::load_preferences;
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+
+/// Mirrors the `notification_kind` enum. The snake_case name doubles as the key
+/// in `user_preferences.notification_settings`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationKind {
+    ListingSold,
+    OrderFilled,
+    Outbid,
+    MembershipExpired,
+    PatreonLinked,
+    PatreonUnlinked,
+    ChaosRealmReady,
+    EnergyRecharged,
+    StreakExpiring,
+    FriendRequest,
+    FriendAccepted,
+    GuildInvite,
+    BattleResult,
+    LadderReward,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ListingSold => "listing_sold",
+            Self::OrderFilled => "order_filled",
+            Self::Outbid => "outbid",
+            Self::MembershipExpired => "membership_expired",
+            Self::PatreonLinked => "patreon_linked",
+            Self::PatreonUnlinked => "patreon_unlinked",
+            Self::ChaosRealmReady => "chaos_realm_ready",
+            Self::EnergyRecharged => "energy_recharged",
+            Self::StreakExpiring => "streak_expiring",
+            Self::FriendRequest => "friend_request",
+            Self::FriendAccepted => "friend_accepted",
+            Self::GuildInvite => "guild_invite",
+            Self::BattleResult => "battle_result",
+            Self::LadderReward => "ladder_reward",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub kind: String,
+    pub message: String,
+    pub data: serde_json::Value,
+    pub read: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationsResponse {
+    pub notifications: Vec<Notification>,
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationsQuery {
+    pub limit: Option<i64>,
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+/// A kind is recorded unless the user switched it off in their notification settings.
+async fn is_enabled(pool: &PgPool, user_id: Uuid, kind: NotificationKind) -> Result<bool, sqlx::Error> {
+    let preferences = load_preferences(pool, user_id).await?;
+    Ok(preferences.notification_settings.in_app_enabled(kind.as_str()))
+}
+
+/// Records a notification in the user's inbox and pushes it to any open session.
+/// Like push events this is best effort: call it after the triggering transaction
+/// commits, and failures are only logged.
+pub async fn notify(
+    pool: &PgPool,
+    redis: &RedisClient,
+    user_id: Uuid,
+    kind: NotificationKind,
+    message: String,
+    data: serde_json::Value,
+) {
+    match is_enabled(pool, user_id, kind).await {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(e) => {
+            error!("Failed to read notification settings: {}", e);
+            return;
+        }
+    }
+
+    let id = match sqlx::query_scalar!(
+        "INSERT INTO notifications (user_id, kind, message, data)
+         VALUES ($1, $2::text::notification_kind, $3, $4)
+         RETURNING id",
+        user_id,
+        kind.as_str(),
+        message,
+        data
+    )
+    .fetch_one(pool)
+    .await
+    {
+        Ok(id) => id,
+        Err(e) => {
+            error!("Failed to record {} notification: {}", kind.as_str(), e);
+            return;
+        }
+    };
+
+    push_service::publish(redis, user_id, &PushEvent::Notification {
+        id: id.to_string(),
+        kind: kind.as_str().to_string(),
+        message,
+    }).await;
+}
+
+pub async fn get_notifications(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Query(query): Query<NotificationsQuery>,
+) -> Result<Json<ApiResponse<NotificationsResponse>>, StatusCode> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let notifications = sqlx::query_as!(
+        Notification,
+        r#"
+        SELECT id, kind::text as "kind!", message, data,
+               read_at IS NOT NULL as "read!",
+               created_at::text as "created_at!"
+        FROM notifications
+        WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+        ORDER BY created_at DESC
+        LIMIT $3
+        "#,
+        user_id.0,
+        query.unread_only,
+        limit
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch notifications: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let unread_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM notifications WHERE user_id = $1 AND read_at IS NULL"#,
+        user_id.0
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to count unread notifications: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(NotificationsResponse { notifications, unread_count }),
+        error: None,
+    }))
+}
+
+pub async fn mark_notification_read(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(notification_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    let updated = sqlx::query!(
+        "UPDATE notifications SET read_at = COALESCE(read_at, NOW()) WHERE id = $1 AND user_id = $2",
+        notification_id,
+        user_id.0
+    )
+    .execute(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to mark notification read: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if updated.rows_affected() == 0 {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Notification not found".to_string()),
+        }));
+    }
+
+    Ok(Json(ApiResponse { success: true, data: None, error: None }))
+}
+
+pub async fn mark_all_notifications_read(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    sqlx::query!(
+        "UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND read_at IS NULL",
+        user_id.0
+    )
+    .execute(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to mark notifications read: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(ApiResponse { success: true, data: None, error: None }))
+}
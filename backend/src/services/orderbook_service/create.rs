@@ -5,9 +5,18 @@ use axum::{
 use crate::AppState;
 use crate::auth::middleware::UserId;
 use crate::services::orderbook_service::models::{CreateOrderRequest, OrderResponse, OrderSide, ErrorResponse};
+use crate::services::orderbook_service::matching::{debit_scrolls, match_order};
+use crate::services::push_service;
+use crate::services::ledger_service::{self, PaxEntryKind, SystemAccount};
+use crate::services::notification_service::{notify, NotificationKind};
+use shared::push_events::PushEvent;
 use tracing::error;
 use tracing::info;
 
+/// Fee charged per order, regardless of quantity
+const ORDER_CREATION_FEE: i32 = 5;
+const MAX_ORDER_QUANTITY: i32 = 1000;
+
 pub async fn create_order(
     State(state): State<AppState>,
     Extension(user_id): Extension<UserId>,
@@ -38,8 +47,14 @@ pub async fn create_order(
         return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "Price must be between 1 and 1,000,000,000 pax".to_string() })));
     }
 
-    // Define the order creation fee
-    const ORDER_CREATION_FEE: i32 = 5;
+    if payload.quantity <= 0 || payload.quantity > MAX_ORDER_QUANTITY {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: format!("Quantity must be between 1 and {}", MAX_ORDER_QUANTITY) })));
+    }
+
+    // Total pax escrowed by a buy order
+    let order_total = payload.price.checked_mul(payload.quantity)
+        .filter(|total| total.checked_add(ORDER_CREATION_FEE).is_some())
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "Order total is too large".to_string() })))?;
 
     // Get user's current currency balance and scroll count
     let record = sqlx::query!(
@@ -65,24 +80,19 @@ pub async fn create_order(
     match payload.side {
         OrderSide::Buy => {
             // For buy orders, check if user has enough currency for both the fee and the order amount
-            if currency_balance < payload.price + ORDER_CREATION_FEE {
-                return Err((StatusCode::PAYMENT_REQUIRED, Json(ErrorResponse { error: format!("Insufficient funds for buy order and fee (need {} pax)", payload.price + ORDER_CREATION_FEE) })));
+            if currency_balance < order_total + ORDER_CREATION_FEE {
+                return Err((StatusCode::PAYMENT_REQUIRED, Json(ErrorResponse { error: format!("Insufficient funds for buy order and fee (need {} pax)", order_total + ORDER_CREATION_FEE) })));
             }
         },
         OrderSide::Sell => {
-            if scroll_count < 1 {
-                return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "You don't have any scrolls to sell".to_string() })));
+            if scroll_count < payload.quantity {
+                return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: format!("You don't have {} scrolls to sell", payload.quantity) })));
             }
         }
     }
 
     // Deduct the order creation fee
-    sqlx::query!(
-        "UPDATE users SET currency_balance = currency_balance - $1 WHERE id = $2",
-        ORDER_CREATION_FEE,
-        user_id.0
-    )
-    .execute(&mut *tx)
+    ledger_service::debit(&mut tx, user_id.0, ORDER_CREATION_FEE, PaxEntryKind::OrderFee, SystemAccount::Mint, None)
     .await
     .map_err(|e| {
         error!("🚫 Failed to deduct order creation fee: {}", e);
@@ -91,10 +101,11 @@ pub async fn create_order(
 
     // Create the order
     let order = sqlx::query!(
-        "INSERT INTO scroll_orderbook (user_id, side, price, status) VALUES ($1, $2, $3, 'active') RETURNING id, created_at",
+        "INSERT INTO scroll_orderbook (user_id, side, price, quantity, status) VALUES ($1, $2, $3, $4, 'active') RETURNING id, created_at",
         user_id.0,
         payload.side as _,
-        payload.price
+        payload.price,
+        payload.quantity
     )
     .fetch_one(&mut *tx)
     .await
@@ -105,12 +116,7 @@ pub async fn create_order(
 
     // For buy orders, reserve the currency
     if payload.side == OrderSide::Buy {
-        sqlx::query!(
-            "UPDATE users SET currency_balance = currency_balance - $1 WHERE id = $2",
-            payload.price,
-            user_id.0
-        )
-        .execute(&mut *tx)
+        ledger_service::debit(&mut tx, user_id.0, order_total, PaxEntryKind::OrderEscrow, SystemAccount::Escrow, Some(order.id))
         .await
         .map_err(|e| {
             error!("🚫 Failed to reserve currency: {}", e);
@@ -118,62 +124,28 @@ pub async fn create_order(
         })?;
     }
 
-    // For sell orders, check for matching buy orders
+    // For sell orders, escrow the scrolls
     if payload.side == OrderSide::Sell {
-        // Remove the scroll from inventory
-        let scroll_exists = sqlx::query!(
-            "SELECT id FROM scrolls WHERE owner_id = $1 AND display_name = 'Summoning Scroll'",
-            user_id.0
-        )
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|e| {
-            error!("🚫 Failed to check scroll existence: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to check scroll existence".to_string() }))
-        })?;
-
-        if let Some(scroll) = scroll_exists {
-            // Update or delete the scroll
-            let count_result = sqlx::query!(
-                "SELECT quantity FROM scrolls WHERE id = $1",
-                scroll.id
-            )
-            .fetch_one(&mut *tx)
+        let debited = debit_scrolls(&mut tx, user_id.0, payload.quantity)
             .await
             .map_err(|e| {
-                error!("🚫 Failed to get scroll quantity: {}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to get scroll quantity".to_string() }))
+                error!("🚫 Failed to escrow scrolls: {}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to escrow scrolls".to_string() }))
             })?;
 
-            if count_result.quantity > 1 {
-                sqlx::query!(
-                    "UPDATE scrolls SET quantity = quantity - 1 WHERE id = $1",
-                    scroll.id
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    error!("🚫 Failed to update scroll quantity: {}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to update scroll quantity".to_string() }))
-                })?;
-            } else {
-                sqlx::query!(
-                    "DELETE FROM scrolls WHERE id = $1",
-                    scroll.id
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    error!("🚫 Failed to delete scroll: {}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to delete scroll".to_string() }))
-                })?;
-            }
+        if !debited {
+            return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: format!("You don't have {} scrolls to sell", payload.quantity) })));
         }
-
-        // Find matching buy orders
-        // TODO: Implement matching logic for buy orders
     }
 
+    // Match against the opposite side of the book
+    let fills = match_order(&mut tx, order.id, user_id.0, &payload.side, payload.price, payload.quantity)
+        .await
+        .map_err(|e| {
+            error!("🚫 Failed to match order: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to match order".to_string() }))
+        })?;
+
     // Get updated balance and scroll count
     let updated_record = sqlx::query!(
         "SELECT currency_balance, (SELECT COALESCE(SUM(quantity), 0) FROM scrolls WHERE owner_id = $1 AND display_name = 'Summoning Scroll') as scroll_count FROM users WHERE id = $1",
@@ -205,15 +177,39 @@ pub async fn create_order(
     let created_at_str = order.created_at.to_string();
 
     // Log the successful order creation
-    info!("📜 Order {} created successfully - {} placed a {} order for {} pax (fee: {} pax)", 
-        order.id, _username, side_str, payload.price, ORDER_CREATION_FEE);
+    info!("📜 Order {} created successfully - {} placed a {} order for {} scroll(s) at {} pax (fee: {} pax)", 
+        order.id, _username, side_str, payload.quantity, payload.price, ORDER_CREATION_FEE);
+
+    for fill in &fills {
+        info!("🔄 Order {} matched order {} for {} scroll(s) at {} pax (trade {})",
+            order.id, fill.resting_order_id, fill.quantity, fill.price, fill.trade_id);
+
+        push_service::publish(&state.redis, fill.resting_user_id, &PushEvent::OrderFilled {
+            order_id: fill.resting_order_id.to_string(),
+            quantity: fill.quantity,
+            price: fill.price,
+        }).await;
+        push_service::publish_balance(&state.pool, &state.redis, fill.resting_user_id).await;
+        notify(
+            &state.pool,
+            &state.redis,
+            fill.resting_user_id,
+            NotificationKind::OrderFilled,
+            format!("{} scroll(s) of your order filled at {} pax", fill.quantity, fill.price),
+            serde_json::json!({ "order_id": fill.resting_order_id, "quantity": fill.quantity, "price": fill.price }),
+        ).await;
+    }
+
+    let filled_quantity: i32 = fills.iter().map(|f| f.quantity).sum();
 
     Ok(Json(OrderResponse {
         id: order.id,
         user_id: user_id.0,
         side: side_str.to_string(),
         price: payload.price,
-        status: "active".to_string(),
+        quantity: payload.quantity,
+        filled_quantity,
+        status: if filled_quantity == payload.quantity { "completed" } else { "active" }.to_string(),
         created_at: created_at_str,
         currency_balance: new_balance,
         scroll_count: new_scroll_count,
@@ -0,0 +1,237 @@
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+use crate::services::orderbook_service::models::OrderSide;
+use crate::services::ledger_service::{self, PaxEntryKind, SystemAccount};
+
+/// A fill produced by matching an incoming order against the book.
+pub struct Fill {
+    pub trade_id: Uuid,
+    pub resting_order_id: Uuid,
+    pub resting_user_id: Uuid,
+    pub price: i32,
+    pub quantity: i32,
+}
+
+/// An executed trade. Order ids are `None` when a side did not come from a resting order.
+pub struct NewTrade<'a> {
+    pub buy_order_id: Option<Uuid>,
+    pub sell_order_id: Option<Uuid>,
+    pub buyer_id: Uuid,
+    pub seller_id: Uuid,
+    pub taker_side: &'a OrderSide,
+    pub price: i32,
+    pub quantity: i32,
+}
+
+/// Adds Summoning Scrolls to a user's stack, creating the stack if needed.
+pub async fn credit_scrolls(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    quantity: i32,
+) -> Result<(), sqlx::Error> {
+    let updated = sqlx::query!(
+        "UPDATE scrolls SET quantity = quantity + $1 WHERE id = (
+            SELECT id FROM scrolls WHERE owner_id = $2 AND display_name = 'Summoning Scroll' ORDER BY created_at LIMIT 1
+        )",
+        quantity,
+        user_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    if updated.rows_affected() == 0 {
+        sqlx::query!(
+            "INSERT INTO scrolls (owner_id, display_name, quantity) VALUES ($1, 'Summoning Scroll', $2)",
+            user_id,
+            quantity
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Removes Summoning Scrolls from a user, oldest stacks first.
+/// Returns false without changing anything if the user holds fewer than `quantity`.
+pub async fn debit_scrolls(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    quantity: i32,
+) -> Result<bool, sqlx::Error> {
+    let stacks = sqlx::query!(
+        "SELECT id, quantity FROM scrolls WHERE owner_id = $1 AND display_name = 'Summoning Scroll' ORDER BY created_at FOR UPDATE",
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    if stacks.iter().map(|s| s.quantity).sum::<i32>() < quantity {
+        return Ok(false);
+    }
+
+    let mut remaining = quantity;
+    for stack in stacks {
+        if remaining == 0 {
+            break;
+        }
+        let taken = remaining.min(stack.quantity);
+        if taken == stack.quantity {
+            sqlx::query!("DELETE FROM scrolls WHERE id = $1", stack.id)
+                .execute(&mut **tx)
+                .await?;
+        } else {
+            sqlx::query!(
+                "UPDATE scrolls SET quantity = quantity - $1, updated_at = NOW() WHERE id = $2",
+                taken,
+                stack.id
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+        remaining -= taken;
+    }
+
+    Ok(true)
+}
+
+/// Adds `quantity` to an order's filled amount, completing it once nothing remains.
+pub async fn apply_fill(
+    tx: &mut Transaction<'_, Postgres>,
+    order_id: Uuid,
+    quantity: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE scroll_orderbook
+        SET filled_quantity = filled_quantity + $1,
+            status = CASE WHEN filled_quantity + $1 >= quantity THEN 'completed'::market_status_type ELSE status END
+        WHERE id = $2",
+        quantity,
+        order_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn record_trade(
+    tx: &mut Transaction<'_, Postgres>,
+    trade: NewTrade<'_>,
+) -> Result<Uuid, sqlx::Error> {
+    sqlx::query_scalar!(
+        "INSERT INTO scroll_trades (buy_order_id, sell_order_id, buyer_id, seller_id, taker_side, price, quantity)
+        VALUES ($1, $2, $3, $4, $5::text::order_side_type, $6, $7)
+        RETURNING id",
+        trade.buy_order_id,
+        trade.sell_order_id,
+        trade.buyer_id,
+        trade.seller_id,
+        trade.taker_side.to_string(),
+        trade.price,
+        trade.quantity
+    )
+    .fetch_one(&mut **tx)
+    .await
+}
+
+/// Matches a freshly placed order against resting orders on the opposite side,
+/// using price-time priority and executing each fill at the resting order's price.
+/// Matching stops when the order is filled or nothing else crosses; the rest stays on the book.
+///
+/// The incoming order must already hold its escrow: `limit_price * quantity` for buys,
+/// `quantity` scrolls for sells. Resting orders from the same user are skipped.
+pub async fn match_order(
+    tx: &mut Transaction<'_, Postgres>,
+    order_id: Uuid,
+    user_id: Uuid,
+    side: &OrderSide,
+    limit_price: i32,
+    quantity: i32,
+) -> Result<Vec<Fill>, sqlx::Error> {
+    let mut fills = Vec::new();
+    let mut unfilled = quantity;
+
+    while unfilled > 0 {
+        let resting = match side {
+            OrderSide::Buy => sqlx::query!(
+                r#"SELECT id, user_id, price, quantity - filled_quantity as "remaining!"
+                FROM scroll_orderbook
+                WHERE side = 'sell' AND status = 'active' AND price <= $1 AND user_id <> $2
+                ORDER BY price ASC, created_at ASC
+                LIMIT 1
+                FOR UPDATE"#,
+                limit_price,
+                user_id
+            )
+            .fetch_optional(&mut **tx)
+            .await?
+            .map(|r| (r.id, r.user_id, r.price, r.remaining)),
+            OrderSide::Sell => sqlx::query!(
+                r#"SELECT id, user_id, price, quantity - filled_quantity as "remaining!"
+                FROM scroll_orderbook
+                WHERE side = 'buy' AND status = 'active' AND price >= $1 AND user_id <> $2
+                ORDER BY price DESC, created_at ASC
+                LIMIT 1
+                FOR UPDATE"#,
+                limit_price,
+                user_id
+            )
+            .fetch_optional(&mut **tx)
+            .await?
+            .map(|r| (r.id, r.user_id, r.price, r.remaining)),
+        };
+
+        let Some((resting_id, resting_user_id, price, resting_remaining)) = resting else {
+            break;
+        };
+
+        let fill_quantity = unfilled.min(resting_remaining);
+        let (buy_order_id, sell_order_id, buyer_id, seller_id) = match side {
+            OrderSide::Buy => (order_id, resting_id, user_id, resting_user_id),
+            OrderSide::Sell => (resting_id, order_id, resting_user_id, user_id),
+        };
+
+        apply_fill(tx, resting_id, fill_quantity).await?;
+
+        ledger_service::credit(
+            tx, seller_id, price * fill_quantity, PaxEntryKind::Sale, SystemAccount::Escrow, Some(buy_order_id),
+        ).await?;
+
+        // An incoming buy escrowed its limit price; return the price improvement
+        if *side == OrderSide::Buy && limit_price > price {
+            ledger_service::credit(
+                tx, buyer_id, (limit_price - price) * fill_quantity,
+                PaxEntryKind::OrderRefund, SystemAccount::Escrow, Some(buy_order_id),
+            ).await?;
+        }
+
+        credit_scrolls(tx, buyer_id, fill_quantity).await?;
+
+        let trade_id = record_trade(tx, NewTrade {
+            buy_order_id: Some(buy_order_id),
+            sell_order_id: Some(sell_order_id),
+            buyer_id,
+            seller_id,
+            taker_side: side,
+            price,
+            quantity: fill_quantity,
+        })
+        .await?;
+
+        fills.push(Fill {
+            trade_id,
+            resting_order_id: resting_id,
+            resting_user_id,
+            price,
+            quantity: fill_quantity,
+        });
+        unfilled -= fill_quantity;
+    }
+
+    if unfilled < quantity {
+        apply_fill(tx, order_id, quantity - unfilled).await?;
+    }
+
+    Ok(fills)
+}
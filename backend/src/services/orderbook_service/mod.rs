@@ -1,10 +1,12 @@
 mod models;
 mod create;
+mod matching;
 mod query;
 
 // Re-export only what's needed by external modules
 pub use create::create_order;
-pub use query::{get_orders, cancel_order, fulfill_order};
+pub use matching::debit_scrolls;
+pub use query::{get_orders, cancel_order, fulfill_order, get_recent_trades, get_last_price};
 
 // Note: The routes function is not currently used in main.rs, but is kept for future use
 // or for documentation purposes. To use it, replace the individual route definitions in main.rs with:
@@ -21,4 +23,6 @@ use axum::{
         .route("/api/orderbook/orders", post(create_order))
         .route("/api/orderbook/orders/:order_id/cancel", post(cancel_order))
         .route("/api/orderbook/orders/:order_id/fulfill", post(fulfill_order))
+        .route("/api/orderbook/trades", get(get_recent_trades))
+        .route("/api/orderbook/trades/last", get(get_last_price))
 } 
\ No newline at end of file
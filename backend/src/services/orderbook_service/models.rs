@@ -19,10 +19,22 @@ impl fmt::Display for OrderSide {
     }
 }
 
+fn default_quantity() -> i32 {
+    1
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateOrderRequest {
     pub side: OrderSide,  // "buy" or "sell"
-    pub price: i32,
+    pub price: i32,  // per scroll
+    #[serde(default = "default_quantity")]
+    pub quantity: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FulfillOrderRequest {
+    #[serde(default = "default_quantity")]
+    pub quantity: i32,
 }
 
 #[derive(Debug, Serialize)]
@@ -31,16 +43,23 @@ pub struct OrderResponse {
     pub user_id: Uuid,
     pub side: String,
     pub price: i32,
+    pub quantity: i32,
+    pub filled_quantity: i32,
     pub status: String,
     pub created_at: String,
     pub currency_balance: i32,
     pub scroll_count: i32,
 }
 
+/// One resting order. Every order at the same side and price carries the same
+/// `depth_at_price`.
 #[derive(Debug, Serialize)]
-pub struct AggregatedOrderResponse {
+pub struct OrderBookEntryResponse {
     pub side: String,
     pub price: i32,
+    pub quantity: i32,
+    pub remaining: i32,
+    pub depth_at_price: i64,  // remaining quantity across all orders at this side and price
     pub id: String,
     pub user_id: String,
     pub username: String,
@@ -60,7 +79,23 @@ pub struct ErrorResponse {
 #[derive(Debug, Serialize)]
 pub struct FulfillOrderResponse {
     pub id: Uuid,
+    pub filled_quantity: i32,
     pub currency_balance: i32,
     pub scroll_count: i32,
     pub message: String,
-} 
\ No newline at end of file
+} 
+
+#[derive(Debug, Serialize)]
+pub struct TradeResponse {
+    pub id: Uuid,
+    pub taker_side: String,
+    pub price: i32,
+    pub quantity: i32,
+    pub executed_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LastPriceResponse {
+    pub last_price: Option<i32>,
+    pub executed_at: Option<String>,
+}
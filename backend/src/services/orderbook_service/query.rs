@@ -5,13 +5,18 @@ use axum::{
 };
 use crate::AppState;
 use crate::auth::middleware::UserId;
-use crate::services::orderbook_service::models::{AggregatedOrderResponse, CancelOrderResponse, FulfillOrderResponse, ErrorResponse};
+use crate::services::orderbook_service::models::{OrderBookEntryResponse, CancelOrderResponse, FulfillOrderRequest, FulfillOrderResponse, ErrorResponse, LastPriceResponse, OrderSide, TradeResponse};
+use crate::services::orderbook_service::matching::{apply_fill, credit_scrolls, debit_scrolls, record_trade, NewTrade};
+use crate::services::ledger_service::{self, PaxEntryKind, SystemAccount};
+use crate::services::push_service;
+use crate::services::notification_service::{notify, NotificationKind};
+use shared::push_events::PushEvent;
 use tracing::{info, error};
 use uuid::Uuid;
 
 pub async fn get_orders(
     State(state): State<AppState>,
-) -> Result<Json<Vec<AggregatedOrderResponse>>, StatusCode> {
+) -> Result<Json<Vec<OrderBookEntryResponse>>, StatusCode> {
     let orders = sqlx::query!(
         r#"
         SELECT 
@@ -19,9 +24,12 @@ pub async fn get_orders(
             o.user_id::text as "user_id!",
             o.side::text as "side!",
             o.price,
-            u.username as "username!"
+            o.quantity,
+            o.quantity - o.filled_quantity as "remaining!",
+            SUM(o.quantity - o.filled_quantity) OVER (PARTITION BY o.side, o.price) as "depth_at_price!",
+            u.display_name as "username!"
         FROM scroll_orderbook o
-        JOIN users u ON o.user_id = u.id
+        JOIN public_users u ON o.user_id = u.id
         WHERE o.status = 'active'
         ORDER BY 
             CASE WHEN o.side = 'buy' THEN o.price END DESC,
@@ -36,9 +44,12 @@ pub async fn get_orders(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    let response = orders.into_iter().map(|order| AggregatedOrderResponse {
+    let response = orders.into_iter().map(|order| OrderBookEntryResponse {
         side: order.side,
         price: order.price,
+        quantity: order.quantity,
+        remaining: order.remaining,
+        depth_at_price: order.depth_at_price,
         id: order.id,
         user_id: order.user_id,
         username: order.username,
@@ -81,10 +92,13 @@ pub async fn cancel_order(
             o.user_id,
             o.side::text as "side!",
             o.status::text as "status!",
+            o.price,
+            o.quantity - o.filled_quantity as "remaining!",
             u.username as "owner_username!"
         FROM scroll_orderbook o
         JOIN users u ON o.user_id = u.id
         WHERE o.id = $1
+        FOR UPDATE OF o
         "#,
         order_id
     )
@@ -127,72 +141,24 @@ pub async fn cancel_order(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    // Return resources based on order type
+    // Return the unfilled part of the escrow
     if order.side == "buy" {
-        // Return currency for buy orders
-        let order_price = sqlx::query!(
-            "SELECT price FROM scroll_orderbook WHERE id = $1",
-            order_id
-        )
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(|e| {
-            error!("🚫 Failed to get order price: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?.price;
-
-        sqlx::query!(
-            "UPDATE users SET currency_balance = currency_balance + $1 WHERE id = $2",
-            order_price,
-            user_id.0
+        ledger_service::credit(
+            &mut tx, user_id.0, order.price * order.remaining,
+            PaxEntryKind::OrderRefund, SystemAccount::Escrow, Some(order_id),
         )
-        .execute(&mut *tx)
         .await
         .map_err(|e| {
             error!("🚫 Failed to return currency: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
     } else {
-        // Return the scroll to the user
-        let scroll_exists = sqlx::query!(
-            "SELECT id FROM scrolls WHERE owner_id = $1 AND display_name = 'Summoning Scroll'",
-            user_id.0
-        )
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|e| {
-            error!("🚫 Failed to check user's scrolls: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-
-        match scroll_exists {
-            Some(scroll) => {
-                sqlx::query!(
-                    "UPDATE scrolls SET quantity = quantity + 1 WHERE id = $1",
-                    scroll.id
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    error!("🚫 Failed to return scroll: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
-            }
-            None => {
-                sqlx::query!(
-                    "INSERT INTO scrolls (owner_id, display_name, quantity) VALUES ($1, $2, $3)",
-                    user_id.0,
-                    "Summoning Scroll",
-                    1
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    error!("🚫 Failed to create scroll: {}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
-            }
-        }
+        credit_scrolls(&mut tx, user_id.0, order.remaining)
+            .await
+            .map_err(|e| {
+                error!("🚫 Failed to return scrolls: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
     }
 
     // Get updated balances
@@ -226,7 +192,13 @@ pub async fn fulfill_order(
     State(state): State<AppState>,
     Extension(user_id): Extension<UserId>,
     Path(order_id): Path<Uuid>,
+    payload: Option<Json<FulfillOrderRequest>>,
 ) -> Result<Json<FulfillOrderResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let requested = payload.map_or(1, |Json(request)| request.quantity);
+    if requested <= 0 {
+        return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "Quantity must be at least 1".to_string() })));
+    }
+
     // Get the current user's username for logging
     let current_user = sqlx::query!(
         "SELECT username FROM users WHERE id = $1",
@@ -239,7 +211,7 @@ pub async fn fulfill_order(
         (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to get current user".to_string() }))
     })?;
 
-    info!("👛 User {} attempting to fulfill order {}", current_user.username, order_id);
+    info!("👛 User {} attempting to fulfill {} scroll(s) of order {}", current_user.username, requested, order_id);
     
     let mut tx = state.pool.begin().await.map_err(|e| {
         error!("🚫 Failed to begin transaction: {}", e);
@@ -249,11 +221,13 @@ pub async fn fulfill_order(
     // Get the order to fulfill
     let order = sqlx::query!(
         r#"
-        SELECT o.id, o.user_id, o.side::text as "side!", o.price, o.status::text as "status!", u.username as seller_username
+        SELECT o.id, o.user_id, o.side::text as "side!", o.price,
+               o.quantity - o.filled_quantity as "remaining!",
+               u.username as seller_username
         FROM scroll_orderbook o
         JOIN users u ON o.user_id = u.id
         WHERE o.id = $1 AND o.status = 'active'
-        FOR UPDATE
+        FOR UPDATE OF o
         "#,
         order_id
     )
@@ -280,8 +254,19 @@ pub async fn fulfill_order(
         return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "Cannot fulfill your own order".to_string() })));
     }
 
-    // Handle based on order side
-    if order.side == "sell" {
+    // Fill as much of the request as the order has left
+    let fill_quantity = requested.min(order.remaining);
+    let total = order.price.checked_mul(fill_quantity)
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "Order total is too large".to_string() })))?;
+
+    // The resting order's owner is the counterparty; the current user takes the other side
+    let (buyer_id, seller_id, taker_side) = if order.side == "sell" {
+        (user_id.0, order.user_id, OrderSide::Buy)
+    } else {
+        (order.user_id, user_id.0, OrderSide::Sell)
+    };
+
+    if taker_side == OrderSide::Buy {
         // Check if user has enough currency
         let buyer_balance = sqlx::query!(
             "SELECT currency_balance FROM users WHERE id = $1 FOR UPDATE",
@@ -294,265 +279,178 @@ pub async fn fulfill_order(
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to fetch buyer balance".to_string() }))
         })?.currency_balance;
 
-        if buyer_balance < order.price {
-            error!("🚫 Insufficient funds: {} has {} pax, but order costs {} pax", 
-                current_user.username, buyer_balance, order.price);
+        if buyer_balance < total {
+            error!("🚫 Insufficient funds: {} has {} pax, but {} scroll(s) cost {} pax", 
+                current_user.username, buyer_balance, fill_quantity, total);
             return Err((StatusCode::PAYMENT_REQUIRED, Json(ErrorResponse { error: "Insufficient funds".to_string() })));
         }
 
-        // Deduct currency from buyer
-        sqlx::query!(
-            "UPDATE users SET currency_balance = currency_balance - $1 WHERE id = $2",
-            order.price,
-            user_id.0
+        // Pay the seller straight from the buyer's balance
+        ledger_service::transfer(
+            &mut tx, user_id.0, seller_id, total,
+            PaxEntryKind::Purchase, PaxEntryKind::Sale, Some(order_id),
         )
-        .execute(&mut *tx)
         .await
         .map_err(|e| {
-            error!("🚫 Failed to deduct currency from buyer: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to deduct currency from buyer".to_string() }))
+            error!("🚫 Failed to transfer currency from buyer: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to transfer currency from buyer".to_string() }))
         })?;
+    } else {
+        // Deduct scrolls from seller; the buy order already escrowed its pax
+        let debited = debit_scrolls(&mut tx, user_id.0, fill_quantity)
+            .await
+            .map_err(|e| {
+                error!("🚫 Failed to deduct seller's scrolls: {}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to deduct seller's scrolls".to_string() }))
+            })?;
+
+        if !debited {
+            error!("🚫 Seller {} has fewer than {} scrolls to sell", current_user.username, fill_quantity);
+            return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: format!("You don't have {} scrolls to sell", fill_quantity) })));
+        }
 
-        // Add currency to seller
-        sqlx::query!(
-            "UPDATE users SET currency_balance = currency_balance + $1 WHERE id = $2",
-            order.price,
-            order.user_id
+        // Pay the seller out of the buy order's escrow
+        ledger_service::credit(
+            &mut tx, seller_id, total,
+            PaxEntryKind::Sale, SystemAccount::Escrow, Some(order_id),
         )
-        .execute(&mut *tx)
         .await
         .map_err(|e| {
             error!("🚫 Failed to add currency to seller: {}", e);
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to add currency to seller".to_string() }))
         })?;
+    }
 
-        // Add scroll to buyer
-        let buyer_has_scrolls = sqlx::query!(
-            "SELECT id FROM scrolls WHERE owner_id = $1 AND display_name = 'Summoning Scroll'",
-            user_id.0
-        )
-        .fetch_optional(&mut *tx)
+    // Add scrolls to buyer
+    credit_scrolls(&mut tx, buyer_id, fill_quantity)
         .await
         .map_err(|e| {
-            error!("🚫 Failed to check buyer's scrolls: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to check buyer's scrolls".to_string() }))
+            error!("🚫 Failed to update buyer's scrolls: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to update buyer's scrolls".to_string() }))
         })?;
 
-        match buyer_has_scrolls {
-            Some(scroll) => {
-                sqlx::query!(
-                    "UPDATE scrolls SET quantity = quantity + 1 WHERE id = $1",
-                    scroll.id
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    error!("🚫 Failed to update buyer's scrolls: {}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to update buyer's scrolls".to_string() }))
-                })?;
-            }
-            None => {
-                sqlx::query!(
-                    "INSERT INTO scrolls (owner_id, display_name, quantity) VALUES ($1, $2, $3)",
-                    user_id.0,
-                    "Summoning Scroll",
-                    1
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    error!("🚫 Failed to create buyer's scrolls: {}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to create buyer's scrolls".to_string() }))
-                })?;
-            }
-        }
-
-        // Mark order as completed
-        sqlx::query!(
-            "UPDATE scroll_orderbook SET status = 'completed' WHERE id = $1",
-            order_id
-        )
-        .execute(&mut *tx)
+    // Mark the fill on the order, completing it if nothing remains
+    apply_fill(&mut tx, order_id, fill_quantity)
         .await
         .map_err(|e| {
             error!("🚫 Failed to update order status: {}", e);
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to update order status".to_string() }))
         })?;
 
-        // Get updated balances for response
-        let record = sqlx::query!(
-            "SELECT currency_balance, (SELECT COALESCE(SUM(quantity), 0) FROM scrolls WHERE owner_id = $1 AND display_name = 'Summoning Scroll') as scroll_count FROM users WHERE id = $1",
-            user_id.0
-        )
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(|e| {
-            error!("🚫 Failed to get updated balances: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to get updated balances".to_string() }))
-        })?;
-
-        let currency_balance = record.currency_balance;
-        let scroll_count = record.scroll_count.unwrap_or(0) as i32;
-
-        // Transaction commit
-        tx.commit().await.map_err(|e| {
-            error!("🚫 Failed to commit transaction: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to commit transaction".to_string() }))
-        })?;
-
-        info!("📜 Order {} fulfilled successfully - {} purchased scroll from {} for {} pax", 
-            order_id, current_user.username, order.seller_username, order.price);
-            
-        Ok(Json(FulfillOrderResponse {
-            id: order_id,
-            currency_balance,
-            scroll_count,
-            message: format!("Successfully purchased 1 scroll from {} for {} pax", order.seller_username, order.price),
-        }))
+    // Record the trade
+    let (buy_order_id, sell_order_id) = if taker_side == OrderSide::Buy {
+        (None, Some(order_id))
     } else {
-        // User wants to sell to a buy order
-        // Check if user has a scroll to sell
-        let seller_scroll = sqlx::query!(
-            "SELECT id, quantity FROM scrolls WHERE owner_id = $1 AND display_name = 'Summoning Scroll'",
-            user_id.0
-        )
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|e| {
-            error!("🚫 Failed to check seller's scrolls: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to check seller's scrolls".to_string() }))
-        })?;
+        (Some(order_id), None)
+    };
+    record_trade(&mut tx, NewTrade {
+        buy_order_id,
+        sell_order_id,
+        buyer_id,
+        seller_id,
+        taker_side: &taker_side,
+        price: order.price,
+        quantity: fill_quantity,
+    })
+    .await
+    .map_err(|e| {
+        error!("🚫 Failed to record trade: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to record trade".to_string() }))
+    })?;
 
-        let seller_scroll = match seller_scroll {
-            Some(s) if s.quantity > 0 => {
-                s
-            },
-            _ => {
-                error!("🚫 Seller {} has no scrolls to sell", current_user.username);
-                return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse { error: "You don't have any scrolls to sell".to_string() })));
-            }
-        };
-
-        // Deduct scroll from seller
-        if seller_scroll.quantity == 1 {
-            sqlx::query!(
-                "DELETE FROM scrolls WHERE id = $1",
-                seller_scroll.id
-            )
-            .execute(&mut *tx)
-            .await
-            .map_err(|e| {
-                error!("🚫 Failed to delete seller's scroll: {}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to delete seller's scroll".to_string() }))
-            })?;
-        } else {
-            sqlx::query!(
-                "UPDATE scrolls SET quantity = quantity - 1 WHERE id = $1",
-                seller_scroll.id
-            )
-            .execute(&mut *tx)
-            .await
-            .map_err(|e| {
-                error!("🚫 Failed to update seller's scrolls: {}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to update seller's scrolls".to_string() }))
-            })?;
-        }
+    // Get updated balances for response
+    let record = sqlx::query!(
+        "SELECT currency_balance, (SELECT COALESCE(SUM(quantity), 0) FROM scrolls WHERE owner_id = $1 AND display_name = 'Summoning Scroll') as scroll_count FROM users WHERE id = $1",
+        user_id.0
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("🚫 Failed to get updated balances: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to get updated balances".to_string() }))
+    })?;
 
-        // Add currency to seller
-        sqlx::query!(
-            "UPDATE users SET currency_balance = currency_balance + $1 WHERE id = $2",
-            order.price,
-            user_id.0
-        )
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| {
-            error!("🚫 Failed to add currency to seller: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to add currency to seller".to_string() }))
-        })?;
+    let currency_balance = record.currency_balance;
+    let scroll_count = record.scroll_count.unwrap_or(0) as i32;
 
-        // Add scroll to buyer
-        let buyer_has_scrolls = sqlx::query!(
-            "SELECT id FROM scrolls WHERE owner_id = $1 AND display_name = 'Summoning Scroll'",
-            order.user_id
-        )
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|e| {
-            error!("🚫 Failed to check buyer's scrolls: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to check buyer's scrolls".to_string() }))
-        })?;
+    // Transaction commit
+    tx.commit().await.map_err(|e| {
+        error!("🚫 Failed to commit transaction: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to commit transaction".to_string() }))
+    })?;
 
-        match buyer_has_scrolls {
-            Some(scroll) => {
-                sqlx::query!(
-                    "UPDATE scrolls SET quantity = quantity + 1 WHERE id = $1",
-                    scroll.id
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    error!("🚫 Failed to update buyer's scrolls: {}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to update buyer's scrolls".to_string() }))
-                })?;
-            }
-            None => {
-                sqlx::query!(
-                    "INSERT INTO scrolls (owner_id, display_name, quantity) VALUES ($1, $2, $3)",
-                    order.user_id,
-                    "Summoning Scroll",
-                    1
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    error!("🚫 Failed to create buyer's scrolls: {}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to create buyer's scrolls".to_string() }))
-                })?;
-            }
-        }
+    let (verb, preposition) = if taker_side == OrderSide::Buy { ("purchased", "from") } else { ("sold", "to") };
 
-        // Mark order as completed
-        sqlx::query!(
-            "UPDATE scroll_orderbook SET status = 'completed' WHERE id = $1",
-            order_id
-        )
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| {
-            error!("🚫 Failed to update order status: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to update order status".to_string() }))
-        })?;
+    info!("📜 Order {} fulfilled successfully - {} {} {} scroll(s) {} {} for {} pax", 
+        order_id, current_user.username, verb, fill_quantity, preposition, order.seller_username, total);
 
-        // Get updated balances for response
-        let record = sqlx::query!(
-            "SELECT currency_balance, (SELECT COALESCE(SUM(quantity), 0) FROM scrolls WHERE owner_id = $1 AND display_name = 'Summoning Scroll') as scroll_count FROM users WHERE id = $1",
-            user_id.0
-        )
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(|e| {
-            error!("🚫 Failed to get updated balances: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to get updated balances".to_string() }))
-        })?;
+    push_service::publish(&state.redis, order.user_id, &PushEvent::OrderFilled {
+        order_id: order_id.to_string(),
+        quantity: fill_quantity,
+        price: order.price,
+    }).await;
+    push_service::publish_balance(&state.pool, &state.redis, order.user_id).await;
+    notify(
+        &state.pool,
+        &state.redis,
+        order.user_id,
+        NotificationKind::OrderFilled,
+        format!("{} filled {} scroll(s) of your {} order at {} pax", current_user.username, fill_quantity, order.side, order.price),
+        serde_json::json!({ "order_id": order_id, "quantity": fill_quantity, "price": order.price }),
+    ).await;
+        
+    Ok(Json(FulfillOrderResponse {
+        id: order_id,
+        filled_quantity: fill_quantity,
+        currency_balance,
+        scroll_count,
+        message: format!("Successfully {} {} scroll(s) {} {} for {} pax",
+            verb, fill_quantity, preposition, order.seller_username, total),
+    }))
+}
+
+pub async fn get_recent_trades(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<TradeResponse>>, StatusCode> {
+    let trades = sqlx::query!(
+        r#"
+        SELECT id, taker_side::text as "taker_side!", price, quantity, executed_at
+        FROM scroll_trades
+        ORDER BY executed_at DESC
+        LIMIT 50
+        "#
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("🚫 Failed to fetch trades: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
-        let currency_balance = record.currency_balance;
-        let scroll_count = record.scroll_count.unwrap_or(0) as i32;
+    Ok(Json(trades.into_iter().map(|trade| TradeResponse {
+        id: trade.id,
+        taker_side: trade.taker_side,
+        price: trade.price,
+        quantity: trade.quantity,
+        executed_at: trade.executed_at.to_string(),
+    }).collect()))
+}
 
-        // Transaction commit
-        tx.commit().await.map_err(|e| {
-            error!("🚫 Failed to commit transaction: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error: "Failed to commit transaction".to_string() }))
-        })?;
+pub async fn get_last_price(
+    State(state): State<AppState>,
+) -> Result<Json<LastPriceResponse>, StatusCode> {
+    let last = sqlx::query!(
+        "SELECT price, executed_at FROM scroll_trades ORDER BY executed_at DESC LIMIT 1"
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("🚫 Failed to fetch last trade: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
-        info!("📜 Order {} fulfilled successfully - {} sold scroll to {} for {} pax", 
-            order_id, current_user.username, order.seller_username, order.price);
-            
-        Ok(Json(FulfillOrderResponse {
-            id: order_id,
-            currency_balance,
-            scroll_count,
-            message: format!("Successfully sold 1 scroll to {} for {} pax", order.seller_username, order.price),
-        }))
-    }
-} 
\ No newline at end of file
+    Ok(Json(LastPriceResponse {
+        last_price: last.as_ref().map(|t| t.price),
+        executed_at: last.map(|t| t.executed_at.to_string()),
+    }))
+}
@@ -12,6 +12,7 @@ use reqwest::Client;
 use crate::auth::middleware::UserId;
 use crate::AppState;
 use crate::patreon_handler::PatreonConfig;
+use super::notification_service::{notify, NotificationKind};
 
 // Duration for membership in days (30 days by default)
 pub const MEMBERSHIP_DURATION_DAYS: i64 = 30;
@@ -439,6 +440,15 @@ pub async fn handle_oauth_callback(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    notify(
+        &state.pool,
+        &state.redis,
+        user_id.0,
+        NotificationKind::PatreonLinked,
+        "Your Patreon account was linked".to_string(),
+        serde_json::json!({ "is_member": is_active_patron }),
+    ).await;
+
     Ok(Json(PatreonLinkResponse {
         success: true,
         message: format!("Successfully linked Patreon account for {}", identity_response.data.attributes.email),
@@ -614,6 +624,15 @@ pub async fn link_patreon_account(
 
     info!("Successfully linked user {} to Patreon account {}", user.username, patreon_supporter.full_name);
 
+    notify(
+        &state.pool,
+        &state.redis,
+        user_id.0,
+        NotificationKind::PatreonLinked,
+        format!("Your account was linked to Patreon supporter {}", patreon_supporter.full_name),
+        serde_json::json!({ "is_member": is_active_patron }),
+    ).await;
+
     Ok(Json(PatreonLinkResponse {
         success: true,
         message: format!("Successfully linked to Patreon account for: {}", patreon_supporter.full_name),
@@ -697,6 +716,15 @@ pub async fn unlink_patreon_account(
 
     info!("Successfully unlinked user {} from Patreon account", user.username);
 
+    notify(
+        &state.pool,
+        &state.redis,
+        user_id.0,
+        NotificationKind::PatreonUnlinked,
+        "Your Patreon account was unlinked".to_string(),
+        serde_json::json!({}),
+    ).await;
+
     Ok(Json(serde_json::json!({
         "success": true,
         "message": "Successfully unlinked your account from Patreon"
@@ -0,0 +1,112 @@
+use axum::{
+    extract::{State, Extension},
+    http::StatusCode,
+    Json,
+};
+use serde::de::DeserializeOwned;
+use shared::preferences::UserPreferences;
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+use crate::auth::middleware::UserId;
+use crate::AppState;
+use super::market_service::ApiResponse;
+
+/// Stored JSON that no longer matches the schema falls back to defaults rather than failing reads.
+fn parse_section<T: DeserializeOwned + Default>(value: serde_json::Value, column: &str, user_id: Uuid) -> T {
+    serde_json::from_value(value).unwrap_or_else(|e| {
+        error!("Ignoring invalid {} for user {}: {}", column, user_id, e);
+        T::default()
+    })
+}
+
+/// Loads a user's preferences, with defaults for anything they never set.
+pub async fn load_preferences(pool: &PgPool, user_id: Uuid) -> Result<UserPreferences, sqlx::Error> {
+    let row = sqlx::query!(
+        "SELECT notification_settings, privacy_settings, ui_preferences FROM user_preferences WHERE user_id = $1",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(match row {
+        Some(row) => UserPreferences {
+            notification_settings: parse_section(row.notification_settings, "notification_settings", user_id),
+            privacy_settings: parse_section(row.privacy_settings, "privacy_settings", user_id),
+            ui_preferences: parse_section(row.ui_preferences, "ui_preferences", user_id),
+        },
+        None => UserPreferences::default(),
+    })
+}
+
+pub async fn get_preferences(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<ApiResponse<UserPreferences>>, StatusCode> {
+    let preferences = load_preferences(&state.pool, user_id.0).await.map_err(|e| {
+        error!("Failed to load preferences: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(preferences),
+        error: None,
+    }))
+}
+
+pub async fn update_preferences(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Json(preferences): Json<UserPreferences>,
+) -> Result<Json<ApiResponse<UserPreferences>>, StatusCode> {
+    if let Err(e) = preferences.check() {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Invalid preferences: {}", e)),
+        }));
+    }
+
+    let to_json = |value: serde_json::Result<serde_json::Value>| value.map_err(|e| {
+        error!("Failed to serialize preferences: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    });
+    let notification_settings = to_json(serde_json::to_value(&preferences.notification_settings))?;
+    let privacy_settings = to_json(serde_json::to_value(&preferences.privacy_settings))?;
+    let ui_preferences = to_json(serde_json::to_value(&preferences.ui_preferences))?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO user_preferences (user_id, notification_settings, privacy_settings, ui_preferences)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (user_id) DO UPDATE SET
+            notification_settings = EXCLUDED.notification_settings,
+            privacy_settings = EXCLUDED.privacy_settings,
+            ui_preferences = EXCLUDED.ui_preferences
+        "#,
+        user_id.0,
+        notification_settings,
+        privacy_settings,
+        ui_preferences
+    )
+    .execute(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to save preferences: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    // Leaderboard visibility may have changed, so don't serve the cached copy
+    if let Ok(mut conn) = state.redis.get_connection() {
+        let _: Result<(), _> = redis::cmd("DEL").arg("user_leaderboard_cache").query(&mut conn);
+    }
+
+    info!("Updated preferences for user {}", user_id.0);
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(preferences),
+        error: None,
+    }))
+}
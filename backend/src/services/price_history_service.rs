@@ -0,0 +1,221 @@
+use axum::{
+    extract::{State, Json, Query},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, Transaction};
+use std::str::FromStr;
+use uuid::Uuid;
+use crate::AppState;
+use crate::generator::prompts::EssenceType;
+use crate::services::creature_bind::RarityType;
+use tracing::error;
+use super::market_service::ApiResponse;
+
+const DEFAULT_BUCKETS: i32 = 48;
+const MAX_BUCKETS: i32 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interval {
+    Hour,
+    Day,
+    Week,
+}
+
+impl Interval {
+    pub fn from_param(value: &str) -> Option<Self> {
+        match value {
+            "1h" => Some(Self::Hour),
+            "1d" => Some(Self::Day),
+            "1w" => Some(Self::Week),
+            _ => None,
+        }
+    }
+
+    /// The `date_trunc` field used to bucket timestamps.
+    fn unit(&self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    pub kind: Option<String>,
+    pub interval: Option<String>,
+    pub rarity: Option<String>,
+    pub essence: Option<String>,
+    pub buckets: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Candle {
+    pub bucket_start: String,
+    pub open: i32,
+    pub high: i32,
+    pub low: i32,
+    pub close: i32,
+    pub volume: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoryResponse {
+    pub kind: String,
+    pub interval: String,
+    pub candles: Vec<Candle>,
+}
+
+/// Snapshots a completed egg or creature sale so its price history outlives the item.
+pub async fn record_sale(
+    tx: &mut Transaction<'_, Postgres>,
+    listing_id: Uuid,
+    item_id: Uuid,
+    price: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO market_sales (listing_id, item_type, essence, rarity, price)
+         SELECT $1::uuid, 'creature', essence, rarity, $3::int FROM creatures WHERE id = $2
+         UNION ALL
+         SELECT $1::uuid, 'egg', essence, NULL, $3::int FROM eggs WHERE id = $2",
+        listing_id,
+        item_id,
+        price
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+fn invalid(message: String) -> Json<ApiResponse<HistoryResponse>> {
+    Json(ApiResponse {
+        success: false,
+        data: None,
+        error: Some(message),
+    })
+}
+
+pub async fn get_price_history(
+    State(state): State<AppState>,
+    Query(params): Query<HistoryQuery>,
+) -> Result<Json<ApiResponse<HistoryResponse>>, StatusCode> {
+    let kind = params.kind.unwrap_or_else(|| "scroll".to_string());
+    let interval_param = params.interval.unwrap_or_else(|| "1d".to_string());
+
+    let Some(interval) = Interval::from_param(&interval_param) else {
+        return Ok(invalid(format!("Invalid interval: {}", interval_param)));
+    };
+    let buckets = params.buckets.unwrap_or(DEFAULT_BUCKETS).clamp(1, MAX_BUCKETS);
+
+    if let Some(rarity) = &params.rarity {
+        if let Err(e) = RarityType::from_str(rarity) {
+            return Ok(invalid(e));
+        }
+    }
+    if let Some(essence) = &params.essence {
+        if EssenceType::from_str(essence).is_err() {
+            return Ok(invalid(format!("Invalid essence: {}", essence)));
+        }
+    }
+
+    let candles = match kind.as_str() {
+        "scroll" => {
+            if params.rarity.is_some() || params.essence.is_some() {
+                return Ok(invalid("Scrolls cannot be filtered by rarity or essence".to_string()));
+            }
+
+            sqlx::query!(
+                r#"
+                SELECT
+                    date_trunc($1, executed_at) as "bucket_start!",
+                    (array_agg(price ORDER BY executed_at ASC))[1] as "open!",
+                    MAX(price) as "high!",
+                    MIN(price) as "low!",
+                    (array_agg(price ORDER BY executed_at DESC))[1] as "close!",
+                    SUM(quantity) as "volume!"
+                FROM scroll_trades
+                WHERE executed_at >= date_trunc($1, NOW()) - ($2::int - 1) * ('1 ' || $1)::interval
+                GROUP BY 1
+                ORDER BY 1
+                "#,
+                interval.unit(),
+                buckets
+            )
+            .fetch_all(&state.pool)
+            .await
+            .map_err(|e| {
+                error!("Failed to aggregate scroll trades: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .into_iter()
+            .map(|row| Candle {
+                bucket_start: row.bucket_start.to_string(),
+                open: row.open,
+                high: row.high,
+                low: row.low,
+                close: row.close,
+                volume: row.volume,
+            })
+            .collect::<Vec<_>>()
+        }
+        "egg" | "creature" => {
+            if kind == "egg" && params.rarity.is_some() {
+                return Ok(invalid("Eggs have no rarity".to_string()));
+            }
+
+            sqlx::query!(
+                r#"
+                SELECT
+                    date_trunc($1, sold_at) as "bucket_start!",
+                    (array_agg(price ORDER BY sold_at ASC))[1] as "open!",
+                    MAX(price) as "high!",
+                    MIN(price) as "low!",
+                    (array_agg(price ORDER BY sold_at DESC))[1] as "close!",
+                    COUNT(*) as "volume!"
+                FROM market_sales
+                WHERE item_type = $3
+                AND ($4::text IS NULL OR rarity::text = $4)
+                AND ($5::text IS NULL OR essence::text = $5)
+                AND sold_at >= date_trunc($1, NOW()) - ($2::int - 1) * ('1 ' || $1)::interval
+                GROUP BY 1
+                ORDER BY 1
+                "#,
+                interval.unit(),
+                buckets,
+                kind,
+                params.rarity,
+                params.essence
+            )
+            .fetch_all(&state.pool)
+            .await
+            .map_err(|e| {
+                error!("Failed to aggregate market sales: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .into_iter()
+            .map(|row| Candle {
+                bucket_start: row.bucket_start.to_string(),
+                open: row.open,
+                high: row.high,
+                low: row.low,
+                close: row.close,
+                volume: row.volume,
+            })
+            .collect::<Vec<_>>()
+        }
+        _ => return Ok(invalid(format!("Invalid kind: {}", kind))),
+    };
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(HistoryResponse {
+            kind,
+            interval: interval_param,
+            candles,
+        }),
+        error: None,
+    }))
+}
@@ -0,0 +1,164 @@
+use axum::{
+    extract::{State, Path},
+    http::StatusCode,
+    Json,
+};
+use serde::Serialize;
+use tracing::error;
+use uuid::Uuid;
+use crate::AppState;
+use super::market_service::ApiResponse;
+
+const TOP_CREATURES_LIMIT: i64 = 6;
+
+#[derive(Debug, Serialize)]
+pub struct ProfileCreature {
+    pub id: Uuid,
+    pub display_name: String,
+    pub image_path: String,
+    pub rarity: String,
+    pub soul: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GameHighScore {
+    pub game_type: String,
+    pub high_score: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WordGameSummary {
+    pub highest_streak: i32,
+    pub total_words_guessed: i32,
+    pub fastest_time: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicProfile {
+    pub username: String,
+    pub rank: String,
+    pub experience: i32,
+    pub joined_at: String,
+    pub is_member: bool,
+    pub achievement_count: i64,
+    pub top_creatures: Vec<ProfileCreature>,
+    pub high_scores: Vec<GameHighScore>,
+    pub word_game: Option<WordGameSummary>,
+}
+
+fn db_error(context: &str) -> impl Fn(sqlx::Error) -> StatusCode + '_ {
+    move |e| {
+        error!("Failed to fetch {} for public profile: {}", context, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// `GET /api/users/:username`. Visibility comes from the `public_users` view, so a private
+/// or anonymous user looks exactly like one that doesn't exist. Scores only show for users
+/// on the leaderboards and creatures only for users in the showcase.
+pub async fn get_public_profile(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+) -> Result<Json<ApiResponse<PublicProfile>>, StatusCode> {
+    let user = sqlx::query!(
+        r#"
+        SELECT u.id, u.username, u.experience, u.rank::text as "rank!", u.is_member,
+               TO_CHAR(u.created_at, 'YYYY-MM-DD') as "joined_at!",
+               pu.on_leaderboards as "on_leaderboards!", pu.in_showcase as "in_showcase!"
+        FROM users u
+        JOIN public_users pu ON pu.id = u.id
+        WHERE LOWER(u.username) = LOWER($1) AND pu.profile_visible
+        "#,
+        username
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(db_error("user"))?;
+
+    let Some(user) = user else {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Player not found".to_string()),
+        }));
+    };
+
+    let achievement_count = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM user_achievements WHERE user_id = $1"#,
+        user.id
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(db_error("achievements"))?;
+
+    let top_creatures = if user.in_showcase {
+        sqlx::query_as!(
+            ProfileCreature,
+            r#"
+            SELECT id, display_name, image_path, rarity::text as "rarity!", soul
+            FROM creatures
+            WHERE owner_id = $1 AND status = 'available'
+            ORDER BY
+                CASE rarity
+                    WHEN 'Mythical' THEN 5
+                    WHEN 'Legendary' THEN 4
+                    WHEN 'Epic' THEN 3
+                    WHEN 'Rare' THEN 2
+                    WHEN 'Uncommon' THEN 1
+                    ELSE 0
+                END DESC,
+                soul DESC
+            LIMIT $2
+            "#,
+            user.id,
+            TOP_CREATURES_LIMIT
+        )
+        .fetch_all(&state.pool)
+        .await
+        .map_err(db_error("creatures"))?
+    } else {
+        Vec::new()
+    };
+
+    let (high_scores, word_game) = if user.on_leaderboards {
+        let high_scores = sqlx::query_as!(
+            GameHighScore,
+            "SELECT game_type, high_score FROM game_leaderboard WHERE user_id = $1 ORDER BY game_type",
+            user.id
+        )
+        .fetch_all(&state.pool)
+        .await
+        .map_err(db_error("high scores"))?;
+
+        let word_game = sqlx::query_as!(
+            WordGameSummary,
+            "SELECT highest_streak, total_words_guessed, fastest_time
+             FROM word_game_stats
+             WHERE user_id = $1 AND total_words_guessed > 0",
+            user.id
+        )
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(db_error("word game stats"))?;
+
+        (high_scores, word_game)
+    } else {
+        (Vec::new(), None)
+    };
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(PublicProfile {
+            username: user.username,
+            rank: user.rank,
+            experience: user.experience,
+            joined_at: user.joined_at,
+            is_member: user.is_member,
+            achievement_count,
+            top_creatures,
+            high_scores,
+            word_game,
+        }),
+        error: None,
+    }))
+}
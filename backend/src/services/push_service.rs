@@ -0,0 +1,171 @@
+use axum::{
+    extract::{State, ws::{Message, WebSocket, WebSocketUpgrade}},
+    response::IntoResponse,
+};
+use futures::{sink::SinkExt, stream::StreamExt};
+use redis::Client as RedisClient;
+use shared::push_events::PushEvent;
+use sqlx::PgPool;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, info};
+use uuid::Uuid;
+use crate::AppState;
+use crate::auth::sessions;
+
+/// Channel every connected client listens on, for events that aren't tied to one user.
+const GLOBAL_CHANNEL: &str = "events:global";
+
+fn user_channel(user_id: Uuid) -> String {
+    format!("events:user:{}", user_id)
+}
+
+async fn publish_to(redis: &RedisClient, channel: &str, event: &PushEvent) {
+    let payload = match serde_json::to_string(event) {
+        Ok(payload) => payload,
+        Err(e) => {
+            error!("Failed to serialize push event: {}", e);
+            return;
+        }
+    };
+
+    let mut conn = match redis.get_async_connection().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            error!("Failed to get redis connection for push event: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = redis::cmd("PUBLISH")
+        .arg(channel)
+        .arg(payload)
+        .query_async::<_, ()>(&mut conn)
+        .await
+    {
+        error!("Failed to publish push event to {}: {}", channel, e);
+    }
+}
+
+/// Pushes an event to every open connection of one user. Delivery is best effort,
+/// so call this after the transaction commits and never fail a request over it.
+pub async fn publish(redis: &RedisClient, user_id: Uuid, event: &PushEvent) {
+    publish_to(redis, &user_channel(user_id), event).await;
+}
+
+/// Pushes an event to every connected user.
+pub async fn broadcast(redis: &RedisClient, event: &PushEvent) {
+    publish_to(redis, GLOBAL_CHANNEL, event).await;
+}
+
+/// Reads a user's current balance and pushes it, for changes made on their behalf
+/// by someone else (a buyer, a matched order, an auction settling).
+pub async fn publish_balance(pool: &PgPool, redis: &RedisClient, user_id: Uuid) {
+    match sqlx::query_scalar!("SELECT currency_balance FROM users WHERE id = $1", user_id)
+        .fetch_one(pool)
+        .await
+    {
+        Ok(balance) => publish(redis, user_id, &PushEvent::BalanceChanged { balance }).await,
+        Err(e) => error!("Failed to fetch balance for push event: {}", e),
+    }
+}
+
+pub async fn events_ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_events_socket(socket, state))
+}
+
+/// Streams a user's push events until they disconnect. The socket is closed when the
+/// access token it opened with expires or its session is signed out.
+async fn handle_events_socket(mut socket: WebSocket, state: AppState) {
+    // Browsers can't set headers on a WebSocket upgrade, so the token comes in as the first message
+    let (user_id, session_id, expires_at) = match socket.recv().await {
+        Some(Ok(Message::Text(token))) if token.starts_with("Bearer ") => {
+            match crate::auth::validate_session_jwt_with_expiry(token.trim_start_matches("Bearer ").trim()) {
+                Ok(claims) => claims,
+                Err(e) => {
+                    error!("Invalid auth token on event stream: {:?}", e);
+                    return;
+                }
+            }
+        }
+        other => {
+            error!("Expected auth token as first event stream message, but received: {:?}", other);
+            return;
+        }
+    };
+
+    let mut conn = match state.redis.get_async_connection().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            error!("Failed to get redis connection for event stream: {}", e);
+            return;
+        }
+    };
+
+    if let Some(session_id) = session_id {
+        if sessions::is_revoked(&mut conn, session_id).await {
+            info!("Refused event stream for revoked session {}", session_id);
+            return;
+        }
+    }
+
+    let revoked_channel = session_id.map(sessions::revoked_session_channel);
+    let mut pubsub = conn.into_pubsub();
+    for channel in [Some(user_channel(user_id)), Some(GLOBAL_CHANNEL.to_string()), revoked_channel.clone()]
+        .into_iter()
+        .flatten()
+    {
+        if let Err(e) = pubsub.subscribe(&channel).await {
+            error!("Failed to subscribe to {}: {}", channel, e);
+            return;
+        }
+    }
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let expiry = tokio::time::sleep(Duration::from_secs(expires_at.saturating_sub(now)));
+    tokio::pin!(expiry);
+
+    info!("Event stream opened for user {}", user_id);
+
+    let (mut sender, mut receiver) = socket.split();
+    let mut messages = Box::pin(pubsub.on_message());
+
+    loop {
+        tokio::select! {
+            message = messages.next() => {
+                let Some(message) = message else { break };
+                if revoked_channel.as_deref() == Some(message.get_channel_name()) {
+                    info!("Session signed out, closing event stream for user {}", user_id);
+                    let _ = sender.send(Message::Close(None)).await;
+                    break;
+                }
+                let payload: String = match message.get_payload() {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        error!("Failed to read push event payload: {}", e);
+                        continue;
+                    }
+                };
+                if sender.send(Message::Text(payload)).await.is_err() {
+                    break;
+                }
+            }
+            _ = &mut expiry => {
+                // The client reconnects with its refreshed token
+                let _ = sender.send(Message::Close(None)).await;
+                break;
+            }
+            incoming = receiver.next() => {
+                // Clients don't send anything after auth; just notice when they go away
+                match incoming {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    info!("Event stream closed for user {}", user_id);
+}
@@ -5,12 +5,14 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use tracing::error;
+use tracing::{error, info};
 use uuid::Uuid;
 use time::OffsetDateTime;
 
 use crate::AppState;
 use crate::auth::middleware::UserId;
+use crate::generator::prompts::AnimalType;
+use super::market_service::ApiResponse;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Scroll {
@@ -109,14 +111,14 @@ pub async fn get_scroll_by_id(
     Ok(Json(scroll.into()))
 }
 
-// Check if a user has a specific scroll available
+// Check if a user has a specific Summoning Scroll available (crafted scrolls cannot summon)
 pub async fn check_scroll_availability(
     pool: &PgPool,
     scroll_id: Uuid,
     owner_id: Uuid,
 ) -> Result<bool, StatusCode> {
     let result = sqlx::query!(
-        "SELECT quantity FROM scrolls WHERE id = $1 AND owner_id = $2",
+        "SELECT quantity FROM scrolls WHERE id = $1 AND owner_id = $2 AND display_name = 'Summoning Scroll'",
         scroll_id,
         owner_id
     )
@@ -173,4 +175,269 @@ pub async fn consume_scroll(
         })?;
         Ok(scroll.quantity - 1)
     }
-}
\ No newline at end of file
+}
+
+/// Effect of a crafted scroll once applied to an incubating egg, keyed by the scroll's name.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollEffect {
+    /// Halves the remaining incubation time as soon as it is applied
+    Haste,
+    /// Biases the hatched animal towards the chosen target
+    Affinity,
+    /// Improves the rarity rolled on hatch
+    Fortune,
+}
+
+impl ScrollEffect {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Scroll of Haste" => Some(Self::Haste),
+            "Scroll of Affinity" => Some(Self::Affinity),
+            "Scroll of Fortune" => Some(Self::Fortune),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApplyScrollRequest {
+    pub scroll_id: Uuid,
+    pub target_animal: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppliedItemResponse {
+    pub scroll_name: String,
+    pub target_animal: Option<String>,
+    pub applied_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApplyScrollResponse {
+    pub applied_items: Vec<AppliedItemResponse>,
+    pub incubation_ends_at: String,
+    pub remaining_scrolls: i32,
+}
+
+async fn fetch_applied_items(
+    executor: impl sqlx::PgExecutor<'_>,
+    egg_id: Uuid,
+) -> Result<Vec<AppliedItemResponse>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT scroll_name, target_animal::text as target_animal,
+               TO_CHAR(applied_at, 'YYYY-MM-DD"T"HH24:MI:SS.MS"Z"') as "applied_at!"
+        FROM applied_items
+        WHERE egg_id = $1
+        ORDER BY applied_at
+        "#,
+        egg_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| AppliedItemResponse {
+        scroll_name: row.scroll_name,
+        target_animal: row.target_animal,
+        applied_at: row.applied_at,
+    }).collect())
+}
+
+// Get the scrolls applied to one of the caller's eggs
+pub async fn get_applied_items(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(egg_id): Path<Uuid>,
+) -> Result<Json<Vec<AppliedItemResponse>>, StatusCode> {
+    let owned = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM eggs WHERE id = $1 AND owner_id = $2) as "exists!""#,
+        egg_id,
+        user_id.0
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to check egg ownership: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if !owned {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let items = fetch_applied_items(&state.pool, egg_id).await.map_err(|e| {
+        error!("Failed to fetch applied items: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(items))
+}
+
+// Apply a crafted scroll to an incubating egg
+pub async fn apply_scroll(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(egg_id): Path<Uuid>,
+    Json(payload): Json<ApplyScrollRequest>,
+) -> Result<Json<ApiResponse<ApplyScrollResponse>>, StatusCode> {
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let egg = sqlx::query!(
+        r#"
+        SELECT owner_id, status::text as "status!", incubation_ends_at
+        FROM eggs
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        egg_id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch egg: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let egg = match egg {
+        Some(egg) if egg.owner_id == user_id.0 => egg,
+        _ => {
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some("Egg not found".to_string()),
+            }));
+        }
+    };
+
+    if egg.status != "available" {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Cannot apply scrolls to an egg that is listed or being traded".to_string()),
+        }));
+    }
+
+    if OffsetDateTime::now_utc() >= egg.incubation_ends_at {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Scrolls can only be applied while the egg is incubating".to_string()),
+        }));
+    }
+
+    let scroll = sqlx::query!(
+        "SELECT display_name FROM scrolls WHERE id = $1 AND owner_id = $2 FOR UPDATE",
+        payload.scroll_id,
+        user_id.0
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch scroll: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let Some(scroll) = scroll else {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some("Scroll not found".to_string()),
+        }));
+    };
+
+    let Some(effect) = ScrollEffect::from_name(&scroll.display_name) else {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!("{} cannot be applied to an egg", scroll.display_name)),
+        }));
+    };
+
+    let target_animal = match (effect, payload.target_animal.as_deref()) {
+        (ScrollEffect::Affinity, Some(animal)) => match animal.parse::<AnimalType>() {
+            Ok(animal) => Some(animal.to_string()),
+            Err(_) => {
+                return Ok(Json(ApiResponse {
+                    success: false,
+                    data: None,
+                    error: Some(format!("Unknown animal: {}", animal)),
+                }));
+            }
+        },
+        (ScrollEffect::Affinity, None) => {
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some("Choose an animal for the Scroll of Affinity".to_string()),
+            }));
+        }
+        _ => None,
+    };
+
+    let inserted = sqlx::query!(
+        "INSERT INTO applied_items (egg_id, scroll_name, target_animal)
+        VALUES ($1, $2, $3::text::animal_type)
+        ON CONFLICT DO NOTHING",
+        egg_id,
+        scroll.display_name,
+        target_animal
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to apply scroll: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if inserted.rows_affected() == 0 {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(format!("{} is already applied to this egg", scroll.display_name)),
+        }));
+    }
+
+    let remaining_scrolls = consume_scroll(&mut tx, payload.scroll_id).await?;
+
+    let incubation_ends_at = if effect == ScrollEffect::Haste {
+        sqlx::query_scalar!(
+            "UPDATE eggs SET incubation_ends_at = NOW() + (incubation_ends_at - NOW()) / 2 WHERE id = $1 RETURNING incubation_ends_at",
+            egg_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to shorten incubation: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+    } else {
+        egg.incubation_ends_at
+    };
+
+    let applied_items = fetch_applied_items(&mut *tx, egg_id).await.map_err(|e| {
+        error!("Failed to fetch applied items: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tx.commit().await.map_err(|e| {
+        error!("Failed to commit transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    info!("📜 User {} applied {} to egg {}", user_id.0, scroll.display_name, egg_id);
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(ApplyScrollResponse {
+            applied_items,
+            incubation_ends_at: incubation_ends_at
+                .format(&time::format_description::well_known::Rfc3339)
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+            remaining_scrolls,
+        }),
+        error: None,
+    }))
+}
@@ -0,0 +1,707 @@
+use axum::{
+    extract::{State, Json, Extension, Path},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, Transaction};
+use std::collections::HashSet;
+use uuid::Uuid;
+use crate::AppState;
+use crate::auth::middleware::UserId;
+use tracing::{error, info};
+use super::market_service::ApiResponse;
+use super::friend_service::is_blocked;
+use super::ledger_service::{self, PaxEntryKind, SystemAccount};
+
+const MAX_TRADE_ITEMS: usize = 10;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TradeItemRef {
+    pub item_id: Uuid,
+    pub item_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TradeTerms {
+    #[serde(default)]
+    pub offered_items: Vec<TradeItemRef>,
+    #[serde(default)]
+    pub requested_items: Vec<TradeItemRef>,
+    #[serde(default)]
+    pub offered_pax: i32,
+    #[serde(default)]
+    pub requested_pax: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTradeRequest {
+    pub recipient_username: String,
+    #[serde(flatten)]
+    pub terms: TradeTerms,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TradeItemResponse {
+    pub item_id: Uuid,
+    pub item_type: String,
+    pub owner_id: Uuid,
+    pub display_name: Option<String>,
+    pub image_path: Option<String>,
+    pub rarity: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TradeOfferResponse {
+    pub id: Uuid,
+    pub proposer_id: Uuid,
+    pub proposer_username: String,
+    pub recipient_id: Uuid,
+    pub recipient_username: String,
+    pub offered_pax: i32,
+    pub requested_pax: i32,
+    pub status: String,
+    pub countered_offer_id: Option<Uuid>,
+    pub created_at: String,
+    pub resolved_at: Option<String>,
+    pub offered_items: Vec<TradeItemResponse>,
+    pub requested_items: Vec<TradeItemResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TradeActionResponse {
+    pub offer_id: Uuid,
+    pub new_balance: i32,
+}
+
+/// Failure of a trade operation: either a rule the caller broke, or a database error.
+enum TradeError {
+    Rejected(String),
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for TradeError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+fn rejected<T>(message: &str) -> Result<T, TradeError> {
+    Err(TradeError::Rejected(message.to_string()))
+}
+
+/// Turns the outcome of a trade operation into the API response, logging database failures.
+fn respond(
+    action: &str,
+    result: Result<TradeActionResponse, TradeError>,
+) -> Result<Json<ApiResponse<TradeActionResponse>>, StatusCode> {
+    match result {
+        Ok(data) => Ok(Json(ApiResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })),
+        Err(TradeError::Rejected(message)) => {
+            info!("❌ Trade {} failed: {}", action, message);
+            Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(message),
+            }))
+        }
+        Err(TradeError::Database(e)) => {
+            error!("Failed to {} trade: {}", action, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+struct LockedItem {
+    owner_id: Uuid,
+    status: String,
+    in_chaos_realm: bool,
+}
+
+async fn lock_item(
+    tx: &mut Transaction<'_, Postgres>,
+    item: &TradeItemRef,
+) -> Result<Option<LockedItem>, sqlx::Error> {
+    match item.item_type.as_str() {
+        "egg" => Ok(sqlx::query!(
+            r#"SELECT owner_id, status::text as "status!" FROM eggs WHERE id = $1 FOR UPDATE"#,
+            item.item_id
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .map(|r| LockedItem { owner_id: r.owner_id, status: r.status, in_chaos_realm: false })),
+        _ => Ok(sqlx::query!(
+            r#"SELECT owner_id, status::text as "status!", in_chaos_realm FROM creatures WHERE id = $1 FOR UPDATE"#,
+            item.item_id
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .map(|r| LockedItem { owner_id: r.owner_id, status: r.status, in_chaos_realm: r.in_chaos_realm })),
+    }
+}
+
+async fn set_item_owner(
+    tx: &mut Transaction<'_, Postgres>,
+    item: &TradeItemRef,
+    owner_id: Uuid,
+    status: &str,
+) -> Result<(), sqlx::Error> {
+    match item.item_type.as_str() {
+        "egg" => sqlx::query!(
+            "UPDATE eggs SET owner_id = $1, status = $2::text::item_status WHERE id = $3",
+            owner_id,
+            status,
+            item.item_id
+        )
+        .execute(&mut **tx)
+        .await?,
+        _ => sqlx::query!(
+            "UPDATE creatures SET owner_id = $1, status = $2::text::item_status WHERE id = $3",
+            owner_id,
+            status,
+            item.item_id
+        )
+        .execute(&mut **tx)
+        .await?,
+    };
+
+    Ok(())
+}
+
+/// Checks that every item belongs to `owner_id` and is free to be traded.
+async fn check_tradeable(
+    tx: &mut Transaction<'_, Postgres>,
+    items: &[TradeItemRef],
+    owner_id: Uuid,
+    expected_status: &str,
+) -> Result<(), TradeError> {
+    for item in items {
+        let Some(locked) = lock_item(tx, item).await? else {
+            return rejected(&format!("The {} in this trade no longer exists", item.item_type));
+        };
+        if locked.owner_id != owner_id {
+            return rejected(&format!("The {} in this trade has changed hands", item.item_type));
+        }
+        if locked.status != expected_status || locked.in_chaos_realm {
+            return rejected(&format!("The {} in this trade is listed, in another trade, or in the Chaos Realm", item.item_type));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_terms(terms: &TradeTerms) -> Result<(), TradeError> {
+    if terms.offered_pax < 0 || terms.requested_pax < 0 {
+        return rejected("Pax amounts cannot be negative");
+    }
+    if terms.offered_items.len() > MAX_TRADE_ITEMS || terms.requested_items.len() > MAX_TRADE_ITEMS {
+        return rejected(&format!("A trade can include at most {} items per side", MAX_TRADE_ITEMS));
+    }
+    if (terms.offered_items.is_empty() && terms.offered_pax == 0)
+        || (terms.requested_items.is_empty() && terms.requested_pax == 0)
+    {
+        return rejected("Both sides of a trade must include items or pax");
+    }
+
+    let mut seen = HashSet::new();
+    for item in terms.offered_items.iter().chain(&terms.requested_items) {
+        if item.item_type != "egg" && item.item_type != "creature" {
+            return rejected("Only eggs and creatures can be traded");
+        }
+        if !seen.insert(item.item_id) {
+            return rejected("An item can only appear once in a trade");
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates a pending offer, escrowing the proposer's items and pax.
+async fn open_offer(
+    tx: &mut Transaction<'_, Postgres>,
+    proposer_id: Uuid,
+    recipient_id: Uuid,
+    terms: &TradeTerms,
+    countered_offer_id: Option<Uuid>,
+) -> Result<TradeActionResponse, TradeError> {
+    validate_terms(terms)?;
+
+    if is_blocked(&mut **tx, proposer_id, recipient_id).await? {
+        return rejected("You cannot trade with this player");
+    }
+
+    check_tradeable(tx, &terms.offered_items, proposer_id, "available").await?;
+    check_tradeable(tx, &terms.requested_items, recipient_id, "available").await?;
+
+    let balance = sqlx::query_scalar!(
+        "SELECT currency_balance FROM users WHERE id = $1 FOR UPDATE",
+        proposer_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    if balance < terms.offered_pax {
+        return rejected("Insufficient funds for the offered pax");
+    }
+
+    let offer_id = sqlx::query_scalar!(
+        "INSERT INTO trade_offers (proposer_id, recipient_id, offered_pax, requested_pax, countered_offer_id)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING id",
+        proposer_id,
+        recipient_id,
+        terms.offered_pax,
+        terms.requested_pax,
+        countered_offer_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    let new_balance = ledger_service::debit(
+        tx, proposer_id, terms.offered_pax, PaxEntryKind::TradeEscrow, SystemAccount::Escrow, Some(offer_id),
+    ).await?;
+
+    let sides = [(&terms.offered_items, proposer_id), (&terms.requested_items, recipient_id)];
+    for (items, owner_id) in sides {
+        for item in items {
+            sqlx::query!(
+                "INSERT INTO trade_offer_items (offer_id, item_id, item_type, owner_id) VALUES ($1, $2, $3, $4)",
+                offer_id,
+                item.item_id,
+                item.item_type,
+                owner_id
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+    }
+
+    for item in &terms.offered_items {
+        set_item_owner(tx, item, proposer_id, "trading").await?;
+    }
+
+    Ok(TradeActionResponse { offer_id, new_balance })
+}
+
+struct PendingOffer {
+    id: Uuid,
+    proposer_id: Uuid,
+    recipient_id: Uuid,
+    offered_pax: i32,
+    requested_pax: i32,
+}
+
+async fn lock_pending_offer(
+    tx: &mut Transaction<'_, Postgres>,
+    offer_id: Uuid,
+) -> Result<PendingOffer, TradeError> {
+    let offer = sqlx::query!(
+        "SELECT id, proposer_id, recipient_id, offered_pax, requested_pax
+         FROM trade_offers
+         WHERE id = $1 AND status = 'pending'::trade_status_type
+         FOR UPDATE",
+        offer_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    match offer {
+        Some(o) => Ok(PendingOffer {
+            id: o.id,
+            proposer_id: o.proposer_id,
+            recipient_id: o.recipient_id,
+            offered_pax: o.offered_pax,
+            requested_pax: o.requested_pax,
+        }),
+        None => rejected("Trade offer not found or no longer pending"),
+    }
+}
+
+async fn offer_items(
+    tx: &mut Transaction<'_, Postgres>,
+    offer_id: Uuid,
+    owner_id: Uuid,
+) -> Result<Vec<TradeItemRef>, sqlx::Error> {
+    Ok(sqlx::query!(
+        "SELECT item_id, item_type FROM trade_offer_items WHERE offer_id = $1 AND owner_id = $2",
+        offer_id,
+        owner_id
+    )
+    .fetch_all(&mut **tx)
+    .await?
+    .into_iter()
+    .map(|r| TradeItemRef { item_id: r.item_id, item_type: r.item_type })
+    .collect())
+}
+
+/// Returns escrowed items and pax to the proposer and closes the offer with `status`.
+async fn close_offer(
+    tx: &mut Transaction<'_, Postgres>,
+    offer: &PendingOffer,
+    status: &str,
+) -> Result<i32, sqlx::Error> {
+    for item in offer_items(tx, offer.id, offer.proposer_id).await? {
+        if lock_item(tx, &item).await?.is_some_and(|i| i.owner_id == offer.proposer_id && i.status == "trading") {
+            set_item_owner(tx, &item, offer.proposer_id, "available").await?;
+        }
+    }
+
+    sqlx::query!(
+        "UPDATE trade_offers SET status = $1::text::trade_status_type, resolved_at = NOW() WHERE id = $2",
+        status,
+        offer.id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    ledger_service::credit(
+        tx, offer.proposer_id, offer.offered_pax, PaxEntryKind::TradeRefund, SystemAccount::Escrow, Some(offer.id),
+    ).await
+}
+
+/// Cancels the user's pending offers ahead of an account deletion, which would otherwise
+/// cascade the offers away and leave the proposer's escrowed items and pax stranded.
+pub async fn cancel_offers_for_deleted_user(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    let offers = sqlx::query_as!(
+        PendingOffer,
+        "SELECT id, proposer_id, recipient_id, offered_pax, requested_pax
+         FROM trade_offers
+         WHERE (proposer_id = $1 OR recipient_id = $1) AND status = 'pending'::trade_status_type
+         FOR UPDATE",
+        user_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    for offer in &offers {
+        close_offer(tx, offer, "cancelled").await?;
+    }
+    Ok(())
+}
+
+async fn user_balance(tx: &mut Transaction<'_, Postgres>, user_id: Uuid) -> Result<i32, sqlx::Error> {
+    sqlx::query_scalar!("SELECT currency_balance FROM users WHERE id = $1", user_id)
+        .fetch_one(&mut **tx)
+        .await
+}
+
+pub async fn create_trade_offer(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Json(payload): Json<CreateTradeRequest>,
+) -> Result<Json<ApiResponse<TradeActionResponse>>, StatusCode> {
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let result = async {
+        let recipient_id = sqlx::query_scalar!(
+            "SELECT id FROM users WHERE username = $1",
+            payload.recipient_username
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(recipient_id) = recipient_id else {
+            return rejected("Recipient not found");
+        };
+        if recipient_id == user_id.0 {
+            return rejected("You cannot trade with yourself");
+        }
+
+        open_offer(&mut tx, user_id.0, recipient_id, &payload.terms, None).await
+    }
+    .await;
+
+    if let Ok(response) = &result {
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit transaction: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        info!("🤝 Trade offer {} sent to {} with {} item(s) and {} pax",
+              response.offer_id, payload.recipient_username, payload.terms.offered_items.len(), payload.terms.offered_pax);
+    }
+
+    respond("create", result)
+}
+
+pub async fn accept_trade_offer(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(offer_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<TradeActionResponse>>, StatusCode> {
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let result = async {
+        let offer = lock_pending_offer(&mut tx, offer_id).await?;
+        if offer.recipient_id != user_id.0 {
+            return rejected("Only the recipient can accept this offer");
+        }
+        if is_blocked(&mut *tx, offer.proposer_id, offer.recipient_id).await? {
+            return rejected("You cannot trade with this player");
+        }
+
+        let offered = offer_items(&mut tx, offer.id, offer.proposer_id).await?;
+        let requested = offer_items(&mut tx, offer.id, offer.recipient_id).await?;
+        check_tradeable(&mut tx, &offered, offer.proposer_id, "trading").await?;
+        check_tradeable(&mut tx, &requested, offer.recipient_id, "available").await?;
+
+        let balance = sqlx::query_scalar!(
+            "SELECT currency_balance FROM users WHERE id = $1 FOR UPDATE",
+            offer.recipient_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if balance < offer.requested_pax {
+            return rejected("Insufficient funds for the requested pax");
+        }
+
+        // The offered pax comes out of escrow; the requested pax moves between the players now
+        ledger_service::credit(
+            &mut tx, offer.recipient_id, offer.offered_pax, PaxEntryKind::TradePayment, SystemAccount::Escrow, Some(offer.id),
+        ).await?;
+        ledger_service::transfer(
+            &mut tx, offer.recipient_id, offer.proposer_id, offer.requested_pax,
+            PaxEntryKind::TradePayment, PaxEntryKind::TradePayment, Some(offer.id),
+        ).await?;
+
+        let transfers = offered.iter().map(|item| (item, offer.proposer_id, offer.recipient_id))
+            .chain(requested.iter().map(|item| (item, offer.recipient_id, offer.proposer_id)));
+        for (item, from_id, to_id) in transfers {
+            set_item_owner(&mut tx, item, to_id, "available").await?;
+
+            sqlx::query!(
+                "INSERT INTO item_events (item_id, item_type, event_type, from_user_id, to_user_id, performed_by_user_id, additional_data)
+                 VALUES ($1, $2, 'traded'::event_type, $3, $4, $5, $6)",
+                item.item_id,
+                item.item_type,
+                from_id,
+                to_id,
+                user_id.0,
+                serde_json::json!({ "trade_offer_id": offer.id })
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            "UPDATE trade_offers SET status = 'accepted'::trade_status_type, resolved_at = NOW() WHERE id = $1",
+            offer.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let new_balance = user_balance(&mut tx, user_id.0).await?;
+        Ok(TradeActionResponse { offer_id: offer.id, new_balance })
+    }
+    .await;
+
+    if result.is_ok() {
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit transaction: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        info!("🤝 Trade offer {} accepted", offer_id);
+    }
+
+    respond("accept", result)
+}
+
+pub async fn counter_trade_offer(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(offer_id): Path<Uuid>,
+    Json(terms): Json<TradeTerms>,
+) -> Result<Json<ApiResponse<TradeActionResponse>>, StatusCode> {
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let result = async {
+        let offer = lock_pending_offer(&mut tx, offer_id).await?;
+        if offer.recipient_id != user_id.0 {
+            return rejected("Only the recipient can counter this offer");
+        }
+
+        close_offer(&mut tx, &offer, "countered").await?;
+        open_offer(&mut tx, user_id.0, offer.proposer_id, &terms, Some(offer.id)).await
+    }
+    .await;
+
+    if let Ok(response) = &result {
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit transaction: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        info!("🤝 Trade offer {} countered with {}", offer_id, response.offer_id);
+    }
+
+    respond("counter", result)
+}
+
+/// Declines (as recipient) or cancels (as proposer) a pending offer, returning the escrow.
+async fn withdraw_trade_offer(
+    state: AppState,
+    user_id: Uuid,
+    offer_id: Uuid,
+    as_recipient: bool,
+) -> Result<Json<ApiResponse<TradeActionResponse>>, StatusCode> {
+    let (action, status) = if as_recipient { ("decline", "declined") } else { ("cancel", "cancelled") };
+
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let result = async {
+        let offer = lock_pending_offer(&mut tx, offer_id).await?;
+        let party = if as_recipient { offer.recipient_id } else { offer.proposer_id };
+        if party != user_id {
+            return rejected(&format!("You cannot {} this offer", action));
+        }
+
+        close_offer(&mut tx, &offer, status).await?;
+        let new_balance = user_balance(&mut tx, user_id).await?;
+        Ok(TradeActionResponse { offer_id: offer.id, new_balance })
+    }
+    .await;
+
+    if result.is_ok() {
+        tx.commit().await.map_err(|e| {
+            error!("Failed to commit transaction: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        info!("🤝 Trade offer {} {}", offer_id, status);
+    }
+
+    respond(action, result)
+}
+
+pub async fn decline_trade_offer(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(offer_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<TradeActionResponse>>, StatusCode> {
+    withdraw_trade_offer(state, user_id.0, offer_id, true).await
+}
+
+pub async fn cancel_trade_offer(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(offer_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<TradeActionResponse>>, StatusCode> {
+    withdraw_trade_offer(state, user_id.0, offer_id, false).await
+}
+
+pub async fn get_trade_offers(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+) -> Result<Json<Vec<TradeOfferResponse>>, StatusCode> {
+    let offers = sqlx::query!(
+        r#"
+        SELECT
+            t.id,
+            t.proposer_id,
+            p.username as proposer_username,
+            t.recipient_id,
+            r.username as recipient_username,
+            t.offered_pax,
+            t.requested_pax,
+            t.status::text as "status!",
+            t.countered_offer_id,
+            t.created_at,
+            t.resolved_at
+        FROM trade_offers t
+        JOIN users p ON t.proposer_id = p.id
+        JOIN users r ON t.recipient_id = r.id
+        WHERE (t.proposer_id = $1 OR t.recipient_id = $1)
+        AND (t.status = 'pending'::trade_status_type OR t.resolved_at > NOW() - INTERVAL '7 days')
+        ORDER BY t.created_at DESC
+        LIMIT 50
+        "#,
+        user_id.0
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch trade offers: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let offer_ids: Vec<Uuid> = offers.iter().map(|o| o.id).collect();
+    let items = sqlx::query!(
+        r#"
+        SELECT
+            ti.offer_id,
+            ti.item_id,
+            ti.item_type,
+            ti.owner_id,
+            COALESCE(e.display_name, c.display_name) as display_name,
+            COALESCE(e.image_path, c.image_path) as image_path,
+            c.rarity::text as rarity
+        FROM trade_offer_items ti
+        LEFT JOIN eggs e ON ti.item_type = 'egg' AND e.id = ti.item_id
+        LEFT JOIN creatures c ON ti.item_type = 'creature' AND c.id = ti.item_id
+        WHERE ti.offer_id = ANY($1)
+        "#,
+        &offer_ids
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch trade offer items: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut responses: Vec<TradeOfferResponse> = offers
+        .into_iter()
+        .map(|o| TradeOfferResponse {
+            id: o.id,
+            proposer_id: o.proposer_id,
+            proposer_username: o.proposer_username,
+            recipient_id: o.recipient_id,
+            recipient_username: o.recipient_username,
+            offered_pax: o.offered_pax,
+            requested_pax: o.requested_pax,
+            status: o.status,
+            countered_offer_id: o.countered_offer_id,
+            created_at: o.created_at.to_string(),
+            resolved_at: o.resolved_at.map(|t| t.to_string()),
+            offered_items: Vec::new(),
+            requested_items: Vec::new(),
+        })
+        .collect();
+
+    for item in items {
+        let Some(offer) = responses.iter_mut().find(|o| o.id == item.offer_id) else {
+            continue;
+        };
+        let response = TradeItemResponse {
+            item_id: item.item_id,
+            item_type: item.item_type,
+            owner_id: item.owner_id,
+            display_name: item.display_name,
+            image_path: item.image_path,
+            rarity: item.rarity,
+        };
+        if item.owner_id == offer.proposer_id {
+            offer.offered_items.push(response);
+        } else {
+            offer.requested_items.push(response);
+        }
+    }
+
+    Ok(Json(responses))
+}
@@ -0,0 +1,297 @@
+use axum::{
+    extract::{State, Json, Extension, Path},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+use crate::AppState;
+use crate::auth::middleware::UserId;
+use tracing::{error, info};
+use super::market_service::ApiResponse;
+use super::friend_service::is_blocked;
+use super::guild_service::find_user;
+
+const TRANSFER_COOLDOWN_SECONDS: i64 = 300; // 5 minutes between gifts
+const MIN_ACCOUNT_AGE_DAYS: i64 = 7;
+
+#[derive(Debug, Deserialize)]
+pub struct TransferRequest {
+    pub item_type: String,
+    pub recipient_username: String,
+    /// Number of scrolls to send from a stack; ignored for eggs and creatures.
+    pub quantity: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransferResponse {
+    pub item_id: Uuid,
+    pub recipient_username: String,
+    pub quantity: i32,
+}
+
+fn failure(message: &str) -> Json<ApiResponse<TransferResponse>> {
+    Json(ApiResponse {
+        success: false,
+        data: None,
+        error: Some(message.to_string()),
+    })
+}
+
+/// Sends a gift. The cooldown is claimed before anything else, so two gifts sent at
+/// once can't both get through, and handed back if the gift doesn't go out.
+pub async fn transfer_item(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Path(item_id): Path<Uuid>,
+    Json(payload): Json<TransferRequest>,
+) -> Result<Json<ApiResponse<TransferResponse>>, StatusCode> {
+    let mut redis_conn = state.redis.get_async_connection().await.map_err(|e| {
+        error!("Redis connection error in transfer_item: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let cooldown_key = format!("transfer:cooldown:{}", user_id.0);
+    let claimed: Option<String> = redis::cmd("SET")
+        .arg(&cooldown_key)
+        .arg(1)
+        .arg("EX")
+        .arg(TRANSFER_COOLDOWN_SECONDS)
+        .arg("NX")
+        .query_async(&mut redis_conn)
+        .await
+        .map_err(|e| {
+            error!("Failed to claim transfer cooldown: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if claimed.is_none() {
+        let remaining: i64 = redis::cmd("TTL")
+            .arg(&cooldown_key)
+            .query_async(&mut redis_conn)
+            .await
+            .unwrap_or(TRANSFER_COOLDOWN_SECONDS);
+        return Ok(failure(&format!("You can send another gift in {} seconds", remaining.max(1))));
+    }
+
+    let result = send_gift(&state, &user_id, item_id, payload).await;
+    if !matches!(&result, Ok(Json(response)) if response.success) {
+        if let Err(e) = redis::cmd("DEL")
+            .arg(&cooldown_key)
+            .query_async::<_, ()>(&mut redis_conn)
+            .await
+        {
+            error!("Failed to release transfer cooldown: {}", e);
+        }
+    }
+    result
+}
+
+async fn send_gift(
+    state: &AppState,
+    user_id: &UserId,
+    item_id: Uuid,
+    payload: TransferRequest,
+) -> Result<Json<ApiResponse<TransferResponse>>, StatusCode> {
+    let mut tx = state.pool.begin().await.map_err(|e| {
+        error!("Failed to begin transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let sender = sqlx::query!(
+        "SELECT username, created_at FROM users WHERE id = $1",
+        user_id.0
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch sender: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if sender.created_at > OffsetDateTime::now_utc() - time::Duration::days(MIN_ACCOUNT_AGE_DAYS) {
+        info!("❌ Transfer failed: {} account is younger than {} days", sender.username, MIN_ACCOUNT_AGE_DAYS);
+        return Ok(failure(&format!("Accounts must be at least {} days old to send gifts", MIN_ACCOUNT_AGE_DAYS)));
+    }
+
+    let recipient_id = find_user(&mut *tx, &payload.recipient_username).await.map_err(|e| {
+        error!("Failed to fetch recipient: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let Some(recipient_id) = recipient_id else {
+        return Ok(failure("Recipient not found"));
+    };
+    if recipient_id == user_id.0 {
+        return Ok(failure("You cannot send a gift to yourself"));
+    }
+    let blocked = is_blocked(&mut *tx, user_id.0, recipient_id).await.map_err(|e| {
+        error!("Failed to check blocks: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if blocked {
+        return Ok(failure("You cannot send gifts to this player"));
+    }
+
+    // The id the event is recorded against; scroll gifts land on the recipient's stack
+    let (event_item_id, quantity) = match payload.item_type.as_str() {
+        "egg" | "creature" => {
+            let item = if payload.item_type == "egg" {
+                sqlx::query!(
+                    r#"SELECT owner_id, status::text as "status!", false as "in_chaos_realm!" FROM eggs WHERE id = $1 FOR UPDATE"#,
+                    item_id
+                )
+                .fetch_optional(&mut *tx)
+                .await
+                .map(|row| row.map(|r| (r.owner_id, r.status, r.in_chaos_realm)))
+            } else {
+                sqlx::query!(
+                    r#"SELECT owner_id, status::text as "status!", in_chaos_realm FROM creatures WHERE id = $1 FOR UPDATE"#,
+                    item_id
+                )
+                .fetch_optional(&mut *tx)
+                .await
+                .map(|row| row.map(|r| (r.owner_id, r.status, r.in_chaos_realm)))
+            }
+            .map_err(|e| {
+                error!("Failed to fetch {}: {}", payload.item_type, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+            let Some((owner_id, status, in_chaos_realm)) = item else {
+                return Ok(failure("Item not found"));
+            };
+            if owner_id != user_id.0 {
+                info!("❌ Transfer failed: User {} doesn't own {} {}", sender.username, payload.item_type, item_id);
+                return Ok(failure("You don't own this item"));
+            }
+            if status != "available" || in_chaos_realm {
+                return Ok(failure("Item is not available for transfer"));
+            }
+
+            let updated = if payload.item_type == "egg" {
+                sqlx::query!("UPDATE eggs SET owner_id = $1 WHERE id = $2", recipient_id, item_id)
+                    .execute(&mut *tx)
+                    .await
+            } else {
+                sqlx::query!("UPDATE creatures SET owner_id = $1 WHERE id = $2", recipient_id, item_id)
+                    .execute(&mut *tx)
+                    .await
+            };
+            updated.map_err(|e| {
+                error!("Failed to transfer {} ownership: {}", payload.item_type, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+            (item_id, 1)
+        }
+        "scroll" => {
+            let quantity = payload.quantity.unwrap_or(1);
+            if quantity <= 0 {
+                return Ok(failure("Quantity must be at least 1"));
+            }
+
+            let stack = sqlx::query!(
+                "SELECT owner_id, quantity, display_name, description, image_path FROM scrolls WHERE id = $1 FOR UPDATE",
+                item_id
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch scroll: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+            let Some(stack) = stack else {
+                return Ok(failure("Item not found"));
+            };
+            if stack.owner_id != user_id.0 {
+                info!("❌ Transfer failed: User {} doesn't own scroll {}", sender.username, item_id);
+                return Ok(failure("You don't own this item"));
+            }
+            if stack.quantity < quantity {
+                return Ok(failure("You don't have that many scrolls"));
+            }
+
+            let result = async {
+                if stack.quantity == quantity {
+                    sqlx::query!("DELETE FROM scrolls WHERE id = $1", item_id)
+                        .execute(&mut *tx)
+                        .await?;
+                } else {
+                    sqlx::query!("UPDATE scrolls SET quantity = quantity - $1 WHERE id = $2", quantity, item_id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+
+                let existing = sqlx::query_scalar!(
+                    "UPDATE scrolls SET quantity = quantity + $1 WHERE id = (
+                        SELECT id FROM scrolls WHERE owner_id = $2 AND display_name = $3 ORDER BY created_at LIMIT 1
+                    ) RETURNING id",
+                    quantity,
+                    recipient_id,
+                    stack.display_name
+                )
+                .fetch_optional(&mut *tx)
+                .await?;
+
+                match existing {
+                    Some(id) => Ok(id),
+                    None => sqlx::query_scalar!(
+                        "INSERT INTO scrolls (owner_id, display_name, description, image_path, quantity)
+                         VALUES ($1, $2, $3, $4, $5)
+                         RETURNING id",
+                        recipient_id,
+                        stack.display_name,
+                        stack.description,
+                        stack.image_path,
+                        quantity
+                    )
+                    .fetch_one(&mut *tx)
+                    .await,
+                }
+            }
+            .await
+            .map_err(|e: sqlx::Error| {
+                error!("Failed to move scrolls: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+
+            (result, quantity)
+        }
+        _ => return Ok(failure("Invalid item type")),
+    };
+
+    sqlx::query!(
+        "INSERT INTO item_events (item_id, item_type, event_type, from_user_id, to_user_id, performed_by_user_id, additional_data)
+         VALUES ($1, $2, 'transferred'::event_type, $3, $4, $3, $5)",
+        event_item_id,
+        payload.item_type,
+        user_id.0,
+        recipient_id,
+        serde_json::json!({ "quantity": quantity })
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        error!("Failed to record item event: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tx.commit().await.map_err(|e| {
+        error!("Failed to commit transaction: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    info!("🎁 {} sent {} {} ({}) to {}", sender.username, quantity, payload.item_type, item_id, payload.recipient_username);
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(TransferResponse {
+            item_id: event_item_id,
+            recipient_username: payload.recipient_username,
+            quantity,
+        }),
+        error: None,
+    }))
+}
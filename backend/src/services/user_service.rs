@@ -86,7 +86,17 @@ pub fn compute_rank(xp: i32) -> &'static str {
 /// Result<(), sqlx::Error> indicating success or failure of the database operations.
 pub async fn update_experience_and_rank(pool: &PgPool, user_id: Uuid, xp_increase: i32) -> Result<(), sqlx::Error> {
     let mut tx = pool.begin().await?;
+    add_experience(&mut tx, user_id, xp_increase).await?;
+    tx.commit().await?;
+    Ok(())
+}
 
+/// Adds experience and updates the rank inside the caller's transaction.
+pub async fn add_experience(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: Uuid,
+    xp_increase: i32,
+) -> Result<(), sqlx::Error> {
     // Define a local struct for the query result
     struct UserXPRecord {
         experience: i32,
@@ -97,7 +107,7 @@ pub async fn update_experience_and_rank(pool: &PgPool, user_id: Uuid, xp_increas
         "SELECT experience, rank::text as \"rank!\" FROM users WHERE id = $1",
         user_id
     )
-    .fetch_one(&mut *tx)
+    .fetch_one(&mut **tx)
     .await?;
 
     let new_experience = user_record.experience + xp_increase;
@@ -109,17 +119,16 @@ pub async fn update_experience_and_rank(pool: &PgPool, user_id: Uuid, xp_increas
             .bind(new_experience)
             .bind(new_rank)
             .bind(user_id)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await?;
     } else {
         sqlx::query("UPDATE users SET experience = $1 WHERE id = $2")
             .bind(new_experience)
             .bind(user_id)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await?;
     }
 
-    tx.commit().await?;
     Ok(())
 }
 
@@ -170,6 +179,13 @@ pub async fn update_user_game_score(
                 game_type,
                 new_score
             );
+
+            if let Err(e) = super::achievement_service::check_achievements(
+                pool, user_id, super::achievement_service::AchievementTrigger::GameReward
+            ).await {
+                error!("Failed to check achievements: {}", e);
+            }
+
             Ok(())
         },
         Err(e) => {
@@ -192,26 +208,33 @@ pub async fn update_user_game_score(
 
 /// Retrieves the top N players for a specific game.
 /// Returns a vector of LeaderboardEntry containing username and score.
+/// With `friends_of` set, only that user and their friends are ranked.
 pub async fn get_game_leaderboard(
     pool: &PgPool,
     game_type: &str,
-    limit: i64
+    limit: i64,
+    friends_of: Option<Uuid>,
 ) -> Result<Vec<LeaderboardEntry>, sqlx::Error> {
     let rows = sqlx::query(
         r#"
         SELECT 
-            u.username,
+            u.display_name as username,
             l.high_score,
             TO_CHAR(l.updated_at, 'YYYY-MM-DD HH24:MI:SS') as updated_at_str
         FROM game_leaderboard l
-        JOIN users u ON l.user_id = u.id
+        JOIN public_users u ON l.user_id = u.id
         WHERE l.game_type = $1
+        AND u.on_leaderboards
+        AND ($3::uuid IS NULL
+             OR u.id = $3
+             OR u.id IN (SELECT friend_id FROM friendships WHERE user_id = $3))
         ORDER BY l.high_score DESC
         LIMIT $2
         "#,
     )
     .bind(game_type)
     .bind(limit)
+    .bind(friends_of)
     .fetch_all(pool)
     .await?;
 
@@ -267,16 +290,18 @@ pub async fn get_user_leaderboard(
         WITH creature_stats AS (
             SELECT
                 u.id,
-                u.username,
+                pu.display_name AS username,
                 u.currency_balance AS pax,
                 COALESCE(SUM(c.soul), 0) AS total_soul,
                 COUNT(DISTINCT c.id) AS creature_count
             FROM
                 users u
+            JOIN
+                public_users pu ON pu.id = u.id
             LEFT JOIN
                 creatures c ON u.id = c.owner_id
-            WHERE u.deleted_at IS NULL
-            GROUP BY u.id, u.username, u.currency_balance
+            WHERE pu.on_leaderboards
+            GROUP BY u.id, pu.display_name, u.currency_balance
         ), egg_counts AS (
             SELECT
                 owner_id,
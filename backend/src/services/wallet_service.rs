@@ -0,0 +1,156 @@
+use axum::{
+    extract::{State, Extension, Query},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+use uuid::Uuid;
+use crate::auth::middleware::UserId;
+use crate::AppState;
+use super::market_service::ApiResponse;
+
+const DEFAULT_PAGE_SIZE: i64 = 25;
+const MAX_PAGE_SIZE: i64 = 100;
+const DEFAULT_SUMMARY_DAYS: i32 = 30;
+const MAX_SUMMARY_DAYS: i32 = 365;
+
+#[derive(Debug, Deserialize)]
+pub struct TransactionsQuery {
+    pub limit: Option<i64>,
+    /// Ledger entry id to page back from; pass the previous page's `next_before`
+    pub before: Option<i64>,
+    /// Only entries of this `pax_entry_kind`, e.g. `daily_claim`
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SummaryQuery {
+    pub days: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WalletTransaction {
+    pub id: i64,
+    pub source: String,
+    pub amount: i32,
+    pub balance_after: i64,
+    /// The other player in a purchase, sale or trade payment
+    pub counterparty: Option<String>,
+    pub reference_id: Option<Uuid>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WalletTransactionsResponse {
+    pub transactions: Vec<WalletTransaction>,
+    /// `None` once there are no older entries
+    pub next_before: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DailySourceSummary {
+    pub day: String,
+    pub source: String,
+    pub earned: i64,
+    pub spent: i64,
+}
+
+/// `GET /api/wallet/transactions`: the player's pax ledger entries, newest first, with
+/// the balance each one left behind.
+pub async fn get_wallet_transactions(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Query(query): Query<TransactionsQuery>,
+) -> Result<Json<ApiResponse<WalletTransactionsResponse>>, StatusCode> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let source = query.source.filter(|source| !source.is_empty());
+
+    // The running balance is summed over every entry before filtering, so it stays
+    // correct on filtered pages. One extra row tells us whether another page exists.
+    let mut transactions = sqlx::query_as!(
+        WalletTransaction,
+        r#"
+        SELECT l.id, l.kind::text as "source!", l.amount,
+               l.balance_after as "balance_after!",
+               cu.display_name as "counterparty?",
+               l.reference_id,
+               TO_CHAR(l.created_at, 'YYYY-MM-DD HH24:MI:SS') as "created_at!"
+        FROM (
+            SELECT id, transaction_id, kind, amount, reference_id, created_at,
+                   SUM(amount) OVER (ORDER BY id) as balance_after
+            FROM pax_ledger
+            WHERE user_id = $1 AND account = 'user'
+        ) l
+        LEFT JOIN pax_ledger o
+          ON o.transaction_id = l.transaction_id AND o.id <> l.id AND o.account = 'user'
+        LEFT JOIN public_users cu ON cu.id = o.user_id
+        WHERE ($2::text IS NULL OR l.kind::text = $2)
+          AND ($3::bigint IS NULL OR l.id < $3)
+        ORDER BY l.id DESC
+        LIMIT $4
+        "#,
+        user_id.0,
+        source,
+        query.before,
+        limit + 1
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch wallet transactions: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let next_before = if transactions.len() as i64 > limit {
+        transactions.truncate(limit as usize);
+        transactions.last().map(|transaction| transaction.id)
+    } else {
+        None
+    };
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(WalletTransactionsResponse { transactions, next_before }),
+        error: None,
+    }))
+}
+
+/// `GET /api/wallet/summary`: pax earned and spent per source per day over the last
+/// `days` days (today included), newest day first.
+pub async fn get_wallet_summary(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<UserId>,
+    Query(query): Query<SummaryQuery>,
+) -> Result<Json<ApiResponse<Vec<DailySourceSummary>>>, StatusCode> {
+    let days = query.days.unwrap_or(DEFAULT_SUMMARY_DAYS).clamp(1, MAX_SUMMARY_DAYS);
+
+    let summary = sqlx::query_as!(
+        DailySourceSummary,
+        r#"
+        SELECT TO_CHAR(DATE(created_at), 'YYYY-MM-DD') as "day!",
+               kind::text as "source!",
+               COALESCE(SUM(amount) FILTER (WHERE amount > 0), 0) as "earned!",
+               COALESCE(-SUM(amount) FILTER (WHERE amount < 0), 0) as "spent!"
+        FROM pax_ledger
+        WHERE user_id = $1 AND account = 'user' AND kind <> 'opening_balance'
+          AND created_at >= CURRENT_DATE - ($2::int - 1)
+        GROUP BY DATE(created_at), kind
+        ORDER BY DATE(created_at) DESC, kind
+        "#,
+        user_id.0,
+        days
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(|e| {
+        error!("Failed to fetch wallet summary: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(summary),
+        error: None,
+    }))
+}
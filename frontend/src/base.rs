@@ -7,6 +7,8 @@ use web_sys::{window, MouseEvent, Event, CustomEvent, CustomEventInit};
 use wasm_bindgen::{JsValue, JsCast};
 use js_sys;
 use crate::hooks::use_currency::use_currency;
+use crate::hooks::use_push_events::use_event_stream;
+use crate::components::NotificationBell;
 use gloo::events::EventListener;
 use crate::config::get_api_base_url;
 
@@ -134,6 +136,8 @@ pub fn base(props: &BaseProps) -> Html {
         .map_or(false, |status| status == "true")
     );
 
+    use_event_stream(auth_state.0);
+
     let global_error = use_state(|| None::<String>);
 
     // Add notification state
@@ -350,13 +354,14 @@ pub fn base(props: &BaseProps) -> Html {
                             if (*auth_state).0 {
                                 <div class="relative dropdown-container">
                                     <div class="flex items-center space-x-4">
-                                        <div class="flex items-center space-x-1 pr-1 pl-2 py-1 bg-gray-100 dark:bg-gray-700 rounded-lg">
+                                        <NotificationBell />
+                                        <Link<Route> to={Route::Wallet} classes={classes!("flex", "items-center", "space-x-1", "pr-1", "pl-2", "py-1", "bg-gray-100", "dark:bg-gray-700", "hover:bg-gray-200", "dark:hover:bg-gray-600", "rounded-lg")}>
                                             <span class="text-sm font-medium text-blue-700 dark:text-blue-300">
                                                 {*current_currency}
                                             </span>
                                             <img src="/static/images/pax-icon-black-0.png" alt="pax icon" class="block dark:hidden w-5 h-5" />
                                             <img src="/static/images/pax-icon-white-0.png" alt="pax icon" class="hidden dark:block w-5 h-5" />
-                                        </div>
+                                        </Link<Route>>
                                         <div class={format!("flex items-center px-3 py-1 h-[28px] rounded-lg text-sm font-medium {}", 
                                             if *is_member {
                                                 "bg-green-100 dark:bg-green-900/30 text-green-700 dark:text-green-400"
@@ -390,6 +395,12 @@ pub fn base(props: &BaseProps) -> Html {
                                                     <Link<Route> to={Route::Market} classes={classes!(styles::DROPDOWN_BUTTON, "flex", "justify-center")}>
                                                         {"Market"}
                                                     </Link<Route>>
+                                                    <Link<Route> to={Route::Guild} classes={classes!(styles::DROPDOWN_BUTTON, "flex", "justify-center")}>
+                                                        {"Guild"}
+                                                    </Link<Route>>
+                                                    <Link<Route> to={Route::Battle} classes={classes!(styles::DROPDOWN_BUTTON, "flex", "justify-center")}>
+                                                        {"Battle"}
+                                                    </Link<Route>>
                                                     <Link<Route> to={Route::Settings} classes={classes!(styles::DROPDOWN_BUTTON, "flex", "justify-center")}>
                                                         {"Settings"}
                                                     </Link<Route>>
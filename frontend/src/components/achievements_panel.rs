@@ -0,0 +1,166 @@
+use yew::prelude::*;
+use serde::Deserialize;
+use gloo_net::http::Request;
+use web_sys::window;
+use crate::config::get_api_base_url;
+use crate::styles;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+pub struct AchievementRewards {
+    #[serde(default)]
+    pub pax: i32,
+    #[serde(default)]
+    pub scrolls: i32,
+    #[serde(default)]
+    pub experience: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Achievement {
+    pub name: String,
+    pub description: String,
+    pub rewards: AchievementRewards,
+    pub progress: i64,
+    pub target: i64,
+    pub completed: bool,
+}
+
+fn format_rewards(rewards: &AchievementRewards) -> String {
+    let mut parts = Vec::new();
+    if rewards.pax > 0 {
+        parts.push(format!("{} pax", rewards.pax));
+    }
+    if rewards.scrolls > 0 {
+        parts.push(format!("{} scroll{}", rewards.scrolls, if rewards.scrolls == 1 { "" } else { "s" }));
+    }
+    if rewards.experience > 0 {
+        parts.push(format!("{} XP", rewards.experience));
+    }
+    parts.join(" · ")
+}
+
+#[function_component(AchievementsPanel)]
+pub fn achievements_panel() -> Html {
+    let achievements = use_state(Vec::<Achievement>::new);
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+
+    {
+        let achievements = achievements.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let token = window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|s| s.get_item("token").ok().flatten())
+                    .or_else(|| window()
+                        .and_then(|w| w.session_storage().ok().flatten())
+                        .and_then(|s| s.get_item("token").ok().flatten()))
+                    .unwrap_or_default();
+
+                match Request::get(&format!("{}/api/achievements", get_api_base_url()))
+                    .header("Authorization", &format!("Bearer {}", token))
+                    .send()
+                    .await
+                {
+                    Ok(response) if response.ok() => {
+                        match response.json::<Vec<Achievement>>().await {
+                            Ok(data) => {
+                                achievements.set(data);
+                                error.set(None);
+                            },
+                            Err(e) => {
+                                log::error!("Failed to parse achievements: {:?}", e);
+                                error.set(Some("Failed to parse achievements".to_string()));
+                            }
+                        }
+                    },
+                    Ok(response) => {
+                        error.set(Some(format!("Server returned status: {}", response.status())));
+                    },
+                    Err(e) => {
+                        log::error!("Failed to fetch achievements: {:?}", e);
+                        error.set(Some("Failed to fetch achievements".to_string()));
+                    }
+                }
+
+                loading.set(false);
+            });
+
+            || ()
+        });
+    }
+
+    let completed_count = achievements.iter().filter(|a| a.completed).count();
+
+    html! {
+        <div class={format!("{} {}", styles::HERO_FEATURES, "p-8")}>
+            <div class="flex items-center justify-between mb-6">
+                <div class="flex items-center space-x-4">
+                    <div class={styles::ICON_WRAPPER_GREEN}>
+                        <span class="text-lg">{"🏆"}</span>
+                    </div>
+                    <span class={styles::TEXT_H3}>{"Achievements"}</span>
+                </div>
+                if !*loading && error.is_none() {
+                    <span class={styles::TEXT_SMALL}>{format!("{}/{}", completed_count, achievements.len())}</span>
+                }
+            </div>
+
+            if *loading {
+                <div class="flex justify-center items-center p-6">
+                    <div class="animate-spin rounded-full h-8 w-8 border-t-2 border-b-2 border-green-500" />
+                </div>
+            } else if let Some(err) = &*error {
+                <div class={styles::ALERT_ERROR}>{err}</div>
+            } else {
+                <div class="space-y-3">
+                    {for achievements.iter().map(|achievement| {
+                        let percent = if achievement.target > 0 {
+                            (achievement.progress * 100 / achievement.target).min(100)
+                        } else {
+                            100
+                        };
+                        html! {
+                            <div class={format!("p-4 rounded-xl border {}",
+                                if achievement.completed {
+                                    "bg-green-100/50 dark:bg-green-900/30 border-green-200 dark:border-green-800"
+                                } else {
+                                    "bg-gray-100/50 dark:bg-gray-800/30 border-gray-200 dark:border-gray-700"
+                                }
+                            )}>
+                                <div class="flex justify-between items-start gap-4">
+                                    <div class="text-left">
+                                        <p class={styles::TEXT_BODY}>
+                                            {if achievement.completed { "✅ " } else { "" }}
+                                            {&achievement.name}
+                                        </p>
+                                        <p class={styles::TEXT_SMALL}>{&achievement.description}</p>
+                                    </div>
+                                    <span class={format!("{} {}", styles::TEXT_SMALL, "whitespace-nowrap")}>
+                                        {format_rewards(&achievement.rewards)}
+                                    </span>
+                                </div>
+                                if !achievement.completed {
+                                    <div class="mt-3 space-y-1">
+                                        <div class="w-full bg-gray-200 dark:bg-gray-700 rounded-full h-2">
+                                            <div
+                                                class="bg-gradient-to-r from-green-500 to-blue-500 h-2 rounded-full transition-all duration-500"
+                                                style={format!("width: {}%", percent)}
+                                            />
+                                        </div>
+                                        <p class={format!("{} {}", styles::TEXT_SMALL, "text-right")}>
+                                            {format!("{} / {}", achievement.progress, achievement.target)}
+                                        </p>
+                                    </div>
+                                }
+                            </div>
+                        }
+                    })}
+                </div>
+            }
+        </div>
+    }
+}
@@ -3,6 +3,7 @@ use gloo_net::http::Request;
 use wasm_bindgen_futures::spawn_local;
 use crate::models::ShowcaseCreatureData;
 use crate::config::get_api_base_url;
+use crate::hooks::use_preferences::use_ui_preferences;
 use log::error;
 use web_sys::{Element, MouseEvent, TouchEvent};
 use gloo_events::EventListener;
@@ -24,6 +25,7 @@ pub fn creature_showcase() -> Html {
     // Scrolling state
     let is_paused = use_state(|| false);
     let scroll_interval = use_mut_ref(|| None::<Interval>);
+    let reduced_motion = use_ui_preferences().reduced_motion;
 
     // Check scroll position and update nav buttons visibility
     {
@@ -167,9 +169,9 @@ pub fn creature_showcase() -> Html {
         let is_paused = is_paused.clone();
         let scroll_container_ref = scroll_container_ref.clone();
         
-        use_effect_with(*is_paused, move |_| {
-            // If paused and we have an interval, clear it
-            if *is_paused {
+        use_effect_with((*is_paused, reduced_motion), move |(_, reduced_motion)| {
+            // If paused (or auto-scrolling is turned off) and we have an interval, clear it
+            if *is_paused || *reduced_motion {
                 *scroll_interval.borrow_mut() = None;
             } else {
                 // If not paused and we don't have an interval, start one
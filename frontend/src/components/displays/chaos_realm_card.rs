@@ -8,6 +8,10 @@ use wasm_bindgen::JsValue;
 use js_sys::Object;
 use crate::config::get_api_base_url;
 use js_sys::Date;
+use std::cell::Cell;
+use std::rc::Rc;
+use shared::push_events::PushEvent;
+use crate::hooks::use_push_event;
 
 // Helper function to get auth token
 async fn get_auth_token() -> String {
@@ -104,7 +108,6 @@ pub fn chaos_realm_card(props: &ChaosRealmCardProps) -> Html {
     let remaining_time = use_state(|| None::<i64>);
     let loading_chaos = use_state(|| props.loading_chaos);
     let error = use_state(|| props.error.clone());
-    let status_check_interval = use_state(|| None::<Interval>);
     // Add a visual state for transition effects
     let visually_in_realm = use_state(|| props.creature.in_chaos_realm);
     
@@ -112,66 +115,55 @@ pub fn chaos_realm_card(props: &ChaosRealmCardProps) -> Html {
         let remaining_time = remaining_time.clone();
         let in_chaos_realm = props.creature.in_chaos_realm;
         let creature_id = props.creature.id.to_string();
-        let status_check_interval = status_check_interval.clone();
         let error_state = error.clone();
         
         use_effect_with(in_chaos_realm, move |in_chaos_realm| {
+            let mut interval = None;
+
             if *in_chaos_realm {
-                // Instead of calculating locally, fetch status from backend every second
-                let creature_id_clone = creature_id.clone();
-                let remaining_time_clone = remaining_time.clone();
-                let error_state_clone = error_state.clone();
-                
-                let interval = Interval::new(1000, move || {
-                    let creature_id_inner = creature_id_clone.clone();
-                    let remaining_time_inner = remaining_time_clone.clone();
-                    let error_state_inner = error_state_clone.clone();
-                    
-                    spawn_local(async move {
-                        match get_chaos_realm_status(&creature_id_inner).await {
-                            Ok(status) => {
-                                if let Some(seconds) = status.remaining_seconds {
-                                    remaining_time_inner.set(Some(seconds));
-                                }
-                            },
-                            Err(e) => {
-                                error_state_inner.set(format!("Failed to check status: {}", e));
-                            }
-                        }
-                    });
-                });
-                
-                status_check_interval.set(Some(interval));
-                
-                // Initial status check
-                let creature_id_init = creature_id.clone();
+                // Ask the backend once for the remaining time, then count down locally;
+                // the server pushes ChaosRealmCompleted when the run actually finishes
+                let ends_at = Rc::new(Cell::new(None::<f64>));
+
+                let ends_at_init = ends_at.clone();
                 let remaining_time_init = remaining_time.clone();
-                let error_state_init = error_state.clone();
-                
                 spawn_local(async move {
-                    match get_chaos_realm_status(&creature_id_init).await {
+                    match get_chaos_realm_status(&creature_id).await {
                         Ok(status) => {
                             if let Some(seconds) = status.remaining_seconds {
+                                ends_at_init.set(Some(Date::now() + seconds as f64 * 1000.0));
                                 remaining_time_init.set(Some(seconds));
                             }
                         },
                         Err(e) => {
-                            error_state_init.set(format!("Failed to check status: {}", e));
+                            error_state.set(format!("Failed to check status: {}", e));
                         }
                     }
                 });
+
+                let remaining_time = remaining_time.clone();
+                interval = Some(Interval::new(1000, move || {
+                    if let Some(ends_at) = ends_at.get() {
+                        let seconds = ((ends_at - Date::now()) / 1000.0).ceil() as i64;
+                        remaining_time.set(Some(seconds.max(0)));
+                    }
+                }));
             }
             
-            // Cleanup function
-            let status_check_interval_clone = status_check_interval.clone();
-            move || {
-                // We need to take ownership of the interval to cancel it
-                if let Some(_) = *status_check_interval_clone {
-                    // Set to None first to avoid borrowing issues
-                    status_check_interval_clone.set(None);
+            move || drop(interval)
+        });
+    }
+
+    {
+        let remaining_time = remaining_time.clone();
+        let creature_id = props.creature.id.to_string();
+        use_push_event(Callback::from(move |event: PushEvent| {
+            if let PushEvent::ChaosRealmCompleted { creature_id: completed_id } = event {
+                if completed_id == creature_id {
+                    remaining_time.set(Some(0));
                 }
             }
-        });
+        }));
     }
 
     let on_claim = {
@@ -15,6 +15,8 @@ use gloo_timers::future::sleep;
 use gloo_utils::format::JsValueSerdeExt;
 use crate::config::get_api_base_url;
 use std::rc::Rc;
+use shared::push_events::PushEvent;
+use crate::hooks::use_push_event;
 
 const CURRENCY_UPDATE_EVENT: &str = "currencyUpdate";
 const ENERGY_RECHARGE_EVENT: &str = "energyRecharge";
@@ -291,6 +293,33 @@ pub fn energy_manager(props: &EnergyManagerProps) -> Html {
         });
     }
 
+    // Effect: Sync up as soon as the server reports the recharge finished, in case the local countdown drifted
+    {
+        let fetch_data = props.fetch_data.clone();
+        let on_energy_update = props.on_energy_update.clone();
+        let creature_id = props.creature.id;
+        let effective_energy_full = effective_energy_full.clone();
+        let force_zero_energy = force_zero_energy.clone();
+        let local_recharge_time = local_recharge_time.clone();
+
+        use_push_event(Callback::from(move |event: PushEvent| {
+            if let PushEvent::EnergyRecharged { creature_id: recharged_id } = event {
+                if recharged_id != creature_id.to_string() {
+                    return;
+                }
+                effective_energy_full.set(true);
+                force_zero_energy.set(false);
+                local_recharge_time.set(None);
+                if let Some(callback) = fetch_data.as_ref() {
+                    callback.emit(());
+                }
+                if let Some(callback) = on_energy_update.as_ref() {
+                    callback.emit((creature_id, true));
+                }
+            }
+        }));
+    }
+
     // Effect: Update chaos realm remaining time
     {
         let chaos_remaining_seconds = chaos_remaining_seconds.clone();
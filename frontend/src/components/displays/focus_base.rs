@@ -140,6 +140,7 @@ pub fn focus_template(props: &Props) -> Html {
                                 loading={props.loading}
                                 error={props.error.clone()}
                                 mode={props.mode.clone()}
+                                fetch_data={props.fetch_data.clone()}
                             />
                         },
                         DisplayItem::Scroll(scroll) => html! {
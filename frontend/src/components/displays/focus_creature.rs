@@ -1,7 +1,7 @@
 use yew::prelude::*;
 use web_sys::MouseEvent;
 use crate::models::Creature;
-use super::{CreatureImageTabs, CreatureDisplayMode, get_creature_title, get_creature_details, get_creature_stats, BindModal, ChaosRealmCard, SoulBindButton, EnergyManager, DisplayMode, RenameCreature};
+use super::{CreatureImageTabs, CreatureDisplayMode, get_creature_title, get_creature_details, get_creature_stats, BindModal, ChaosRealmCard, SoulBindButton, EnergyManager, DisplayMode, RenameCreature, GiftItem, ItemTimeline};
 use crate::pages::inventory::handlers::CreatureResponse;
 use crate::styles;
 use uuid::Uuid;
@@ -448,6 +448,11 @@ pub fn creature_focus(props: &CreatureFocusProps) -> Html {
                         </div>
                     </div>
 
+                    <ItemTimeline
+                        item_id={display_creature.id}
+                        refresh_key={format!("{:?}:{:?}", display_creature.display_name, display_creature.rarity)}
+                    />
+
                     <div class="bg-white dark:bg-gray-800 rounded-2xl p-4 ring-1 ring-gray-200 dark:ring-white/10 shadow-lg">
                         <h3 class="text-lg font-medium text-gray-900 dark:text-white mb-2">{"Attributes"}</h3>
                         <div class="divide-y divide-gray-200 dark:divide-gray-700">
@@ -691,6 +696,18 @@ pub fn creature_focus(props: &CreatureFocusProps) -> Html {
                     } else {
                         html! {}
                     }}
+
+                    {if matches!(props.mode, DisplayMode::Focus) {
+                        html! {
+                            <GiftItem
+                                item_id={display_creature.id}
+                                item_type="creature"
+                                on_sent={props.fetch_data.clone().unwrap_or_default()}
+                            />
+                        }
+                    } else {
+                        html! {}
+                    }}
                 </div>
             </div>
 
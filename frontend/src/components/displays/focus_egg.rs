@@ -1,10 +1,28 @@
 use js_sys::Date;
 use wasm_bindgen::JsValue;
 use yew::prelude::*;
-use web_sys::MouseEvent;
-use crate::models::Egg;
+use web_sys::{window, MouseEvent};
+use gloo_net::http::Request;
+use serde::Deserialize;
+use uuid::Uuid;
+use wasm_bindgen_futures::spawn_local;
+use crate::config::get_api_base_url;
+use crate::models::{Egg, Scroll};
 use crate::styles;
-use super::{get_image_url, DisplayItem, DisplayMode, get_egg_details};
+use super::{get_image_url, DisplayItem, DisplayMode, get_egg_details, GiftItem};
+
+const APPLICABLE_SCROLLS: [&str; 3] = ["Scroll of Haste", "Scroll of Affinity", "Scroll of Fortune"];
+
+const ANIMALS: [&str; 68] = [
+    "Dragon", "Chimera", "Alien", "Unicorn", "Lizard", "Kraken", "Penguin", "Megalodon",
+    "Mammoth", "Tyrannosaurus", "Pangolin", "Bee", "Whale", "Squid", "Axolotl", "Chameleon",
+    "Jellyfish", "Mantis", "Scorpion", "Peacock", "Parrot", "Eagle", "Owl", "Crow", "Duck",
+    "Chicken", "Crocodile", "Turtle", "Tiger", "Wolf", "Lion", "Jaguar", "Fox", "Dog", "Cat",
+    "Rhinoceros", "Bear", "Deer", "Dolphin", "Elephant", "Crab", "Raccoon", "Sheep", "Goat",
+    "Pig", "Mouse", "Hamster", "Rabbit", "Squirrel", "Rat", "Frog", "Otter", "Horse", "Donkey",
+    "Turkey", "Goose", "Llama", "Bison", "Giraffe", "Zebra", "Panda", "Kangaroo", "Koala",
+    "Flamingo", "Cow", "Spider", "Sloth", "Toucan",
+];
 
 #[derive(Properties, PartialEq)]
 pub struct EggFocusProps {
@@ -14,6 +32,8 @@ pub struct EggFocusProps {
     pub loading: bool,
     pub error: String,
     pub mode: DisplayMode,
+    #[prop_or_default]
+    pub fetch_data: Option<Callback<()>>,
 }
 
 #[derive(Clone, PartialEq)]
@@ -23,6 +43,25 @@ struct IncubationStatus {
     progress_percent: f64,
 }
 
+#[derive(Clone, PartialEq, Deserialize)]
+struct AppliedItem {
+    scroll_name: String,
+    target_animal: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApplyScrollData {
+    applied_items: Vec<AppliedItem>,
+    incubation_ends_at: String,
+}
+
+#[derive(Deserialize)]
+struct ApplyScrollResponse {
+    success: bool,
+    data: Option<ApplyScrollData>,
+    error: Option<String>,
+}
+
 fn format_time_remaining(seconds: i64) -> String {
     let hours = seconds / 3600;
     let minutes = (seconds % 3600) / 60;
@@ -37,60 +76,63 @@ fn format_time_remaining(seconds: i64) -> String {
     }
 }
 
+fn compute_incubation_status(ends_at: &str) -> IncubationStatus {
+    let now = Date::new_0();
+    let incubation_ends = if ends_at.contains('T') {
+        Date::new(&JsValue::from_str(ends_at))
+    } else {
+        Date::new(&JsValue::from_str(&format!("{}T00:00:00Z", ends_at)))
+    };
+
+    let remaining_ms = incubation_ends.get_time() - now.get_time();
+    let seconds_remaining = (remaining_ms / 1000.0).floor() as i64;
+    let total_incubation = 82800.0; // 23 hours (82800 seconds)
+    let progress_percent = ((total_incubation - seconds_remaining as f64) / total_incubation * 100.0).max(0.0).min(100.0);
+
+    IncubationStatus {
+        is_incubating: seconds_remaining > 0,
+        seconds_remaining: seconds_remaining.abs(),
+        progress_percent,
+    }
+}
+
+fn get_token() -> String {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .unwrap_or_default()
+}
+
 #[function_component(EggFocus)]
 pub fn egg_focus(props: &EggFocusProps) -> Html {
-    let incubation_status = {
-        let mut status = None;
-        if let Some(ends_at) = &props.egg.incubation_ends_at {
-            let now = Date::new_0();
-            let incubation_ends = if ends_at.contains('T') {
-                Date::new(&JsValue::from_str(ends_at))
-            } else {
-                Date::new(&JsValue::from_str(&format!("{}T00:00:00Z", ends_at)))
-            };
+    let ends_at = use_state(|| props.egg.incubation_ends_at.clone());
+    let incubation_status = use_state(|| props.egg.incubation_ends_at.as_deref().map(compute_incubation_status));
+    let applied_items = use_state(Vec::<AppliedItem>::new);
+    let owned_scrolls = use_state(Vec::<Scroll>::new);
+    let selected_scroll = use_state(|| None::<Uuid>);
+    let target_animal = use_state(|| ANIMALS[0].to_string());
+    let applying = use_state(|| false);
+    let apply_error = use_state(String::new);
 
-            let remaining_ms = incubation_ends.get_time() - now.get_time();
-            let seconds_remaining = (remaining_ms / 1000.0).floor() as i64;
-            let total_incubation = 82800.0; // 23 hours (82800 seconds)
-            let progress_percent = ((total_incubation - seconds_remaining as f64) / total_incubation * 100.0).max(0.0).min(100.0);
-            
-            status = Some(IncubationStatus {
-                is_incubating: seconds_remaining > 0,
-                seconds_remaining: seconds_remaining.abs(),
-                progress_percent,
-            });
-        }
-        use_state(|| status)
-    };
+    let is_owner = window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("user_id").ok().flatten())
+        .is_some_and(|id| id == props.egg.owner_id.to_string());
 
     {
         let incubation_status = incubation_status.clone();
-        let incubation_ends_at = props.egg.incubation_ends_at.clone();
 
-        use_effect_with((), move |_| {
+        use_effect_with((*ends_at).clone(), move |incubation_ends_at| {
             let calculate_time = {
                 let incubation_status = incubation_status.clone();
                 let incubation_ends_at = incubation_ends_at.clone();
                 
                 move || {
                     if let Some(ends_at) = &incubation_ends_at {
-                        let now = Date::new_0();
-                        let incubation_ends = if ends_at.contains('T') {
-                            Date::new(&JsValue::from_str(ends_at))
-                        } else {
-                            Date::new(&JsValue::from_str(&format!("{}T00:00:00Z", ends_at)))
-                        };
-
-                        let remaining_ms = incubation_ends.get_time() - now.get_time();
-                        let seconds_remaining = (remaining_ms / 1000.0).floor() as i64;
-                        let total_incubation = 82800.0; // 23 hours (82800 seconds)
-                        let progress_percent = ((total_incubation - seconds_remaining as f64) / total_incubation * 100.0).max(0.0).min(100.0);
-
-                        incubation_status.set(Some(IncubationStatus {
-                            is_incubating: seconds_remaining > 0,
-                            seconds_remaining: seconds_remaining.abs(),
-                            progress_percent,
-                        }));
+                        incubation_status.set(Some(compute_incubation_status(ends_at)));
                     }
                 }
             };
@@ -104,6 +146,134 @@ pub fn egg_focus(props: &EggFocusProps) -> Html {
         });
     }
 
+    {
+        let applied_items = applied_items.clone();
+        let owned_scrolls = owned_scrolls.clone();
+        let egg_id = props.egg.id;
+
+        use_effect_with(egg_id, move |_| {
+            // Applied scrolls are private to the egg's owner
+            if is_owner {
+                spawn_local(async move {
+                    let token = get_token();
+                    match Request::get(&format!("{}/api/eggs/{}/applied-items", get_api_base_url(), egg_id))
+                        .header("Authorization", &format!("Bearer {}", token))
+                        .send()
+                        .await
+                    {
+                        Ok(response) if response.ok() => {
+                            if let Ok(data) = response.json::<Vec<AppliedItem>>().await {
+                                applied_items.set(data);
+                            }
+                        },
+                        _ => log::error!("Failed to fetch applied scrolls"),
+                    }
+
+                    if let Ok(response) = Request::get(&format!("{}/api/scrolls", get_api_base_url()))
+                        .header("Authorization", &format!("Bearer {}", token))
+                        .send()
+                        .await
+                    {
+                        if let Ok(data) = response.json::<Vec<Scroll>>().await {
+                            owned_scrolls.set(data.into_iter()
+                                .filter(|scroll| APPLICABLE_SCROLLS.contains(&scroll.display_name.as_str()))
+                                .collect());
+                        }
+                    }
+                });
+            }
+
+            || ()
+        });
+    }
+
+    let available_scrolls: Vec<Scroll> = owned_scrolls.iter()
+        .filter(|scroll| !applied_items.iter().any(|item| item.scroll_name == scroll.display_name))
+        .cloned()
+        .collect();
+    let is_incubating = incubation_status.as_ref().is_some_and(|status| status.is_incubating);
+    let can_apply = is_owner && is_incubating && !available_scrolls.is_empty();
+    let selected_name = selected_scroll.and_then(|id| {
+        available_scrolls.iter().find(|scroll| scroll.id == id).map(|scroll| scroll.display_name.clone())
+    });
+
+    let on_scroll_change = {
+        let selected_scroll = selected_scroll.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<web_sys::HtmlSelectElement>() {
+                selected_scroll.set(Uuid::parse_str(&select.value()).ok());
+            }
+        })
+    };
+
+    let on_animal_change = {
+        let target_animal = target_animal.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<web_sys::HtmlSelectElement>() {
+                target_animal.set(select.value());
+            }
+        })
+    };
+
+    let handle_apply = {
+        let egg_id = props.egg.id;
+        let selected_scroll = selected_scroll.clone();
+        let selected_name = selected_name.clone();
+        let target_animal = target_animal.clone();
+        let applying = applying.clone();
+        let apply_error = apply_error.clone();
+        let applied_items = applied_items.clone();
+        let owned_scrolls = owned_scrolls.clone();
+        let ends_at = ends_at.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            let Some(scroll_id) = *selected_scroll else {
+                return;
+            };
+            let target = (selected_name.as_deref() == Some("Scroll of Affinity"))
+                .then(|| (*target_animal).clone());
+            let selected_scroll = selected_scroll.clone();
+            let applying = applying.clone();
+            let apply_error = apply_error.clone();
+            let applied_items = applied_items.clone();
+            let owned_scrolls = owned_scrolls.clone();
+            let ends_at = ends_at.clone();
+            applying.set(true);
+            apply_error.set(String::new());
+
+            spawn_local(async move {
+                match Request::post(&format!("{}/api/eggs/{}/apply-scroll", get_api_base_url(), egg_id))
+                    .header("Authorization", &format!("Bearer {}", get_token()))
+                    .json(&serde_json::json!({ "scroll_id": scroll_id, "target_animal": target }))
+                    .unwrap()
+                    .send()
+                    .await
+                {
+                    Ok(response) => match response.json::<ApplyScrollResponse>().await {
+                        Ok(ApplyScrollResponse { success: true, data: Some(data), .. }) => {
+                            applied_items.set(data.applied_items);
+                            ends_at.set(Some(data.incubation_ends_at));
+                            owned_scrolls.set(owned_scrolls.iter()
+                                .filter_map(|scroll| {
+                                    let mut scroll = scroll.clone();
+                                    if scroll.id == scroll_id {
+                                        scroll.quantity -= 1;
+                                    }
+                                    (scroll.quantity > 0).then_some(scroll)
+                                })
+                                .collect());
+                            selected_scroll.set(None);
+                        },
+                        Ok(result) => apply_error.set(result.error.unwrap_or_else(|| "Failed to apply scroll".to_string())),
+                        Err(_) => apply_error.set("Failed to apply scroll".to_string()),
+                    },
+                    Err(_) => apply_error.set("Network error occurred.".to_string()),
+                }
+                applying.set(false);
+            });
+        })
+    };
+
     html! {
         <div class="grid grid-cols-1 md:grid-cols-[1fr_3.3fr_1fr] gap-4">
             // Left column - History
@@ -130,6 +300,68 @@ pub fn egg_focus(props: &EggFocusProps) -> Html {
                         </div>
                     </div>
                 </div>
+
+                <div class={styles::FOCUS_CARD}>
+                    <h3 class={styles::FOCUS_CARD_TITLE}>{"Applied Scrolls"}</h3>
+                    <div class={styles::FOCUS_GRID_CONTENT}>
+                        if applied_items.is_empty() {
+                            <div class={styles::FOCUS_VALUE_SECONDARY}>{"None"}</div>
+                        }
+                        {for applied_items.iter().map(|item| html! {
+                            <div class={styles::FOCUS_GROUP}>
+                                <div class={styles::FOCUS_VALUE}>{&item.scroll_name}</div>
+                                if let Some(animal) = &item.target_animal {
+                                    <div class={styles::FOCUS_VALUE_SECONDARY}>{format!("Towards {}", animal)}</div>
+                                }
+                            </div>
+                        })}
+                        if can_apply {
+                            <div class="space-y-2">
+                                <select onchange={on_scroll_change} class={styles::INPUT}>
+                                    <option value="" selected={selected_scroll.is_none()}>{"Choose a scroll"}</option>
+                                    {for available_scrolls.iter().map(|scroll| html! {
+                                        <option value={scroll.id.to_string()} selected={*selected_scroll == Some(scroll.id)}>
+                                            {format!("{} (x{})", scroll.display_name, scroll.quantity)}
+                                        </option>
+                                    })}
+                                </select>
+                                if selected_name.as_deref() == Some("Scroll of Affinity") {
+                                    <select onchange={on_animal_change} class={styles::INPUT}>
+                                        {for ANIMALS.iter().map(|animal| html! {
+                                            <option value={*animal} selected={*target_animal == *animal}>{*animal}</option>
+                                        })}
+                                    </select>
+                                }
+                                <button
+                                    onclick={handle_apply}
+                                    disabled={*applying || selected_scroll.is_none()}
+                                    class={classes!(
+                                        styles::FOCUS_BUTTON,
+                                        "w-full",
+                                        if *applying || selected_scroll.is_none() {
+                                            "bg-gray-400 cursor-not-allowed"
+                                        } else {
+                                            "bg-gradient-to-r from-blue-500 to-purple-500 text-white hover:opacity-90"
+                                        }
+                                    )}
+                                >
+                                    {if *applying { "Applying..." } else { "Apply Scroll" }}
+                                </button>
+                                if !apply_error.is_empty() {
+                                    <p class="text-red-500 dark:text-red-400 text-sm">{&*apply_error}</p>
+                                }
+                            </div>
+                        }
+                    </div>
+                </div>
+
+                if is_owner {
+                    <GiftItem
+                        item_id={props.egg.id}
+                        item_type="egg"
+                        on_sent={props.fetch_data.clone().unwrap_or_default()}
+                    />
+                }
             </div>
 
             // Middle column - Image and Title
@@ -1,6 +1,6 @@
 use yew::prelude::*;
 use crate::models::Scroll;
-use super::DisplayMode;
+use super::{DisplayMode, GiftItem};
 use gloo_net::http::Request;
 use wasm_bindgen_futures::spawn_local;
 use web_sys::window;
@@ -154,27 +154,51 @@ pub fn scroll_focus(props: &ScrollFocusProps) -> Html {
             </div>
             
             <div class="flex flex-col space-y-4 w-full max-w-[450px]">
-                <button 
-                    onclick={handle_summon}
-                    disabled={*loading}
-                    class={classes!(
-                        "w-full",
-                        "px-6",
-                        "py-3",
-                        "rounded-xl",
-                        "font-semibold",
-                        "shadow-lg",
-                        "transition-all",
-                        "duration-500",
-                        if *loading {
-                            "bg-gray-400 cursor-not-allowed"
-                        } else {
-                            "bg-gradient-to-r from-blue-500 to-purple-500 text-white hover:opacity-90"
-                        }
-                    )}
-                >
-                    {if *loading { "Summoning..." } else { "Summon New Egg (55 pax)" }}
-                </button>
+                if local_scroll.display_name != "Summoning Scroll" {
+                    <p class="text-center text-gray-600 dark:text-gray-300">
+                        {local_scroll.description.clone().unwrap_or_default()}
+                        {format!(" ({} owned)", local_scroll.quantity)}
+                    </p>
+                } else {
+                    <button 
+                        onclick={handle_summon}
+                        disabled={*loading}
+                        class={classes!(
+                            "w-full",
+                            "px-6",
+                            "py-3",
+                            "rounded-xl",
+                            "font-semibold",
+                            "shadow-lg",
+                            "transition-all",
+                            "duration-500",
+                            if *loading {
+                                "bg-gray-400 cursor-not-allowed"
+                            } else {
+                                "bg-gradient-to-r from-blue-500 to-purple-500 text-white hover:opacity-90"
+                            }
+                        )}
+                    >
+                        {if *loading { "Summoning..." } else { "Summon New Egg (55 pax)" }}
+                    </button>
+                }
+                if matches!(props.mode, DisplayMode::Focus) {
+                    <GiftItem
+                        item_id={local_scroll.id}
+                        item_type="scroll"
+                        max_quantity={Some(local_scroll.quantity)}
+                        on_sent={{
+                            let fetch_data = props.fetch_data.clone();
+                            let on_close = props.on_close.clone();
+                            Callback::from(move |_| {
+                                if let Some(fetch_data) = &fetch_data {
+                                    fetch_data.emit(());
+                                }
+                                on_close.emit(());
+                            })
+                        }}
+                    />
+                }
             </div>
             
             if !error.is_empty() {
@@ -0,0 +1,163 @@
+use yew::prelude::*;
+use web_sys::{HtmlInputElement, window};
+use gloo_net::http::Request;
+use wasm_bindgen_futures::spawn_local;
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::styles;
+use crate::config::get_api_base_url;
+
+#[derive(Properties, PartialEq)]
+pub struct GiftItemProps {
+    pub item_id: Uuid,
+    pub item_type: String,
+    /// Stack size for scrolls; eggs and creatures are always sent whole.
+    #[prop_or_default]
+    pub max_quantity: Option<i32>,
+    pub on_sent: Callback<()>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiftResponse {
+    success: bool,
+    error: Option<String>,
+}
+
+#[function_component(GiftItem)]
+pub fn gift_item(props: &GiftItemProps) -> Html {
+    let recipient = use_state(String::new);
+    let quantity = use_state(|| "1".to_string());
+    let sending = use_state(|| false);
+    let error = use_state(String::new);
+    let message = use_state(String::new);
+
+    let on_recipient_input = {
+        let recipient = recipient.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                recipient.set(input.value());
+            }
+        })
+    };
+
+    let on_quantity_input = {
+        let quantity = quantity.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                quantity.set(input.value());
+            }
+        })
+    };
+
+    let handle_send = {
+        let recipient = recipient.clone();
+        let quantity = quantity.clone();
+        let sending = sending.clone();
+        let error = error.clone();
+        let message = message.clone();
+        let item_id = props.item_id;
+        let item_type = props.item_type.clone();
+        let is_stack = props.max_quantity.is_some();
+        let on_sent = props.on_sent.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            let amount = match quantity.parse::<i32>() {
+                Ok(q) if q > 0 => q,
+                _ => {
+                    error.set("Invalid quantity".to_string());
+                    return;
+                }
+            };
+
+            let token = window()
+                .and_then(|w| w.local_storage().ok().flatten())
+                .and_then(|s| s.get_item("token").ok().flatten())
+                .or_else(|| window()
+                    .and_then(|w| w.session_storage().ok().flatten())
+                    .and_then(|s| s.get_item("token").ok().flatten()))
+                .unwrap_or_default();
+
+            let body = serde_json::json!({
+                "item_type": item_type,
+                "recipient_username": *recipient,
+                "quantity": if is_stack { Some(amount) } else { None },
+            });
+            let recipient_name = (*recipient).clone();
+            let sending = sending.clone();
+            let error = error.clone();
+            let message = message.clone();
+            let on_sent = on_sent.clone();
+            sending.set(true);
+            error.set(String::new());
+
+            spawn_local(async move {
+                match Request::post(&format!("{}/api/items/{}/transfer", get_api_base_url(), item_id))
+                    .header("Authorization", &format!("Bearer {}", token))
+                    .json(&body)
+                    .unwrap()
+                    .send()
+                    .await
+                {
+                    Ok(response) => match response.json::<GiftResponse>().await {
+                        Ok(GiftResponse { success: true, .. }) => {
+                            message.set(format!("Sent to {}!", recipient_name));
+                            on_sent.emit(());
+                        },
+                        Ok(result) => error.set(result.error.unwrap_or_else(|| "Failed to send gift".to_string())),
+                        Err(_) => error.set("Failed to send gift".to_string()),
+                    },
+                    Err(_) => error.set("Network error occurred.".to_string()),
+                }
+                sending.set(false);
+            });
+        })
+    };
+
+    let disabled = *sending || recipient.is_empty();
+
+    html! {
+        <div class={styles::FOCUS_CARD}>
+            <h3 class={styles::FOCUS_CARD_TITLE}>{"Send as Gift"}</h3>
+            <div class="space-y-2">
+                <input
+                    type="text"
+                    placeholder="Recipient username"
+                    class={styles::INPUT}
+                    value={(*recipient).clone()}
+                    oninput={on_recipient_input}
+                />
+                if let Some(max) = props.max_quantity {
+                    <input
+                        type="number"
+                        min="1"
+                        max={max.to_string()}
+                        class={styles::INPUT}
+                        value={(*quantity).clone()}
+                        oninput={on_quantity_input}
+                    />
+                }
+                <button
+                    onclick={handle_send}
+                    disabled={disabled}
+                    class={classes!(
+                        styles::FOCUS_BUTTON,
+                        "w-full",
+                        if disabled {
+                            "bg-gray-400 cursor-not-allowed"
+                        } else {
+                            "bg-gradient-to-r from-blue-500 to-purple-500 text-white hover:opacity-90"
+                        }
+                    )}
+                >
+                    {if *sending { "Sending..." } else { "Send Gift" }}
+                </button>
+                if !message.is_empty() {
+                    <p class="text-green-600 dark:text-green-400 text-sm">{&*message}</p>
+                }
+                if !error.is_empty() {
+                    <p class="text-red-500 dark:text-red-400 text-sm">{&*error}</p>
+                }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,172 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use wasm_bindgen_futures::spawn_local;
+use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
+use crate::styles;
+use crate::config::get_api_base_url;
+use crate::hooks::auth_state::use_auth_token;
+
+#[derive(Properties, PartialEq)]
+pub struct ItemTimelineProps {
+    pub item_id: Uuid,
+    /// Changes whenever the item is modified so the timeline refetches,
+    /// e.g. after a rename or soul bind.
+    #[prop_or_default]
+    pub refresh_key: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct ItemHistoryEvent {
+    event_type: String,
+    timestamp: String,
+    performed_by: Option<String>,
+    from_user: Option<String>,
+    to_user: Option<String>,
+    additional_data: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryData {
+    events: Vec<ItemHistoryEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryResponse {
+    success: bool,
+    data: Option<HistoryData>,
+    error: Option<String>,
+}
+
+fn user_or_unknown(name: &Option<String>) -> &str {
+    name.as_deref().unwrap_or("Unknown")
+}
+
+fn data_field<'a>(event: &'a ItemHistoryEvent, key: &str) -> Option<&'a Value> {
+    event.additional_data.as_ref().and_then(|d| d.get(key))
+}
+
+fn describe_event(event: &ItemHistoryEvent) -> (&'static str, String) {
+    let price = data_field(event, "price").and_then(Value::as_i64);
+    match event.event_type.as_str() {
+        "summoned" => ("✨", format!("Egg summoned by {}", user_or_unknown(&event.performed_by))),
+        "hatched" => ("🐣", format!("Hatched by {}", user_or_unknown(&event.performed_by))),
+        "listed_for_sale" => ("🏷️", format!("Listed for sale by {}", user_or_unknown(&event.performed_by))),
+        "sale_cancelled" => ("↩️", format!("Listing cancelled by {}", user_or_unknown(&event.performed_by))),
+        "sold" | "auction_won" => (
+            "💰",
+            format!(
+                "Sold by {} to {}{}",
+                user_or_unknown(&event.from_user),
+                user_or_unknown(&event.to_user),
+                price.map(|p| format!(" for {} pax", p)).unwrap_or_default()
+            ),
+        ),
+        "bid_placed" => ("🔨", format!("Bid placed by {}", user_or_unknown(&event.performed_by))),
+        "transferred" => ("🎁", format!("Gifted by {} to {}", user_or_unknown(&event.from_user), user_or_unknown(&event.to_user))),
+        "traded" => ("🤝", format!("Traded from {} to {}", user_or_unknown(&event.from_user), user_or_unknown(&event.to_user))),
+        "crafted" => ("⚒️", format!("Crafted by {}", user_or_unknown(&event.performed_by))),
+        "soul_bound" => (
+            "🔗",
+            match data_field(event, "to_rarity").and_then(Value::as_str) {
+                Some(rarity) => format!("Soul bound to {} by {}", rarity, user_or_unknown(&event.performed_by)),
+                None => format!("Soul bound by {}", user_or_unknown(&event.performed_by)),
+            },
+        ),
+        "renamed" => (
+            "🏷️",
+            match data_field(event, "new_name").and_then(Value::as_str) {
+                Some(name) => format!("Renamed to \"{}\" by {}", name, user_or_unknown(&event.performed_by)),
+                None => format!("Renamed by {}", user_or_unknown(&event.performed_by)),
+            },
+        ),
+        "entered_chaos_realm" => ("🌀", format!("Entered the Chaos Realm with {}", user_or_unknown(&event.performed_by))),
+        _ => ("•", event.event_type.replace('_', " ")),
+    }
+}
+
+/// Timestamp without the seconds and offset, e.g. "2024-05-01 13:00".
+fn short_timestamp(timestamp: &str) -> &str {
+    timestamp.get(..16).unwrap_or(timestamp)
+}
+
+#[function_component(ItemTimeline)]
+pub fn item_timeline(props: &ItemTimelineProps) -> Html {
+    let token = use_auth_token();
+    let events = use_state(Vec::<ItemHistoryEvent>::new);
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+
+    {
+        let events = events.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+
+        use_effect_with(
+            (props.item_id, props.refresh_key.clone(), token.clone()),
+            move |(item_id, _, token)| {
+                let url = format!("{}/api/items/{}/history", get_api_base_url(), item_id);
+                let token = token.clone();
+                loading.set(true);
+
+                spawn_local(async move {
+                    match Request::get(&url)
+                        .header("Authorization", &format!("Bearer {}", token))
+                        .send()
+                        .await
+                    {
+                        Ok(response) => match response.json::<HistoryResponse>().await {
+                            Ok(HistoryResponse { success: true, data: Some(data), .. }) => {
+                                events.set(data.events);
+                                error.set(None);
+                            },
+                            Ok(result) => {
+                                error.set(Some(result.error.unwrap_or_else(|| "Failed to load history".to_string())));
+                            },
+                            Err(e) => {
+                                log::error!("Failed to parse item history: {:?}", e);
+                                error.set(Some("Failed to load history".to_string()));
+                            }
+                        },
+                        Err(e) => {
+                            log::error!("Failed to fetch item history: {:?}", e);
+                            error.set(Some("Failed to load history".to_string()));
+                        }
+                    }
+                    loading.set(false);
+                });
+
+                || ()
+            },
+        );
+    }
+
+    html! {
+        <div class={styles::FOCUS_CARD}>
+            <h3 class={styles::FOCUS_CARD_TITLE}>{"Provenance"}</h3>
+            if *loading && events.is_empty() {
+                <div class="flex justify-center py-4">
+                    <div class="animate-spin rounded-full h-6 w-6 border-t-2 border-b-2 border-blue-500" />
+                </div>
+            } else if let Some(err) = &*error {
+                <p class="text-red-500 dark:text-red-400 text-sm">{err}</p>
+            } else if events.is_empty() {
+                <p class="text-sm text-gray-500 dark:text-gray-400">{"No recorded history yet"}</p>
+            } else {
+                <ol class="relative border-l border-gray-200 dark:border-gray-700 ml-2 space-y-3">
+                    {for events.iter().map(|event| {
+                        let (icon, description) = describe_event(event);
+                        html! {
+                            <li class="ml-4">
+                                <span class="absolute -left-2 flex items-center justify-center w-4 h-4 text-xs">{icon}</span>
+                                <p class="text-sm text-gray-900 dark:text-white">{description}</p>
+                                <p class="text-xs text-gray-500 dark:text-gray-400">{short_timestamp(&event.timestamp)}</p>
+                            </li>
+                        }
+                    })}
+                </ol>
+            }
+        </div>
+    }
+}
@@ -18,6 +18,8 @@ mod energy_manager;
 mod card_scroll;
 mod focus_scroll;
 mod rename_creature;
+mod gift_item;
+mod item_timeline;
 
 pub use base_creature::*;
 pub use base_egg::{get_egg_title, get_egg_stats, get_egg_card_stats, get_egg_description, get_egg_details};
@@ -34,6 +36,8 @@ pub use energy_manager::{EnergyManager, EnergyManagerProps};
 pub use card_scroll::{ScrollCard, ScrollCardProps};
 pub use focus_scroll::{ScrollFocus, ScrollFocusProps};
 pub use rename_creature::*;
+pub use gift_item::{GiftItem, GiftItemProps};
+pub use item_timeline::{ItemTimeline, ItemTimelineProps};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DisplayMode {
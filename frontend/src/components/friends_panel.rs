@@ -0,0 +1,280 @@
+use yew::prelude::*;
+use serde::Deserialize;
+use gloo_net::http::Request;
+use uuid::Uuid;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use crate::config::get_api_base_url;
+use crate::components::PlayerLink;
+use crate::styles;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FriendEntry {
+    pub user_id: Uuid,
+    pub username: String,
+    pub since: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FriendsOverview {
+    pub friends: Vec<FriendEntry>,
+    pub incoming: Vec<FriendEntry>,
+    pub outgoing: Vec<FriendEntry>,
+    pub blocked: Vec<FriendEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FriendActivity {
+    pub id: Uuid,
+    pub username: String,
+    pub event_type: String,
+    pub item_name: String,
+    pub price: Option<i32>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    success: bool,
+    data: Option<T>,
+    error: Option<String>,
+}
+
+fn get_token() -> String {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .unwrap_or_default()
+}
+
+async fn fetch<T: for<'de> Deserialize<'de>>(path: &str) -> Result<T, String> {
+    match Request::get(&format!("{}{}", get_api_base_url(), path))
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .send()
+        .await
+    {
+        Ok(response) if response.ok() => match response.json::<ApiResponse<T>>().await {
+            Ok(ApiResponse { success: true, data: Some(data), .. }) => Ok(data),
+            Ok(result) => Err(result.error.unwrap_or_else(|| "Failed to load friends".to_string())),
+            Err(e) => {
+                log::error!("Failed to parse friends response: {:?}", e);
+                Err("Failed to load friends".to_string())
+            }
+        },
+        Ok(response) => Err(format!("Server returned status: {}", response.status())),
+        Err(e) => {
+            log::error!("Failed to fetch {}: {:?}", path, e);
+            Err("Network error occurred.".to_string())
+        }
+    }
+}
+
+/// Sends a friend action. Requests and blocks name the player by username; the other
+/// actions address them by id in the path.
+async fn send_action(method: &str, path: &str, username: Option<&str>) -> Result<(), String> {
+    let url = format!("{}{}", get_api_base_url(), path);
+    let auth = format!("Bearer {}", get_token());
+    let request = match (method, username) {
+        ("DELETE", _) => Request::delete(&url).header("Authorization", &auth).build(),
+        (_, Some(username)) => Request::post(&url)
+            .header("Authorization", &auth)
+            .json(&serde_json::json!({ "username": username })),
+        _ => Request::post(&url).header("Authorization", &auth).build(),
+    }
+    .map_err(|_| "Failed to build request".to_string())?;
+
+    match request.send().await {
+        Ok(response) => match response.json::<ApiResponse<()>>().await {
+            Ok(ApiResponse { success: true, .. }) => Ok(()),
+            Ok(result) => Err(result.error.unwrap_or_else(|| "Request failed".to_string())),
+            Err(_) => Err(format!("Server returned status: {}", response.status())),
+        },
+        Err(e) => {
+            log::error!("Friend action failed: {:?}", e);
+            Err("Network error occurred.".to_string())
+        }
+    }
+}
+
+fn describe(activity: &FriendActivity) -> String {
+    match (activity.event_type.as_str(), activity.price) {
+        ("hatched", _) => format!("hatched {}", activity.item_name),
+        (_, Some(price)) => format!("sold {} for {} pax", activity.item_name, price),
+        _ => format!("sold {}", activity.item_name),
+    }
+}
+
+#[function_component(FriendsPanel)]
+pub fn friends_panel() -> Html {
+    let overview = use_state(|| None::<FriendsOverview>);
+    let activity = use_state(Vec::<FriendActivity>::new);
+    let username = use_state(String::new);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let overview = overview.clone();
+        let activity = activity.clone();
+        let error = error.clone();
+        use_effect_with(*reload, move |_| {
+            spawn_local(async move {
+                match fetch::<FriendsOverview>("/api/friends").await {
+                    Ok(data) => overview.set(Some(data)),
+                    Err(e) => error.set(Some(e)),
+                }
+                if let Ok(data) = fetch::<Vec<FriendActivity>>("/api/friends/activity").await {
+                    activity.set(data);
+                }
+            });
+            || ()
+        });
+    }
+
+    // Runs an action, then reloads the lists so every section reflects the change
+    let act = {
+        let error = error.clone();
+        let reload = reload.clone();
+        move |method: &'static str, path: String, name: Option<String>| {
+            let error = error.clone();
+            let reload = reload.clone();
+            Callback::from(move |_: MouseEvent| {
+                let error = error.clone();
+                let reload = reload.clone();
+                let path = path.clone();
+                let name = name.clone();
+                spawn_local(async move {
+                    match send_action(method, &path, name.as_deref()).await {
+                        Ok(()) => error.set(None),
+                        Err(e) => error.set(Some(e)),
+                    }
+                    reload.set(*reload + 1);
+                });
+            })
+        }
+    };
+
+    let on_input = {
+        let username = username.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                username.set(input.value());
+            }
+        })
+    };
+
+    let trimmed = username.trim().to_string();
+    let accept_button = "px-3 py-1 rounded-lg text-sm font-medium bg-green-600 text-white hover:bg-green-700";
+    let plain_button = "px-3 py-1 rounded-lg text-sm font-medium bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200";
+
+    html! {
+        <div class={format!("{} {}", styles::HERO_FEATURES, "p-8 mt-8")}>
+            <div class="flex items-center space-x-4 mb-6">
+                <div class={styles::ICON_WRAPPER_BLUE}>
+                    <span class="text-lg">{"👥"}</span>
+                </div>
+                <span class={styles::TEXT_H3}>{"Friends"}</span>
+            </div>
+
+            <div class="flex gap-2 mb-4">
+                <input type="text" class={styles::INPUT} placeholder="Username" value={(*username).clone()} oninput={on_input} />
+                <button class={classes!(styles::BUTTON_PRIMARY, "mt-2")} disabled={trimmed.is_empty()}
+                    onclick={act("POST", "/api/friends/requests".to_string(), Some(trimmed.clone()))}>
+                    {"Add"}
+                </button>
+                <button class={classes!(styles::BUTTON_SECONDARY, "mt-2")} disabled={trimmed.is_empty()}
+                    onclick={act("POST", "/api/friends/blocks".to_string(), Some(trimmed.clone()))}>
+                    {"Block"}
+                </button>
+            </div>
+
+            if let Some(message) = &*error {
+                <div class={classes!(styles::ALERT_ERROR, "mb-4")}>{message}</div>
+            }
+
+            if let Some(data) = &*overview {
+                <div class="space-y-6 text-left">
+                    if !data.incoming.is_empty() {
+                        <div>
+                            <p class={styles::TEXT_SMALL}>{"Requests"}</p>
+                            {for data.incoming.iter().map(|entry| html! {
+                                <div key={entry.user_id.to_string()} class="flex items-center justify-between py-2">
+                                    <span class={styles::TEXT_BODY}><PlayerLink username={entry.username.clone()} /></span>
+                                    <div class="flex gap-2">
+                                        <button class={accept_button}
+                                            onclick={act("POST", format!("/api/friends/{}/accept", entry.user_id), None)}>{"Accept"}</button>
+                                        <button class={plain_button}
+                                            onclick={act("DELETE", format!("/api/friends/{}", entry.user_id), None)}>{"Decline"}</button>
+                                    </div>
+                                </div>
+                            })}
+                        </div>
+                    }
+
+                    <div>
+                        <p class={styles::TEXT_SMALL}>{format!("Friends ({})", data.friends.len())}</p>
+                        if data.friends.is_empty() {
+                            <p class={styles::TEXT_SECONDARY}>{"Add players by username to compare scores and follow their hatches."}</p>
+                        }
+                        {for data.friends.iter().map(|entry| html! {
+                            <div key={entry.user_id.to_string()} class="flex items-center justify-between py-2">
+                                <div>
+                                    <span class={styles::TEXT_BODY}><PlayerLink username={entry.username.clone()} /></span>
+                                    <p class={styles::TEXT_SMALL}>{format!("Friends since {}", entry.since)}</p>
+                                </div>
+                                <button class={plain_button}
+                                    onclick={act("DELETE", format!("/api/friends/{}", entry.user_id), None)}>{"Remove"}</button>
+                            </div>
+                        })}
+                    </div>
+
+                    if !data.outgoing.is_empty() {
+                        <div>
+                            <p class={styles::TEXT_SMALL}>{"Sent requests"}</p>
+                            {for data.outgoing.iter().map(|entry| html! {
+                                <div key={entry.user_id.to_string()} class="flex items-center justify-between py-2">
+                                    <span class={styles::TEXT_BODY}>{&entry.username}</span>
+                                    <button class={plain_button}
+                                        onclick={act("DELETE", format!("/api/friends/{}", entry.user_id), None)}>{"Cancel"}</button>
+                                </div>
+                            })}
+                        </div>
+                    }
+
+                    if !data.blocked.is_empty() {
+                        <div>
+                            <p class={styles::TEXT_SMALL}>{"Blocked"}</p>
+                            {for data.blocked.iter().map(|entry| html! {
+                                <div key={entry.user_id.to_string()} class="flex items-center justify-between py-2">
+                                    <span class={styles::TEXT_BODY}>{&entry.username}</span>
+                                    <button class={plain_button}
+                                        onclick={act("DELETE", format!("/api/friends/blocks/{}", entry.user_id), None)}>{"Unblock"}</button>
+                                </div>
+                            })}
+                        </div>
+                    }
+
+                    if !activity.is_empty() {
+                        <div>
+                            <p class={styles::TEXT_SMALL}>{"Recent activity"}</p>
+                            {for activity.iter().map(|item| html! {
+                                <div key={item.id.to_string()} class="flex items-center justify-between py-2">
+                                    <span class={styles::TEXT_BODY}>
+                                        <span class="font-semibold">{&item.username}</span>{" "}{describe(item)}
+                                    </span>
+                                    <span class={format!("{} {}", styles::TEXT_SMALL, "whitespace-nowrap")}>{&item.created_at}</span>
+                                </div>
+                            })}
+                        </div>
+                    }
+                </div>
+            } else if error.is_none() {
+                <div class="flex justify-center items-center p-6">
+                    <div class="animate-spin rounded-full h-8 w-8 border-t-2 border-b-2 border-blue-500" />
+                </div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,68 @@
+use yew::prelude::*;
+use web_sys::window;
+
+/// Which players a game leaderboard ranks. Maps to the backend's `scope` query parameter.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum LeaderboardScope {
+    #[default]
+    Global,
+    Friends,
+}
+
+impl LeaderboardScope {
+    /// Suffix for a leaderboard URL that already has a query string.
+    pub fn query(&self) -> &'static str {
+        match self {
+            Self::Global => "",
+            Self::Friends => "&scope=friends",
+        }
+    }
+}
+
+fn is_logged_in() -> bool {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .is_some_and(|token| !token.is_empty())
+}
+
+#[derive(Properties, PartialEq)]
+pub struct LeaderboardScopeToggleProps {
+    pub scope: LeaderboardScope,
+    pub on_change: Callback<LeaderboardScope>,
+}
+
+/// Everyone / Friends switch shown above the game leaderboards. Hidden for visitors,
+/// since the friends board needs a signed-in player.
+#[function_component(LeaderboardScopeToggle)]
+pub fn leaderboard_scope_toggle(props: &LeaderboardScopeToggleProps) -> Html {
+    if !is_logged_in() {
+        return html! {};
+    }
+
+    let button = |scope: LeaderboardScope, label: &str| {
+        let on_change = props.on_change.clone();
+        let class = if props.scope == scope {
+            "px-3 py-1 rounded-md text-sm font-medium bg-white dark:bg-gray-800 text-gray-900 dark:text-white shadow"
+        } else {
+            "px-3 py-1 rounded-md text-sm font-medium text-gray-600 dark:text-gray-300 hover:text-gray-900 dark:hover:text-white"
+        };
+        html! {
+            <button type="button" class={class} onclick={Callback::from(move |_| on_change.emit(scope))}>
+                {label.to_string()}
+            </button>
+        }
+    };
+
+    html! {
+        <div class="flex justify-center mb-4">
+            <div class="inline-flex gap-1 p-1 rounded-lg bg-gray-100 dark:bg-gray-700">
+                {button(LeaderboardScope::Global, "Everyone")}
+                {button(LeaderboardScope::Friends, "Friends")}
+            </div>
+        </div>
+    }
+}
@@ -1,7 +1,9 @@
 use yew::prelude::*;
 use gloo_net::http::Request;
 use web_sys::window;
-use gloo_timers::callback::Interval;
+use gloo_timers::callback::Timeout;
+use shared::push_events::PushEvent;
+use crate::hooks::use_push_event;
 use serde::Deserialize;
 use crate::config::get_api_base_url;
 use crate::styles;
@@ -102,22 +104,31 @@ pub fn magic_button() -> Html {
         });
     }
 
-    // Regular cooldown updates
+    // Count the cooldown down locally and only ask the server again once it runs out
     {
         let cooldown = cooldown.clone();
         let check_cooldown = check_cooldown.clone();
-        use_effect_with((), move |_| {
-            let interval = Interval::new(1000, move || {
-                if *cooldown > 0 {
-                    cooldown.set((*cooldown).max(0) - 1);
-                } else {
+        use_effect_with(*cooldown, move |&remaining| {
+            let timeout = (remaining > 0).then(|| Timeout::new(1000, move || {
+                cooldown.set(remaining - 1);
+                if remaining == 1 {
                     check_cooldown();
                 }
-            });
-            || drop(interval)
+            }));
+            move || drop(timeout)
         });
     }
 
+    // Someone else pressed the button, so the recent clicks and total are stale
+    {
+        let check_cooldown = check_cooldown.clone();
+        use_push_event(Callback::from(move |event: PushEvent| {
+            if event == PushEvent::MagicButtonPressed {
+                check_cooldown();
+            }
+        }));
+    }
+
     // Format timestamp function
     let format_timestamp = |timestamp: &str| {
         let parse_and_format = |dt: chrono::DateTime<chrono::FixedOffset>| {
@@ -7,6 +7,14 @@ pub mod magic_button;
 pub mod stats_counter;
 pub mod creature_showcase;
 pub mod user_leaderboard;
+pub mod achievements_panel;
+pub mod recipes_panel;
+pub mod trades_panel;
+pub mod notification_bell;
+pub mod player_link;
+pub mod leaderboard_scope;
+pub mod friends_panel;
+pub mod ladder_leaderboard;
 
 pub use auth::*;
 pub use settings::*;
@@ -15,4 +23,12 @@ pub use gradient_background::GradientBackground;
 pub use membership_required::MembershipRequired;
 pub use magic_button::MagicButton;
 pub use stats_counter::StatsCounter;
-pub use creature_showcase::CreatureShowcase; 
\ No newline at end of file
+pub use creature_showcase::CreatureShowcase;
+pub use achievements_panel::AchievementsPanel;
+pub use recipes_panel::RecipesPanel;
+pub use trades_panel::TradesPanel;
+pub use notification_bell::NotificationBell;
+pub use player_link::PlayerLink;
+pub use leaderboard_scope::{LeaderboardScope, LeaderboardScopeToggle};
+pub use friends_panel::FriendsPanel;
+pub use ladder_leaderboard::LadderLeaderboard; 
\ No newline at end of file
@@ -0,0 +1,188 @@
+use yew::prelude::*;
+use serde::Deserialize;
+use gloo_net::http::Request;
+use uuid::Uuid;
+use web_sys::window;
+use shared::push_events::PushEvent;
+use crate::config::get_api_base_url;
+use crate::hooks::use_push_event;
+use crate::styles;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub kind: String,
+    pub message: String,
+    pub read: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct NotificationsData {
+    notifications: Vec<Notification>,
+    unread_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotificationsResponse {
+    success: bool,
+    data: Option<NotificationsData>,
+    error: Option<String>,
+}
+
+fn get_token() -> String {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .unwrap_or_default()
+}
+
+async fn fetch_notifications() -> Result<NotificationsData, String> {
+    match Request::get(&format!("{}/api/notifications", get_api_base_url()))
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .send()
+        .await
+    {
+        Ok(response) if response.ok() => match response.json::<NotificationsResponse>().await {
+            Ok(NotificationsResponse { success: true, data: Some(data), .. }) => Ok(data),
+            Ok(result) => Err(result.error.unwrap_or_else(|| "Failed to load notifications".to_string())),
+            Err(e) => {
+                log::error!("Failed to parse notifications: {:?}", e);
+                Err("Failed to parse notifications".to_string())
+            }
+        },
+        Ok(response) => Err(format!("Server returned status: {}", response.status())),
+        Err(e) => {
+            log::error!("Failed to fetch notifications: {:?}", e);
+            Err("Failed to fetch notifications".to_string())
+        }
+    }
+}
+
+async fn post_read(path: &str) {
+    let result = Request::post(&format!("{}/api/notifications{}", get_api_base_url(), path))
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .send()
+        .await;
+    if let Err(e) = result {
+        log::error!("Failed to mark notification read: {:?}", e);
+    }
+}
+
+#[function_component(NotificationBell)]
+pub fn notification_bell() -> Html {
+    let notifications = use_state(Vec::<Notification>::new);
+    let unread_count = use_state(|| 0i64);
+    let open = use_state(|| false);
+    let reload = use_state(|| 0u32);
+
+    {
+        let notifications = notifications.clone();
+        let unread_count = unread_count.clone();
+
+        use_effect_with(*reload, move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match fetch_notifications().await {
+                    Ok(data) => {
+                        notifications.set(data.notifications);
+                        unread_count.set(data.unread_count);
+                    },
+                    Err(e) => log::error!("{}", e),
+                }
+            });
+
+            || ()
+        });
+    }
+
+    {
+        let reload = reload.clone();
+        use_push_event(Callback::from(move |event: PushEvent| {
+            if let PushEvent::Notification { .. } = event {
+                reload.set(*reload + 1);
+            }
+        }));
+    }
+
+    let toggle = {
+        let open = open.clone();
+        Callback::from(move |_: MouseEvent| open.set(!*open))
+    };
+
+    let mark_all_read = {
+        let reload = reload.clone();
+        Callback::from(move |_: MouseEvent| {
+            let reload = reload.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                post_read("/read-all").await;
+                reload.set(*reload + 1);
+            });
+        })
+    };
+
+    let mark_read = {
+        let reload = reload.clone();
+        move |id: Uuid| {
+            let reload = reload.clone();
+            Callback::from(move |_: MouseEvent| {
+                let reload = reload.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    post_read(&format!("/{}/read", id)).await;
+                    reload.set(*reload + 1);
+                });
+            })
+        }
+    };
+
+    html! {
+        <div class="relative">
+            <button onclick={toggle} class={classes!(styles::NAV_LINK, "flex", "items-center")} aria-label="Notifications">
+                <svg xmlns="http://www.w3.org/2000/svg" class="w-5 h-5" fill="none" viewBox="0 0 24 24" stroke="currentColor" stroke-width="2">
+                    <path stroke-linecap="round" stroke-linejoin="round" d="M15 17h5l-1.405-1.405A2.032 2.032 0 0118 14.158V11a6.002 6.002 0 00-4-5.659V5a2 2 0 10-4 0v.341C7.67 6.165 6 8.388 6 11v3.159c0 .538-.214 1.055-.595 1.436L4 17h5m6 0v1a3 3 0 11-6 0v-1m6 0H9" />
+                </svg>
+                if *unread_count > 0 {
+                    <span class="absolute top-0 right-0 min-w-[18px] h-[18px] px-1 rounded-full bg-red-500 text-white text-xs leading-[18px] text-center">
+                        {if *unread_count > 99 { "99+".to_string() } else { unread_count.to_string() }}
+                    </span>
+                }
+            </button>
+            if *open {
+                <div class={classes!(styles::DROPDOWN, "w-80", "top-full", "mt-2", "z-50")}>
+                    <div class="flex items-center justify-between px-4 py-2">
+                        <span class="text-sm font-medium text-gray-900 dark:text-white">{"Notifications"}</span>
+                        if *unread_count > 0 {
+                            <button onclick={mark_all_read} class="text-xs text-blue-600 dark:text-blue-400 hover:underline">
+                                {"Mark all read"}
+                            </button>
+                        }
+                    </div>
+                    <div class="max-h-96 overflow-y-auto divide-y divide-gray-100 dark:divide-gray-700">
+                        if notifications.is_empty() {
+                            <p class="px-4 py-6 text-sm text-center text-gray-500 dark:text-gray-400">{"No notifications yet"}</p>
+                        } else {
+                            {for notifications.iter().map(|notification| html! {
+                                <div key={notification.id.to_string()} class={classes!(
+                                    "flex", "items-start", "justify-between", "gap-2", "px-4", "py-3",
+                                    (!notification.read).then_some("bg-blue-50 dark:bg-blue-900/20")
+                                )}>
+                                    <div>
+                                        <p class="text-sm text-gray-700 dark:text-gray-300">{&notification.message}</p>
+                                        <p class="text-xs text-gray-500 dark:text-gray-400">{&notification.created_at}</p>
+                                    </div>
+                                    if !notification.read {
+                                        <button onclick={mark_read(notification.id)} class="text-xs text-blue-600 dark:text-blue-400 hover:underline whitespace-nowrap">
+                                            {"Mark read"}
+                                        </button>
+                                    }
+                                </div>
+                            })}
+                        }
+                    </div>
+                </div>
+            }
+        </div>
+    }
+}
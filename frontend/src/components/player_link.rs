@@ -0,0 +1,25 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use crate::Route;
+
+/// Name shown by the `public_users` view for players who chose to stay anonymous.
+const ANONYMOUS_NAME: &str = "Anonymous";
+
+#[derive(Properties, PartialEq)]
+pub struct PlayerLinkProps {
+    pub username: String,
+}
+
+/// A username that links to the player's public profile, or plain text for anonymous players.
+#[function_component(PlayerLink)]
+pub fn player_link(props: &PlayerLinkProps) -> Html {
+    if props.username == ANONYMOUS_NAME {
+        return html! { <span class="italic">{&props.username}</span> };
+    }
+
+    html! {
+        <Link<Route> to={Route::PublicProfile { username: props.username.clone() }} classes="hover:text-blue-600 dark:hover:text-blue-400 hover:underline">
+            {&props.username}
+        </Link<Route>>
+    }
+}
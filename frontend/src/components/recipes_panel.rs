@@ -0,0 +1,323 @@
+use yew::prelude::*;
+use serde::Deserialize;
+use gloo_net::http::Request;
+use uuid::Uuid;
+use web_sys::window;
+use crate::config::get_api_base_url;
+use crate::models::{Creature, Egg};
+use crate::styles;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+pub struct Ingredients {
+    #[serde(default)]
+    pub pax: i32,
+    #[serde(default)]
+    pub scrolls: i32,
+    #[serde(default)]
+    pub eggs: i32,
+    #[serde(default)]
+    pub creatures: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Recipe {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub ingredients: Ingredients,
+    pub required_rank: String,
+    pub rank_met: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CraftedData {
+    new_balance: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct CraftResponse {
+    success: bool,
+    data: Option<CraftedData>,
+    error: Option<String>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RecipesPanelProps {
+    pub eggs: Vec<Egg>,
+    pub creatures: Vec<Creature>,
+    pub on_crafted: Callback<()>,
+}
+
+fn get_token() -> String {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .unwrap_or_default()
+}
+
+fn format_ingredients(ingredients: &Ingredients) -> String {
+    let mut parts = Vec::new();
+    if ingredients.pax > 0 {
+        parts.push(format!("{} pax", ingredients.pax));
+    }
+    if ingredients.scrolls > 0 {
+        parts.push(format!("{} Summoning Scroll{}", ingredients.scrolls, if ingredients.scrolls == 1 { "" } else { "s" }));
+    }
+    if ingredients.eggs > 0 {
+        parts.push(format!("{} egg{}", ingredients.eggs, if ingredients.eggs == 1 { "" } else { "s" }));
+    }
+    if ingredients.creatures > 0 {
+        parts.push(format!("{} creature{}", ingredients.creatures, if ingredients.creatures == 1 { "" } else { "s" }));
+    }
+    parts.join(" · ")
+}
+
+fn toggle(ids: &UseStateHandle<Vec<Uuid>>, id: Uuid, limit: i32) {
+    let mut current = (**ids).clone();
+    if let Some(pos) = current.iter().position(|x| *x == id) {
+        current.remove(pos);
+    } else if (current.len() as i32) < limit {
+        current.push(id);
+    }
+    ids.set(current);
+}
+
+#[function_component(RecipesPanel)]
+pub fn recipes_panel(props: &RecipesPanelProps) -> Html {
+    let recipes = use_state(Vec::<Recipe>::new);
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+    let message = use_state(|| None::<String>);
+    let crafting = use_state(|| false);
+    let selected_recipe = use_state(|| None::<Uuid>);
+    let selected_eggs = use_state(Vec::<Uuid>::new);
+    let selected_creatures = use_state(Vec::<Uuid>::new);
+
+    {
+        let recipes = recipes.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match Request::get(&format!("{}/api/recipes", get_api_base_url()))
+                    .header("Authorization", &format!("Bearer {}", get_token()))
+                    .send()
+                    .await
+                {
+                    Ok(response) if response.ok() => {
+                        match response.json::<Vec<Recipe>>().await {
+                            Ok(data) => {
+                                recipes.set(data);
+                                error.set(None);
+                            },
+                            Err(e) => {
+                                log::error!("Failed to parse recipes: {:?}", e);
+                                error.set(Some("Failed to parse recipes".to_string()));
+                            }
+                        }
+                    },
+                    Ok(response) => {
+                        error.set(Some(format!("Server returned status: {}", response.status())));
+                    },
+                    Err(e) => {
+                        log::error!("Failed to fetch recipes: {:?}", e);
+                        error.set(Some("Failed to fetch recipes".to_string()));
+                    }
+                }
+
+                loading.set(false);
+            });
+
+            || ()
+        });
+    }
+
+    let handle_select = {
+        let selected_recipe = selected_recipe.clone();
+        let selected_eggs = selected_eggs.clone();
+        let selected_creatures = selected_creatures.clone();
+        let message = message.clone();
+        Callback::from(move |recipe_id: Uuid| {
+            if *selected_recipe == Some(recipe_id) {
+                selected_recipe.set(None);
+            } else {
+                selected_recipe.set(Some(recipe_id));
+            }
+            selected_eggs.set(Vec::new());
+            selected_creatures.set(Vec::new());
+            message.set(None);
+        })
+    };
+
+    let handle_craft = {
+        let crafting = crafting.clone();
+        let message = message.clone();
+        let selected_recipe = selected_recipe.clone();
+        let selected_eggs = selected_eggs.clone();
+        let selected_creatures = selected_creatures.clone();
+        let on_crafted = props.on_crafted.clone();
+        Callback::from(move |recipe: Recipe| {
+            let crafting = crafting.clone();
+            let message = message.clone();
+            let selected_recipe = selected_recipe.clone();
+            let selected_eggs = selected_eggs.clone();
+            let selected_creatures = selected_creatures.clone();
+            let on_crafted = on_crafted.clone();
+            crafting.set(true);
+            message.set(None);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                let body = serde_json::json!({
+                    "egg_ids": *selected_eggs,
+                    "creature_ids": *selected_creatures,
+                });
+                match Request::post(&format!("{}/api/recipes/{}/craft", get_api_base_url(), recipe.id))
+                    .header("Authorization", &format!("Bearer {}", get_token()))
+                    .json(&body)
+                    .unwrap()
+                    .send()
+                    .await
+                {
+                    Ok(response) => match response.json::<CraftResponse>().await {
+                        Ok(CraftResponse { success: true, data: Some(data), .. }) => {
+                            if let Some(window) = window() {
+                                if let Some(storage) = window.local_storage().ok().flatten() {
+                                    let _ = storage.set_item("currency", &data.new_balance.to_string());
+                                }
+                                let event_init = web_sys::CustomEventInit::new();
+                                event_init.set_detail(&wasm_bindgen::JsValue::from_f64(data.new_balance as f64));
+                                if let Ok(event) = web_sys::CustomEvent::new_with_event_init_dict("currencyUpdate", &event_init) {
+                                    let _ = window.dispatch_event(&event);
+                                }
+                            }
+                            message.set(Some(format!("Crafted {}!", recipe.name)));
+                            selected_recipe.set(None);
+                            selected_eggs.set(Vec::new());
+                            selected_creatures.set(Vec::new());
+                            on_crafted.emit(());
+                        },
+                        Ok(result) => {
+                            message.set(Some(result.error.unwrap_or_else(|| "Crafting failed".to_string())));
+                        },
+                        Err(_) => message.set(Some("Crafting failed".to_string())),
+                    },
+                    Err(_) => message.set(Some("Network error occurred.".to_string())),
+                }
+                crafting.set(false);
+            });
+        })
+    };
+
+    html! {
+        <div class={format!("{} {}", styles::HERO_FEATURES, "p-8")}>
+            <div class="flex items-center space-x-4 mb-6">
+                <div class={styles::ICON_WRAPPER_PURPLE}>
+                    <span class="text-lg">{"📜"}</span>
+                </div>
+                <span class={styles::TEXT_H3}>{"Scroll Crafting"}</span>
+            </div>
+
+            if let Some(msg) = &*message {
+                <p class={format!("{} {}", styles::TEXT_SMALL, "mb-4")}>{msg}</p>
+            }
+
+            if *loading {
+                <div class="flex justify-center items-center p-6">
+                    <div class="animate-spin rounded-full h-8 w-8 border-t-2 border-b-2 border-purple-500" />
+                </div>
+            } else if let Some(err) = &*error {
+                <div class={styles::ALERT_ERROR}>{err}</div>
+            } else {
+                <div class="grid grid-cols-1 md:grid-cols-3 gap-4">
+                    {for recipes.iter().map(|recipe| {
+                        let needs_sacrifice = recipe.ingredients.eggs > 0 || recipe.ingredients.creatures > 0;
+                        let is_selected = *selected_recipe == Some(recipe.id);
+                        let ready = !needs_sacrifice || (is_selected
+                            && selected_eggs.len() as i32 == recipe.ingredients.eggs
+                            && selected_creatures.len() as i32 == recipe.ingredients.creatures);
+                        let on_select = {
+                            let handle_select = handle_select.clone();
+                            let id = recipe.id;
+                            Callback::from(move |_| handle_select.emit(id))
+                        };
+                        let on_craft = {
+                            let handle_craft = handle_craft.clone();
+                            let recipe = recipe.clone();
+                            Callback::from(move |_| handle_craft.emit(recipe.clone()))
+                        };
+
+                        html! {
+                            <div class="p-4 rounded-xl border bg-gray-100/50 dark:bg-gray-800/30 border-gray-200 dark:border-gray-700 text-left space-y-3">
+                                <div>
+                                    <p class={styles::TEXT_BODY}>{&recipe.name}</p>
+                                    <p class={styles::TEXT_SMALL}>{&recipe.description}</p>
+                                </div>
+                                <p class={styles::TEXT_SMALL}>{format_ingredients(&recipe.ingredients)}</p>
+                                if !recipe.rank_met {
+                                    <p class={styles::TEXT_SMALL}>{format!("🔒 Requires {} rank", recipe.required_rank)}</p>
+                                } else {
+                                    if needs_sacrifice {
+                                        <button onclick={on_select} class={format!("{} {}", styles::TEXT_SMALL, "underline")}>
+                                            {if is_selected { "Hide sacrifices" } else { "Choose sacrifices" }}
+                                        </button>
+                                    }
+                                    if is_selected && recipe.ingredients.eggs > 0 {
+                                        <div class="flex flex-wrap gap-2">
+                                            {for props.eggs.iter().map(|egg| {
+                                                let chosen = selected_eggs.contains(&egg.id);
+                                                let limit = recipe.ingredients.eggs;
+                                                let selected_eggs = selected_eggs.clone();
+                                                let id = egg.id;
+                                                html! {
+                                                    <button
+                                                        onclick={Callback::from(move |_| toggle(&selected_eggs, id, limit))}
+                                                        class={format!("px-2 py-1 rounded-lg text-xs {}",
+                                                            if chosen { "bg-purple-500 text-white" } else { "bg-gray-200 dark:bg-gray-700" })}
+                                                    >
+                                                        {egg.display_name.clone().unwrap_or_else(|| "Egg".to_string())}
+                                                    </button>
+                                                }
+                                            })}
+                                        </div>
+                                    }
+                                    if is_selected && recipe.ingredients.creatures > 0 {
+                                        <div class="flex flex-wrap gap-2">
+                                            {for props.creatures.iter().filter(|c| c.status == "available" && !c.in_chaos_realm).map(|creature| {
+                                                let chosen = selected_creatures.contains(&creature.id);
+                                                let limit = recipe.ingredients.creatures;
+                                                let selected_creatures = selected_creatures.clone();
+                                                let id = creature.id;
+                                                html! {
+                                                    <button
+                                                        onclick={Callback::from(move |_| toggle(&selected_creatures, id, limit))}
+                                                        class={format!("px-2 py-1 rounded-lg text-xs {}",
+                                                            if chosen { "bg-purple-500 text-white" } else { "bg-gray-200 dark:bg-gray-700" })}
+                                                    >
+                                                        {format!("{} ({})",
+                                                            creature.display_name.clone().unwrap_or_else(|| "Creature".to_string()),
+                                                            creature.rarity.clone().unwrap_or_default())}
+                                                    </button>
+                                                }
+                                            })}
+                                        </div>
+                                    }
+                                    <button
+                                        onclick={on_craft}
+                                        disabled={*crafting || !ready}
+                                        class={format!("{} {}", styles::BUTTON_PRIMARY, "w-full disabled:opacity-50 disabled:cursor-not-allowed")}
+                                    >
+                                        {if *crafting { "Crafting..." } else { "Craft" }}
+                                    </button>
+                                }
+                            </div>
+                        }
+                    })}
+                </div>
+            }
+        </div>
+    }
+}
@@ -1,5 +1,5 @@
 use gloo_net::http::Request;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen_futures::spawn_local;
 use web_sys::{window, console};
 use yew::prelude::*;
@@ -10,6 +10,197 @@ use crate::config::get_api_base_url;
 #[derive(Serialize)]
 struct DeleteAccountRequest {
     email: String,
+    two_factor_code: Option<String>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct TwoFactorStatus {
+    enabled: bool,
+    recovery_codes_remaining: i64,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct TwoFactorEnrollment {
+    secret: String,
+    qr_code: Option<String>,
+}
+
+fn get_token() -> String {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .unwrap_or_default()
+}
+
+/// Calls a `/api/users/me/two-factor` endpoint; errors carry the server's message.
+async fn two_factor_request(path: &str, body: Option<serde_json::Value>) -> Result<serde_json::Value, String> {
+    let url = format!("{}/api/users/me/two-factor{}", get_api_base_url(), path);
+    let auth = format!("Bearer {}", get_token());
+    let request = match body {
+        Some(body) => Request::post(&url).header("Authorization", &auth).json(&body),
+        None if path.is_empty() => Request::get(&url).header("Authorization", &auth).build(),
+        None => Request::post(&url).header("Authorization", &auth).build(),
+    }
+    .map_err(|_| "Failed to build request".to_string())?;
+
+    match request.send().await {
+        Ok(response) => {
+            let ok = response.ok();
+            let body = response.json::<serde_json::Value>().await
+                .map_err(|_| format!("Server returned status: {}", response.status()))?;
+            if ok {
+                Ok(body)
+            } else {
+                Err(body["error"].as_str().unwrap_or("Request failed").to_string())
+            }
+        }
+        Err(e) => {
+            console::error_2(&"Two-factor request failed:".into(), &format!("{:?}", e).into());
+            Err("Network error occurred.".to_string())
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct TwoFactorPanelProps {
+    on_status: Callback<bool>,
+}
+
+#[function_component(TwoFactorPanel)]
+fn two_factor_panel(props: &TwoFactorPanelProps) -> Html {
+    let status = use_state(|| None::<TwoFactorStatus>);
+    let enrollment = use_state(|| None::<TwoFactorEnrollment>);
+    let recovery_codes = use_state(Vec::<String>::new);
+    let code = use_state(String::new);
+    let error = use_state(String::new);
+    let busy = use_state(|| false);
+    let reload = use_state(|| 0u32);
+
+    {
+        let status = status.clone();
+        let error = error.clone();
+        let on_status = props.on_status.clone();
+        use_effect_with(*reload, move |_| {
+            spawn_local(async move {
+                match two_factor_request("", None).await {
+                    Ok(body) => match serde_json::from_value::<TwoFactorStatus>(body) {
+                        Ok(loaded) => {
+                            on_status.emit(loaded.enabled);
+                            status.set(Some(loaded));
+                        }
+                        Err(_) => error.set("Failed to load two-factor status".to_string()),
+                    },
+                    Err(e) => error.set(e),
+                }
+            });
+            || ()
+        });
+    }
+
+    // Runs one request, then shows any recovery codes it returned and reloads the status
+    let act = {
+        let enrollment = enrollment.clone();
+        let recovery_codes = recovery_codes.clone();
+        let code = code.clone();
+        let error = error.clone();
+        let busy = busy.clone();
+        let reload = reload.clone();
+        move |path: &'static str, send_code: bool| {
+            let enrollment = enrollment.clone();
+            let recovery_codes = recovery_codes.clone();
+            let code = code.clone();
+            let error = error.clone();
+            let busy = busy.clone();
+            let reload = reload.clone();
+            Callback::from(move |_: MouseEvent| {
+                let enrollment = enrollment.clone();
+                let recovery_codes = recovery_codes.clone();
+                let code = code.clone();
+                let error = error.clone();
+                let busy = busy.clone();
+                let reload = reload.clone();
+                let body = send_code.then(|| serde_json::json!({ "code": code.trim() }));
+                busy.set(true);
+                spawn_local(async move {
+                    match two_factor_request(path, body).await {
+                        Ok(response) => {
+                            error.set(String::new());
+                            code.set(String::new());
+                            if path == "/enroll" {
+                                enrollment.set(serde_json::from_value(response).ok());
+                            } else {
+                                enrollment.set(None);
+                                let codes = serde_json::from_value::<Vec<String>>(response["recovery_codes"].clone())
+                                    .unwrap_or_default();
+                                recovery_codes.set(codes);
+                                reload.set(*reload + 1);
+                            }
+                        }
+                        Err(e) => error.set(e),
+                    }
+                    busy.set(false);
+                });
+            })
+        }
+    };
+
+    let on_code_input = {
+        let code = code.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            code.set(input.value());
+        })
+    };
+    let code_input = html! {
+        <input type="text" class={styles::INPUT} placeholder="Code from your app" autocomplete="one-time-code"
+            value={(*code).clone()} oninput={on_code_input} disabled={*busy} />
+    };
+    let no_code = code.trim().is_empty() || *busy;
+
+    html! {
+        <div class="space-y-4">
+            <h3 class={styles::TEXT_H3}>{"Two-Factor Authentication"}</h3>
+            if !recovery_codes.is_empty() {
+                <div class={styles::CARD_SUCCESS}>
+                    <p>{"Save these recovery codes somewhere safe. Each one signs you in once if you lose your phone, and they won't be shown again."}</p>
+                    <div class="mt-2 grid grid-cols-2 gap-1 font-mono text-sm">
+                        {for recovery_codes.iter().map(|code| html! { <span>{code}</span> })}
+                    </div>
+                </div>
+            }
+            if let Some(setup) = &*enrollment {
+                <p class={styles::TEXT_SECONDARY}>{"Scan this QR code with your authenticator app, then enter the code it shows."}</p>
+                if let Some(qr_code) = &setup.qr_code {
+                    <img src={qr_code.clone()} alt="Two-factor QR code" class="w-48 h-48 bg-white p-2 rounded-lg" />
+                }
+                <p class={styles::TEXT_SMALL}>{"Can't scan it? Enter this key instead: "}<span class="font-mono">{&setup.secret}</span></p>
+                {code_input}
+                <button class={styles::BUTTON_PRIMARY} disabled={no_code} onclick={act("/confirm", true)}>{"Turn on"}</button>
+            } else if let Some(current) = &*status {
+                if current.enabled {
+                    <p class={styles::TEXT_SECONDARY}>
+                        {format!("Two-factor authentication is on. {} recovery codes left.", current.recovery_codes_remaining)}
+                    </p>
+                    {code_input}
+                    <div class="flex space-x-4">
+                        <button class={styles::BUTTON_SECONDARY} disabled={no_code} onclick={act("/recovery-codes", true)}>{"New recovery codes"}</button>
+                        <button class={styles::BUTTON_DANGER} disabled={no_code} onclick={act("/disable", true)}>{"Turn off"}</button>
+                    </div>
+                } else {
+                    <p class={styles::TEXT_SECONDARY}>{"Protect your creatures and pax with a code from an authenticator app each time you sign in."}</p>
+                    <button class={styles::BUTTON_PRIMARY} disabled={*busy} onclick={act("/enroll", false)}>{"Set up"}</button>
+                }
+            }
+            if !error.is_empty() {
+                <div class={styles::CARD_ERROR}>
+                    <p>{&*error}</p>
+                </div>
+            }
+        </div>
+    }
 }
 
 #[derive(Properties, PartialEq)]
@@ -26,14 +217,22 @@ pub fn account_management(props: &Props) -> Html {
     let success = use_state(String::new);
     let loading = use_state(|| false);
     let email_sent = use_state(|| false);
+    let two_factor_enabled = use_state(|| false);
+    let two_factor_code = use_state(String::new);
     let _navigator = use_navigator().unwrap();
 
+    let on_two_factor_status = {
+        let two_factor_enabled = two_factor_enabled.clone();
+        Callback::from(move |enabled: bool| two_factor_enabled.set(enabled))
+    };
+
     let handle_submit = {
         let email = email.clone();
         let error = error.clone();
         let success = success.clone();
         let loading = loading.clone();
         let email_sent = email_sent.clone();
+        let two_factor_code = two_factor_code.clone();
         let on_error = props.on_error.clone();
 
         Callback::from(move |e: SubmitEvent| {
@@ -43,6 +242,7 @@ pub fn account_management(props: &Props) -> Html {
             success.set(String::new());
 
             let email_value = (*email).clone();
+            let code_value = Some(two_factor_code.trim().to_string()).filter(|code| !code.is_empty());
             let success_state = success.clone();
             let loading_state = loading.clone();
             let error_state = error.clone();
@@ -76,6 +276,7 @@ pub fn account_management(props: &Props) -> Html {
 
                 let request = DeleteAccountRequest {
                     email: email_value,
+                    two_factor_code: code_value,
                 };
 
                 match Request::post(&format!("{}/api/users/me/delete-request", get_api_base_url()))
@@ -123,6 +324,8 @@ pub fn account_management(props: &Props) -> Html {
     };
 
     html! {
+        <div class="space-y-8">
+        <TwoFactorPanel on_status={on_two_factor_status} />
         <div class="space-y-4">
             <h3 class={styles::TEXT_H3}>{"Delete Account"}</h3>
             if *show_confirm {
@@ -147,6 +350,21 @@ pub fn account_management(props: &Props) -> Html {
                                 }}
                             />
                         </div>
+                        if *two_factor_enabled {
+                            <div>
+                                <label class={styles::TEXT_LABEL}>{"Two-Factor Code"}</label>
+                                <input
+                                    type="text"
+                                    required=true
+                                    autocomplete="one-time-code"
+                                    class={styles::INPUT}
+                                    onchange={let two_factor_code = two_factor_code.clone(); move |e: Event| {
+                                        let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                                        two_factor_code.set(input.value());
+                                    }}
+                                />
+                            </div>
+                        }
                         <div class="flex space-x-4">
                             if *loading {
                                 <button type="button" class={styles::BUTTON_DANGER} disabled=true>
@@ -193,5 +411,6 @@ pub fn account_management(props: &Props) -> Html {
                 </div>
             }
         </div>
+        </div>
     }
-}
\ No newline at end of file
+}
@@ -2,8 +2,12 @@ mod account_management;
 mod membership_code;
 mod temporary_membership;
 pub mod patreon_link;
+mod preferences_panel;
+mod sessions_panel;
 
 pub use account_management::AccountManagement;
 pub use membership_code::MembershipCode;
 pub use temporary_membership::TemporaryMembership;
-pub use patreon_link::PatreonLink;
\ No newline at end of file
+pub use patreon_link::PatreonLink;
+pub use preferences_panel::PreferencesPanel;
+pub use sessions_panel::SessionsPanel;
\ No newline at end of file
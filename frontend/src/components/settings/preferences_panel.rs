@@ -0,0 +1,197 @@
+use yew::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use shared::preferences::{EmailDigest, InventorySort, UserPreferences};
+use crate::hooks::use_preferences::{fetch_preferences, save_preferences};
+use crate::styles;
+
+const SORT_OPTIONS: &[(InventorySort, &str, &str)] = &[
+    (InventorySort::Default, "default", "Default"),
+    (InventorySort::Rarity, "rarity", "Rarity"),
+    (InventorySort::Energy, "energy", "Energy"),
+    (InventorySort::Essence, "essence", "Essence"),
+];
+
+const DIGEST_OPTIONS: &[(EmailDigest, &str, &str)] = &[
+    (EmailDigest::Off, "off", "Off"),
+    (EmailDigest::Hourly, "hourly", "Hourly"),
+    (EmailDigest::Daily, "daily", "Daily"),
+];
+
+fn select_value(e: &Event) -> Option<String> {
+    e.target_dyn_into::<web_sys::HtmlSelectElement>().map(|select| select.value())
+}
+
+fn checkbox_value(e: &Event) -> Option<bool> {
+    e.target_dyn_into::<web_sys::HtmlInputElement>().map(|input| input.checked())
+}
+
+#[function_component(PreferencesPanel)]
+pub fn preferences_panel() -> Html {
+    let preferences = use_state(|| None::<UserPreferences>);
+    let error = use_state(String::new);
+    let success = use_state(String::new);
+    let saving = use_state(|| false);
+
+    {
+        let preferences = preferences.clone();
+        let error = error.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match fetch_preferences().await {
+                    Ok(data) => preferences.set(Some(data)),
+                    Err(e) => error.set(e),
+                }
+            });
+            || ()
+        });
+    }
+
+    // Every control edits a copy of the loaded preferences and saves the whole set
+    let update = {
+        let preferences = preferences.clone();
+        let error = error.clone();
+        let success = success.clone();
+        let saving = saving.clone();
+        Callback::from(move |edit: Box<dyn FnOnce(&mut UserPreferences)>| {
+            let Some(mut updated) = (*preferences).clone() else { return };
+            edit(&mut updated);
+
+            let preferences = preferences.clone();
+            let error = error.clone();
+            let success = success.clone();
+            let saving = saving.clone();
+            saving.set(true);
+            error.set(String::new());
+            success.set(String::new());
+            spawn_local(async move {
+                match save_preferences(&updated).await {
+                    Ok(saved) => {
+                        preferences.set(Some(saved));
+                        success.set("Preferences saved".to_string());
+                    },
+                    Err(e) => error.set(e),
+                }
+                saving.set(false);
+            });
+        })
+    };
+
+    let on_sort_change = {
+        let update = update.clone();
+        Callback::from(move |e: Event| {
+            if let Some(value) = select_value(&e) {
+                if let Some((sort, _, _)) = SORT_OPTIONS.iter().find(|(_, key, _)| *key == value) {
+                    let sort = *sort;
+                    update.emit(Box::new(move |p| p.ui_preferences.inventory_sort = sort));
+                }
+            }
+        })
+    };
+
+    let on_digest_change = {
+        let update = update.clone();
+        Callback::from(move |e: Event| {
+            if let Some(value) = select_value(&e) {
+                if let Some((digest, _, _)) = DIGEST_OPTIONS.iter().find(|(_, key, _)| *key == value) {
+                    let digest = *digest;
+                    update.emit(Box::new(move |p| p.notification_settings.email_digest = digest));
+                }
+            }
+        })
+    };
+
+    let on_toggle = |apply: fn(&mut UserPreferences, bool)| {
+        let update = update.clone();
+        Callback::from(move |e: Event| {
+            if let Some(checked) = checkbox_value(&e) {
+                update.emit(Box::new(move |p| apply(p, checked)));
+            }
+        })
+    };
+
+    let toggle_row = |label: &str, description: &str, checked: bool, onchange: Callback<Event>| html! {
+        <label class="flex items-start justify-between gap-4 py-3">
+            <div>
+                <span class={styles::TEXT_BODY}>{label.to_string()}</span>
+                <p class="text-sm text-gray-500 dark:text-gray-400">{description.to_string()}</p>
+            </div>
+            <input type="checkbox" class="mt-1 h-4 w-4 rounded" checked={checked} disabled={*saving} onchange={onchange} />
+        </label>
+    };
+
+    html! {
+        <div class="mt-8">
+            <div class="mb-6">
+                <h2 class={styles::TEXT_H2}>{"Preferences"}</h2>
+                <p class={styles::TEXT_BODY}>{"Choose how the site looks and what others can see about you."}</p>
+            </div>
+
+            if !error.is_empty() {
+                <div class={classes!(styles::CARD_ERROR, "mb-4")}>{&*error}</div>
+            }
+            if !success.is_empty() {
+                <div class={classes!(styles::CARD_SUCCESS, "mb-4")}>{&*success}</div>
+            }
+
+            if let Some(prefs) = &*preferences {
+                <div class="p-4 rounded-lg bg-gray-50 dark:bg-gray-800 border border-gray-200 dark:border-gray-700 divide-y divide-gray-200 dark:divide-gray-700">
+                    <label class="flex items-center justify-between gap-4 py-3">
+                        <span class={styles::TEXT_BODY}>{"Default inventory sort"}</span>
+                        <select class="rounded-lg bg-white dark:bg-gray-900 py-1 px-2 text-sm text-gray-900 dark:text-white ring-1 ring-gray-300 dark:ring-gray-700"
+                            disabled={*saving} onchange={on_sort_change}>
+                            {for SORT_OPTIONS.iter().map(|(sort, key, label)| html! {
+                                <option value={*key} selected={prefs.ui_preferences.inventory_sort == *sort}>{*label}</option>
+                            })}
+                        </select>
+                    </label>
+                    {toggle_row(
+                        "Reduce motion",
+                        "Skip the wheel spin and showcase scrolling animations.",
+                        prefs.ui_preferences.reduced_motion,
+                        on_toggle(|p, checked| p.ui_preferences.reduced_motion = checked),
+                    )}
+                    {toggle_row(
+                        "Show me on leaderboards",
+                        "Your username and scores appear on the public leaderboards.",
+                        prefs.privacy_settings.show_on_leaderboards,
+                        on_toggle(|p, checked| p.privacy_settings.show_on_leaderboards = checked),
+                    )}
+                    {toggle_row(
+                        "Feature my creatures in the showcase",
+                        "Your rarest creatures can appear in the home page showcase.",
+                        prefs.privacy_settings.show_in_showcase,
+                        on_toggle(|p, checked| p.privacy_settings.show_in_showcase = checked),
+                    )}
+                    {toggle_row(
+                        "Public profile",
+                        "Other players can open your profile page from the leaderboards.",
+                        prefs.privacy_settings.public_profile,
+                        on_toggle(|p, checked| p.privacy_settings.public_profile = checked),
+                    )}
+                    {toggle_row(
+                        "Appear as anonymous",
+                        "Show \"Anonymous\" instead of your username on leaderboards, the showcase and the magic button.",
+                        prefs.privacy_settings.appear_anonymous,
+                        on_toggle(|p, checked| p.privacy_settings.appear_anonymous = checked),
+                    )}
+                    <label class="flex items-center justify-between gap-4 py-3">
+                        <div>
+                            <span class={styles::TEXT_BODY}>{"Email digest"}</span>
+                            <p class="text-sm text-gray-500 dark:text-gray-400">{"Sales, chaos realm rewards, recharged energy and streak reminders."}</p>
+                        </div>
+                        <select class="rounded-lg bg-white dark:bg-gray-900 py-1 px-2 text-sm text-gray-900 dark:text-white ring-1 ring-gray-300 dark:ring-gray-700"
+                            disabled={*saving} onchange={on_digest_change}>
+                            {for DIGEST_OPTIONS.iter().map(|(digest, key, label)| html! {
+                                <option value={*key} selected={prefs.notification_settings.email_digest == *digest}>{*label}</option>
+                            })}
+                        </select>
+                    </label>
+                </div>
+            } else if error.is_empty() {
+                <div class="flex justify-center py-8">
+                    <div class="animate-spin rounded-full h-8 w-8 border-t-2 border-b-2 border-blue-500" />
+                </div>
+            }
+        </div>
+    }
+}
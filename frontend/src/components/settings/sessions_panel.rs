@@ -0,0 +1,184 @@
+use gloo_net::http::Request;
+use serde::Deserialize;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use yew::prelude::*;
+use crate::config::get_api_base_url;
+use crate::styles;
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct Session {
+    id: String,
+    device: String,
+    ip_address: Option<String>,
+    signed_in_at: String,
+    last_used_at: String,
+    current: bool,
+}
+
+#[derive(Deserialize)]
+struct SessionsResponse {
+    sessions: Vec<Session>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SessionsPanelProps {
+    /// Called once this device's own session has been signed out
+    pub on_signed_out: Callback<()>,
+}
+
+fn get_token() -> String {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .unwrap_or_default()
+}
+
+async fn fetch_sessions() -> Result<Vec<Session>, String> {
+    let response = Request::get(&format!("{}/api/users/me/sessions", get_api_base_url()))
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .send()
+        .await
+        .map_err(|_| "Network error occurred.".to_string())?;
+
+    if !response.ok() {
+        return Err(format!("Failed to load sessions ({})", response.status()));
+    }
+    response
+        .json::<SessionsResponse>()
+        .await
+        .map(|body| body.sessions)
+        .map_err(|_| "Failed to read sessions".to_string())
+}
+
+/// Signs out one session, or every session when `session_id` is `None`.
+async fn sign_out(session_id: Option<&str>) -> Result<(), String> {
+    let url = match session_id {
+        Some(id) => format!("{}/api/users/me/sessions/{}", get_api_base_url(), id),
+        None => format!("{}/api/users/me/sessions", get_api_base_url()),
+    };
+    let response = Request::delete(&url)
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .send()
+        .await
+        .map_err(|_| "Network error occurred.".to_string())?;
+
+    if response.ok() {
+        Ok(())
+    } else {
+        Err(format!("Failed to sign out ({})", response.status()))
+    }
+}
+
+#[function_component(SessionsPanel)]
+pub fn sessions_panel(props: &SessionsPanelProps) -> Html {
+    let sessions = use_state(|| None::<Vec<Session>>);
+    let error = use_state(String::new);
+    let busy = use_state(|| false);
+    let reload = use_state(|| 0u32);
+
+    {
+        let sessions = sessions.clone();
+        let error = error.clone();
+        use_effect_with(*reload, move |_| {
+            spawn_local(async move {
+                match fetch_sessions().await {
+                    Ok(loaded) => sessions.set(Some(loaded)),
+                    Err(e) => error.set(e),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_sign_out = {
+        let error = error.clone();
+        let busy = busy.clone();
+        let reload = reload.clone();
+        let on_signed_out = props.on_signed_out.clone();
+        Callback::from(move |(session_id, current): (Option<String>, bool)| {
+            let error = error.clone();
+            let busy = busy.clone();
+            let reload = reload.clone();
+            let on_signed_out = on_signed_out.clone();
+            busy.set(true);
+            spawn_local(async move {
+                match sign_out(session_id.as_deref()).await {
+                    // Signing out everywhere includes this device
+                    Ok(()) if current || session_id.is_none() => on_signed_out.emit(()),
+                    Ok(()) => {
+                        error.set(String::new());
+                        reload.set(*reload + 1);
+                    }
+                    Err(e) => error.set(e),
+                }
+                busy.set(false);
+            });
+        })
+    };
+
+    html! {
+        <div class="mt-8">
+            <div class="mb-6">
+                <h2 class={styles::TEXT_H2}>{"Signed-in Devices"}</h2>
+                <p class={styles::TEXT_BODY}>{"Devices signed in to your account. Sign out any you don't recognise."}</p>
+            </div>
+            {
+                match &*sessions {
+                    None if error.is_empty() => html! { <div class={styles::LOADING_SPINNER}></div> },
+                    None => html! {},
+                    Some(list) => html! {
+                        <ul class="space-y-3">
+                            {for list.iter().map(|session| {
+                                let onclick = {
+                                    let on_sign_out = on_sign_out.clone();
+                                    let id = session.id.clone();
+                                    let current = session.current;
+                                    Callback::from(move |_: MouseEvent| on_sign_out.emit((Some(id.clone()), current)))
+                                };
+                                html! {
+                                    <li key={session.id.clone()} class="flex items-center justify-between gap-4">
+                                        <div>
+                                            <p class={styles::TEXT_BODY}>
+                                                {&session.device}
+                                                if session.current {
+                                                    <span class="ml-2 text-xs text-green-600 dark:text-green-400">{"This device"}</span>
+                                                }
+                                            </p>
+                                            <p class={styles::TEXT_SMALL}>
+                                                {format!(
+                                                    "{} · signed in {} · last used {}",
+                                                    session.ip_address.as_deref().unwrap_or("Unknown location"),
+                                                    session.signed_in_at,
+                                                    session.last_used_at
+                                                )}
+                                            </p>
+                                        </div>
+                                        <button class={styles::BUTTON_SECONDARY} disabled={*busy} {onclick}>
+                                            {"Sign out"}
+                                        </button>
+                                    </li>
+                                }
+                            })}
+                        </ul>
+                    },
+                }
+            }
+            <button
+                class={classes!(styles::BUTTON_DANGER, "mt-4")}
+                disabled={*busy}
+                onclick={let on_sign_out = on_sign_out.clone(); move |_: MouseEvent| on_sign_out.emit((None, true))}
+            >
+                {"Sign out everywhere"}
+            </button>
+            if !error.is_empty() {
+                <div class={classes!(styles::CARD_ERROR, "mt-4")}>
+                    <p>{&*error}</p>
+                </div>
+            }
+        </div>
+    }
+}
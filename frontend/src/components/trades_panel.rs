@@ -0,0 +1,486 @@
+use yew::prelude::*;
+use serde::{Deserialize, Serialize};
+use gloo_net::http::Request;
+use uuid::Uuid;
+use web_sys::window;
+use crate::config::get_api_base_url;
+use crate::models::{Creature, Egg};
+use crate::styles;
+
+const MAX_TRADE_ITEMS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TradeItemRef {
+    pub item_id: Uuid,
+    pub item_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TradeItem {
+    pub item_id: Uuid,
+    pub item_type: String,
+    pub display_name: Option<String>,
+    pub rarity: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TradeOffer {
+    pub id: Uuid,
+    pub proposer_id: Uuid,
+    pub proposer_username: String,
+    pub recipient_id: Uuid,
+    pub recipient_username: String,
+    pub offered_pax: i32,
+    pub requested_pax: i32,
+    pub status: String,
+    pub offered_items: Vec<TradeItem>,
+    pub requested_items: Vec<TradeItem>,
+}
+
+#[derive(Debug, Serialize)]
+struct TradeTerms {
+    offered_items: Vec<TradeItemRef>,
+    requested_items: Vec<TradeItemRef>,
+    offered_pax: i32,
+    requested_pax: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct TradeActionData {
+    new_balance: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct TradeActionResponse {
+    success: bool,
+    data: Option<TradeActionData>,
+    error: Option<String>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TradesPanelProps {
+    pub eggs: Vec<Egg>,
+    pub creatures: Vec<Creature>,
+    pub on_traded: Callback<()>,
+}
+
+fn get_token() -> String {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .unwrap_or_default()
+}
+
+fn get_current_user_id() -> Option<Uuid> {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("user_id").ok().flatten())
+        .and_then(|id| Uuid::parse_str(&id).ok())
+}
+
+fn dispatch_currency_update(new_balance: i32) {
+    if let Some(window) = window() {
+        if let Some(storage) = window.local_storage().ok().flatten() {
+            let _ = storage.set_item("currency", &new_balance.to_string());
+        }
+        let event_init = web_sys::CustomEventInit::new();
+        event_init.set_detail(&wasm_bindgen::JsValue::from_f64(new_balance as f64));
+        if let Ok(event) = web_sys::CustomEvent::new_with_event_init_dict("currencyUpdate", &event_init) {
+            let _ = window.dispatch_event(&event);
+        }
+    }
+}
+
+fn to_refs(items: &[TradeItem]) -> Vec<TradeItemRef> {
+    items.iter()
+        .map(|item| TradeItemRef { item_id: item.item_id, item_type: item.item_type.clone() })
+        .collect()
+}
+
+fn describe_side(items: &[TradeItem], pax: i32) -> String {
+    let mut parts: Vec<String> = items.iter()
+        .map(|item| match &item.rarity {
+            Some(rarity) => format!("{} ({})", item.display_name.clone().unwrap_or_else(|| "Creature".to_string()), rarity),
+            None => item.display_name.clone().unwrap_or_else(|| item.item_type.clone()),
+        })
+        .collect();
+    if pax > 0 {
+        parts.push(format!("{} pax", pax));
+    }
+    if parts.is_empty() { "nothing".to_string() } else { parts.join(", ") }
+}
+
+fn input_value(e: &InputEvent) -> Option<String> {
+    e.target_dyn_into::<web_sys::HtmlInputElement>().map(|input| input.value())
+}
+
+async fn fetch_offers() -> Result<Vec<TradeOffer>, String> {
+    match Request::get(&format!("{}/api/trades", get_api_base_url()))
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .send()
+        .await
+    {
+        Ok(response) if response.ok() => response.json::<Vec<TradeOffer>>().await
+            .map_err(|e| {
+                log::error!("Failed to parse trade offers: {:?}", e);
+                "Failed to parse trade offers".to_string()
+            }),
+        Ok(response) => Err(format!("Server returned status: {}", response.status())),
+        Err(e) => {
+            log::error!("Failed to fetch trade offers: {:?}", e);
+            Err("Failed to fetch trade offers".to_string())
+        }
+    }
+}
+
+/// Posts a trade action and returns the server's error message on failure.
+async fn post_trade_action(path: String, body: Option<serde_json::Value>) -> Result<(), String> {
+    let request = Request::post(&format!("{}/api/trades{}", get_api_base_url(), path))
+        .header("Authorization", &format!("Bearer {}", get_token()));
+    let request = match body {
+        Some(body) => request.json(&body).map_err(|_| "Failed to build request".to_string())?,
+        None => request.build().map_err(|_| "Failed to build request".to_string())?,
+    };
+
+    match request.send().await {
+        Ok(response) => match response.json::<TradeActionResponse>().await {
+            Ok(TradeActionResponse { success: true, data: Some(data), .. }) => {
+                dispatch_currency_update(data.new_balance);
+                Ok(())
+            },
+            Ok(result) => Err(result.error.unwrap_or_else(|| "Trade failed".to_string())),
+            Err(_) => Err("Trade failed".to_string()),
+        },
+        Err(_) => Err("Network error occurred.".to_string()),
+    }
+}
+
+#[function_component(TradesPanel)]
+pub fn trades_panel(props: &TradesPanelProps) -> Html {
+    let offers = use_state(Vec::<TradeOffer>::new);
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+    let message = use_state(|| None::<String>);
+    let busy = use_state(|| false);
+    let reload = use_state(|| 0u32);
+
+    let recipient = use_state(String::new);
+    let offered_pax = use_state(|| "0".to_string());
+    let requested_pax = use_state(|| "0".to_string());
+    let selected = use_state(Vec::<TradeItemRef>::new);
+
+    let countering = use_state(|| None::<Uuid>);
+    let counter_offered_pax = use_state(|| "0".to_string());
+    let counter_requested_pax = use_state(|| "0".to_string());
+
+    let current_user_id = get_current_user_id();
+
+    {
+        let offers = offers.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+
+        use_effect_with(*reload, move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match fetch_offers().await {
+                    Ok(data) => {
+                        offers.set(data);
+                        error.set(None);
+                    },
+                    Err(e) => error.set(Some(e)),
+                }
+                loading.set(false);
+            });
+
+            || ()
+        });
+    }
+
+    // Runs a trade action, then reloads offers and the caller's inventory
+    let run_action = {
+        let busy = busy.clone();
+        let message = message.clone();
+        let reload = reload.clone();
+        let countering = countering.clone();
+        let on_traded = props.on_traded.clone();
+        Callback::from(move |(path, body, success): (String, Option<serde_json::Value>, String)| {
+            let busy = busy.clone();
+            let message = message.clone();
+            let reload = reload.clone();
+            let countering = countering.clone();
+            let on_traded = on_traded.clone();
+            busy.set(true);
+            message.set(None);
+
+            wasm_bindgen_futures::spawn_local(async move {
+                match post_trade_action(path, body).await {
+                    Ok(()) => {
+                        message.set(Some(success));
+                        countering.set(None);
+                        reload.set(*reload + 1);
+                        on_traded.emit(());
+                    },
+                    Err(e) => message.set(Some(e)),
+                }
+                busy.set(false);
+            });
+        })
+    };
+
+    let handle_propose = {
+        let run_action = run_action.clone();
+        let recipient = recipient.clone();
+        let offered_pax = offered_pax.clone();
+        let requested_pax = requested_pax.clone();
+        let selected = selected.clone();
+        let message = message.clone();
+        Callback::from(move |_: MouseEvent| {
+            let (Ok(offered), Ok(requested)) = (offered_pax.parse::<i32>(), requested_pax.parse::<i32>()) else {
+                message.set(Some("Invalid pax amount".to_string()));
+                return;
+            };
+            let terms = TradeTerms {
+                offered_items: (*selected).clone(),
+                requested_items: Vec::new(),
+                offered_pax: offered,
+                requested_pax: requested,
+            };
+            let mut body = serde_json::to_value(&terms).unwrap_or_default();
+            body["recipient_username"] = serde_json::Value::String((*recipient).clone());
+            run_action.emit((String::new(), Some(body), format!("Trade offer sent to {}", *recipient)));
+            selected.set(Vec::new());
+            offered_pax.set("0".to_string());
+            requested_pax.set("0".to_string());
+        })
+    };
+
+    let toggle_item = {
+        let selected = selected.clone();
+        Callback::from(move |item: TradeItemRef| {
+            let mut current = (*selected).clone();
+            if let Some(pos) = current.iter().position(|x| x.item_id == item.item_id) {
+                current.remove(pos);
+            } else if current.len() < MAX_TRADE_ITEMS {
+                current.push(item);
+            }
+            selected.set(current);
+        })
+    };
+
+    let tradeable: Vec<(TradeItemRef, String)> = props.eggs.iter()
+        .map(|egg| (
+            TradeItemRef { item_id: egg.id, item_type: "egg".to_string() },
+            egg.display_name.clone().unwrap_or_else(|| "Egg".to_string()),
+        ))
+        .chain(props.creatures.iter().filter(|c| c.status == "available" && !c.in_chaos_realm).map(|creature| (
+            TradeItemRef { item_id: creature.id, item_type: "creature".to_string() },
+            format!("{} ({})",
+                creature.display_name.clone().unwrap_or_else(|| "Creature".to_string()),
+                creature.rarity.clone().unwrap_or_default()),
+        )))
+        .collect();
+
+    let small_input = "w-24 p-2 bg-white dark:bg-gray-800 border dark:border-gray-600 rounded-lg text-gray-900 dark:text-gray-100 text-sm";
+
+    html! {
+        <div class={format!("{} {}", styles::HERO_FEATURES, "p-8")}>
+            <div class="flex items-center space-x-4 mb-6">
+                <div class={styles::ICON_WRAPPER_PURPLE}>
+                    <span class="text-lg">{"🤝"}</span>
+                </div>
+                <span class={styles::TEXT_H3}>{"Trades"}</span>
+            </div>
+
+            if let Some(msg) = &*message {
+                <p class={format!("{} {}", styles::TEXT_SMALL, "mb-4")}>{msg}</p>
+            }
+
+            <div class="p-4 rounded-xl border bg-gray-100/50 dark:bg-gray-800/30 border-gray-200 dark:border-gray-700 text-left space-y-3 mb-6">
+                <p class={styles::TEXT_BODY}>{"Propose a trade"}</p>
+                <div class="flex flex-wrap items-end gap-3">
+                    <input
+                        type="text"
+                        placeholder="Username"
+                        class="p-2 bg-white dark:bg-gray-800 border dark:border-gray-600 rounded-lg text-gray-900 dark:text-gray-100 text-sm"
+                        value={(*recipient).clone()}
+                        oninput={{
+                            let recipient = recipient.clone();
+                            Callback::from(move |e: InputEvent| if let Some(v) = input_value(&e) { recipient.set(v) })
+                        }}
+                    />
+                    <label class={styles::TEXT_SMALL}>
+                        {"You give (pax) "}
+                        <input type="number" min="0" class={small_input} value={(*offered_pax).clone()}
+                            oninput={{
+                                let offered_pax = offered_pax.clone();
+                                Callback::from(move |e: InputEvent| if let Some(v) = input_value(&e) { offered_pax.set(v) })
+                            }}
+                        />
+                    </label>
+                    <label class={styles::TEXT_SMALL}>
+                        {"You ask (pax) "}
+                        <input type="number" min="0" class={small_input} value={(*requested_pax).clone()}
+                            oninput={{
+                                let requested_pax = requested_pax.clone();
+                                Callback::from(move |e: InputEvent| if let Some(v) = input_value(&e) { requested_pax.set(v) })
+                            }}
+                        />
+                    </label>
+                </div>
+                <div class="flex flex-wrap gap-2">
+                    {for tradeable.iter().map(|(item, label)| {
+                        let chosen = selected.iter().any(|s| s.item_id == item.item_id);
+                        let toggle_item = toggle_item.clone();
+                        let item = item.clone();
+                        html! {
+                            <button
+                                onclick={Callback::from(move |_| toggle_item.emit(item.clone()))}
+                                class={format!("px-2 py-1 rounded-lg text-xs {}",
+                                    if chosen { "bg-purple-500 text-white" } else { "bg-gray-200 dark:bg-gray-700" })}
+                            >
+                                {label}
+                            </button>
+                        }
+                    })}
+                </div>
+                <button
+                    onclick={handle_propose}
+                    disabled={*busy || recipient.is_empty()}
+                    class={format!("{} {}", styles::BUTTON_PRIMARY, "disabled:opacity-50 disabled:cursor-not-allowed")}
+                >
+                    {if *busy { "Sending..." } else { "Send Offer" }}
+                </button>
+            </div>
+
+            if *loading {
+                <div class="flex justify-center items-center p-6">
+                    <div class="animate-spin rounded-full h-8 w-8 border-t-2 border-b-2 border-purple-500" />
+                </div>
+            } else if let Some(err) = &*error {
+                <div class={styles::ALERT_ERROR}>{err}</div>
+            } else if offers.is_empty() {
+                <p class={styles::TEXT_SMALL}>{"No trade offers yet"}</p>
+            } else {
+                <div class="space-y-3">
+                    {for offers.iter().map(|offer| {
+                        let incoming = current_user_id == Some(offer.recipient_id);
+                        let pending = offer.status == "pending";
+                        let counterparty = if incoming { &offer.proposer_username } else { &offer.recipient_username };
+                        let (you_give, you_get) = if incoming {
+                            (describe_side(&offer.requested_items, offer.requested_pax), describe_side(&offer.offered_items, offer.offered_pax))
+                        } else {
+                            (describe_side(&offer.offered_items, offer.offered_pax), describe_side(&offer.requested_items, offer.requested_pax))
+                        };
+                        let id = offer.id;
+
+                        let action = |path: &str, success: &str| {
+                            let run_action = run_action.clone();
+                            let path = format!("/{}/{}", id, path);
+                            let success = success.to_string();
+                            Callback::from(move |_: MouseEvent| run_action.emit((path.clone(), None, success.clone())))
+                        };
+
+                        let start_counter = {
+                            let countering = countering.clone();
+                            let counter_offered_pax = counter_offered_pax.clone();
+                            let counter_requested_pax = counter_requested_pax.clone();
+                            let (give, get) = (offer.requested_pax, offer.offered_pax);
+                            Callback::from(move |_: MouseEvent| {
+                                counter_offered_pax.set(give.to_string());
+                                counter_requested_pax.set(get.to_string());
+                                countering.set(Some(id));
+                            })
+                        };
+
+                        // A counter swaps roles: the items we were asked for become our offer
+                        let send_counter = {
+                            let run_action = run_action.clone();
+                            let message = message.clone();
+                            let counter_offered_pax = counter_offered_pax.clone();
+                            let counter_requested_pax = counter_requested_pax.clone();
+                            let offered_items = to_refs(&offer.requested_items);
+                            let requested_items = to_refs(&offer.offered_items);
+                            Callback::from(move |_: MouseEvent| {
+                                let (Ok(offered), Ok(requested)) = (counter_offered_pax.parse::<i32>(), counter_requested_pax.parse::<i32>()) else {
+                                    message.set(Some("Invalid pax amount".to_string()));
+                                    return;
+                                };
+                                let terms = TradeTerms {
+                                    offered_items: offered_items.clone(),
+                                    requested_items: requested_items.clone(),
+                                    offered_pax: offered,
+                                    requested_pax: requested,
+                                };
+                                run_action.emit((format!("/{}/counter", id), serde_json::to_value(&terms).ok(), "Counter offer sent".to_string()));
+                            })
+                        };
+
+                        html! {
+                            <div class="p-4 rounded-xl border bg-gray-100/50 dark:bg-gray-800/30 border-gray-200 dark:border-gray-700 text-left space-y-2">
+                                <div class="flex justify-between">
+                                    <p class={styles::TEXT_BODY}>
+                                        {if incoming { format!("From {}", counterparty) } else { format!("To {}", counterparty) }}
+                                    </p>
+                                    <span class={styles::TEXT_SMALL}>{&offer.status}</span>
+                                </div>
+                                <p class={styles::TEXT_SMALL}>{format!("You give: {}", you_give)}</p>
+                                <p class={styles::TEXT_SMALL}>{format!("You get: {}", you_get)}</p>
+                                if pending && incoming {
+                                    <div class="flex flex-wrap gap-2">
+                                        <button onclick={action("accept", "Trade completed!")} disabled={*busy}
+                                            class="px-3 py-1 bg-green-500 text-white text-sm rounded-lg hover:bg-green-600 disabled:opacity-50">
+                                            {"Accept"}
+                                        </button>
+                                        <button onclick={start_counter} disabled={*busy}
+                                            class="px-3 py-1 bg-blue-500 text-white text-sm rounded-lg hover:bg-blue-600 disabled:opacity-50">
+                                            {"Counter"}
+                                        </button>
+                                        <button onclick={action("decline", "Offer declined")} disabled={*busy}
+                                            class="px-3 py-1 bg-red-500 text-white text-sm rounded-lg hover:bg-red-600 disabled:opacity-50">
+                                            {"Decline"}
+                                        </button>
+                                    </div>
+                                    if *countering == Some(id) {
+                                        <div class="flex flex-wrap items-end gap-3">
+                                            <label class={styles::TEXT_SMALL}>
+                                                {"You give (pax) "}
+                                                <input type="number" min="0" class={small_input} value={(*counter_offered_pax).clone()}
+                                                    oninput={{
+                                                        let counter_offered_pax = counter_offered_pax.clone();
+                                                        Callback::from(move |e: InputEvent| if let Some(v) = input_value(&e) { counter_offered_pax.set(v) })
+                                                    }}
+                                                />
+                                            </label>
+                                            <label class={styles::TEXT_SMALL}>
+                                                {"You ask (pax) "}
+                                                <input type="number" min="0" class={small_input} value={(*counter_requested_pax).clone()}
+                                                    oninput={{
+                                                        let counter_requested_pax = counter_requested_pax.clone();
+                                                        Callback::from(move |e: InputEvent| if let Some(v) = input_value(&e) { counter_requested_pax.set(v) })
+                                                    }}
+                                                />
+                                            </label>
+                                            <button onclick={send_counter} disabled={*busy}
+                                                class="px-3 py-1 bg-blue-500 text-white text-sm rounded-lg hover:bg-blue-600 disabled:opacity-50">
+                                                {"Send Counter"}
+                                            </button>
+                                        </div>
+                                    }
+                                }
+                                if pending && !incoming {
+                                    <button onclick={action("cancel", "Offer cancelled")} disabled={*busy}
+                                        class="px-3 py-1 bg-yellow-500 text-white text-sm rounded-lg hover:bg-yellow-600 disabled:opacity-50">
+                                        {"Cancel"}
+                                    </button>
+                                }
+                            </div>
+                        }
+                    })}
+                </div>
+            }
+        </div>
+    }
+}
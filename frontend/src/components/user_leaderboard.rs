@@ -3,6 +3,7 @@ use serde::Deserialize;
 use gloo_net::http::Request;
 use web_sys::window;
 use crate::config::get_api_base_url;
+use crate::components::PlayerLink;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct UserLeaderboardEntry {
@@ -151,7 +152,9 @@ pub fn user_leaderboard() -> Html {
                                                         </div>
                                                     </td>
                                                     <td class="px-6 py-4 whitespace-nowrap align-middle">
-                                                        <div class="text-base font-semibold text-gray-900 dark:text-white">{&entry.username}</div>
+                                                        <div class="text-base font-semibold text-gray-900 dark:text-white">
+                                                            <PlayerLink username={entry.username.clone()} />
+                                                        </div>
                                                     </td>
                                                     <td class="px-6 py-4 whitespace-nowrap text-center align-middle">
                                                         <div class="text-base font-bold text-purple-600 dark:text-purple-400">{entry.total_soul}</div>
@@ -4,7 +4,10 @@ pub mod validation;
 pub mod use_currency;
 pub mod use_membership;
 pub mod use_global_stats;
+pub mod use_push_events;
+pub mod use_preferences;
 
 pub use auth_state::*;
 pub use use_currency::*;
-pub use use_global_stats::*;
\ No newline at end of file
+pub use use_global_stats::*;
+pub use use_push_events::*;
\ No newline at end of file
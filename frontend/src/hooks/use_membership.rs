@@ -4,10 +4,12 @@ use gloo_net::http::Request;
 use serde::Deserialize;
 use crate::config::get_api_base_url;
 use web_sys::window;
-use gloo_timers::callback::Interval;
+use gloo_timers::callback::Timeout;
+use shared::push_events::PushEvent;
+use crate::hooks::use_push_event;
 use crate::base::dispatch_membership_event;
 
-const UPDATE_INTERVAL: u32 = 1000; // Check every 1 second
+const UPDATE_INTERVAL: u32 = 1000; // Countdown tick, no network involved
 
 #[derive(Deserialize, Clone, Debug, PartialEq)]
 pub struct MembershipStatus {
@@ -104,7 +106,7 @@ pub fn use_membership() -> MembershipInfo {
     let loading = use_state(|| true);
     let member_until = use_state(|| None::<String>);
     
-    // Initial fetch and periodic refresh
+    // Initial fetch
     {
         let is_member_clone = is_member.clone();
         let remaining_seconds_clone = remaining_seconds.clone();
@@ -112,17 +114,42 @@ pub fn use_membership() -> MembershipInfo {
         let member_until_clone = member_until.clone();
         
         use_effect_with((), move |_| {
-            // Initial fetch
-            fetch_membership_status(is_member_clone.clone(), remaining_seconds_clone.clone(), loading_clone.clone(), member_until_clone.clone());
-            
-            // Set up interval for periodic refresh
-            let interval = Interval::new(UPDATE_INTERVAL, move || {
-                fetch_membership_status(is_member_clone.clone(), remaining_seconds_clone.clone(), loading_clone.clone(), member_until_clone.clone());
-            });
-            
-            move || { drop(interval); }
+            fetch_membership_status(is_member_clone, remaining_seconds_clone, loading_clone, member_until_clone);
+            || ()
+        });
+    }
+
+    // Count the remaining time down locally and re-check with the server once it runs out
+    {
+        let is_member_clone = is_member.clone();
+        let remaining_seconds_clone = remaining_seconds.clone();
+        let loading_clone = loading.clone();
+        let member_until_clone = member_until.clone();
+
+        use_effect_with(*remaining_seconds, move |&remaining| {
+            let timeout = (remaining > 0).then(|| Timeout::new(UPDATE_INTERVAL, move || {
+                remaining_seconds_clone.set(remaining - 1);
+                if remaining == 1 {
+                    fetch_membership_status(is_member_clone, remaining_seconds_clone, loading_clone, member_until_clone);
+                }
+            }));
+            move || drop(timeout)
         });
     }
+
+    // The server pushes an event when the hourly sweep expires the membership
+    {
+        let is_member_clone = is_member.clone();
+        let remaining_seconds_clone = remaining_seconds.clone();
+        let loading_clone = loading.clone();
+        let member_until_clone = member_until.clone();
+
+        use_push_event(Callback::from(move |event: PushEvent| {
+            if event == PushEvent::MembershipExpired {
+                fetch_membership_status(is_member_clone.clone(), remaining_seconds_clone.clone(), loading_clone.clone(), member_until_clone.clone());
+            }
+        }));
+    }
     
     MembershipInfo {
         is_member: *is_member,
@@ -0,0 +1,102 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::Deserialize;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use shared::preferences::{UiPreferences, UserPreferences};
+use crate::config::get_api_base_url;
+
+/// UI preferences are cached in local storage so pages can apply them on first render,
+/// before the server copy arrives (and for logged-out visitors, who only have defaults).
+const UI_PREFERENCES_KEY: &str = "ui_preferences";
+
+#[derive(Deserialize)]
+struct PreferencesResponse {
+    success: bool,
+    data: Option<UserPreferences>,
+    error: Option<String>,
+}
+
+fn get_token() -> Option<String> {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .filter(|token| !token.is_empty())
+}
+
+pub fn stored_ui_preferences() -> UiPreferences {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item(UI_PREFERENCES_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn store_ui_preferences(preferences: &UiPreferences) {
+    if let (Some(storage), Ok(json)) = (
+        window().and_then(|w| w.local_storage().ok().flatten()),
+        serde_json::to_string(preferences),
+    ) {
+        let _ = storage.set_item(UI_PREFERENCES_KEY, &json);
+    }
+}
+
+async fn read_response(response: Result<gloo_net::http::Response, gloo_net::Error>) -> Result<UserPreferences, String> {
+    match response {
+        Ok(response) => match response.json::<PreferencesResponse>().await {
+            Ok(PreferencesResponse { success: true, data: Some(preferences), .. }) => {
+                store_ui_preferences(&preferences.ui_preferences);
+                Ok(preferences)
+            },
+            Ok(result) => Err(result.error.unwrap_or_else(|| "Failed to load preferences".to_string())),
+            Err(_) => Err(format!("Server returned status: {}", response.status())),
+        },
+        Err(e) => {
+            log::error!("Preferences request failed: {:?}", e);
+            Err("Network error occurred.".to_string())
+        }
+    }
+}
+
+pub async fn fetch_preferences() -> Result<UserPreferences, String> {
+    let token = get_token().ok_or_else(|| "Not authenticated".to_string())?;
+    let response = Request::get(&format!("{}/api/settings/preferences", get_api_base_url()))
+        .header("Authorization", &format!("Bearer {}", token))
+        .send()
+        .await;
+    read_response(response).await
+}
+
+pub async fn save_preferences(preferences: &UserPreferences) -> Result<UserPreferences, String> {
+    let token = get_token().ok_or_else(|| "Not authenticated".to_string())?;
+    let request = Request::put(&format!("{}/api/settings/preferences", get_api_base_url()))
+        .header("Authorization", &format!("Bearer {}", token))
+        .json(preferences)
+        .map_err(|_| "Failed to build request".to_string())?;
+    read_response(request.send().await).await
+}
+
+/// The current user's UI preferences: the cached copy right away, refreshed from the server once.
+#[hook]
+pub fn use_ui_preferences() -> UiPreferences {
+    let preferences = use_state(stored_ui_preferences);
+
+    {
+        let preferences = preferences.clone();
+        use_effect_with((), move |_| {
+            if get_token().is_some() {
+                spawn_local(async move {
+                    if let Ok(latest) = fetch_preferences().await {
+                        preferences.set(latest.ui_preferences);
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    (*preferences).clone()
+}
@@ -0,0 +1,136 @@
+use yew::prelude::*;
+use futures::{SinkExt, StreamExt};
+use futures::future::{AbortHandle, Abortable};
+use gloo::events::EventListener;
+use gloo_net::websocket::{futures::WebSocket, Message};
+use gloo_timers::future::TimeoutFuture;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{window, CustomEvent, CustomEventInit, Event};
+use shared::push_events::PushEvent;
+use crate::base::dispatch_membership_event;
+use crate::config::get_api_base_url;
+
+/// Window event re-dispatching every push event, with the JSON payload as its detail.
+pub const PUSH_EVENT: &str = "pushEvent";
+
+const RECONNECT_DELAY_MS: u32 = 5_000;
+
+fn get_token() -> Option<String> {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .filter(|token| !token.is_empty())
+}
+
+fn event_stream_url() -> String {
+    let api_base = get_api_base_url();
+    if api_base.is_empty() {
+        // Production serves the API from the page's own origin
+        let location = window().map(|w| w.location());
+        let protocol = location.as_ref().and_then(|l| l.protocol().ok()).unwrap_or_default();
+        let host = location.as_ref().and_then(|l| l.host().ok()).unwrap_or_default();
+        let ws_protocol = if protocol.starts_with("https") { "wss" } else { "ws" };
+        format!("{}://{}/api/events", ws_protocol, host)
+    } else {
+        format!("{}/api/events", api_base.replace("http://", "ws://").replace("https://", "wss://"))
+    }
+}
+
+fn dispatch(name: &str, detail: &JsValue) {
+    if let Some(window) = window() {
+        let event_init = CustomEventInit::new();
+        event_init.set_detail(detail);
+        if let Ok(event) = CustomEvent::new_with_event_init_dict(name, &event_init) {
+            let _ = window.dispatch_event(&event);
+        }
+    }
+}
+
+fn handle_push_message(text: &str) {
+    let event = match serde_json::from_str::<PushEvent>(text) {
+        Ok(event) => event,
+        Err(e) => {
+            log::warn!("Ignoring unknown push event {}: {:?}", text, e);
+            return;
+        }
+    };
+
+    // Events the whole app already understands get translated to their existing window events
+    match &event {
+        PushEvent::BalanceChanged { balance } => dispatch("currencyUpdate", &JsValue::from_f64(*balance as f64)),
+        PushEvent::MembershipExpired => dispatch_membership_event(false),
+        _ => {}
+    }
+
+    dispatch(PUSH_EVENT, &JsValue::from_str(text));
+}
+
+async fn run_event_stream(token: String) {
+    loop {
+        match WebSocket::open(&event_stream_url()) {
+            Ok(ws) => {
+                let (mut write, mut read) = ws.split();
+                if write.send(Message::Text(format!("Bearer {}", token))).await.is_ok() {
+                    while let Some(Ok(message)) = read.next().await {
+                        if let Message::Text(text) = message {
+                            handle_push_message(&text);
+                        }
+                    }
+                }
+                log::info!("Event stream disconnected, reconnecting");
+            }
+            Err(e) => log::error!("Failed to open event stream: {:?}", e),
+        }
+        TimeoutFuture::new(RECONNECT_DELAY_MS).await;
+    }
+}
+
+/// Keeps the `/api/events` push connection open while logged in. Mount once, in `Base`;
+/// components subscribe with `use_push_event` instead of opening their own connection.
+#[hook]
+pub fn use_event_stream(logged_in: bool) {
+    use_effect_with(logged_in, move |logged_in| {
+        let abort_handle = match (*logged_in, get_token()) {
+            (true, Some(token)) => {
+                let (abort_handle, registration) = AbortHandle::new_pair();
+                spawn_local(async move {
+                    let _ = Abortable::new(run_event_stream(token), registration).await;
+                });
+                Some(abort_handle)
+            }
+            _ => None,
+        };
+
+        move || {
+            if let Some(handle) = abort_handle {
+                handle.abort();
+            }
+        }
+    });
+}
+
+/// Calls `callback` for every event pushed by the server while the component is mounted.
+#[hook]
+pub fn use_push_event(callback: Callback<PushEvent>) {
+    // Keep the latest callback around so the window listener doesn't need re-registering every render
+    let latest = use_mut_ref(|| callback.clone());
+    *latest.borrow_mut() = callback;
+
+    use_effect_with((), move |_| {
+        let listener = window().map(|window| {
+            EventListener::new(&window, PUSH_EVENT, move |event: &Event| {
+                let payload = event
+                    .dyn_ref::<CustomEvent>()
+                    .and_then(|event| event.detail().as_string());
+                if let Some(event) = payload.and_then(|text| serde_json::from_str::<PushEvent>(&text).ok()) {
+                    latest.borrow().emit(event);
+                }
+            })
+        });
+        move || drop(listener)
+    });
+}
@@ -8,10 +8,15 @@ pub mod config;
 
 use yew::prelude::*;
 use yew_router::prelude::*;
+use uuid::Uuid;
 use crate::pages::{
    auth::{Auth, AuthMode},
    home::Home,
    profile::Profile,
+   public_profile::PublicProfile,
+   guild::Guild,
+   battle::Battle,
+   wallet::Wallet,
    inventory::Inventory,
    market::Market,
    settings::Settings,
@@ -29,6 +34,11 @@ pub enum Route {
    #[at("/verify-magic-link")] VerifyMagicLink,
    #[at("/verify-delete-account")] VerifyDeleteAccount,
    #[at("/profile")] Profile,
+   #[at("/users/:username")] PublicProfile { username: String },
+   #[at("/guild")] Guild,
+   #[at("/battle")] Battle,
+   #[at("/battle/:opponent")] BattleOpponent { opponent: Uuid },
+   #[at("/wallet")] Wallet,
    #[at("/inventory")] Inventory,
    #[at("/market")] Market,
    #[at("/settings")] Settings,
@@ -111,6 +121,11 @@ pub fn switch(route: Route) -> Html {
        Route::VerifyMagicLink => html! { <VerifyMagicLink /> },
        Route::VerifyDeleteAccount => html! { <VerifyDeleteAccount /> },
        Route::Profile => html! { <Profile /> },
+       Route::PublicProfile { username } => html! { <PublicProfile {username} /> },
+       Route::Guild => html! { <Guild /> },
+       Route::Battle => html! { <Battle /> },
+       Route::BattleOpponent { opponent } => html! { <Battle opponent={Some(opponent)} /> },
+       Route::Wallet => html! { <Wallet /> },
        Route::Inventory => html! { <Inventory /> },
        Route::Market => html! { <Market /> },
        Route::Settings => html! { <Settings /> },
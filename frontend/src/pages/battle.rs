@@ -0,0 +1,429 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use gloo_timers::callback::Timeout;
+use serde::Deserialize;
+use serde_json::json;
+use shared::shared_battle::{self, BattleOutcome, BattleTurn, Combatant, Side};
+use uuid::Uuid;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use crate::{base::Base, styles};
+use crate::config::get_api_base_url;
+use crate::components::{GradientBackground, LadderLeaderboard, PlayerLink};
+use crate::models::Creature;
+
+/// Delay between replayed turns, in milliseconds.
+const TURN_DELAY_MS: u32 = 600;
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct BattleRecord {
+    id: Uuid,
+    challenger_username: String,
+    defender_username: Option<String>,
+    challenger: Combatant,
+    defender: Combatant,
+    seed: u64,
+    challenger_won: bool,
+    reward_pax: i32,
+    reward_experience: i32,
+    #[serde(default)]
+    rating_change: Option<i32>,
+    created_at: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct LadderStatus {
+    ends_at: String,
+    rating: i32,
+    wins: i32,
+    losses: i32,
+    rank: Option<i64>,
+    last_season_rank: Option<i32>,
+}
+
+impl BattleRecord {
+    /// Re-runs the battle locally; the shared engine gives the same turns the backend settled.
+    fn replay(&self) -> BattleOutcome {
+        shared_battle::simulate(&self.challenger, &self.defender, self.seed)
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiResponse<T> {
+    success: bool,
+    data: Option<T>,
+    error: Option<String>,
+}
+
+fn get_token() -> String {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .unwrap_or_default()
+}
+
+async fn fetch<T: for<'de> Deserialize<'de>>(path: &str) -> Result<Option<T>, String> {
+    match Request::get(&format!("{}{}", get_api_base_url(), path))
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .send()
+        .await
+    {
+        Ok(response) => match response.json::<ApiResponse<T>>().await {
+            Ok(ApiResponse { success: true, data, .. }) => Ok(data),
+            Ok(result) => Err(result.error.unwrap_or_else(|| "Failed to load battles".to_string())),
+            Err(_) => Err(format!("Server returned status: {}", response.status())),
+        },
+        Err(e) => {
+            log::error!("Failed to fetch {}: {:?}", path, e);
+            Err("Network error occurred.".to_string())
+        }
+    }
+}
+
+async fn fetch_creatures() -> Result<Vec<Creature>, String> {
+    match Request::get(&format!("{}/api/creatures", get_api_base_url()))
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .send()
+        .await
+    {
+        Ok(response) if response.ok() => response
+            .json::<Vec<Creature>>()
+            .await
+            .map_err(|_| "Failed to load creatures".to_string()),
+        Ok(response) => Err(format!("Server returned status: {}", response.status())),
+        Err(e) => {
+            log::error!("Failed to fetch creatures: {:?}", e);
+            Err("Network error occurred.".to_string())
+        }
+    }
+}
+
+/// Starts a battle with `creature_id`: ranked against a matched player, or unranked
+/// against `opponent` (a wild creature when that is empty too).
+async fn start_battle(creature_id: Uuid, opponent: Option<Uuid>, ranked: bool) -> Result<BattleRecord, String> {
+    let (url, body) = if ranked {
+        (format!("{}/api/ladder/battle", get_api_base_url()), json!({ "creature_id": creature_id }))
+    } else {
+        (
+            format!("{}/api/creatures/{}/battle", get_api_base_url(), creature_id),
+            json!({ "opponent_creature_id": opponent }),
+        )
+    };
+    let request = Request::post(&url)
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .json(&body)
+        .map_err(|_| "Failed to build request".to_string())?;
+
+    match request.send().await {
+        Ok(response) => match response.json::<ApiResponse<BattleRecord>>().await {
+            Ok(ApiResponse { success: true, data: Some(record), .. }) => Ok(record),
+            Ok(result) => Err(result.error.unwrap_or_else(|| "Battle failed".to_string())),
+            Err(_) => Err(format!("Server returned status: {}", response.status())),
+        },
+        Err(e) => {
+            log::error!("Battle request failed: {:?}", e);
+            Err("Network error occurred.".to_string())
+        }
+    }
+}
+
+fn describe_turn(turn: &BattleTurn, record: &BattleRecord) -> String {
+    let (attacker, defender) = match turn.attacker {
+        Side::Challenger => (&record.challenger.name, &record.defender.name),
+        Side::Defender => (&record.defender.name, &record.challenger.name),
+    };
+    if turn.dodged {
+        return format!("{} dodged {}'s attack", defender, attacker);
+    }
+    let mut text = format!("{} hit {} for {}", attacker, defender, turn.damage);
+    if turn.critical {
+        text.push_str(" — critical hit!");
+    }
+    if turn.effectiveness > shared_battle::NEUTRAL_MULTIPLIER {
+        text.push_str(" It's super effective.");
+    } else if turn.effectiveness < shared_battle::NEUTRAL_MULTIPLIER {
+        text.push_str(" It's not very effective.");
+    }
+    text
+}
+
+fn health_bar(combatant: &Combatant, health: i32, username: Option<&str>) -> Html {
+    let max = combatant.max_health();
+    let percent = (health.max(0) * 100 / max.max(1)).clamp(0, 100);
+    html! {
+        <div class="space-y-1">
+            <div class="flex items-center justify-between">
+                <span class="font-semibold text-gray-900 dark:text-white">{&combatant.name}</span>
+                <span class={styles::TEXT_SMALL}>{format!("{} · {}/{}", combatant.essence.name(), health.max(0), max)}</span>
+            </div>
+            if let Some(username) = username {
+                <p class={styles::TEXT_SMALL}><PlayerLink username={username.to_string()} /></p>
+            }
+            <div class="h-3 rounded-full bg-gray-200 dark:bg-gray-700 overflow-hidden">
+                <div class="h-3 bg-green-500 transition-all duration-300" style={format!("width: {}%", percent)} />
+            </div>
+            <p class={styles::TEXT_SMALL}>
+                {format!("❤️ {}  ⚔️ {}  💨 {}", combatant.stats.health, combatant.stats.attack, combatant.stats.speed)}
+            </p>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct BattleProps {
+    /// A player's creature to challenge; without one the battle is against a wild creature.
+    #[prop_or_default]
+    pub opponent: Option<Uuid>,
+}
+
+#[function_component(Battle)]
+pub fn battle(props: &BattleProps) -> Html {
+    let creatures = use_state(Vec::<Creature>::new);
+    let history = use_state(Vec::<BattleRecord>::new);
+    let ladder = use_state(|| None::<LadderStatus>);
+    let selected = use_state(|| None::<Uuid>);
+    let fighting = use_state(|| false);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+    let replay = use_state(|| None::<(BattleRecord, BattleOutcome)>);
+    let shown = use_state(|| 0usize);
+
+    {
+        let creatures = creatures.clone();
+        let history = history.clone();
+        let ladder = ladder.clone();
+        let error = error.clone();
+        use_effect_with(*reload, move |_| {
+            spawn_local(async move {
+                match fetch_creatures().await {
+                    Ok(data) => creatures.set(data),
+                    Err(e) => error.set(Some(e)),
+                }
+                if let Ok(data) = fetch::<Vec<BattleRecord>>("/api/battles").await {
+                    history.set(data.unwrap_or_default());
+                }
+                if let Ok(data) = fetch::<LadderStatus>("/api/ladder").await {
+                    ladder.set(data);
+                }
+            });
+            || ()
+        });
+    }
+
+    // Reveals one more turn of the replay after each delay until the log is complete
+    {
+        let shown = shown.clone();
+        let total = replay.as_ref().map_or(0, |(_, outcome)| outcome.turns.len());
+        use_effect_with((replay.as_ref().map(|(record, _)| record.id), *shown), move |(_, count)| {
+            let timeout = (*count < total).then(|| {
+                let next = *count + 1;
+                Timeout::new(TURN_DELAY_MS, move || shown.set(next))
+            });
+            move || drop(timeout)
+        });
+    }
+
+    let show_replay = {
+        let replay = replay.clone();
+        let shown = shown.clone();
+        Callback::from(move |record: BattleRecord| {
+            let outcome = record.replay();
+            if (outcome.winner == Side::Challenger) != record.challenger_won {
+                log::warn!("Replay of battle {} disagrees with the recorded result", record.id);
+            }
+            shown.set(0);
+            replay.set(Some((record, outcome)));
+        })
+    };
+
+    let fight = {
+        let selected = selected.clone();
+        let fighting = fighting.clone();
+        let error = error.clone();
+        let reload = reload.clone();
+        let show_replay = show_replay.clone();
+        let opponent = props.opponent;
+        move |ranked: bool| {
+            let selected = selected.clone();
+            let fighting = fighting.clone();
+            let error = error.clone();
+            let reload = reload.clone();
+            let show_replay = show_replay.clone();
+            Callback::from(move |_: MouseEvent| {
+                let Some(creature_id) = *selected else { return };
+                let fighting = fighting.clone();
+                let error = error.clone();
+                let reload = reload.clone();
+                let show_replay = show_replay.clone();
+                fighting.set(true);
+                spawn_local(async move {
+                    match start_battle(creature_id, opponent, ranked).await {
+                        Ok(record) => {
+                            error.set(None);
+                            show_replay.emit(record);
+                        }
+                        Err(e) => error.set(Some(e)),
+                    }
+                    fighting.set(false);
+                    reload.set(*reload + 1);
+                });
+            })
+        }
+    };
+
+    let ready: Vec<&Creature> = creatures
+        .iter()
+        .filter(|c| c.energy_full && c.status == "available" && !c.in_chaos_realm)
+        .collect();
+    let title = if props.opponent.is_some() { "Challenge a Creature" } else { "Battle a Wild Creature" };
+    let creature_button = "w-full text-left px-4 py-2 rounded-lg border border-gray-200 dark:border-gray-700 hover:bg-gray-50 dark:hover:bg-gray-700";
+    let creature_button_selected = "w-full text-left px-4 py-2 rounded-lg border-2 border-blue-500 bg-blue-50 dark:bg-gray-700";
+
+    html! {
+        <Base>
+            <GradientBackground>
+                <div class="max-w-3xl mx-auto px-4 sm:px-6 lg:px-8 py-6 space-y-6">
+                    if let Some(message) = &*error {
+                        <div class={styles::ALERT_ERROR}>{message}</div>
+                    }
+
+                    <div class={styles::FOCUS_CARD}>
+                        <h2 class={styles::FOCUS_CARD_TITLE}>{title}</h2>
+                        <p class={classes!(styles::TEXT_SECONDARY, "mb-4")}>
+                            {"Battles use your creature's full energy. Winners earn pax, experience and soul; essence matchups change how hard each side hits."}
+                        </p>
+                        if ready.is_empty() {
+                            <p class={styles::TEXT_BODY}>{"None of your creatures have full energy right now."}</p>
+                        } else {
+                            <div class="space-y-2 mb-4">
+                                {for ready.iter().map(|creature| {
+                                    let id = creature.id;
+                                    let onclick = {
+                                        let selected = selected.clone();
+                                        Callback::from(move |_: MouseEvent| selected.set(Some(id)))
+                                    };
+                                    let class = if *selected == Some(id) { creature_button_selected } else { creature_button };
+                                    html! {
+                                        <button key={id.to_string()} type="button" {class} {onclick}>
+                                            <span class="font-semibold text-gray-900 dark:text-white">
+                                                {creature.display_name.clone().unwrap_or_else(|| "Unnamed creature".to_string())}
+                                            </span>
+                                            <span class={classes!(styles::TEXT_SMALL, "ml-2")}>
+                                                {creature.essence.clone().unwrap_or_default()}
+                                            </span>
+                                        </button>
+                                    }
+                                })}
+                            </div>
+                            <div class="flex gap-2">
+                                <button class={styles::BUTTON_PRIMARY} disabled={selected.is_none() || *fighting} onclick={fight(false)}>
+                                    {if *fighting { "Fighting..." } else { "Fight" }}
+                                </button>
+                                if props.opponent.is_none() {
+                                    <button class={styles::BUTTON_SECONDARY} disabled={selected.is_none() || *fighting} onclick={fight(true)}>
+                                        {"Ranked Match"}
+                                    </button>
+                                }
+                            </div>
+                        }
+                    </div>
+
+                    if let Some((record, outcome)) = &*replay {
+                        <div class={styles::FOCUS_CARD}>
+                            <h2 class={styles::FOCUS_CARD_TITLE}>{"Battle"}</h2>
+                            {{
+                                let last = (*shown).checked_sub(1).and_then(|i| outcome.turns.get(i));
+                                let challenger_health = last.map_or(record.challenger.max_health(), |t| t.challenger_health);
+                                let defender_health = last.map_or(record.defender.max_health(), |t| t.defender_health);
+                                html! {
+                                    <div class="grid grid-cols-1 sm:grid-cols-2 gap-6 mb-4">
+                                        {health_bar(&record.challenger, challenger_health, Some(&record.challenger_username))}
+                                        {health_bar(&record.defender, defender_health, record.defender_username.as_deref())}
+                                    </div>
+                                }
+                            }}
+                            <div class="space-y-1 max-h-64 overflow-y-auto">
+                                {for outcome.turns.iter().take(*shown).map(|turn| html! {
+                                    <p key={turn.turn} class={styles::TEXT_BODY}>
+                                        <span class={classes!(styles::TEXT_SMALL, "mr-2")}>{format!("#{}", turn.turn)}</span>
+                                        {describe_turn(turn, record)}
+                                    </p>
+                                })}
+                            </div>
+                            if *shown >= outcome.turns.len() {
+                                <p class={classes!(styles::TEXT_H3, "mt-4")}>
+                                    {match outcome.winner {
+                                        Side::Challenger => format!("{} wins!", record.challenger.name),
+                                        Side::Defender => format!("{} wins!", record.defender.name),
+                                    }}
+                                </p>
+                                if record.reward_pax > 0 {
+                                    <p class={styles::TEXT_SUCCESS}>
+                                        {format!("+{} pax · +{} XP · +1 soul", record.reward_pax, record.reward_experience)}
+                                    </p>
+                                }
+                                if let Some(change) = record.rating_change {
+                                    <p class={styles::TEXT_BODY}>{format!("Rating {:+}", change)}</p>
+                                }
+                            }
+                        </div>
+                    }
+
+                    if let Some(status) = &*ladder {
+                        <div class={styles::FOCUS_CARD}>
+                            <h2 class={styles::FOCUS_CARD_TITLE}>{"Ranked Ladder"}</h2>
+                            <p class={styles::TEXT_BODY}>
+                                {format!("Rating {} · {}W / {}L", status.rating, status.wins, status.losses)}
+                                {status.rank.map(|rank| format!(" · #{}", rank)).unwrap_or_default()}
+                            </p>
+                            <p class={styles::TEXT_SMALL}>
+                                {format!("Season ends {}. The top 10 with at least 5 ranked battles earn pax and scrolls.", status.ends_at)}
+                            </p>
+                            if let Some(rank) = status.last_season_rank {
+                                <p class={styles::TEXT_SMALL}>{format!("Last season you finished #{}", rank)}</p>
+                            }
+                        </div>
+                        <LadderLeaderboard update_trigger={*reload} />
+                    }
+
+                    if !history.is_empty() {
+                        <div class={styles::FOCUS_CARD}>
+                            <h2 class={styles::FOCUS_CARD_TITLE}>{"Recent Battles"}</h2>
+                            <div class="divide-y divide-gray-200 dark:divide-gray-700">
+                                {for history.iter().map(|record| {
+                                    let onclick = {
+                                        let show_replay = show_replay.clone();
+                                        let record = record.clone();
+                                        Callback::from(move |_: MouseEvent| show_replay.emit(record.clone()))
+                                    };
+                                    html! {
+                                        <div key={record.id.to_string()} class="flex items-center justify-between py-3">
+                                            <div>
+                                                <p class={styles::TEXT_BODY}>
+                                                    {format!("{} vs {}", record.challenger.name, record.defender.name)}
+                                                </p>
+                                                <p class={styles::TEXT_SMALL}>
+                                                    {format!(
+                                                        "{} · {} · {}",
+                                                        record.defender_username.as_deref().unwrap_or("Wild"),
+                                                        if record.challenger_won { "Challenger won" } else { "Defender won" },
+                                                        record.created_at
+                                                    )}
+                                                    {record.rating_change.map(|change| format!(" · Ranked {:+}", change)).unwrap_or_default()}
+                                                </p>
+                                            </div>
+                                            <button class={styles::BUTTON_SECONDARY} {onclick}>{"Replay"}</button>
+                                        </div>
+                                    }
+                                })}
+                            </div>
+                        </div>
+                    }
+                </div>
+            </GradientBackground>
+        </Base>
+    }
+}
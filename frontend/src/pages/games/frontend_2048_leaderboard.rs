@@ -4,6 +4,7 @@ use serde::Deserialize;
 use wasm_bindgen::JsValue;
 use js_sys::{Date, Object};
 use crate::config::get_api_base_url;
+use crate::components::{LeaderboardScope, LeaderboardScopeToggle};
 use web_sys::window;
 
 #[derive(Properties, PartialEq)]
@@ -61,17 +62,19 @@ fn get_auth_token() -> Option<String> {
 #[function_component(Game2048Leaderboard)]
 pub fn game_2048_leaderboard(props: &Props) -> Html {
     let leaderboard = use_state(|| Vec::<LeaderboardEntry>::new());
+    let scope = use_state(LeaderboardScope::default);
     
     // Function to fetch leaderboard data
     let fetch_leaderboard = {
         let leaderboard = leaderboard.clone();
+        let scope = *scope;
         move || {
             wasm_bindgen_futures::spawn_local({
                 let leaderboard = leaderboard.clone();
                 async move {
                     let token = get_auth_token();
                     let api_base = get_api_base_url();
-                    let url = format!("{}/api/leaderboard/2048?limit=10", api_base);
+                    let url = format!("{}/api/leaderboard/2048?limit=10{}", api_base, scope.query());
                     
                     match Request::get(&url)
                         .header("Authorization", &format!("Bearer {}", token.unwrap_or_default()))
@@ -101,20 +104,26 @@ pub fn game_2048_leaderboard(props: &Props) -> Html {
         }
     };
 
-    // Fetch on mount and when update_trigger changes
+    // Fetch on mount and when update_trigger or the scope changes
     {
         let fetch_leaderboard = fetch_leaderboard.clone();
-        use_effect_with(props.update_trigger, move |_| {
+        use_effect_with((props.update_trigger, *scope), move |_| {
             fetch_leaderboard();
             || ()
         });
     }
     
+    let on_scope_change = {
+        let scope = scope.clone();
+        Callback::from(move |selected| scope.set(selected))
+    };
+
     html! {
         <div class="mt-8 bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6 max-w-3xl mx-auto">
             <h2 class="text-2xl font-bold mb-4 text-gray-800 dark:text-gray-100 text-center">
                 {"2048 Leaderboard"}
             </h2>
+            <LeaderboardScopeToggle scope={*scope} on_change={on_scope_change} />
             <div class="overflow-x-auto">
                 <table class="w-full border-collapse bg-white dark:bg-gray-800">
                     <thead>
@@ -14,6 +14,7 @@ use wasm_bindgen::JsCast;
 use crate::hooks::use_currency::use_currency;
 use crate::config::get_api_base_url;
 use crate::hooks::use_membership::use_membership;
+use crate::hooks::use_preferences::use_ui_preferences;
 use crate::components::MembershipRequired;
 use crate::styles;
 use serde::Deserialize;
@@ -58,6 +59,7 @@ use wheel_utils::{
 
 // Add a constant for the wheel cooldown to match the backend
 const WHEEL_SPIN_COOLDOWN: f64 = 82800.0; // 23 hours (was 30 seconds)
+const REDUCED_MOTION_SPIN_MS: f64 = 300.0;
 
 // Function to fetch wheel status from server
 async fn fetch_wheel_status() -> Result<WheelStatusResponse, String> {
@@ -124,6 +126,7 @@ pub fn frontend_wheel_game() -> Html {
     }
 
     // Game state
+    let reduced_motion = use_ui_preferences().reduced_motion;
     let game_state = use_state(|| None::<WheelGame>);
     let is_spinning = use_state(|| false);
     let result_number = use_state(|| None::<f64>);
@@ -432,7 +435,11 @@ pub fn frontend_wheel_game() -> Html {
                                     let current_rotation = *rotation;
                                     
                                     // Add minimum spins (at least 3 full rotations for effect)
-                                    let min_spins = 8.0 * 360.0; // Increased from 5.0 to 8.0 rotations for faster initial spin
+                                    let min_spins = if reduced_motion {
+                                        0.0 // Reduced motion: go straight to the result
+                                    } else {
+                                        8.0 * 360.0 // Increased from 5.0 to 8.0 rotations for faster initial spin
+                                    };
                                     
                                     // Determine the target position based on the outcome
                                     let target_position = if is_scroll_win {
@@ -460,7 +467,7 @@ pub fn frontend_wheel_game() -> Html {
                                     
                                     // Animate the wheel spinning
                                     let start_time = js_sys::Date::now();
-                                    let duration = 6000.0; // 6 seconds spin
+                                    let duration = if reduced_motion { REDUCED_MOTION_SPIN_MS } else { 6000.0 }; // 6 seconds spin
                                     let start_rotation = current_rotation;
                                     let rotation_change = final_rotation - start_rotation;
                                     
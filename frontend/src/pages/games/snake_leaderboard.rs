@@ -4,6 +4,7 @@ use serde::Deserialize;
 use wasm_bindgen::JsValue;
 use js_sys::{Date, Object};
 use crate::config::get_api_base_url;
+use crate::components::{LeaderboardScope, LeaderboardScopeToggle};
 use web_sys::window;
 
 #[derive(Properties, PartialEq)]
@@ -61,10 +62,12 @@ fn get_auth_token() -> Option<String> {
 #[function_component(SnakeLeaderboard)]
 pub fn snake_leaderboard(props: &Props) -> Html {
     let leaderboard = use_state(|| Vec::<LeaderboardEntry>::new());
+    let scope = use_state(LeaderboardScope::default);
     
     // Function to fetch leaderboard data
     let fetch_leaderboard = {
         let leaderboard = leaderboard.clone();
+        let scope = *scope;
         move || {
             wasm_bindgen_futures::spawn_local({
                 let leaderboard = leaderboard.clone();
@@ -73,7 +76,7 @@ pub fn snake_leaderboard(props: &Props) -> Html {
                     let api_base = get_api_base_url();
                     // Construct the URL using the game_type parameter
                     let game_type = "snake"; // Or pass this dynamically if needed
-                    let url = format!("{}/api/leaderboard/{}?limit=10", api_base, game_type);
+                    let url = format!("{}/api/leaderboard/{}?limit=10{}", api_base, game_type, scope.query());
                     
                     match Request::get(&url)
                         .header("Authorization", &format!("Bearer {}", token.unwrap_or_default()))
@@ -107,20 +110,26 @@ pub fn snake_leaderboard(props: &Props) -> Html {
         }
     };
 
-    // Fetch on mount and when update_trigger changes
+    // Fetch on mount and when update_trigger or the scope changes
     {
         let fetch_leaderboard = fetch_leaderboard.clone();
-        use_effect_with(props.update_trigger, move |_| {
+        use_effect_with((props.update_trigger, *scope), move |_| {
             fetch_leaderboard();
             || ()
         });
     }
     
+    let on_scope_change = {
+        let scope = scope.clone();
+        Callback::from(move |selected| scope.set(selected))
+    };
+
     html! {
         <div class="mt-8 bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6 max-w-3xl mx-auto">
             <h2 class="text-2xl font-bold mb-4 text-gray-800 dark:text-gray-100 text-center">
                 {"Snake Leaderboard"}
             </h2>
+            <LeaderboardScopeToggle scope={*scope} on_change={on_scope_change} />
             <div class="overflow-x-auto">
                 <table class="w-full border-collapse bg-white dark:bg-gray-800">
                     <thead>
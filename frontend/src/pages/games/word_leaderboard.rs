@@ -4,6 +4,8 @@ use serde::Deserialize;
 use wasm_bindgen::JsValue;
 use js_sys;
 use crate::config::get_api_base_url;
+use crate::components::{LeaderboardScope, LeaderboardScopeToggle};
+use crate::components::PlayerLink;
 use web_sys::window;
 
 #[derive(Properties, PartialEq)]
@@ -100,17 +102,19 @@ fn get_auth_token() -> Option<String> {
 #[function_component(WordLeaderboard)]
 pub fn word_leaderboard(props: &Props) -> Html {
     let leaderboard = use_state(|| Vec::<WordLeaderboardEntry>::new());
+    let scope = use_state(LeaderboardScope::default);
     
     // Function to fetch leaderboard data
     let fetch_leaderboard = {
         let leaderboard = leaderboard.clone();
+        let scope = *scope;
         move || {
             wasm_bindgen_futures::spawn_local({
                 let leaderboard = leaderboard.clone();
                 async move {
                     let token = get_auth_token();
                     let api_base = get_api_base_url();
-                    let url = format!("{}/word-game/leaderboard?limit=10", api_base);
+                    let url = format!("{}/word-game/leaderboard?limit=10{}", api_base, scope.query());
                     
                     if let Ok(resp) = Request::get(&url)
                         .header("Authorization", &format!("Bearer {}", token.unwrap_or_default()))
@@ -130,20 +134,26 @@ pub fn word_leaderboard(props: &Props) -> Html {
         }
     };
 
-    // Fetch on mount and when update_trigger changes
+    // Fetch on mount and when update_trigger or the scope changes
     {
         let fetch_leaderboard = fetch_leaderboard.clone();
-        use_effect_with(props.update_trigger, move |_| {
+        use_effect_with((props.update_trigger, *scope), move |_| {
             fetch_leaderboard();
             || ()
         });
     }
     
+    let on_scope_change = {
+        let scope = scope.clone();
+        Callback::from(move |selected| scope.set(selected))
+    };
+
     html! {
         <div class="bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6 w-full max-w-7xl mx-auto">
             <h2 class="text-2xl font-bold mb-4 text-gray-800 dark:text-gray-100 text-center">
                 {"Leaderboard"}
             </h2>
+            <LeaderboardScopeToggle scope={*scope} on_change={on_scope_change} />
             <div class="overflow-x-auto [&::-webkit-scrollbar]:h-1.5 [&::-webkit-scrollbar-thumb]:rounded-full [&::-webkit-scrollbar-track]:bg-transparent [&::-webkit-scrollbar-thumb]:bg-gray-300 dark:[&::-webkit-scrollbar-thumb]:bg-gray-600 hover:[&::-webkit-scrollbar-thumb]:bg-gray-400 dark:hover:[&::-webkit-scrollbar-thumb]:bg-gray-500">
                 <table class="w-full border-collapse bg-white dark:bg-gray-800">
                     <thead>
@@ -197,7 +207,7 @@ pub fn word_leaderboard(props: &Props) -> Html {
                                         </span>
                                     </td>
                                     <td class="px-4 py-2 whitespace-nowrap text-sm font-medium text-gray-800 dark:text-gray-200 text-center border-l border-gray-200 dark:border-gray-600">
-                                        {&entry.username}
+                                        <PlayerLink username={entry.username.clone()} />
                                     </td>
                                     <td class="px-4 py-2 whitespace-nowrap text-sm font-bold text-orange-600 dark:text-orange-400 text-center border-l border-gray-200 dark:border-gray-600">
                                         {entry.total_words_guessed}
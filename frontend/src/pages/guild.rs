@@ -0,0 +1,407 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use crate::{base::Base, styles};
+use crate::config::get_api_base_url;
+use crate::components::{GradientBackground, PlayerLink};
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct GuildMember {
+    user_id: Uuid,
+    username: String,
+    role: String,
+    experience: i32,
+    joined_at: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct TreasuryEntry {
+    username: Option<String>,
+    recipient_username: Option<String>,
+    amount: i32,
+    reason: String,
+    treasury_after: i32,
+    created_at: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct GuildDetails {
+    name: String,
+    description: String,
+    treasury: i32,
+    my_role: String,
+    members: Vec<GuildMember>,
+    pending_invites: Vec<String>,
+    treasury_log: Vec<TreasuryEntry>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct PendingGuildInvite {
+    guild_id: Uuid,
+    guild_name: String,
+    invited_by: String,
+    member_count: i64,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct GuildLeaderboardEntry {
+    name: String,
+    member_count: i64,
+    total_experience: i64,
+    total_score: i64,
+}
+
+#[derive(Deserialize)]
+struct ApiResponse<T> {
+    success: bool,
+    data: Option<T>,
+    error: Option<String>,
+}
+
+fn get_token() -> String {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .unwrap_or_default()
+}
+
+/// GETs an `ApiResponse` endpoint. `Ok(None)` means success with a null `data`.
+async fn fetch<T: for<'de> Deserialize<'de>>(path: &str) -> Result<Option<T>, String> {
+    match Request::get(&format!("{}{}", get_api_base_url(), path))
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .send()
+        .await
+    {
+        Ok(response) => match response.json::<ApiResponse<T>>().await {
+            Ok(ApiResponse { success: true, data, .. }) => Ok(data),
+            Ok(result) => Err(result.error.unwrap_or_else(|| "Failed to load guild".to_string())),
+            Err(_) => Err(format!("Server returned status: {}", response.status())),
+        },
+        Err(e) => {
+            log::error!("Failed to fetch {}: {:?}", path, e);
+            Err("Network error occurred.".to_string())
+        }
+    }
+}
+
+async fn send(method: &str, path: &str, body: Option<serde_json::Value>) -> Result<(), String> {
+    let url = format!("{}{}", get_api_base_url(), path);
+    let auth = format!("Bearer {}", get_token());
+    let request = match (method, body) {
+        ("DELETE", _) => Request::delete(&url).header("Authorization", &auth).build(),
+        (_, Some(body)) => Request::post(&url).header("Authorization", &auth).json(&body),
+        _ => Request::post(&url).header("Authorization", &auth).build(),
+    }
+    .map_err(|_| "Failed to build request".to_string())?;
+
+    match request.send().await {
+        Ok(response) => match response.json::<ApiResponse<serde_json::Value>>().await {
+            Ok(ApiResponse { success: true, .. }) => Ok(()),
+            Ok(result) => Err(result.error.unwrap_or_else(|| "Request failed".to_string())),
+            Err(_) => Err(format!("Server returned status: {}", response.status())),
+        },
+        Err(e) => {
+            log::error!("Guild request failed: {:?}", e);
+            Err("Network error occurred.".to_string())
+        }
+    }
+}
+
+fn input_value(e: &InputEvent) -> Option<String> {
+    e.target_dyn_into::<web_sys::HtmlInputElement>().map(|input| input.value())
+}
+
+fn text_input(value: &UseStateHandle<String>, placeholder: &str) -> Html {
+    let oninput = {
+        let value = value.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(text) = input_value(&e) {
+                value.set(text);
+            }
+        })
+    };
+    html! {
+        <input type="text" class={styles::INPUT} placeholder={placeholder.to_string()} value={(**value).clone()} {oninput} />
+    }
+}
+
+fn describe_entry(entry: &TreasuryEntry) -> String {
+    let who = entry.username.clone().unwrap_or_else(|| "A former member".to_string());
+    if entry.amount > 0 {
+        format!("{} deposited {} pax", who, entry.amount)
+    } else {
+        let to = entry.recipient_username.clone().unwrap_or_else(|| "a former member".to_string());
+        let reason = if entry.reason.is_empty() { String::new() } else { format!(" ({})", entry.reason) };
+        format!("{} paid {} pax to {}{}", who, -entry.amount, to, reason)
+    }
+}
+
+#[function_component(Guild)]
+pub fn guild() -> Html {
+    let guild = use_state(|| None::<GuildDetails>);
+    let invites = use_state(Vec::<PendingGuildInvite>::new);
+    let leaderboard = use_state(Vec::<GuildLeaderboardEntry>::new);
+    let sort_by_score = use_state(|| false);
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+    let reload = use_state(|| 0u32);
+
+    let name = use_state(String::new);
+    let description = use_state(String::new);
+    let invitee = use_state(String::new);
+    let amount = use_state(String::new);
+    let recipient = use_state(String::new);
+    let reason = use_state(String::new);
+
+    {
+        let guild = guild.clone();
+        let invites = invites.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        use_effect_with(*reload, move |_| {
+            spawn_local(async move {
+                match fetch::<GuildDetails>("/api/guilds/mine").await {
+                    Ok(data) => guild.set(data),
+                    Err(e) => error.set(Some(e)),
+                }
+                if let Ok(data) = fetch::<Vec<PendingGuildInvite>>("/api/guilds/invites").await {
+                    invites.set(data.unwrap_or_default());
+                }
+                loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    {
+        let leaderboard = leaderboard.clone();
+        use_effect_with((*reload, *sort_by_score), move |(_, by_score)| {
+            let sort = if *by_score { "score" } else { "experience" };
+            let path = format!("/api/leaderboard/guilds?sort={}", sort);
+            spawn_local(async move {
+                if let Ok(data) = fetch::<Vec<GuildLeaderboardEntry>>(&path).await {
+                    leaderboard.set(data.unwrap_or_default());
+                }
+            });
+            || ()
+        });
+    }
+
+    // Every action reloads the page data afterwards, whether or not it succeeded
+    let act = {
+        let error = error.clone();
+        let reload = reload.clone();
+        move |method: &'static str, path: String, body: Option<serde_json::Value>| {
+            let error = error.clone();
+            let reload = reload.clone();
+            Callback::from(move |_: MouseEvent| {
+                let error = error.clone();
+                let reload = reload.clone();
+                let path = path.clone();
+                let body = body.clone();
+                spawn_local(async move {
+                    match send(method, &path, body).await {
+                        Ok(()) => error.set(None),
+                        Err(e) => error.set(Some(e)),
+                    }
+                    reload.set(*reload + 1);
+                });
+            })
+        }
+    };
+
+    let parsed_amount = amount.trim().parse::<i32>().unwrap_or(0);
+    let plain_button = "px-3 py-1 rounded-lg text-sm font-medium bg-gray-200 dark:bg-gray-700 text-gray-800 dark:text-gray-200";
+
+    let sort_toggle = {
+        let sort_by_score = sort_by_score.clone();
+        Callback::from(move |_: MouseEvent| sort_by_score.set(!*sort_by_score))
+    };
+
+    html! {
+        <Base>
+            <GradientBackground>
+                <div class="max-w-3xl mx-auto px-4 sm:px-6 lg:px-8 py-6 space-y-6">
+                    if let Some(message) = &*error {
+                        <div class={styles::ALERT_ERROR}>{message}</div>
+                    }
+
+                    if *loading {
+                        <div class="flex justify-center py-16">
+                            <div class={styles::LOADING_SPINNER}></div>
+                        </div>
+                    } else if let Some(data) = &*guild {
+                        <div class={styles::FOCUS_CARD}>
+                            <div class="flex items-start justify-between gap-4">
+                                <div>
+                                    <h1 class="text-3xl font-bold text-gray-900 dark:text-white">{&data.name}</h1>
+                                    if !data.description.is_empty() {
+                                        <p class={styles::TEXT_SECONDARY}>{&data.description}</p>
+                                    }
+                                </div>
+                                <span class="px-3 py-1 rounded-lg text-sm font-medium bg-purple-100 dark:bg-purple-900/30 text-purple-700 dark:text-purple-400 capitalize">
+                                    {&data.my_role}
+                                </span>
+                            </div>
+                            <div class="mt-6 flex items-end gap-2">
+                                <div class="flex-1">
+                                    <p class={styles::TEXT_SMALL}>{"Treasury"}</p>
+                                    <p class={styles::TEXT_H3}>{format!("{} pax", data.treasury)}</p>
+                                </div>
+                                <div class="w-32">{text_input(&amount, "Amount")}</div>
+                                <button class={classes!(styles::BUTTON_PRIMARY, "mt-2")} disabled={parsed_amount <= 0}
+                                    onclick={act("POST", "/api/guilds/treasury/deposit".to_string(), Some(json!({ "amount": parsed_amount })))}>
+                                    {"Deposit"}
+                                </button>
+                            </div>
+                            if data.my_role != "member" {
+                                <div class="mt-4 flex items-end gap-2">
+                                    <div class="flex-1">{text_input(&recipient, "Pay member")}</div>
+                                    <div class="flex-1">{text_input(&reason, "Reason")}</div>
+                                    <button class={classes!(styles::BUTTON_SECONDARY, "mt-2")} disabled={parsed_amount <= 0 || recipient.trim().is_empty()}
+                                        onclick={act("POST", "/api/guilds/treasury/spend".to_string(), Some(json!({
+                                            "recipient_username": recipient.trim(),
+                                            "amount": parsed_amount,
+                                            "reason": reason.trim(),
+                                        })))}>
+                                        {"Pay"}
+                                    </button>
+                                </div>
+                            }
+                        </div>
+
+                        <div class={styles::FOCUS_CARD}>
+                            <h2 class={styles::FOCUS_CARD_TITLE}>{format!("Members ({})", data.members.len())}</h2>
+                            <div class="divide-y divide-gray-200 dark:divide-gray-700">
+                                {for data.members.iter().map(|member| {
+                                    let is_leader = data.my_role == "leader";
+                                    let can_remove = match member.role.as_str() {
+                                        "member" => data.my_role != "member",
+                                        "officer" => is_leader,
+                                        _ => false,
+                                    };
+                                    let role_path = format!("/api/guilds/members/{}/role", member.user_id);
+                                    html! {
+                                        <div key={member.user_id.to_string()} class="flex items-center justify-between py-2 gap-2">
+                                            <div>
+                                                <span class={styles::TEXT_BODY}><PlayerLink username={member.username.clone()} /></span>
+                                                <p class={styles::TEXT_SMALL}>{format!("{} · {} XP · joined {}", member.role, member.experience, member.joined_at)}</p>
+                                            </div>
+                                            <div class="flex gap-2">
+                                                if is_leader && member.role == "member" {
+                                                    <button class={plain_button} onclick={act("POST", role_path.clone(), Some(json!({ "role": "officer" })))}>{"Promote"}</button>
+                                                }
+                                                if is_leader && member.role == "officer" {
+                                                    <button class={plain_button} onclick={act("POST", role_path.clone(), Some(json!({ "role": "leader" })))}>{"Make leader"}</button>
+                                                    <button class={plain_button} onclick={act("POST", role_path.clone(), Some(json!({ "role": "member" })))}>{"Demote"}</button>
+                                                }
+                                                if can_remove {
+                                                    <button class={plain_button} onclick={act("DELETE", format!("/api/guilds/members/{}", member.user_id), None)}>{"Remove"}</button>
+                                                }
+                                            </div>
+                                        </div>
+                                    }
+                                })}
+                            </div>
+                            if data.my_role != "member" {
+                                <div class="mt-4 flex items-end gap-2">
+                                    <div class="flex-1">{text_input(&invitee, "Invite by username")}</div>
+                                    <button class={classes!(styles::BUTTON_PRIMARY, "mt-2")} disabled={invitee.trim().is_empty()}
+                                        onclick={act("POST", "/api/guilds/invites".to_string(), Some(json!({ "username": invitee.trim() })))}>
+                                        {"Invite"}
+                                    </button>
+                                </div>
+                                if !data.pending_invites.is_empty() {
+                                    <p class={classes!(styles::TEXT_SMALL, "mt-2")}>{format!("Invited: {}", data.pending_invites.join(", "))}</p>
+                                }
+                            }
+                        </div>
+
+                        if !data.treasury_log.is_empty() {
+                            <div class={styles::FOCUS_CARD}>
+                                <h2 class={styles::FOCUS_CARD_TITLE}>{"Treasury history"}</h2>
+                                <div class="divide-y divide-gray-200 dark:divide-gray-700">
+                                    {for data.treasury_log.iter().map(|entry| html! {
+                                        <div class="flex justify-between py-2 gap-4">
+                                            <span class={styles::TEXT_BODY}>{describe_entry(entry)}</span>
+                                            <span class={classes!(styles::TEXT_SMALL, "whitespace-nowrap")}>
+                                                {format!("{} · {} pax left", entry.created_at, entry.treasury_after)}
+                                            </span>
+                                        </div>
+                                    })}
+                                </div>
+                            </div>
+                        }
+
+                        <div class="flex justify-end">
+                            <button class={styles::BUTTON_DANGER} onclick={act("POST", "/api/guilds/leave".to_string(), None)}>
+                                {if data.my_role == "leader" && data.members.len() == 1 { "Disband guild" } else { "Leave guild" }}
+                            </button>
+                        </div>
+                    } else {
+                        if !invites.is_empty() {
+                            <div class={styles::FOCUS_CARD}>
+                                <h2 class={styles::FOCUS_CARD_TITLE}>{"Invitations"}</h2>
+                                {for invites.iter().map(|invite| html! {
+                                    <div key={invite.guild_id.to_string()} class="flex items-center justify-between py-2">
+                                        <div>
+                                            <span class={styles::TEXT_BODY}>{&invite.guild_name}</span>
+                                            <p class={styles::TEXT_SMALL}>{format!("Invited by {} · {} members", invite.invited_by, invite.member_count)}</p>
+                                        </div>
+                                        <div class="flex gap-2">
+                                            <button class={styles::BUTTON_PRIMARY} onclick={act("POST", format!("/api/guilds/invites/{}/accept", invite.guild_id), None)}>{"Join"}</button>
+                                            <button class={styles::BUTTON_SECONDARY} onclick={act("DELETE", format!("/api/guilds/invites/{}", invite.guild_id), None)}>{"Decline"}</button>
+                                        </div>
+                                    </div>
+                                })}
+                            </div>
+                        }
+
+                        <div class={styles::FOCUS_CARD}>
+                            <h2 class={styles::FOCUS_CARD_TITLE}>{"Found a guild"}</h2>
+                            <p class={styles::TEXT_SECONDARY}>{"Pool pax in a shared treasury and climb the guild leaderboard together."}</p>
+                            {text_input(&name, "Guild name")}
+                            {text_input(&description, "Description (optional)")}
+                            <button class={classes!(styles::BUTTON_PRIMARY, "mt-4")} disabled={name.trim().is_empty()}
+                                onclick={act("POST", "/api/guilds".to_string(), Some(json!({
+                                    "name": name.trim(),
+                                    "description": description.trim(),
+                                })))}>
+                                {"Create guild"}
+                            </button>
+                        </div>
+                    }
+
+                    <div class={styles::FOCUS_CARD}>
+                        <div class="flex items-center justify-between mb-4">
+                            <h2 class={styles::FOCUS_CARD_TITLE}>{"Guild leaderboard"}</h2>
+                            <button class={plain_button} onclick={sort_toggle}>
+                                {if *sort_by_score { "Ranked by game scores" } else { "Ranked by XP" }}
+                            </button>
+                        </div>
+                        if leaderboard.is_empty() {
+                            <p class={styles::TEXT_SECONDARY}>{"No guilds yet."}</p>
+                        }
+                        <div class="divide-y divide-gray-200 dark:divide-gray-700">
+                            {for leaderboard.iter().enumerate().map(|(index, entry)| html! {
+                                <div class="flex justify-between py-2">
+                                    <span class={styles::TEXT_BODY}>{format!("{}. {}", index + 1, entry.name)}</span>
+                                    <span class={styles::TEXT_SMALL}>
+                                        {format!("{} members · {} XP · {} points", entry.member_count, entry.total_experience, entry.total_score)}
+                                    </span>
+                                </div>
+                            })}
+                        </div>
+                    </div>
+                </div>
+            </GradientBackground>
+        </Base>
+    }
+}
@@ -1,5 +1,6 @@
 use yew::prelude::*;
 use crate::components::displays::DisplayItem;
+use shared::preferences::InventorySort;
 #[allow(unused_imports)]
 use wasm_bindgen::JsCast;
 
@@ -19,6 +20,17 @@ pub enum SortCriteria {
     Essence,
 }
 
+impl From<InventorySort> for SortCriteria {
+    fn from(sort: InventorySort) -> Self {
+        match sort {
+            InventorySort::Default => Self::Default,
+            InventorySort::Rarity => Self::Rarity,
+            InventorySort::Energy => Self::Energy,
+            InventorySort::Essence => Self::Essence,
+        }
+    }
+}
+
 impl SortCriteria {
     pub fn label(&self) -> &'static str {
         match self {
@@ -18,8 +18,9 @@ use wasm_bindgen_futures::spawn_local;
 use filters::{FilterBar, CollectionType, SortCriteria, sort_items};
 use uuid::Uuid;
 use crate::hooks::use_currency::use_currency;
+use crate::hooks::use_preferences::use_ui_preferences;
 use crate::config::get_api_base_url;
-use crate::components::GradientBackground;
+use crate::components::{GradientBackground, RecipesPanel, TradesPanel};
 use std::collections::HashMap;
 
 #[function_component(Inventory)]
@@ -35,7 +36,16 @@ pub fn inventory() -> Html {
     let hatch_state = use_state(HatchState::default);
     let _current_currency = use_currency();
     let collection_type = use_state(|| CollectionType::All);
-    let sort_criteria = use_state(|| SortCriteria::Default);
+    let ui_preferences = use_ui_preferences();
+    let sort_criteria = use_state(|| SortCriteria::from(ui_preferences.inventory_sort));
+    {
+        // Pick up the server copy of the preference once it arrives
+        let sort_criteria = sort_criteria.clone();
+        use_effect_with(ui_preferences.inventory_sort, move |sort| {
+            sort_criteria.set(SortCriteria::from(*sort));
+            || ()
+        });
+    }
     let sort_ascending = use_state(|| false);
     let scrolls = use_state(Vec::new);
     let listed_creature_ids = use_state(|| Vec::new());
@@ -315,6 +325,22 @@ pub fn inventory() -> Html {
                                 })}
                             </div>
                         </div>
+
+                        <div class="mt-12">
+                            <RecipesPanel
+                                eggs={(*eggs).clone()}
+                                creatures={(*creatures).clone()}
+                                on_crafted={fetch_data.reform(|_| ())}
+                            />
+                        </div>
+
+                        <div class="mt-12">
+                            <TradesPanel
+                                eggs={(*eggs).clone()}
+                                creatures={(*creatures).clone()}
+                                on_traded={fetch_data.reform(|_| ())}
+                            />
+                        </div>
                     </div>
                 </div>
 
@@ -366,7 +392,7 @@ pub fn inventory() -> Html {
                                             );
                                             Some(Callback::from(move |_| item_handle_hatch.emit(egg_id)))
                                         },
-                                        DisplayItem::Scroll(scroll) => {
+                                        DisplayItem::Scroll(scroll) if scroll.display_name == "Summoning Scroll" => {
                                              let scroll_clone = scroll.clone();
                                              let summon_handle_close = modal_handle_close.clone();
                                              let summon_eggs = modal_eggs.clone();
@@ -390,7 +416,7 @@ pub fn inventory() -> Html {
                                 action_label={
                                     match item_to_display {
                                         DisplayItem::Egg(_) => Some("Hatch Egg".to_string()),
-                                        DisplayItem::Scroll(_) => Some("Summon Egg (100 pax)".to_string()),
+                                        DisplayItem::Scroll(scroll) if scroll.display_name == "Summoning Scroll" => Some("Summon Egg (100 pax)".to_string()),
                                         _ => None,
                                     }
                                 }
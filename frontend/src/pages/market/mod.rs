@@ -18,6 +18,8 @@ mod handlers;
 mod refresh;
 mod orderbook_modal;
 use orderbook_modal::OrderbookModal;
+mod price_chart;
+use price_chart::PriceChart;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct MarketListing {
@@ -463,6 +465,8 @@ pub fn market() -> Html {
                             }
                         }}
 
+                        <PriceChart />
+
                         <div class="mt-8">
                             <div class="grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-6">
                                 {for (*listings).iter().filter_map(|listing| {
@@ -25,9 +25,12 @@ fn get_current_time() -> f64 {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
-pub struct AggregatedOrder {
+pub struct OrderBookEntry {
     pub side: String,
     pub price: i32,
+    pub quantity: i32,
+    pub remaining: i32,
+    pub depth_at_price: i64,
     pub id: String,
     pub user_id: String,
     pub username: String,
@@ -119,7 +122,7 @@ fn base64_decode_url_safe(input: &str) -> String {
     }
 }
 
-async fn fetch_orders(token: &str) -> Result<Vec<AggregatedOrder>, String> {
+async fn fetch_orders(token: &str) -> Result<Vec<OrderBookEntry>, String> {
     match Request::get(&format!("{}/api/scrolls/orders", get_api_base_url()))
         .header("Authorization", &format!("Bearer {}", token))
         .send()
@@ -128,7 +131,7 @@ async fn fetch_orders(token: &str) -> Result<Vec<AggregatedOrder>, String> {
         Ok(response) => {
             match response.status() {
                 200 => {
-                    match response.json::<Vec<AggregatedOrder>>().await {
+                    match response.json::<Vec<OrderBookEntry>>().await {
                         Ok(data) => Ok(data),
                         Err(e) => Err(format!("Failed to parse response: {:?}", e))
                     }
@@ -141,9 +144,11 @@ async fn fetch_orders(token: &str) -> Result<Vec<AggregatedOrder>, String> {
     }
 }
 
-async fn fulfill_order(token: &str, order_id: &str) -> Result<String, String> {
+async fn fulfill_order(token: &str, order_id: &str, quantity: i32) -> Result<String, String> {
     match Request::post(&format!("{}/api/scrolls/orders/{}/fulfill", get_api_base_url(), order_id))
         .header("Authorization", &format!("Bearer {}", token))
+        .json(&serde_json::json!({ "quantity": quantity }))
+        .expect("Failed to serialize payload")
         .send()
         .await
     {
@@ -268,9 +273,12 @@ async fn cancel_order(token: &str, order_id: &str) -> Result<String, String> {
 
 #[function_component(OrderbookModal)]
 pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
-    let orders = use_state(|| Vec::<AggregatedOrder>::new());
+    let orders = use_state(|| Vec::<OrderBookEntry>::new());
     let sell_price_input = use_state(|| "".to_string());
     let buy_price_input = use_state(|| "".to_string());
+    let sell_quantity_input = use_state(|| "1".to_string());
+    let buy_quantity_input = use_state(|| "1".to_string());
+    let fill_quantity_input = use_state(|| "1".to_string());
     let sell_error = use_state(|| "".to_string());
     let buy_error = use_state(|| "".to_string());
     let sell_loading = use_state(|| false);
@@ -456,12 +464,12 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
     }
 
     // Split orders into sell and buy lists
-    let sell_orders: Vec<AggregatedOrder> = (*orders)
+    let sell_orders: Vec<OrderBookEntry> = (*orders)
         .clone()
         .into_iter()
         .filter(|order| order.side == "sell")
         .collect();
-    let buy_orders: Vec<AggregatedOrder> = (*orders)
+    let buy_orders: Vec<OrderBookEntry> = (*orders)
         .clone()
         .into_iter()
         .filter(|order| order.side == "buy")
@@ -571,9 +579,37 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
         })
     };
 
+    let on_sell_quantity_input = {
+        let sell_quantity_input = sell_quantity_input.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                sell_quantity_input.set(input.value());
+            }
+        })
+    };
+
+    let on_buy_quantity_input = {
+        let buy_quantity_input = buy_quantity_input.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                buy_quantity_input.set(input.value());
+            }
+        })
+    };
+
+    let on_fill_quantity_input = {
+        let fill_quantity_input = fill_quantity_input.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                fill_quantity_input.set(input.value());
+            }
+        })
+    };
+
     // Add a callback for fulfilling orders
     let on_fulfill_order = {
         let token = get_token();
+        let fill_quantity_input = fill_quantity_input.clone();
         let orders = orders.clone();
         let fulfill_loading = fulfill_loading.clone();
         let fulfill_message = fulfill_message.clone();
@@ -587,11 +623,19 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
             let fulfill_message = fulfill_message.clone();
             let currency = currency.clone();
             let scrolls = scrolls.clone();
+
+            let quantity = match fill_quantity_input.parse::<i32>() {
+                Ok(q) if q > 0 => q,
+                _ => {
+                    fulfill_message.set("Error: Invalid quantity".to_string());
+                    return;
+                }
+            };
             
             fulfill_loading.set(Some(order_id.clone()));
             
             spawn_local(async move {
-                match fulfill_order(&token, &order_id).await {
+                match fulfill_order(&token, &order_id, quantity).await {
                     Ok(message) => {
                         fulfill_message.set(message);
                         let token_for_orders = token.clone();
@@ -665,6 +709,7 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
     let on_sell_order = {
         let token = get_token();
         let sell_price_input = sell_price_input.clone();
+        let sell_quantity_input = sell_quantity_input.clone();
         let sell_error = sell_error.clone();
         let sell_loading = sell_loading.clone();
         let orders = orders.clone();
@@ -674,6 +719,7 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
         Callback::from(move |_| {
             let token = token.clone();
             let sell_price_input = sell_price_input.clone();
+            let sell_quantity_input = sell_quantity_input.clone();
             let sell_error = sell_error.clone();
             let sell_loading = sell_loading.clone();
             let orders = orders.clone();
@@ -687,6 +733,14 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                     return;
                 }
             };
+
+            let quantity = match sell_quantity_input.parse::<i32>() {
+                Ok(q) if q > 0 => q,
+                _ => {
+                    sell_error.set("Invalid quantity".to_string());
+                    return;
+                }
+            };
             
             sell_loading.set(true);
             sell_error.set("".to_string());
@@ -694,6 +748,7 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
             let payload = CreateOrderPayload {
                 side: "sell".to_string(),
                 price,
+                quantity,
             };
             
             spawn_local(async move {
@@ -708,6 +763,7 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                         match response.status() {
                             200 | 201 => {
                                 sell_price_input.set("".to_string());
+                                sell_quantity_input.set("1".to_string());
                                 let token_for_orders = token.clone();
                                 spawn_local(async move {
                                     let _ = refresh_orders(token_for_orders, orders).await;
@@ -742,17 +798,21 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
     let on_buy_order = {
         let token = get_token();
         let buy_price_input = buy_price_input.clone();
+        let buy_quantity_input = buy_quantity_input.clone();
         let buy_error = buy_error.clone();
         let buy_loading = buy_loading.clone();
         let orders = orders.clone();
+        let scrolls = scrolls.clone();
         let currency = currency.clone();
         
         Callback::from(move |_| {
             let token = token.clone();
             let buy_price_input = buy_price_input.clone();
+            let buy_quantity_input = buy_quantity_input.clone();
             let buy_error = buy_error.clone();
             let buy_loading = buy_loading.clone();
             let orders = orders.clone();
+            let scrolls = scrolls.clone();
             let currency = currency.clone();
             
             let price = match buy_price_input.parse::<i32>() {
@@ -762,6 +822,14 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                     return;
                 }
             };
+
+            let quantity = match buy_quantity_input.parse::<i32>() {
+                Ok(q) if q > 0 => q,
+                _ => {
+                    buy_error.set("Invalid quantity".to_string());
+                    return;
+                }
+            };
             
             buy_loading.set(true);
             buy_error.set("".to_string());
@@ -769,6 +837,7 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
             let payload = CreateOrderPayload {
                 side: "buy".to_string(),
                 price,
+                quantity,
             };
             
             spawn_local(async move {
@@ -783,10 +852,13 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                         match response.status() {
                             200 | 201 => {
                                 buy_price_input.set("".to_string());
+                                buy_quantity_input.set("1".to_string());
                                 let token_for_orders = token.clone();
                                 spawn_local(async move {
                                     let _ = refresh_orders(token_for_orders, orders).await;
                                 });
+                                // The order may have matched immediately
+                                let _ = refresh_scrolls(&token, scrolls).await;
                                 let _ = refresh_currency(&token, currency).await;
                             },
                             400 => {
@@ -837,6 +909,18 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                         </button>
                     </div>
 
+                    <div class="flex items-center gap-2 mb-4">
+                        <span class="text-sm text-gray-600 dark:text-gray-400">{"Fill up to"}</span>
+                        <input 
+                            type="number" 
+                            min="1"
+                            class="w-20 p-1 bg-white dark:bg-gray-800 border dark:border-gray-600 rounded-lg text-gray-900 dark:text-gray-100 text-sm [appearance:textfield] [&::-webkit-outer-spin-button]:appearance-none [&::-webkit-inner-spin-button]:appearance-none"
+                            value={(*fill_quantity_input).clone()}
+                            oninput={on_fill_quantity_input} 
+                        />
+                        <span class="text-sm text-gray-600 dark:text-gray-400">{"scrolls when buying or selling into an order"}</span>
+                    </div>
+
                     if !(*fulfill_message).is_empty() {
                         <div class="mb-4 p-3 bg-blue-100 dark:bg-blue-900 text-blue-800 dark:text-blue-200 rounded-lg">
                             {(*fulfill_message).clone()}
@@ -851,7 +935,18 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                                 </h3>
                                 <div class="flex items-end gap-3">
                                     <div class="flex flex-col">
-                                        <span class="text-xs text-gray-300 mb-1">{"1 scroll for"}</span>
+                                        <span class="text-xs text-gray-300 mb-1">{"Scrolls"}</span>
+                                        <input 
+                                            type="number" 
+                                            min="1"
+                                            placeholder="(qty)" 
+                                            class="w-20 p-2 bg-white dark:bg-gray-800 border dark:border-gray-600 rounded-lg text-gray-900 dark:text-gray-100 placeholder-gray-500 dark:placeholder-gray-400 text-sm [appearance:textfield] [&::-webkit-outer-spin-button]:appearance-none [&::-webkit-inner-spin-button]:appearance-none"
+                                            value={(*sell_quantity_input).clone()}
+                                            oninput={on_sell_quantity_input.clone()} 
+                                        />
+                                    </div>
+                                    <div class="flex flex-col">
+                                        <span class="text-xs text-gray-300 mb-1">{"for each at"}</span>
                                         <input 
                                             type="number" 
                                             placeholder="(price)" 
@@ -876,8 +971,10 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                             <h3 class="text-xl font-semibold text-red-600 dark:text-red-400 mb-3">{"Sell Orders"}</h3>
                             if !sorted_sell.is_empty() {
                                 <div class="bg-white dark:bg-gray-800 rounded-xl p-4">
-                                    <div class="grid grid-cols-[1fr,auto,auto] gap-4">
+                                    <div class="grid grid-cols-[1fr,auto,auto,auto,auto] gap-4">
                                         <div class="font-medium text-gray-600 dark:text-gray-400 text-sm">{"Price"}</div>
+                                        <div class="font-medium text-gray-600 dark:text-gray-400 text-sm">{"Qty"}</div>
+                                        <div class="font-medium text-gray-600 dark:text-gray-400 text-sm">{"Depth"}</div>
                                         <div class="font-medium text-gray-600 dark:text-gray-400 text-sm">{"Seller"}</div>
                                         <div class="font-medium text-gray-600 dark:text-gray-400 text-sm text-right">{"Action"}</div>
                                         { for sorted_sell.iter().map(|order| {
@@ -891,6 +988,8 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                                             html! {
                                                 <>
                                                     <div class="text-gray-900 dark:text-gray-100 font-medium">{ order.price }</div>
+                                                    <div class="text-gray-600 dark:text-gray-400">{ format!("{}/{}", order.remaining, order.quantity) }</div>
+                                                    <div class="text-gray-600 dark:text-gray-400">{ order.depth_at_price }</div>
                                                     <div class="text-gray-600 dark:text-gray-400">{ &order.username }</div>
                                                     <div class="text-right">
                                                         {
@@ -941,7 +1040,18 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                                 </h3>
                                 <div class="flex items-end gap-3">
                                     <div class="flex flex-col">
-                                        <span class="text-xs text-gray-300 mb-1">{"1 scroll for"}</span>
+                                        <span class="text-xs text-gray-300 mb-1">{"Scrolls"}</span>
+                                        <input 
+                                            type="number" 
+                                            min="1"
+                                            placeholder="(qty)" 
+                                            class="w-20 p-2 bg-white dark:bg-gray-800 border dark:border-gray-600 rounded-lg text-gray-900 dark:text-gray-100 placeholder-gray-500 dark:placeholder-gray-400 text-sm [appearance:textfield] [&::-webkit-outer-spin-button]:appearance-none [&::-webkit-inner-spin-button]:appearance-none"
+                                            value={(*buy_quantity_input).clone()}
+                                            oninput={on_buy_quantity_input.clone()} 
+                                        />
+                                    </div>
+                                    <div class="flex flex-col">
+                                        <span class="text-xs text-gray-300 mb-1">{"for each at"}</span>
                                         <input 
                                             type="number" 
                                             placeholder="(price)" 
@@ -966,8 +1076,10 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                             <h3 class="text-xl font-semibold text-green-600 dark:text-green-400 mb-3">{"Buy Orders"}</h3>
                             if !sorted_buy.is_empty() {
                                 <div class="bg-white dark:bg-gray-800 rounded-xl p-4">
-                                    <div class="grid grid-cols-[1fr,auto,auto] gap-4">
+                                    <div class="grid grid-cols-[1fr,auto,auto,auto,auto] gap-4">
                                         <div class="font-medium text-gray-600 dark:text-gray-400 text-sm">{"Price"}</div>
+                                        <div class="font-medium text-gray-600 dark:text-gray-400 text-sm">{"Qty"}</div>
+                                        <div class="font-medium text-gray-600 dark:text-gray-400 text-sm">{"Depth"}</div>
                                         <div class="font-medium text-gray-600 dark:text-gray-400 text-sm">{"Buyer"}</div>
                                         <div class="font-medium text-gray-600 dark:text-gray-400 text-sm text-right">{"Action"}</div>
                                         { for sorted_buy.iter().map(|order| {
@@ -981,6 +1093,8 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
                                             html! {
                                                 <>
                                                     <div class="text-gray-900 dark:text-gray-100 font-medium">{ order.price }</div>
+                                                    <div class="text-gray-600 dark:text-gray-400">{ format!("{}/{}", order.remaining, order.quantity) }</div>
+                                                    <div class="text-gray-600 dark:text-gray-400">{ order.depth_at_price }</div>
                                                     <div class="text-gray-600 dark:text-gray-400">{ &order.username }</div>
                                                     <div class="text-right">
                                                         {
@@ -1035,9 +1149,10 @@ pub fn orderbook_modal(props: &OrderbookModalProps) -> Html {
 struct CreateOrderPayload {
     side: String,
     price: i32,
+    quantity: i32,
 }
 
-async fn refresh_orders(token: String, orders: UseStateHandle<Vec<AggregatedOrder>>) {
+async fn refresh_orders(token: String, orders: UseStateHandle<Vec<OrderBookEntry>>) {
     web_sys::console::log_1(&"Refreshing orders...".into());
     
     match Request::get(&format!("{}/api/scrolls/orders", get_api_base_url()))
@@ -1048,7 +1163,7 @@ async fn refresh_orders(token: String, orders: UseStateHandle<Vec<AggregatedOrde
         Ok(response) => {
             web_sys::console::log_1(&format!("Orders response status: {}", response.status()).into());
             
-            match response.json::<Vec<AggregatedOrder>>().await {
+            match response.json::<Vec<OrderBookEntry>>().await {
                 Ok(data) => {
                     web_sys::console::log_1(&format!("Received {} orders", data.len()).into());
                     orders.set(data);
@@ -0,0 +1,269 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::Deserialize;
+use wasm_bindgen_futures::spawn_local;
+use crate::config::get_api_base_url;
+use crate::hooks::auth_state::use_auth_token;
+
+const CHART_WIDTH: f64 = 800.0;
+const CHART_HEIGHT: f64 = 240.0;
+const VOLUME_HEIGHT: f64 = 50.0;
+
+const INTERVALS: [&str; 3] = ["1h", "1d", "1w"];
+const RARITIES: [&str; 6] = ["Common", "Uncommon", "Rare", "Epic", "Legendary", "Mythical"];
+const ESSENCES: [&str; 15] = [
+    "Celestial", "Ancient", "Psychic", "Undead", "Fairy", "Dark", "Electric",
+    "Fire", "Toxic", "Construct", "Air", "Earth", "Plant", "Water", "Fungal",
+];
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Candle {
+    pub bucket_start: String,
+    pub open: i32,
+    pub high: i32,
+    pub low: i32,
+    pub close: i32,
+    pub volume: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryData {
+    candles: Vec<Candle>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryResponse {
+    success: bool,
+    data: Option<HistoryData>,
+    error: Option<String>,
+}
+
+fn select_value(e: &Event) -> Option<String> {
+    e.target_dyn_into::<web_sys::HtmlSelectElement>().map(|select| select.value())
+}
+
+/// Bucket label without the seconds and offset, e.g. "2024-05-01 13:00".
+fn bucket_label(bucket_start: &str) -> &str {
+    bucket_start.get(..16).unwrap_or(bucket_start)
+}
+
+fn render_candles(candles: &[Candle]) -> Html {
+    let high = candles.iter().map(|c| c.high).max().unwrap_or(0) as f64;
+    let low = candles.iter().map(|c| c.low).min().unwrap_or(0) as f64;
+    let range = (high - low).max(1.0);
+    let max_volume = candles.iter().map(|c| c.volume).max().unwrap_or(1).max(1) as f64;
+    let slot = CHART_WIDTH / candles.len() as f64;
+    let body_width = (slot * 0.6).max(1.0);
+    let y = |price: i32| (high - price as f64) / range * (CHART_HEIGHT - 10.0) + 5.0;
+
+    html! {
+        <svg
+            viewBox={format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT + VOLUME_HEIGHT)}
+            class="w-full h-72"
+            preserveAspectRatio="none"
+        >
+            {for candles.iter().enumerate().map(|(i, candle)| {
+                let center = slot * i as f64 + slot / 2.0;
+                let rising = candle.close >= candle.open;
+                let color = if rising { "#22c55e" } else { "#ef4444" };
+                let body_top = y(candle.open.max(candle.close));
+                let body_height = (y(candle.open.min(candle.close)) - body_top).max(1.0);
+                let volume_height = candle.volume as f64 / max_volume * (VOLUME_HEIGHT - 5.0);
+
+                html! {
+                    <g>
+                        <title>{format!("{}\nO {} H {} L {} C {}\nVolume {}",
+                            bucket_label(&candle.bucket_start), candle.open, candle.high, candle.low, candle.close, candle.volume)}</title>
+                        <line
+                            x1={center.to_string()} x2={center.to_string()}
+                            y1={y(candle.high).to_string()} y2={y(candle.low).to_string()}
+                            stroke={color} stroke-width="1"
+                        />
+                        <rect
+                            x={(center - body_width / 2.0).to_string()} y={body_top.to_string()}
+                            width={body_width.to_string()} height={body_height.to_string()}
+                            fill={color}
+                        />
+                        <rect
+                            x={(center - body_width / 2.0).to_string()}
+                            y={(CHART_HEIGHT + VOLUME_HEIGHT - volume_height).to_string()}
+                            width={body_width.to_string()} height={volume_height.to_string()}
+                            fill="#6366f1" opacity="0.5"
+                        />
+                    </g>
+                }
+            })}
+        </svg>
+    }
+}
+
+#[function_component(PriceChart)]
+pub fn price_chart() -> Html {
+    let token = use_auth_token();
+    let kind = use_state(|| "scroll".to_string());
+    let interval = use_state(|| "1d".to_string());
+    let rarity = use_state(String::new);
+    let essence = use_state(String::new);
+    let candles = use_state(Vec::<Candle>::new);
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+
+    {
+        let candles = candles.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+
+        use_effect_with(
+            ((*kind).clone(), (*interval).clone(), (*rarity).clone(), (*essence).clone(), token.clone()),
+            move |(kind, interval, rarity, essence, token)| {
+                let mut url = format!("{}/api/market/history?kind={}&interval={}", get_api_base_url(), kind, interval);
+                if kind == "creature" && !rarity.is_empty() {
+                    url.push_str(&format!("&rarity={}", rarity));
+                }
+                if kind != "scroll" && !essence.is_empty() {
+                    url.push_str(&format!("&essence={}", essence));
+                }
+                let token = token.clone();
+                loading.set(true);
+
+                spawn_local(async move {
+                    match Request::get(&url)
+                        .header("Authorization", &format!("Bearer {}", token))
+                        .send()
+                        .await
+                    {
+                        Ok(response) => match response.json::<HistoryResponse>().await {
+                            Ok(HistoryResponse { success: true, data: Some(data), .. }) => {
+                                candles.set(data.candles);
+                                error.set(None);
+                            },
+                            Ok(result) => {
+                                error.set(Some(result.error.unwrap_or_else(|| "Failed to load price history".to_string())));
+                            },
+                            Err(e) => {
+                                log::error!("Failed to parse price history: {:?}", e);
+                                error.set(Some("Failed to load price history".to_string()));
+                            }
+                        },
+                        Err(e) => {
+                            log::error!("Failed to fetch price history: {:?}", e);
+                            error.set(Some("Failed to load price history".to_string()));
+                        }
+                    }
+                    loading.set(false);
+                });
+
+                || ()
+            },
+        );
+    }
+
+    let on_kind_change = {
+        let kind = kind.clone();
+        let rarity = rarity.clone();
+        let essence = essence.clone();
+        Callback::from(move |e: Event| {
+            if let Some(value) = select_value(&e) {
+                kind.set(value);
+                rarity.set(String::new());
+                essence.set(String::new());
+            }
+        })
+    };
+
+    let on_rarity_change = {
+        let rarity = rarity.clone();
+        Callback::from(move |e: Event| {
+            if let Some(value) = select_value(&e) {
+                rarity.set(value);
+            }
+        })
+    };
+
+    let on_essence_change = {
+        let essence = essence.clone();
+        Callback::from(move |e: Event| {
+            if let Some(value) = select_value(&e) {
+                essence.set(value);
+            }
+        })
+    };
+
+    let select_class = "p-2 bg-white dark:bg-gray-800 border dark:border-gray-600 rounded-lg text-gray-900 dark:text-gray-100 text-sm";
+    let last = candles.last();
+    let total_volume: i64 = candles.iter().map(|c| c.volume).sum();
+
+    html! {
+        <div class="mt-8 p-6 bg-white/80 dark:bg-gray-800/80 rounded-2xl shadow-lg">
+            <div class="flex flex-col md:flex-row md:items-center md:justify-between gap-4 mb-4">
+                <div>
+                    <h2 class="text-2xl font-bold text-gray-900 dark:text-white">{"Price History"}</h2>
+                    if let Some(last) = last {
+                        <p class="text-sm text-gray-600 dark:text-gray-400">
+                            {format!("Last close {} pax · {} sold in range", last.close, total_volume)}
+                        </p>
+                    }
+                </div>
+                <div class="flex flex-wrap items-center gap-2">
+                    <select class={select_class} onchange={on_kind_change}>
+                        <option value="scroll" selected={*kind == "scroll"}>{"Scrolls"}</option>
+                        <option value="creature" selected={*kind == "creature"}>{"Creatures"}</option>
+                        <option value="egg" selected={*kind == "egg"}>{"Eggs"}</option>
+                    </select>
+                    if *kind == "creature" {
+                        <select class={select_class} onchange={on_rarity_change}>
+                            <option value="" selected={rarity.is_empty()}>{"Any rarity"}</option>
+                            {for RARITIES.iter().map(|r| html! {
+                                <option value={*r} selected={*rarity == *r}>{*r}</option>
+                            })}
+                        </select>
+                    }
+                    if *kind != "scroll" {
+                        <select class={select_class} onchange={on_essence_change}>
+                            <option value="" selected={essence.is_empty()}>{"Any essence"}</option>
+                            {for ESSENCES.iter().map(|e| html! {
+                                <option value={*e} selected={*essence == *e}>{*e}</option>
+                            })}
+                        </select>
+                    }
+                    <div class="flex rounded-lg overflow-hidden border dark:border-gray-600">
+                        {for INTERVALS.iter().map(|i| {
+                            let interval = interval.clone();
+                            let active = *interval == *i;
+                            let value = i.to_string();
+                            html! {
+                                <button
+                                    onclick={Callback::from(move |_| interval.set(value.clone()))}
+                                    class={classes!(
+                                        "px-3", "py-1", "text-sm",
+                                        if active { "bg-blue-500 text-white" } else { "bg-white dark:bg-gray-800 text-gray-700 dark:text-gray-300" }
+                                    )}
+                                >
+                                    {*i}
+                                </button>
+                            }
+                        })}
+                    </div>
+                </div>
+            </div>
+
+            if *loading && candles.is_empty() {
+                <div class="flex justify-center items-center h-72">
+                    <div class="animate-spin rounded-full h-8 w-8 border-t-2 border-b-2 border-blue-500" />
+                </div>
+            } else if let Some(err) = &*error {
+                <div class="p-4 bg-red-100 text-red-700 rounded-lg">{err}</div>
+            } else if candles.is_empty() {
+                <p class="h-72 flex items-center justify-center text-gray-500 dark:text-gray-400">
+                    {"No sales in this range yet"}
+                </p>
+            } else {
+                {render_candles(&candles)}
+                <div class="flex justify-between text-xs text-gray-500 dark:text-gray-400 mt-1">
+                    <span>{candles.first().map(|c| bucket_label(&c.bucket_start).to_string()).unwrap_or_default()}</span>
+                    <span>{last.map(|c| bucket_label(&c.bucket_start).to_string()).unwrap_or_default()}</span>
+                </div>
+            }
+        </div>
+    }
+}
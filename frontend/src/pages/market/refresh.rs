@@ -5,67 +5,16 @@ use futures::future::join_all;
 use super::MarketListing;
 use super::ApiResponse;
 use wasm_bindgen_futures::spawn_local;
-use web_sys::window;
-use wasm_bindgen::JsValue;
+use web_sys::{window, CustomEvent, Event};
+use wasm_bindgen::JsCast;
+use gloo::events::EventListener;
+use shared::push_events::PushEvent;
+use crate::hooks::PUSH_EVENT;
 use log;
 use std::future::Future;
 use std::pin::Pin;
 use crate::config::get_api_base_url;
 
-thread_local! {
-    static LAST_REFRESH: std::cell::RefCell<f64> = std::cell::RefCell::new(0.0);
-}
-
-fn get_current_time() -> f64 {
-    if let Some(window) = window() {
-        if let Some(performance) = window.performance() {
-            return performance.now();
-        }
-    }
-    0.0
-}
-
-pub async fn update_currency(token: &str) -> bool {
-    let mut updated = false;
-    if let Ok(response) = Request::get(&format!("{}/api/profile", get_api_base_url()))
-        .header("Authorization", &format!("Bearer {}", token))
-        .send()
-        .await 
-    {
-        if response.status() == 200 {
-            if let Ok(profile) = response.json::<serde_json::Value>().await {
-                if let Some(balance) = profile.get("currency_balance").and_then(|b| b.as_i64()) {
-                    if let Some(window) = window() {
-                        if let Some(storage) = window.local_storage().ok().flatten() {
-                            // Check if the balance has changed
-                            if let Ok(Some(current_balance)) = storage.get_item("currency") {
-                                if let Ok(current_balance) = current_balance.parse::<i64>() {
-                                    if current_balance != balance {
-                                        updated = true;
-                                        log::info!("Currency balance changed from {} to {}", current_balance, balance);
-                                    }
-                                }
-                            }
-                            
-                            let _ = storage.set_item("currency", &balance.to_string());
-                        }
-                        
-                        let event_init = web_sys::CustomEventInit::new();
-                        event_init.set_detail(&JsValue::from_f64(balance as f64));
-                        if let Ok(event) = web_sys::CustomEvent::new_with_event_init_dict(
-                            "currencyUpdate",
-                            &event_init
-                        ) {
-                            let _ = window.dispatch_event(&event);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    updated
-}
-
 pub async fn fetch_listing_item(listing_id: &str, token: &str) -> Option<DisplayItem> {
     match Request::get(&format!("{}/api/market/listings/{}/item", get_api_base_url(), listing_id))
         .header("Authorization", &format!("Bearer {}", token))
@@ -97,9 +46,6 @@ async fn fetch_listings(
     is_refreshing.set(true);
     error.set(String::new()); // Clear any previous errors
 
-    // Update currency first
-    let _ = update_currency(token).await;
-
     match Request::get(&format!("{}/api/market/listings", get_api_base_url()))
         .header("Authorization", &format!("Bearer {}", token))
         .send()
@@ -175,7 +121,7 @@ async fn fetch_listings(
                     }
                 }
                 429 => {
-                    log::warn!("Rate limit hit, will retry in 3 seconds");
+                    log::warn!("Rate limit hit, will retry on the next market update");
                     error.set("Rate limit reached. Please wait...".to_string());
                 }
                 _ => {
@@ -192,12 +138,13 @@ async fn fetch_listings(
     is_refreshing.set(false);
 }
 
+/// Fetches listings now and again whenever the server pushes a market change.
 pub fn setup_refresh(
     token: String,
     listings: UseStateHandle<Vec<MarketListing>>,
     error: UseStateHandle<String>,
     is_refreshing: UseStateHandle<bool>,
-) -> gloo_timers::callback::Interval {
+) -> EventListener {
     // Immediate initial fetch
     let token_clone = token.clone();
     let listings_clone = listings.clone();
@@ -208,41 +155,26 @@ pub fn setup_refresh(
         fetch_listings(&token_clone, &listings_clone, &error_clone, &is_refreshing_clone).await;
     });
 
-    let handle_refresh = {
+    let window = window().expect("no global window exists");
+    EventListener::new(&window, PUSH_EVENT, move |event: &Event| {
+        let push_event = event
+            .dyn_ref::<CustomEvent>()
+            .and_then(|event| event.detail().as_string())
+            .and_then(|text| serde_json::from_str::<PushEvent>(&text).ok());
+
+        if !matches!(
+            push_event,
+            Some(PushEvent::MarketChanged | PushEvent::ListingSold { .. } | PushEvent::ListingBought { .. })
+        ) {
+            return;
+        }
+
         let listings = listings.clone();
         let token = token.clone();
         let error = error.clone();
         let is_refreshing = is_refreshing.clone();
-        
-        move || {
-            let listings = listings.clone();
-            let token = token.clone();
-            let error = error.clone();
-            let is_refreshing = is_refreshing.clone();
-            
-            // Check if enough time has passed since last refresh
-            let should_refresh = LAST_REFRESH.with(|last| {
-                let now = get_current_time();
-                let elapsed = now - *last.borrow();
-                if elapsed >= 3000.0 { // 3000ms = 3 seconds
-                    *last.borrow_mut() = now;
-                    true
-                } else {
-                    false
-                }
-            });
-
-            if !should_refresh {
-                return;
-            }
-            
-            spawn_local(async move {
-                fetch_listings(&token, &listings, &error, &is_refreshing).await;
-            });
-        }
-    };
-
-    // Set interval to 3 seconds
-    let interval = gloo_timers::callback::Interval::new(3_000, handle_refresh.clone());
-    interval
-} 
\ No newline at end of file
+        spawn_local(async move {
+            fetch_listings(&token, &listings, &error, &is_refreshing).await;
+        });
+    })
+}
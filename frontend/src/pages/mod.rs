@@ -2,6 +2,10 @@ pub mod games;
 pub mod auth;
 pub mod home;
 pub mod profile;
+pub mod public_profile;
+pub mod guild;
+pub mod battle;
+pub mod wallet;
 pub mod inventory;
 pub mod market;
 pub mod settings;
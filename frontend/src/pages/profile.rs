@@ -8,7 +8,7 @@ use web_sys::js_sys::{Date, Object, Reflect};
 use wasm_bindgen::JsValue;
 use crate::config::get_asset_url;
 use crate::config::get_api_base_url;
-use crate::components::GradientBackground;
+use crate::components::{GradientBackground, AchievementsPanel, FriendsPanel};
 
 #[derive(Deserialize, Clone, PartialEq)]
 pub struct UserProfile {
@@ -216,6 +216,8 @@ pub fn profile() -> Html {
                                     </div>
                                 </div>
                             </div>
+                            <AchievementsPanel />
+                            <FriendsPanel />
                         </div>
                     } else if !(*error).is_empty() {
                         <div class={styles::ALERT_ERROR}>{&*error}</div>
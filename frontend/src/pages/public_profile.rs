@@ -0,0 +1,194 @@
+use yew::prelude::*;
+use yew_router::prelude::Link;
+use gloo_net::http::Request;
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::{base::Base, styles};
+use crate::config::{get_api_base_url, get_asset_url};
+use crate::components::GradientBackground;
+use crate::Route;
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct ProfileCreature {
+    id: Uuid,
+    display_name: String,
+    image_path: String,
+    rarity: String,
+    soul: i32,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct GameHighScore {
+    game_type: String,
+    high_score: i32,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct WordGameSummary {
+    highest_streak: i32,
+    total_words_guessed: i32,
+    fastest_time: Option<i32>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct PublicProfileData {
+    username: String,
+    rank: String,
+    experience: i32,
+    joined_at: String,
+    is_member: bool,
+    achievement_count: i64,
+    top_creatures: Vec<ProfileCreature>,
+    high_scores: Vec<GameHighScore>,
+    word_game: Option<WordGameSummary>,
+}
+
+#[derive(Deserialize)]
+struct PublicProfileResponse {
+    success: bool,
+    data: Option<PublicProfileData>,
+    error: Option<String>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PublicProfileProps {
+    pub username: String,
+}
+
+fn game_label(game_type: &str) -> String {
+    match game_type {
+        "snake" => "Snake".to_string(),
+        "2048" => "2048".to_string(),
+        "hexort" => "Hexort".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[function_component(PublicProfile)]
+pub fn public_profile(props: &PublicProfileProps) -> Html {
+    let profile = use_state(|| None::<PublicProfileData>);
+    let error = use_state(|| None::<String>);
+    let loading = use_state(|| true);
+
+    {
+        let profile = profile.clone();
+        let error = error.clone();
+        let loading = loading.clone();
+
+        use_effect_with(props.username.clone(), move |username| {
+            let username = username.clone();
+            loading.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let url = format!("{}/api/users/{}", get_api_base_url(), String::from(js_sys::encode_uri_component(&username)));
+                match Request::get(&url).send().await {
+                    Ok(response) => match response.json::<PublicProfileResponse>().await {
+                        Ok(PublicProfileResponse { success: true, data: Some(data), .. }) => {
+                            profile.set(Some(data));
+                            error.set(None);
+                        },
+                        Ok(result) => error.set(Some(result.error.unwrap_or_else(|| "Player not found".to_string()))),
+                        Err(_) => error.set(Some("Failed to load profile".to_string())),
+                    },
+                    Err(e) => {
+                        log::error!("Failed to fetch public profile: {:?}", e);
+                        error.set(Some("Network error occurred.".to_string()));
+                    }
+                }
+                loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    html! {
+        <Base>
+            <GradientBackground>
+                <div class="max-w-3xl mx-auto px-4 sm:px-6 lg:px-8 py-6">
+                    if *loading {
+                        <div class="flex justify-center py-16">
+                            <div class={styles::LOADING_SPINNER}></div>
+                        </div>
+                    } else if let Some(message) = &*error {
+                        <div class={styles::FOCUS_CARD}>
+                            <p class={styles::TEXT_BODY}>{message}</p>
+                            <p class={styles::TEXT_SECONDARY}>{"The player may have made their profile private."}</p>
+                        </div>
+                    } else if let Some(user) = &*profile {
+                        <div class="space-y-6">
+                            <div class={styles::FOCUS_CARD}>
+                                <div class="flex items-center justify-between">
+                                    <div>
+                                        <h1 class="text-3xl font-bold text-gray-900 dark:text-white truncate max-w-[300px]">{&user.username}</h1>
+                                        <p class={styles::TEXT_SECONDARY}>{format!("Joined {}", user.joined_at)}</p>
+                                    </div>
+                                    if user.is_member {
+                                        <span class="px-3 py-1 rounded-lg text-sm font-medium bg-green-100 dark:bg-green-900/30 text-green-700 dark:text-green-400">
+                                            {"Member"}
+                                        </span>
+                                    }
+                                </div>
+                                <div class="mt-6 grid grid-cols-3 gap-4 text-center">
+                                    <div>
+                                        <p class={styles::TEXT_SMALL}>{"Rank"}</p>
+                                        <p class={format!("{} {}", styles::TEXT_H3, "text-purple-600 dark:text-purple-400")}>{&user.rank}</p>
+                                    </div>
+                                    <div>
+                                        <p class={styles::TEXT_SMALL}>{"Experience"}</p>
+                                        <p class={styles::TEXT_H3}>{format!("{} XP", user.experience)}</p>
+                                    </div>
+                                    <div>
+                                        <p class={styles::TEXT_SMALL}>{"Achievements"}</p>
+                                        <p class={styles::TEXT_H3}>{user.achievement_count}</p>
+                                    </div>
+                                </div>
+                            </div>
+
+                            if !user.top_creatures.is_empty() {
+                                <div class={styles::FOCUS_CARD}>
+                                    <h2 class={styles::FOCUS_CARD_TITLE}>{"Top Creatures"}</h2>
+                                    <div class="grid grid-cols-2 sm:grid-cols-3 gap-4">
+                                        {for user.top_creatures.iter().map(|creature| html! {
+                                            <div key={creature.id.to_string()} class="rounded-xl overflow-hidden border border-gray-200 dark:border-gray-700">
+                                                <img src={get_asset_url(&creature.image_path)} alt={creature.display_name.clone()} class="w-full aspect-square object-cover" />
+                                                <div class="p-2">
+                                                    <p class="text-sm font-semibold text-gray-900 dark:text-white truncate">{&creature.display_name}</p>
+                                                    <p class={styles::TEXT_SMALL}>{format!("{} · {} soul", creature.rarity, creature.soul)}</p>
+                                                    <Link<Route> to={Route::BattleOpponent { opponent: creature.id }} classes={classes!(styles::TEXT_LINK, "text-sm")}>
+                                                        {"Challenge"}
+                                                    </Link<Route>>
+                                                </div>
+                                            </div>
+                                        })}
+                                    </div>
+                                </div>
+                            }
+
+                            if !user.high_scores.is_empty() || user.word_game.is_some() {
+                                <div class={styles::FOCUS_CARD}>
+                                    <h2 class={styles::FOCUS_CARD_TITLE}>{"High Scores"}</h2>
+                                    <div class="divide-y divide-gray-200 dark:divide-gray-700">
+                                        {for user.high_scores.iter().map(|score| html! {
+                                            <div class="flex justify-between py-2">
+                                                <span class={styles::TEXT_BODY}>{game_label(&score.game_type)}</span>
+                                                <span class="font-semibold text-gray-900 dark:text-white">{score.high_score}</span>
+                                            </div>
+                                        })}
+                                        if let Some(word) = &user.word_game {
+                                            <div class="flex justify-between py-2">
+                                                <span class={styles::TEXT_BODY}>{"Word game"}</span>
+                                                <span class="font-semibold text-gray-900 dark:text-white">
+                                                    {format!("{} words, best streak {}", word.total_words_guessed, word.highest_streak)}
+                                                    {word.fastest_time.map(|t| format!(", fastest {}s", t)).unwrap_or_default()}
+                                                </span>
+                                            </div>
+                                        }
+                                    </div>
+                                </div>
+                            }
+                        </div>
+                    }
+                </div>
+            </GradientBackground>
+        </Base>
+    }
+}
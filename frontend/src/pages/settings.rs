@@ -6,7 +6,8 @@ use gloo_timers::callback::Timeout;
 use wasm_bindgen_futures::spawn_local;
 use wasm_bindgen::JsCast;
 use crate::components::settings::{
-    AccountManagement, MembershipCode, TemporaryMembership, PatreonLink
+    AccountManagement, MembershipCode, TemporaryMembership, PatreonLink, PreferencesPanel,
+    SessionsPanel
 };
 
 // Function to format ISO date string to a more readable format
@@ -303,6 +304,20 @@ pub fn settings() -> Html {
             Box::new(|| {}) as Box<dyn FnOnce()>
         });
     }
+
+    // Clears the stored session and sends the player back to the login page
+    let sign_out = Callback::from(move |_| {
+        if let Some(window) = window() {
+            for storage in [window.local_storage().unwrap().unwrap(), window.session_storage().unwrap().unwrap()].iter() {
+                storage.remove_item("token").ok();
+                storage.remove_item("username").ok();
+                storage.remove_item("csrf_token").ok();
+                storage.remove_item("currency").ok();
+                storage.remove_item("user_id").ok();
+            }
+            window.location().set_href("/login").ok();
+        }
+    });
     
     html! {
         <crate::base::Base>
@@ -493,21 +508,14 @@ pub fn settings() -> Html {
                                     </div>
                                 }
                                 
+                                <PreferencesPanel />
+
                                 <PatreonLink />
+
+                                <SessionsPanel on_signed_out={sign_out.clone()} />
                                 
                                 <AccountManagement
-                                    on_success={Some(Callback::from(move |_| {
-                                        if let Some(window) = window() {
-                                            for storage in [window.local_storage().unwrap().unwrap(), window.session_storage().unwrap().unwrap()].iter() {
-                                                storage.remove_item("token").ok();
-                                                storage.remove_item("username").ok();
-                                                storage.remove_item("csrf_token").ok();
-                                                storage.remove_item("currency").ok();
-                                                storage.remove_item("user_id").ok();
-                                            }
-                                            window.location().set_href("/login").ok();
-                                        }
-                                    }))}
+                                    on_success={Some(sign_out)}
                                     on_error={form_state.handle_error.clone()}
                                 />
                             </div>
@@ -28,6 +28,30 @@ struct RegistrationNeededResponse {
     needs_registration: bool,
 }
 
+#[derive(Deserialize)]
+struct TwoFactorRequiredResponse {
+    two_factor_token: String,
+}
+
+#[derive(Serialize)]
+struct TwoFactorLogin {
+    token: String,
+    code: String,
+}
+
+fn store_session(auth_response: &AuthResponse) -> Result<(), String> {
+    let storage = window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .ok_or_else(|| "Failed to access local storage".to_string())?;
+
+    let _ = storage.set_item("token", &auth_response.token);
+    let _ = storage.set_item("csrf_token", &auth_response.csrf_token);
+    let _ = storage.set_item("user_id", &auth_response.user_id);
+    let _ = storage.set_item("username", &auth_response.username);
+    let _ = storage.set_item("currency", &auth_response.currency_balance.to_string());
+    Ok(())
+}
+
 #[function_component(VerifyMagicLink)]
 pub fn verify_magic_link() -> Html {
     let navigator = use_navigator().unwrap();
@@ -40,6 +64,8 @@ pub fn verify_magic_link() -> Html {
     let username_error = use_state(String::new);
     let loading = use_state(|| false);
     let success = use_state(|| String::new());
+    let two_factor_token = use_state(|| None::<String>);
+    let two_factor_code_ref = use_node_ref();
 
     // Get token from URL
     {
@@ -47,6 +73,7 @@ pub fn verify_magic_link() -> Html {
         let error = error.clone();
         let email = email.clone();
         let needs_registration = needs_registration.clone();
+        let two_factor_token = two_factor_token.clone();
         let navigator = navigator.clone();
         
         use_effect_with((), move |_| {
@@ -92,6 +119,17 @@ pub fn verify_magic_link() -> Html {
                                         error.set("Failed to parse authentication response".to_string());
                                     }
                                 }
+                            } else if response.status() == 202 {
+                                // The account has 2FA: ask for a code before signing in
+                                match response.json::<TwoFactorRequiredResponse>().await {
+                                    Ok(challenge) => {
+                                        two_factor_token.set(Some(challenge.two_factor_token));
+                                        status.set("Two-factor authentication".to_string());
+                                    },
+                                    Err(_) => {
+                                        error.set("Failed to parse authentication response".to_string());
+                                    }
+                                }
                             } else if response.status() == 404 {
                                 // User doesn't exist, show registration form
                                 match response.json::<RegistrationNeededResponse>().await {
@@ -224,6 +262,70 @@ pub fn verify_magic_link() -> Html {
         })
     };
 
+    let handle_two_factor = {
+        let two_factor_token = two_factor_token.clone();
+        let two_factor_code_ref = two_factor_code_ref.clone();
+        let error = error.clone();
+        let loading = loading.clone();
+        let navigator = navigator.clone();
+
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let Some(token) = (*two_factor_token).clone() else {
+                return;
+            };
+            if *loading {
+                return;
+            }
+
+            let code = two_factor_code_ref
+                .cast::<web_sys::HtmlInputElement>()
+                .map(|input| input.value())
+                .unwrap_or_default();
+            if code.trim().is_empty() {
+                error.set("Please enter your code".to_string());
+                return;
+            }
+
+            loading.set(true);
+            error.set(String::new());
+            let error = error.clone();
+            let loading = loading.clone();
+            let navigator = navigator.clone();
+
+            spawn_local(async move {
+                let login = TwoFactorLogin { token, code: code.trim().to_string() };
+                let result = Request::post(&format!("{}/api/auth/two-factor/verify", get_api_base_url()))
+                    .json(&login)
+                    .unwrap()
+                    .send()
+                    .await;
+                loading.set(false);
+
+                match result {
+                    Ok(response) if response.status() == 200 => {
+                        match response.json::<AuthResponse>().await {
+                            Ok(auth_response) => match store_session(&auth_response) {
+                                Ok(()) => navigator.push(&Route::Home),
+                                Err(e) => error.set(e),
+                            },
+                            Err(_) => error.set("Failed to parse authentication response".to_string()),
+                        }
+                    },
+                    Ok(response) => {
+                        let message = response.json::<serde_json::Value>().await.ok()
+                            .and_then(|body| body["error"].as_str().map(str::to_string))
+                            .unwrap_or_else(|| "Failed to verify code".to_string());
+                        error.set(message);
+                    },
+                    Err(_) => {
+                        error.set("Failed to send verification request".to_string());
+                    }
+                }
+            });
+        })
+    };
+
     let go_to_home = {
         let navigator = navigator.clone();
         Callback::from(move |_| navigator.push(&Route::Home))
@@ -244,6 +346,12 @@ pub fn verify_magic_link() -> Html {
                                                 {"Create a username to complete your registration"}
                                             </p>
                                         }
+                                    } else if two_factor_token.is_some() {
+                                        html! {
+                                            <p class={styles::TEXT_SMALL}>
+                                                {"Enter the code from your authenticator app, or one of your recovery codes"}
+                                            </p>
+                                        }
                                     } else if !(*success).is_empty() {
                                         html! {
                                             <>
@@ -337,6 +445,33 @@ pub fn verify_magic_link() -> Html {
                                             }
                                         </form>
                                     }
+                                } else if two_factor_token.is_some() {
+                                    html! {
+                                        <form onsubmit={handle_two_factor} class={styles::FORM}>
+                                            <div>
+                                                <label for="two-factor-code" class={styles::TEXT_LABEL}>
+                                                    {"Code"}
+                                                </label>
+                                                <input
+                                                    id="two-factor-code"
+                                                    type="text"
+                                                    ref={two_factor_code_ref}
+                                                    required=true
+                                                    disabled={*loading}
+                                                    autocomplete="one-time-code"
+                                                    placeholder="123456"
+                                                    class={styles::INPUT}
+                                                />
+                                            </div>
+                                            <button
+                                                type="submit"
+                                                disabled={*loading}
+                                                class={styles::BUTTON_PRIMARY}
+                                            >
+                                                {if *loading { "Verifying..." } else { "Sign in" }}
+                                            </button>
+                                        </form>
+                                    }
                                 } else if (*error).is_empty() && (*success).is_empty() {
                                     html! {
                                         <div class="flex justify-center py-4">
@@ -0,0 +1,352 @@
+use std::collections::BTreeMap;
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::Deserialize;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use crate::{base::Base, styles};
+use crate::config::get_api_base_url;
+use crate::components::{GradientBackground, PlayerLink};
+use crate::hooks::use_currency::use_currency;
+
+const SUMMARY_DAYS: i32 = 30;
+
+/// Every `pax_entry_kind`, in the order the filter lists them.
+const SOURCES: &[(&str, &str)] = &[
+    ("daily_claim", "Daily reward"),
+    ("game_reward", "Game reward"),
+    ("magic_button", "Magic button"),
+    ("wheel", "Wheel of fortune"),
+    ("chaos_reward", "Chaos realm"),
+    ("achievement_reward", "Achievement"),
+    ("battle_reward", "Battle win"),
+    ("ladder_reward", "Ladder season reward"),
+    ("sale", "Sale"),
+    ("purchase", "Purchase"),
+    ("listing_fee", "Listing fee"),
+    ("auction_bid", "Auction bid"),
+    ("auction_refund", "Auction refund"),
+    ("order_fee", "Order fee"),
+    ("order_escrow", "Buy order reserve"),
+    ("order_refund", "Buy order refund"),
+    ("trade_escrow", "Trade offer"),
+    ("trade_refund", "Trade refund"),
+    ("trade_payment", "Trade payment"),
+    ("guild_deposit", "Guild deposit"),
+    ("guild_payout", "Guild payout"),
+    ("bind_cost", "Binding"),
+    ("recharge_cost", "Energy recharge"),
+    ("egg_cost", "Egg summoning"),
+    ("rename_cost", "Creature rename"),
+    ("crafting_cost", "Crafting"),
+    ("membership_purchase", "Membership"),
+    ("opening_balance", "Opening balance"),
+];
+
+fn source_label(source: &str) -> &str {
+    SOURCES.iter()
+        .find(|(key, _)| *key == source)
+        .map_or(source, |(_, label)| *label)
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct WalletTransaction {
+    id: i64,
+    source: String,
+    amount: i32,
+    balance_after: i64,
+    counterparty: Option<String>,
+    created_at: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct WalletTransactionsResponse {
+    transactions: Vec<WalletTransaction>,
+    next_before: Option<i64>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct DailySourceSummary {
+    day: String,
+    source: String,
+    earned: i64,
+    spent: i64,
+}
+
+#[derive(Deserialize)]
+struct ApiResponse<T> {
+    success: bool,
+    data: Option<T>,
+    error: Option<String>,
+}
+
+fn get_token() -> String {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item("token").ok().flatten())
+        .or_else(|| window()
+            .and_then(|w| w.session_storage().ok().flatten())
+            .and_then(|s| s.get_item("token").ok().flatten()))
+        .unwrap_or_default()
+}
+
+async fn fetch<T: for<'de> Deserialize<'de>>(path: &str) -> Result<Option<T>, String> {
+    match Request::get(&format!("{}{}", get_api_base_url(), path))
+        .header("Authorization", &format!("Bearer {}", get_token()))
+        .send()
+        .await
+    {
+        Ok(response) => match response.json::<ApiResponse<T>>().await {
+            Ok(ApiResponse { success: true, data, .. }) => Ok(data),
+            Ok(result) => Err(result.error.unwrap_or_else(|| "Failed to load wallet".to_string())),
+            Err(_) => Err(format!("Server returned status: {}", response.status())),
+        },
+        Err(e) => {
+            log::error!("Failed to fetch {}: {:?}", path, e);
+            Err("Network error occurred.".to_string())
+        }
+    }
+}
+
+fn transactions_path(source: &str, before: Option<i64>) -> String {
+    let mut path = "/api/wallet/transactions?limit=25".to_string();
+    if !source.is_empty() {
+        path.push_str(&format!("&source={}", source));
+    }
+    if let Some(before) = before {
+        path.push_str(&format!("&before={}", before));
+    }
+    path
+}
+
+fn signed(amount: i64) -> String {
+    if amount > 0 { format!("+{}", amount) } else { amount.to_string() }
+}
+
+fn amount_class(amount: i64) -> &'static str {
+    if amount >= 0 { "text-green-600 dark:text-green-400" } else { "text-red-600 dark:text-red-400" }
+}
+
+#[function_component(Wallet)]
+pub fn wallet() -> Html {
+    let balance = use_currency();
+    let transactions = use_state(Vec::<WalletTransaction>::new);
+    let next_before = use_state(|| None::<i64>);
+    let summary = use_state(Vec::<DailySourceSummary>::new);
+    let source = use_state(String::new);
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+
+    {
+        let summary = summary.clone();
+        let error = error.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match fetch::<Vec<DailySourceSummary>>(&format!("/api/wallet/summary?days={}", SUMMARY_DAYS)).await {
+                    Ok(data) => summary.set(data.unwrap_or_default()),
+                    Err(e) => error.set(Some(e)),
+                }
+            });
+            || ()
+        });
+    }
+
+    // Changing the filter starts the history over from the newest entry
+    {
+        let transactions = transactions.clone();
+        let next_before = next_before.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        use_effect_with((*source).clone(), move |source| {
+            let path = transactions_path(source, None);
+            loading.set(true);
+            spawn_local(async move {
+                match fetch::<WalletTransactionsResponse>(&path).await {
+                    Ok(Some(page)) => {
+                        transactions.set(page.transactions);
+                        next_before.set(page.next_before);
+                    }
+                    Ok(None) => transactions.set(Vec::new()),
+                    Err(e) => error.set(Some(e)),
+                }
+                loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    let load_more = {
+        let transactions = transactions.clone();
+        let next_before = next_before.clone();
+        let source = source.clone();
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let transactions = transactions.clone();
+            let next_before = next_before.clone();
+            let error = error.clone();
+            let path = transactions_path(&source, *next_before);
+            spawn_local(async move {
+                match fetch::<WalletTransactionsResponse>(&path).await {
+                    Ok(Some(page)) => {
+                        let mut loaded = (*transactions).clone();
+                        loaded.extend(page.transactions);
+                        transactions.set(loaded);
+                        next_before.set(page.next_before);
+                    }
+                    Ok(None) => next_before.set(None),
+                    Err(e) => error.set(Some(e)),
+                }
+            });
+        })
+    };
+
+    let on_source_change = {
+        let source = source.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<web_sys::HtmlSelectElement>() {
+                source.set(select.value());
+            }
+        })
+    };
+
+    // Totals per source over the whole period, biggest movers first
+    let mut by_source: BTreeMap<&str, (i64, i64)> = BTreeMap::new();
+    // Net change per day, newest first as the API returns them
+    let mut by_day: Vec<(&str, i64, i64)> = Vec::new();
+    for row in summary.iter() {
+        let totals = by_source.entry(row.source.as_str()).or_default();
+        totals.0 += row.earned;
+        totals.1 += row.spent;
+        match by_day.last_mut() {
+            Some((day, earned, spent)) if *day == row.day => {
+                *earned += row.earned;
+                *spent += row.spent;
+            }
+            _ => by_day.push((row.day.as_str(), row.earned, row.spent)),
+        }
+    }
+    let mut source_totals: Vec<(&str, i64, i64)> = by_source.into_iter()
+        .map(|(source, (earned, spent))| (source, earned, spent))
+        .collect();
+    source_totals.sort_by_key(|(_, earned, spent)| std::cmp::Reverse(earned + spent));
+    let total_earned: i64 = source_totals.iter().map(|(_, earned, _)| earned).sum();
+    let total_spent: i64 = source_totals.iter().map(|(_, _, spent)| spent).sum();
+
+    html! {
+        <Base>
+            <GradientBackground>
+                <div class="max-w-3xl mx-auto px-4 sm:px-6 lg:px-8 py-6 space-y-6">
+                    if let Some(message) = &*error {
+                        <div class={styles::ALERT_ERROR}>{message}</div>
+                    }
+
+                    <div class={styles::FOCUS_CARD}>
+                        <h1 class={styles::TEXT_H1}>{"Wallet"}</h1>
+                        <p class={classes!(styles::TEXT_H3, "mt-2")}>{format!("{} pax", *balance)}</p>
+                        <div class="mt-4 grid grid-cols-3 gap-4">
+                            <div>
+                                <p class={styles::TEXT_SMALL}>{format!("Earned ({} days)", SUMMARY_DAYS)}</p>
+                                <p class={classes!("text-lg", "font-semibold", amount_class(total_earned))}>{signed(total_earned)}</p>
+                            </div>
+                            <div>
+                                <p class={styles::TEXT_SMALL}>{format!("Spent ({} days)", SUMMARY_DAYS)}</p>
+                                <p class={classes!("text-lg", "font-semibold", amount_class(-total_spent))}>{signed(-total_spent)}</p>
+                            </div>
+                            <div>
+                                <p class={styles::TEXT_SMALL}>{"Net"}</p>
+                                <p class={classes!("text-lg", "font-semibold", amount_class(total_earned - total_spent))}>{signed(total_earned - total_spent)}</p>
+                            </div>
+                        </div>
+                    </div>
+
+                    <div class={styles::FOCUS_CARD}>
+                        <h2 class={styles::FOCUS_CARD_TITLE}>{"Where your pax came from and went"}</h2>
+                        if source_totals.is_empty() {
+                            <p class={styles::TEXT_SECONDARY}>{format!("No pax moved in the last {} days.", SUMMARY_DAYS)}</p>
+                        }
+                        <div class="divide-y divide-gray-200 dark:divide-gray-700">
+                            {for source_totals.iter().map(|(source, earned, spent)| html! {
+                                <div class="flex justify-between py-2 gap-4">
+                                    <span class={styles::TEXT_BODY}>{source_label(source)}</span>
+                                    <span class="text-sm space-x-3">
+                                        if *earned > 0 {
+                                            <span class={amount_class(*earned)}>{signed(*earned)}</span>
+                                        }
+                                        if *spent > 0 {
+                                            <span class={amount_class(-*spent)}>{signed(-*spent)}</span>
+                                        }
+                                    </span>
+                                </div>
+                            })}
+                        </div>
+                    </div>
+
+                    if !by_day.is_empty() {
+                        <div class={styles::FOCUS_CARD}>
+                            <h2 class={styles::FOCUS_CARD_TITLE}>{"By day"}</h2>
+                            <div class="divide-y divide-gray-200 dark:divide-gray-700">
+                                {for by_day.iter().map(|(day, earned, spent)| html! {
+                                    <div class="flex justify-between py-2 gap-4">
+                                        <span class={styles::TEXT_BODY}>{*day}</span>
+                                        <span class={styles::TEXT_SMALL}>
+                                            {format!("{} earned · {} spent · ", earned, spent)}
+                                            <span class={amount_class(earned - spent)}>{signed(earned - spent)}</span>
+                                        </span>
+                                    </div>
+                                })}
+                            </div>
+                        </div>
+                    }
+
+                    <div class={styles::FOCUS_CARD}>
+                        <div class="flex items-center justify-between mb-4 gap-4">
+                            <h2 class={styles::FOCUS_CARD_TITLE}>{"Transactions"}</h2>
+                            <select class="rounded-lg bg-white dark:bg-gray-900 py-1 px-2 text-sm text-gray-900 dark:text-white ring-1 ring-gray-300 dark:ring-gray-700"
+                                onchange={on_source_change}>
+                                <option value="" selected={source.is_empty()}>{"All sources"}</option>
+                                {for SOURCES.iter().map(|(key, label)| html! {
+                                    <option value={*key} selected={*source == *key}>{*label}</option>
+                                })}
+                            </select>
+                        </div>
+                        if *loading {
+                            <div class="flex justify-center py-8">
+                                <div class={styles::LOADING_SPINNER}></div>
+                            </div>
+                        } else if transactions.is_empty() {
+                            <p class={styles::TEXT_SECONDARY}>{"No transactions yet."}</p>
+                        } else {
+                            <div class="divide-y divide-gray-200 dark:divide-gray-700">
+                                {for transactions.iter().map(|transaction| html! {
+                                    <div key={transaction.id.to_string()} class="flex justify-between py-2 gap-4">
+                                        <div>
+                                            <span class={styles::TEXT_BODY}>{source_label(&transaction.source)}</span>
+                                            if let Some(username) = &transaction.counterparty {
+                                                <span class={styles::TEXT_SMALL}>
+                                                    {if transaction.amount < 0 { " to " } else { " from " }}
+                                                    <PlayerLink username={username.clone()} />
+                                                </span>
+                                            }
+                                            <p class={styles::TEXT_SMALL}>{&transaction.created_at}</p>
+                                        </div>
+                                        <div class="text-right whitespace-nowrap">
+                                            <p class={classes!("font-semibold", amount_class(transaction.amount as i64))}>
+                                                {signed(transaction.amount as i64)}
+                                            </p>
+                                            <p class={styles::TEXT_SMALL}>{format!("{} pax after", transaction.balance_after)}</p>
+                                        </div>
+                                    </div>
+                                })}
+                            </div>
+                            if next_before.is_some() {
+                                <div class="flex justify-center mt-4">
+                                    <button class={styles::BUTTON_SECONDARY} onclick={load_more}>{"Load more"}</button>
+                                </div>
+                            }
+                        }
+                    </div>
+                </div>
+            </GradientBackground>
+        </Base>
+    }
+}
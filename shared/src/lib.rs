@@ -1,4 +1,6 @@
 pub mod password_reset;
+pub mod push_events;
+pub mod preferences;
 pub mod validation;
 pub mod constants;
 pub mod rate_limit;
@@ -7,4 +9,5 @@ pub mod shared_match_game;
 pub mod shared_snake_game;
 pub mod shared_2048_game;
 pub mod shared_wheel_game;
-pub mod shared_word_game;
\ No newline at end of file
+pub mod shared_word_game;
+pub mod shared_battle;
\ No newline at end of file
@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+/// Kinds of the `notification_kind` enum, the only keys allowed in the per-kind maps.
+pub const NOTIFICATION_KINDS: &[&str] = &[
+    "listing_sold",
+    "order_filled",
+    "outbid",
+    "membership_expired",
+    "patreon_linked",
+    "patreon_unlinked",
+    "chaos_realm_ready",
+    "energy_recharged",
+    "streak_expiring",
+    "friend_request",
+    "friend_accepted",
+    "guild_invite",
+    "battle_result",
+    "ladder_reward",
+];
+
+fn validate_notification_kinds(kinds: &BTreeMap<String, bool>) -> Result<(), ValidationError> {
+    if kinds.keys().all(|kind| NOTIFICATION_KINDS.contains(&kind.as_str())) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("unknown_notification_kind"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailDigest {
+    #[default]
+    Off,
+    Hourly,
+    Daily,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InventorySort {
+    #[default]
+    Default,
+    Rarity,
+    Energy,
+    Essence,
+}
+
+/// Stored in `user_preferences.notification_settings`. Kinds missing from the maps are on.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationSettings {
+    #[validate(custom = "validate_notification_kinds")]
+    pub in_app: BTreeMap<String, bool>,
+    pub email_digest: EmailDigest,
+    #[validate(custom = "validate_notification_kinds")]
+    pub email: BTreeMap<String, bool>,
+}
+
+impl NotificationSettings {
+    pub fn in_app_enabled(&self, kind: &str) -> bool {
+        self.in_app.get(kind).copied().unwrap_or(true)
+    }
+
+    pub fn email_enabled(&self, kind: &str) -> bool {
+        self.email_digest != EmailDigest::Off && self.email.get(kind).copied().unwrap_or(true)
+    }
+}
+
+/// Stored in `user_preferences.privacy_settings` and applied by the `public_users` view.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrivacySettings {
+    pub show_on_leaderboards: bool,
+    pub show_in_showcase: bool,
+    /// Show "Anonymous" instead of the username wherever the user is still listed.
+    pub appear_anonymous: bool,
+    /// Whether `/users/<username>` shows anything. Anonymous users never have a public profile.
+    pub public_profile: bool,
+}
+
+impl Default for PrivacySettings {
+    fn default() -> Self {
+        Self {
+            show_on_leaderboards: true,
+            show_in_showcase: true,
+            appear_anonymous: false,
+            public_profile: true,
+        }
+    }
+}
+
+/// Stored in `user_preferences.ui_preferences`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiPreferences {
+    pub inventory_sort: InventorySort,
+    pub reduced_motion: bool,
+}
+
+/// Body of `GET/PUT /api/settings/preferences`. A PUT replaces all three sections;
+/// missing fields fall back to their defaults, unknown ones are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct UserPreferences {
+    #[validate]
+    pub notification_settings: NotificationSettings,
+    #[validate]
+    pub privacy_settings: PrivacySettings,
+    #[validate]
+    pub ui_preferences: UiPreferences,
+}
+
+impl UserPreferences {
+    /// Runs the `Validate` rules, flattened into a message for API responses.
+    pub fn check(&self) -> Result<(), String> {
+        self.validate().map_err(|e| e.to_string())
+    }
+}
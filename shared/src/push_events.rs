@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// Events pushed to clients over the `/api/events` WebSocket.
+/// Ids are sent as strings so this crate doesn't need to depend on `uuid`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum PushEvent {
+    BalanceChanged { balance: i32 },
+    ListingSold { listing_id: String, price: i32 },
+    ListingBought { listing_id: String, price: i32 },
+    OrderFilled { order_id: String, quantity: i32, price: i32 },
+    EnergyRecharged { creature_id: String },
+    ChaosRealmCompleted { creature_id: String },
+    MembershipExpired,
+    /// A new entry landed in the user's notification inbox.
+    Notification { id: String, kind: String, message: String },
+    /// Broadcast to everyone when a listing is created, cancelled or sold.
+    MarketChanged,
+    /// Broadcast to everyone when someone presses the magic button.
+    MagicButtonPressed,
+}
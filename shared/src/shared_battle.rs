@@ -0,0 +1,382 @@
+use serde::{Serialize, Deserialize};
+
+/// Battles end after this many attacks; the side with more health left (as a share of
+/// its maximum) wins, and the defender keeps ties.
+pub const MAX_TURNS: u32 = 50;
+
+const BASE_HEALTH: i32 = 20;
+const HEALTH_PER_POINT: i32 = 5;
+const BASE_DAMAGE: i32 = 3;
+const DAMAGE_PER_POINT: i32 = 2;
+const CRITICAL_CHANCE: u64 = 10;      // percent
+const MAX_DODGE_CHANCE: i32 = 30;     // percent
+const BASE_DODGE_CHANCE: i32 = 5;     // percent
+const DODGE_PER_SPEED_POINT: i32 = 3; // percent
+
+/// Damage multipliers in percent, kept as integers so every platform replays a battle identically
+pub const STRONG_MULTIPLIER: i32 = 150;
+pub const NEUTRAL_MULTIPLIER: i32 = 100;
+pub const WEAK_MULTIPLIER: i32 = 75;
+
+/// Mirrors the `essence_type` enum in the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Essence {
+    Celestial, Ancient, Psychic, Undead, Fairy, Dark, Electric,
+    Fire, Toxic, Construct, Air, Earth, Plant, Water, Fungal,
+}
+
+impl Essence {
+    pub const ALL: [Essence; 15] = [
+        Essence::Celestial, Essence::Ancient, Essence::Psychic, Essence::Undead, Essence::Fairy,
+        Essence::Dark, Essence::Electric, Essence::Fire, Essence::Toxic, Essence::Construct,
+        Essence::Air, Essence::Earth, Essence::Plant, Essence::Water, Essence::Fungal,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|essence| essence.name() == name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Essence::Celestial => "Celestial",
+            Essence::Ancient => "Ancient",
+            Essence::Psychic => "Psychic",
+            Essence::Undead => "Undead",
+            Essence::Fairy => "Fairy",
+            Essence::Dark => "Dark",
+            Essence::Electric => "Electric",
+            Essence::Fire => "Fire",
+            Essence::Toxic => "Toxic",
+            Essence::Construct => "Construct",
+            Essence::Air => "Air",
+            Essence::Earth => "Earth",
+            Essence::Plant => "Plant",
+            Essence::Water => "Water",
+            Essence::Fungal => "Fungal",
+        }
+    }
+
+    /// The essences this one deals extra damage to.
+    pub fn strong_against(&self) -> [Essence; 2] {
+        match self {
+            Essence::Fire => [Essence::Plant, Essence::Fungal],
+            Essence::Water => [Essence::Fire, Essence::Earth],
+            Essence::Plant => [Essence::Water, Essence::Earth],
+            Essence::Earth => [Essence::Electric, Essence::Fire],
+            Essence::Electric => [Essence::Water, Essence::Air],
+            Essence::Air => [Essence::Plant, Essence::Toxic],
+            Essence::Toxic => [Essence::Plant, Essence::Fairy],
+            Essence::Fungal => [Essence::Undead, Essence::Toxic],
+            Essence::Construct => [Essence::Air, Essence::Fairy],
+            Essence::Psychic => [Essence::Toxic, Essence::Construct],
+            Essence::Undead => [Essence::Ancient, Essence::Psychic],
+            Essence::Dark => [Essence::Celestial, Essence::Psychic],
+            Essence::Fairy => [Essence::Dark, Essence::Undead],
+            Essence::Celestial => [Essence::Undead, Essence::Fungal],
+            Essence::Ancient => [Essence::Celestial, Essence::Construct],
+        }
+    }
+
+    /// Damage multiplier in percent when this essence attacks `defender`.
+    pub fn multiplier_against(&self, defender: Essence) -> i32 {
+        if self.strong_against().contains(&defender) {
+            STRONG_MULTIPLIER
+        } else if defender.strong_against().contains(self) {
+            WEAK_MULTIPLIER
+        } else {
+            NEUTRAL_MULTIPLIER
+        }
+    }
+}
+
+/// The `stats` JSONB column on creatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreatureStats {
+    pub health: i32,
+    pub attack: i32,
+    pub speed: i32,
+}
+
+impl Default for CreatureStats {
+    fn default() -> Self {
+        Self { health: 1, attack: 1, speed: 1 }
+    }
+}
+
+/// A creature as it entered the battle. Battles store these snapshots so a replay
+/// still matches after the creature's stats change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Combatant {
+    pub name: String,
+    pub essence: Essence,
+    pub stats: CreatureStats,
+}
+
+impl Combatant {
+    pub fn max_health(&self) -> i32 {
+        BASE_HEALTH + self.stats.health.max(1) * HEALTH_PER_POINT
+    }
+
+    fn base_damage(&self) -> i32 {
+        BASE_DAMAGE + self.stats.attack.max(1) * DAMAGE_PER_POINT
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Challenger,
+    Defender,
+}
+
+impl Side {
+    fn other(&self) -> Side {
+        match self {
+            Side::Challenger => Side::Defender,
+            Side::Defender => Side::Challenger,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BattleTurn {
+    pub turn: u32,
+    pub attacker: Side,
+    pub damage: i32,
+    pub dodged: bool,
+    pub critical: bool,
+    /// Essence multiplier in percent
+    pub effectiveness: i32,
+    pub challenger_health: i32,
+    pub defender_health: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BattleOutcome {
+    pub winner: Side,
+    pub turns: Vec<BattleTurn>,
+}
+
+/// SplitMix64: tiny, fast and identical on every target, unlike `rand`'s generators,
+/// whose output may change between crate versions.
+#[derive(Debug, Clone)]
+pub struct BattleRng(u64);
+
+impl BattleRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+/// Generates an AI opponent close to the challenger's strength: a random essence and
+/// each stat within two points of the challenger's.
+pub fn wild_opponent(challenger: &Combatant, seed: u64) -> Combatant {
+    let mut rng = BattleRng::new(seed ^ 0xA5A5_A5A5_A5A5_A5A5);
+    let essence = Essence::ALL[rng.below(Essence::ALL.len() as u64) as usize];
+    let mut vary = |value: i32| (value + rng.below(5) as i32 - 2).max(1);
+    let stats = CreatureStats {
+        health: vary(challenger.stats.health),
+        attack: vary(challenger.stats.attack),
+        speed: vary(challenger.stats.speed),
+    };
+
+    Combatant {
+        name: format!("Wild {} creature", essence.name()),
+        essence,
+        stats,
+    }
+}
+
+/// Runs a battle to completion. The same combatants and seed always give the same
+/// outcome, which is how the frontend replays battles the backend has already settled.
+pub fn simulate(challenger: &Combatant, defender: &Combatant, seed: u64) -> BattleOutcome {
+    let mut rng = BattleRng::new(seed);
+    let mut health = [challenger.max_health(), defender.max_health()];
+    let combatant = |side: Side| match side {
+        Side::Challenger => challenger,
+        Side::Defender => defender,
+    };
+    let index = |side: Side| match side {
+        Side::Challenger => 0,
+        Side::Defender => 1,
+    };
+
+    // The faster creature strikes first; a coin flip settles equal speed
+    let mut attacker = match challenger.stats.speed.cmp(&defender.stats.speed) {
+        std::cmp::Ordering::Greater => Side::Challenger,
+        std::cmp::Ordering::Less => Side::Defender,
+        std::cmp::Ordering::Equal => if rng.below(2) == 0 { Side::Challenger } else { Side::Defender },
+    };
+
+    let mut turns = Vec::new();
+    for turn in 1..=MAX_TURNS {
+        let attacking = combatant(attacker);
+        let defending = combatant(attacker.other());
+
+        let dodge_chance = (BASE_DODGE_CHANCE
+            + (defending.stats.speed - attacking.stats.speed) * DODGE_PER_SPEED_POINT)
+            .clamp(0, MAX_DODGE_CHANCE) as u64;
+        let dodged = rng.below(100) < dodge_chance;
+        let critical = !dodged && rng.below(100) < CRITICAL_CHANCE;
+        let effectiveness = attacking.essence.multiplier_against(defending.essence);
+
+        let damage = if dodged {
+            0
+        } else {
+            // 85-115% variance, then essence, then critical hits at 150%
+            let variance = 85 + rng.below(31) as i32;
+            let mut damage = attacking.base_damage() * variance / 100 * effectiveness / 100;
+            if critical {
+                damage = damage * 3 / 2;
+            }
+            damage.max(1)
+        };
+
+        let target = index(attacker.other());
+        health[target] = (health[target] - damage).max(0);
+
+        turns.push(BattleTurn {
+            turn,
+            attacker,
+            damage,
+            dodged,
+            critical,
+            effectiveness,
+            challenger_health: health[0],
+            defender_health: health[1],
+        });
+
+        if health[target] == 0 {
+            return BattleOutcome { winner: attacker, turns };
+        }
+        attacker = attacker.other();
+    }
+
+    // Out of turns: compare remaining health as a share of each side's maximum
+    let challenger_share = health[0] as i64 * defender.max_health() as i64;
+    let defender_share = health[1] as i64 * challenger.max_health() as i64;
+    let winner = if challenger_share > defender_share { Side::Challenger } else { Side::Defender };
+    BattleOutcome { winner, turns }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combatant(essence: Essence, health: i32, attack: i32, speed: i32) -> Combatant {
+        Combatant {
+            name: essence.name().to_string(),
+            essence,
+            stats: CreatureStats { health, attack, speed },
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_outcome() {
+        let challenger = combatant(Essence::Fire, 4, 5, 3);
+        let defender = combatant(Essence::Water, 5, 4, 3);
+        for seed in [0, 1, 42, u64::MAX] {
+            assert_eq!(simulate(&challenger, &defender, seed), simulate(&challenger, &defender, seed));
+        }
+    }
+
+    #[test]
+    fn test_essence_multipliers() {
+        assert_eq!(Essence::Water.multiplier_against(Essence::Fire), STRONG_MULTIPLIER);
+        assert_eq!(Essence::Fire.multiplier_against(Essence::Water), WEAK_MULTIPLIER);
+        assert_eq!(Essence::Fire.multiplier_against(Essence::Plant), STRONG_MULTIPLIER);
+        assert_eq!(Essence::Plant.multiplier_against(Essence::Fire), WEAK_MULTIPLIER);
+        assert_eq!(Essence::Fire.multiplier_against(Essence::Fire), NEUTRAL_MULTIPLIER);
+        assert_eq!(Essence::Fire.multiplier_against(Essence::Air), NEUTRAL_MULTIPLIER);
+    }
+
+    #[test]
+    fn test_turns_record_effectiveness() {
+        let challenger = combatant(Essence::Water, 5, 5, 5);
+        let defender = combatant(Essence::Fire, 5, 5, 5);
+        let outcome = simulate(&challenger, &defender, 7);
+        for turn in &outcome.turns {
+            let expected = match turn.attacker {
+                Side::Challenger => STRONG_MULTIPLIER,
+                Side::Defender => WEAK_MULTIPLIER,
+            };
+            assert_eq!(turn.effectiveness, expected);
+        }
+    }
+
+    #[test]
+    fn test_essence_names_round_trip() {
+        for essence in Essence::ALL {
+            assert_eq!(Essence::from_name(essence.name()), Some(essence));
+        }
+        assert_eq!(Essence::from_name("Lava"), None);
+    }
+
+    #[test]
+    fn test_faster_creature_strikes_first() {
+        let slow = combatant(Essence::Earth, 5, 5, 1);
+        let fast = combatant(Essence::Earth, 5, 5, 8);
+        for seed in 0..10 {
+            assert_eq!(simulate(&slow, &fast, seed).turns[0].attacker, Side::Defender);
+            assert_eq!(simulate(&fast, &slow, seed).turns[0].attacker, Side::Challenger);
+        }
+    }
+
+    #[test]
+    fn test_knockout_ends_battle() {
+        let challenger = combatant(Essence::Water, 10, 20, 10);
+        let defender = combatant(Essence::Fire, 1, 1, 1);
+        for seed in 0..10 {
+            let outcome = simulate(&challenger, &defender, seed);
+            let last = outcome.turns.last().unwrap();
+            assert_eq!(outcome.winner, Side::Challenger);
+            assert_eq!(last.defender_health, 0);
+            assert!(outcome.turns.len() < MAX_TURNS as usize);
+        }
+    }
+
+    #[test]
+    fn test_turn_limit_picks_healthier_side() {
+        let challenger = combatant(Essence::Earth, 1000, 1, 5);
+        let defender = combatant(Essence::Earth, 1000, 1, 5);
+        for seed in 0..10 {
+            let outcome = simulate(&challenger, &defender, seed);
+            assert_eq!(outcome.turns.len(), MAX_TURNS as usize);
+
+            let last = outcome.turns.last().unwrap();
+            let expected = if last.challenger_health > last.defender_health {
+                Side::Challenger
+            } else {
+                Side::Defender
+            };
+            assert_eq!(outcome.winner, expected);
+        }
+    }
+
+    #[test]
+    fn test_wild_opponent_close_to_challenger() {
+        let challenger = combatant(Essence::Fire, 5, 5, 5);
+        let opponent = wild_opponent(&challenger, 99);
+        assert_eq!(opponent, wild_opponent(&challenger, 99));
+        for (stat, base) in [
+            (opponent.stats.health, challenger.stats.health),
+            (opponent.stats.attack, challenger.stats.attack),
+            (opponent.stats.speed, challenger.stats.speed),
+        ] {
+            assert!((stat - base).abs() <= 2);
+        }
+    }
+}